@@ -298,6 +298,8 @@ fn make_authority_state(
                 &sui_config::genesis::Genesis::get_default_genesis(),
                 &prometheus::Registry::new(),
                 tx_reconfigure_consensus,
+                Default::default(),
+                None,
             )
             .await
         }),
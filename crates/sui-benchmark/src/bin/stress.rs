@@ -204,6 +204,8 @@ async fn main() -> Result<()> {
             recv_timeout: Duration::from_secs(4),
             buffer_size: 650000,
             db_folder_path: PathBuf::from("/tmp/client_db"),
+            name_registry: None,
+            submit_retry: Default::default(),
         };
         gateway_config.save(&opts.gateway_config_path)?;
         // bring up servers ..
@@ -5,7 +5,10 @@ use async_trait::async_trait;
 use clap::*;
 use std::net::SocketAddr;
 use sui::client_commands::WalletContext;
-use sui::config::SuiClientConfig;
+use sui::config::{
+    SuiClientConfig, DEFAULT_GAS_BUDGET_MARGIN_PERCENT, DEFAULT_MAX_GAS_BUDGET,
+    DEFAULT_PROFILE_NAME,
+};
 use sui_config::genesis_config::GenesisConfig;
 use sui_config::Config;
 use sui_config::SUI_KEYSTORE_FILENAME;
@@ -289,6 +292,11 @@ pub async fn new_wallet_context_from_cluster(
         gateway: ClientType::RPC(rpc_url.into(), None),
         active_address: Some(address),
         fullnode: None,
+        max_gas_budget: DEFAULT_MAX_GAS_BUDGET,
+        gas_budget_margin_percent: DEFAULT_GAS_BUDGET_MARGIN_PERCENT,
+        address_book: Default::default(),
+        active_profile: DEFAULT_PROFILE_NAME.to_string(),
+        profiles: Default::default(),
     }
     .persisted(&wallet_config_path)
     .save()
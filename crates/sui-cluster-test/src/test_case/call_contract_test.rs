@@ -50,6 +50,7 @@ impl TestCaseImpl for CallContractTest {
             vec![],
             Some(*gas_obj.id()),
             5000,
+            None,
             args,
             wallet_context,
         )
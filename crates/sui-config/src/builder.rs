@@ -196,11 +196,18 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
                     consensus_config: Some(consensus_config),
                     enable_event_processing: false,
                     enable_gossip: true,
+                    gossip_degree: crate::node::default_gossip_degree(),
                     enable_checkpoint: true,
                     enable_reconfig: false,
+                    enable_coin_supply: false,
                     genesis: crate::node::Genesis::new(genesis.clone()),
                     grpc_load_shed: initial_accounts_config.grpc_load_shed,
                     grpc_concurrency_limit: initial_accounts_config.grpc_concurrency_limit,
+                    grpc_compression: true,
+                    num_effects_checked_at_startup:
+                        crate::node::default_num_effects_checked_at_startup(),
+                    authority_store_pruning_config: Default::default(),
+                    authority_batch_config: Default::default(),
                 }
             })
             .collect();
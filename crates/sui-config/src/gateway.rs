@@ -6,6 +6,7 @@ use serde::Deserialize;
 use serde::Serialize;
 use std::path::PathBuf;
 use std::time::Duration;
+use sui_types::base_types::ObjectID;
 use sui_types::committee::EpochId;
 
 #[derive(Serialize, Deserialize)]
@@ -16,6 +17,13 @@ pub struct GatewayConfig {
     pub recv_timeout: Duration,
     pub buffer_size: usize,
     pub db_folder_path: PathBuf,
+    /// Object mapping names to addresses, so that names such as `alice.sui` can be given in
+    /// place of addresses.
+    #[serde(default)]
+    pub name_registry: Option<ObjectID>,
+    /// How requests submitting transactions to an authority are retried on network errors.
+    #[serde(default)]
+    pub submit_retry: SubmitRetryConfig,
 }
 
 impl Config for GatewayConfig {}
@@ -29,6 +37,37 @@ impl Default for GatewayConfig {
             recv_timeout: Duration::from_micros(4000000),
             buffer_size: 650000,
             db_folder_path: Default::default(),
+            name_registry: None,
+            submit_retry: SubmitRetryConfig::default(),
+        }
+    }
+}
+
+/// Retries of the requests submitting a transaction or a certificate to an authority, when they
+/// fail with a network error rather than an answer of the authority. Authorities handle the same
+/// transaction or certificate idempotently, so that retrying is safe.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SubmitRetryConfig {
+    /// The most times a request is retried, it is never retried if zero.
+    pub max_retries: u32,
+    /// The delay before the first retry, doubled before each of the next ones.
+    pub base_delay: Duration,
+}
+
+impl SubmitRetryConfig {
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay: Duration::ZERO,
+        }
+    }
+}
+
+impl Default for SubmitRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
         }
     }
 }
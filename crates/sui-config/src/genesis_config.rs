@@ -161,18 +161,10 @@ impl GenesisConfig {
 
         let mut accounts = Vec::new();
         for _ in 0..num_accounts {
-            let mut objects = Vec::new();
-            for _ in 0..num_objects_per_account {
-                objects.push(ObjectConfig {
-                    object_id: ObjectID::random(),
-                    gas_value: DEFAULT_GAS_AMOUNT,
-                })
-            }
-            accounts.push(AccountConfig {
-                address: None,
-                gas_objects: objects,
-                gas_object_ranges: Some(Vec::new()),
-            })
+            accounts.push(AccountConfig::with_gas_objects(
+                None,
+                num_objects_per_account,
+            ))
         }
 
         Self {
@@ -182,6 +174,30 @@ impl GenesisConfig {
     }
 }
 
+impl AccountConfig {
+    /// An account holding `num_objects` gas objects of the default value, at a generated address
+    /// if none is given.
+    pub fn with_gas_objects(address: Option<SuiAddress>, num_objects: usize) -> Self {
+        let gas_objects = (0..num_objects)
+            .map(|_| ObjectConfig {
+                object_id: ObjectID::random(),
+                gas_value: DEFAULT_GAS_AMOUNT,
+            })
+            .collect();
+        Self {
+            address,
+            gas_objects,
+            gas_object_ranges: Some(Vec::new()),
+        }
+    }
+
+    /// A funded account of `address`, holding as many gas objects as the accounts created for
+    /// local testing.
+    pub fn for_local_testing(address: SuiAddress) -> Self {
+        Self::with_gas_objects(Some(address), DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT)
+    }
+}
+
 impl Default for GenesisConfig {
     fn default() -> Self {
         Self {
@@ -18,7 +18,9 @@ pub mod node;
 mod swarm;
 pub mod utils;
 
-pub use node::{ConsensusConfig, NodeConfig, ValidatorInfo};
+pub use node::{
+    AuthorityBatchConfig, AuthorityStorePruningConfig, ConsensusConfig, NodeConfig, ValidatorInfo,
+};
 pub use swarm::NetworkConfig;
 
 const SUI_DIR: &str = ".sui";
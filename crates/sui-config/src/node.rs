@@ -12,6 +12,7 @@ use std::collections::BTreeMap;
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use sui_types::base_types::SuiAddress;
 use sui_types::committee::StakeUnit;
 use sui_types::crypto::AccountKeyPair;
@@ -25,6 +26,9 @@ use sui_types::sui_serde::KeyPairBase64;
 // Default max number of concurrent requests served
 pub const DEFAULT_GRPC_CONCURRENCY_LIMIT: usize = 20000;
 
+// Default number of peers followed concurrently by gossip
+pub const DEFAULT_GOSSIP_DEGREE: usize = 4;
+
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -60,18 +64,45 @@ pub struct NodeConfig {
     #[serde(default)]
     pub enable_gossip: bool,
 
+    /// The number of peers gossip follows concurrently.
+    #[serde(default = "default_gossip_degree")]
+    pub gossip_degree: usize,
+
     #[serde(default = "bool_true")]
     pub enable_checkpoint: bool,
 
     #[serde(default)]
     pub enable_reconfig: bool,
 
+    /// Serve the total supply of coin types. Computing a supply scans every object, so it is
+    /// disabled by default.
+    #[serde(default)]
+    pub enable_coin_supply: bool,
+
     #[serde(default)]
     pub grpc_load_shed: Option<bool>,
 
     #[serde(default = "default_concurrency_limit")]
     pub grpc_concurrency_limit: Option<usize>,
 
+    /// Compress validator service responses with gzip for clients that accept it, and accept
+    /// compressed responses from the validators this node follows. Clients only ask for it on
+    /// bulk methods, e.g. batch streams and checkpoints, so small latency sensitive responses
+    /// are sent uncompressed either way.
+    #[serde(default = "bool_true")]
+    pub grpc_compression: bool,
+
+    /// How many of the latest executed transactions have their effects checked against the
+    /// stored objects at startup, to detect a store left inconsistent by a crash.
+    #[serde(default = "default_num_effects_checked_at_startup")]
+    pub num_effects_checked_at_startup: u64,
+
+    #[serde(default)]
+    pub authority_store_pruning_config: AuthorityStorePruningConfig,
+
+    #[serde(default)]
+    pub authority_batch_config: AuthorityBatchConfig,
+
     pub genesis: Genesis,
 }
 
@@ -93,6 +124,10 @@ fn default_metrics_address() -> SocketAddr {
     SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 9184)
 }
 
+pub fn default_num_effects_checked_at_startup() -> u64 {
+    1_000
+}
+
 pub fn default_admin_interface_port() -> u16 {
     1337
 }
@@ -107,6 +142,10 @@ pub fn default_websocket_address() -> Option<SocketAddr> {
     Some(SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 9001))
 }
 
+pub fn default_gossip_degree() -> usize {
+    DEFAULT_GOSSIP_DEGREE
+}
+
 pub fn default_concurrency_limit() -> Option<usize> {
     Some(DEFAULT_GRPC_CONCURRENCY_LIMIT)
 }
@@ -115,6 +154,115 @@ pub fn bool_true() -> bool {
     true
 }
 
+/// How much history the authority store keeps. Object versions and transaction effects are
+/// retained independently, e.g. an explorer may keep every effect while pruning old objects.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuthorityStorePruningConfig {
+    /// How many of the latest versions of each object to keep. All are kept if None.
+    #[serde(default)]
+    pub num_latest_object_versions_to_retain: Option<u64>,
+    /// How many of the latest executed transactions to keep the effects and certificates of. All
+    /// are kept if None. Effects are only pruned once the object versions the transaction took
+    /// as input are pruned.
+    #[serde(default)]
+    pub num_latest_effects_to_retain: Option<u64>,
+    /// How often the store is pruned, in seconds.
+    #[serde(default = "default_pruning_interval_secs")]
+    pub pruning_interval_secs: u64,
+}
+
+impl AuthorityStorePruningConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.num_latest_object_versions_to_retain.is_some()
+            || self.num_latest_effects_to_retain.is_some()
+    }
+}
+
+impl Default for AuthorityStorePruningConfig {
+    fn default() -> Self {
+        Self {
+            num_latest_object_versions_to_retain: None,
+            num_latest_effects_to_retain: None,
+            pruning_interval_secs: default_pruning_interval_secs(),
+        }
+    }
+}
+
+fn default_pruning_interval_secs() -> u64 {
+    3600
+}
+
+/// How the authority batches executed transactions and streams them to subscribers.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuthorityBatchConfig {
+    /// How many executed transactions, or events, a subscriber may fall behind before it is
+    /// dropped.
+    #[serde(
+        default = "default_broadcast_capacity",
+        deserialize_with = "deserialize_broadcast_capacity"
+    )]
+    pub broadcast_capacity: usize,
+    /// The number of transactions after which a batch is closed.
+    #[serde(default = "default_min_batch_size")]
+    pub min_batch_size: u64,
+    /// The serialized size, in bytes, of the certificates and effects of the transactions in a
+    /// batch after which it is closed, even if it holds fewer than `min_batch_size` transactions.
+    #[serde(default = "default_max_batch_size_bytes")]
+    pub max_batch_size_bytes: u64,
+    /// The time after which a batch is closed, whatever its size, in milliseconds.
+    #[serde(default = "default_max_batch_delay_ms")]
+    pub max_delay_ms: u64,
+}
+
+impl AuthorityBatchConfig {
+    pub fn max_delay(&self) -> Duration {
+        Duration::from_millis(self.max_delay_ms)
+    }
+}
+
+impl Default for AuthorityBatchConfig {
+    fn default() -> Self {
+        Self {
+            broadcast_capacity: default_broadcast_capacity(),
+            min_batch_size: default_min_batch_size(),
+            max_batch_size_bytes: default_max_batch_size_bytes(),
+            max_delay_ms: default_max_batch_delay_ms(),
+        }
+    }
+}
+
+fn default_broadcast_capacity() -> usize {
+    10_000
+}
+
+/// Broadcast channels cannot be created without capacity.
+fn deserialize_broadcast_capacity<'de, D>(deserializer: D) -> Result<usize, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let capacity = usize::deserialize(deserializer)?;
+    if capacity == 0 {
+        return Err(serde::de::Error::custom(
+            "broadcast-capacity must be greater than 0",
+        ));
+    }
+    Ok(capacity)
+}
+
+fn default_min_batch_size() -> u64 {
+    1000
+}
+
+fn default_max_batch_size_bytes() -> u64 {
+    16 * 1024 * 1024
+}
+
+fn default_max_batch_delay_ms() -> u64 {
+    1_000
+}
+
 impl Config for NodeConfig {}
 
 impl NodeConfig {
@@ -295,7 +443,7 @@ enum GenesisLocation {
 
 #[cfg(test)]
 mod tests {
-    use super::Genesis;
+    use super::{AuthorityBatchConfig, Genesis};
     use crate::{genesis, NodeConfig};
 
     #[test]
@@ -340,4 +488,19 @@ mod tests {
 
         let _template: NodeConfig = serde_yaml::from_str(TEMPLATE).unwrap();
     }
+
+    #[test]
+    fn reject_zero_broadcast_capacity() {
+        let config: AuthorityBatchConfig =
+            serde_yaml::from_str("broadcast-capacity: 16\n").unwrap();
+        assert_eq!(config.broadcast_capacity, 16);
+        assert_eq!(
+            config.min_batch_size,
+            AuthorityBatchConfig::default().min_batch_size
+        );
+
+        let error =
+            serde_yaml::from_str::<AuthorityBatchConfig>("broadcast-capacity: 0\n").unwrap_err();
+        assert!(error.to_string().contains("greater than 0"));
+    }
 }
@@ -102,11 +102,17 @@ impl NetworkConfig {
             consensus_config: None,
             enable_event_processing: true,
             enable_gossip: true,
+            gossip_degree: crate::node::default_gossip_degree(),
             enable_checkpoint: true,
             enable_reconfig: false,
+            enable_coin_supply: false,
             genesis: validator_config.genesis.clone(),
             grpc_load_shed: None,
             grpc_concurrency_limit: None,
+            grpc_compression: true,
+            num_effects_checked_at_startup: crate::node::default_num_effects_checked_at_startup(),
+            authority_store_pruning_config: Default::default(),
+            authority_batch_config: Default::default(),
         }
     }
 }
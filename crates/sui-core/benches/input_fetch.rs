@@ -0,0 +1,41 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_core::authority::AuthorityStore;
+use sui_types::base_types::{dbg_addr, ObjectID};
+use sui_types::messages::InputObjectKind;
+use sui_types::object::Object;
+use tokio::runtime::Builder;
+
+const NUM_INPUTS: usize = 50;
+const NUM_ITERATIONS: u32 = 10_000;
+
+fn main() {
+    let runtime = Builder::new_multi_thread().enable_all().build().unwrap();
+
+    let working_dir = tempfile::tempdir().unwrap();
+    let store = AuthorityStore::open(working_dir.path(), None);
+
+    let objects: Vec<_> = (0..NUM_INPUTS)
+        .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), dbg_addr(1)))
+        .collect();
+    runtime
+        .block_on(store.bulk_object_insert(&objects.iter().collect::<Vec<_>>()))
+        .unwrap();
+    let inputs: Vec<_> = objects
+        .iter()
+        .map(|o| InputObjectKind::ImmOrOwnedMoveObject(o.compute_object_reference()))
+        .collect();
+
+    let start = std::time::Instant::now();
+    for _ in 0..NUM_ITERATIONS {
+        let fetched = store.get_input_objects(&inputs).unwrap();
+        assert_eq!(fetched.len(), NUM_INPUTS);
+    }
+    let duration = start.elapsed();
+
+    println!(
+        "Fetching {NUM_INPUTS} input objects: {:?} per transaction",
+        duration / NUM_ITERATIONS
+    );
+}
@@ -0,0 +1,73 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_core::authority::AuthorityStore;
+use sui_types::base_types::{
+    dbg_addr, AuthorityName, ObjectDigest, ObjectID, ObjectRef, SequenceNumber, TransactionDigest,
+};
+use sui_types::crypto::{get_key_pair, AccountKeyPair, AuthorityKeyPair, KeypairTraits, Signature};
+use sui_types::messages::{SignedTransaction, Transaction, TransactionData};
+use tokio::runtime::Builder;
+
+const NUM_STORED: usize = 10_000;
+const NUM_ITERATIONS: u32 = 100_000;
+
+fn random_object_ref() -> ObjectRef {
+    (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    )
+}
+
+fn main() {
+    let runtime = Builder::new_multi_thread().enable_all().build().unwrap();
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let (_, authority_key): (_, AuthorityKeyPair) = get_key_pair();
+    let authority: AuthorityName = authority_key.public().into();
+
+    let _guard = runtime.enter();
+
+    let working_dir = tempfile::tempdir().unwrap();
+    let store = AuthorityStore::open(working_dir.path(), None);
+    let mut stored = Vec::with_capacity(NUM_STORED);
+    for _ in 0..NUM_STORED {
+        let data = TransactionData::new_transfer(
+            dbg_addr(2),
+            random_object_ref(),
+            sender,
+            random_object_ref(),
+            10000,
+        );
+        let signature = Signature::new(&data, &sender_key);
+        let transaction = SignedTransaction::new(
+            0,
+            Transaction::new(data, signature),
+            authority,
+            &authority_key,
+        );
+        stored.push(*transaction.digest());
+        runtime
+            .block_on(store.lock_and_write_transaction(0, &[], transaction))
+            .unwrap();
+    }
+    assert!(stored
+        .iter()
+        .all(|digest| store.transaction_exists(digest).unwrap()));
+
+    let fresh: Vec<_> = (0..NUM_ITERATIONS)
+        .map(|_| TransactionDigest::random())
+        .collect();
+
+    let start = std::time::Instant::now();
+    for digest in &fresh {
+        assert!(!store.transaction_exists(digest).unwrap());
+        assert!(!store.effects_exists(digest).unwrap());
+    }
+    let duration = start.elapsed();
+
+    println!(
+        "Checking a fresh transaction against {NUM_STORED} stored ones: {:?} per transaction",
+        duration / NUM_ITERATIONS
+    );
+}
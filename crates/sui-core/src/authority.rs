@@ -8,7 +8,7 @@ use crate::{
     checkpoints::CheckpointStore,
     event_handler::EventHandler,
     execution_engine,
-    query_helpers::QueryHelpers,
+    query_helpers::{QueryHelpers, MAX_TX_RANGE_SIZE},
     transaction_input_checker,
     transaction_streamer::TransactionStreamer,
 };
@@ -18,31 +18,40 @@ use bincode::Error;
 use chrono::prelude::*;
 use fastcrypto::ed25519::Ed25519KeyPair as ConsensusKeyPair;
 use fastcrypto::traits::KeyPair;
+use futures::{stream, Stream};
 use move_bytecode_utils::module_cache::SyncModuleCache;
-use move_core_types::{language_storage::ModuleId, resolver::ModuleResolver};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag, TypeTag},
+    resolver::ModuleResolver,
+};
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use narwhal_config::Committee as ConsensusCommittee;
 use narwhal_executor::ExecutionStateError;
 use narwhal_executor::{ExecutionIndices, ExecutionState};
 use parking_lot::Mutex;
 use prometheus::{
-    register_histogram_with_registry, register_int_counter_with_registry,
-    register_int_gauge_with_registry, Histogram, IntCounter, IntGauge,
+    register_histogram_with_registry, register_int_counter_vec_with_registry,
+    register_int_counter_with_registry, register_int_gauge_vec_with_registry,
+    register_int_gauge_with_registry, Histogram, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
 };
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 use std::path::PathBuf;
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     pin::Pin,
     sync::{
         atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
+    time::Duration,
 };
 use sui_adapter::adapter;
 use sui_adapter::temporary_store::InnerTemporaryStore;
 use sui_config::genesis::Genesis;
-use sui_json_rpc_types::SuiEventEnvelope;
+use sui_config::{AuthorityBatchConfig, AuthorityStorePruningConfig};
+use sui_json_rpc_types::{SuiEventEnvelope, SystemState};
 use sui_storage::{
     event_store::{EventStore, EventStoreType, StoredEvent},
     write_ahead_log::{DBTxGuard, TxGuard, WriteAheadLog},
@@ -51,12 +60,16 @@ use sui_storage::{
 use sui_types::{
     base_types::*,
     batch::{TxSequenceNumber, UpdateItem},
+    coin::Coin,
     committee::Committee,
     crypto::AuthoritySignature,
     error::{SuiError, SuiResult},
+    event::Event,
     fp_ensure,
+    gas::{self, SuiGasStatus},
     messages::*,
-    object::{Object, ObjectFormatOptions, ObjectRead},
+    object::{Object, ObjectFormatOptions, ObjectRead, ObjectTombstone},
+    protocol_config::ProtocolConfig,
     storage::{BackingPackageStore, DeleteKind},
     MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
 };
@@ -90,20 +103,44 @@ pub mod authority_store_tables;
 mod authority_store;
 use crate::epoch::epoch_store::EpochStore;
 pub use authority_store::{
-    AuthorityStore, GatewayStore, ResolverWrapper, SuiDataStore, UpdateType,
+    AuthorityStore, GatewayStore, PreExecutionStore, ResolverWrapper, SuiDataStore, UpdateType,
 };
 use sui_types::committee::EpochId;
 use sui_types::crypto::AuthorityKeyPair;
 use sui_types::messages_checkpoint::{
-    CheckpointRequest, CheckpointRequestType, CheckpointResponse, CheckpointSequenceNumber,
+    AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointContents, CheckpointRequest,
+    CheckpointRequestType, CheckpointResponse, CheckpointSequenceNumber,
 };
 use sui_types::object::Owner;
 use sui_types::sui_system_state::SuiSystemState;
 
 pub mod authority_notifier;
+mod coin_supply_cache;
+pub use coin_supply_cache::CoinSupplyCache;
+mod throughput_counter;
+use throughput_counter::ThroughputCounter;
 
 pub const MAX_ITEMS_LIMIT: u64 = 1_000;
-const BROADCAST_CAPACITY: usize = 10_000;
+
+/// The most executed transactions whose events `handle_event_streaming` replays from the store.
+pub const MAX_EVENT_REPLAY_TRANSACTIONS: u64 = 10_000;
+/// How many executed transactions the events of are read from the store at a time when
+/// replaying them.
+const EVENT_REPLAY_PAGE_SIZE: u64 = 100;
+
+/// The events emitted by an executed transaction, along with its sequence number and digest.
+pub type TransactionEvents = (TxSequenceNumber, TransactionDigest, Vec<Event>);
+pub type EventBroadcastSender = tokio::sync::broadcast::Sender<TransactionEvents>;
+pub type EventBroadcastReceiver = tokio::sync::broadcast::Receiver<TransactionEvents>;
+/// How often to check whether a transaction was checkpointed while waiting for it.
+const CHECKPOINT_WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Number of packages, those which used the most gas, given their own label in the per-package
+/// metrics. Calls to the other packages are counted under `OTHER_PACKAGES_LABEL`.
+const MAX_TRACKED_PACKAGES: usize = 100;
+/// Number of packages whose usage is remembered to rank them. When full, the unlabeled package
+/// which used the least gas is forgotten.
+const MAX_PACKAGE_CANDIDATES: usize = 10 * MAX_TRACKED_PACKAGES;
+const OTHER_PACKAGES_LABEL: &str = "other";
 
 pub(crate) const MAX_TX_RECOVERY_RETRY: u32 = 3;
 type CertTxGuard<'a> = DBTxGuard<'a, CertifiedTransaction>;
@@ -116,12 +153,24 @@ pub struct AuthorityMetrics {
     total_effects: IntCounter,
     total_events: IntCounter,
     signature_errors: IntCounter,
+    tx_rejected: IntCounterVec,
     pub shared_obj_tx: IntCounter,
+    shared_object_lock_errors: IntCounter,
+    shared_object_retries: IntCounter,
     tx_already_processed: IntCounter,
     num_input_objs: Histogram,
     num_shared_objects: Histogram,
     batch_size: Histogram,
 
+    pub package_calls: IntGaugeVec,
+    pub package_gas: IntGaugeVec,
+    total_gas_charged: IntCounter,
+    gas_charged_per_tx: Histogram,
+    package_usage: Mutex<PackageUsage>,
+
+    cert_processing_latency: Histogram,
+    db_update_latency: Histogram,
+
     total_consensus_txns: IntCounter,
 
     pub follower_items_streamed: IntCounter,
@@ -142,6 +191,11 @@ const POSITIVE_INT_BUCKETS: &[f64] = &[
     1., 2., 5., 10., 20., 50., 100., 200., 500., 1000., 2000., 5000., 10000., 20000., 50000.,
 ];
 
+// Buckets for latencies in seconds, from 1ms to 10s
+const LATENCY_SEC_BUCKETS: &[f64] = &[
+    0.001, 0.002, 0.005, 0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1., 2., 5., 10.,
+];
+
 impl AuthorityMetrics {
     pub fn new(registry: &prometheus::Registry) -> AuthorityMetrics {
         Self {
@@ -182,12 +236,31 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            tx_rejected: register_int_counter_vec_with_registry!(
+                "tx_rejected",
+                "Number of transactions and certificates rejected, by reason",
+                &["reason"],
+                registry,
+            )
+            .unwrap(),
             shared_obj_tx: register_int_counter_with_registry!(
                 "num_shared_obj_tx",
                 "Number of transactions involving shared objects",
                 registry,
             )
             .unwrap(),
+            shared_object_lock_errors: register_int_counter_with_registry!(
+                "shared_object_lock_errors",
+                "Number of certificates rejected because their shared object locks did not match",
+                registry,
+            )
+            .unwrap(),
+            shared_object_retries: register_int_counter_with_registry!(
+                "shared_object_retries",
+                "Number of shared object certificates sequenced again by consensus",
+                registry,
+            )
+            .unwrap(),
             tx_already_processed: register_int_counter_with_registry!(
                 "num_tx_already_processed",
                 "Number of transaction orders already processed previously",
@@ -215,6 +288,48 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            package_calls: register_int_gauge_vec_with_registry!(
+                "package_calls",
+                "Number of executed Move calls, by called package",
+                &["package"],
+                registry,
+            )
+            .unwrap(),
+            package_gas: register_int_gauge_vec_with_registry!(
+                "package_gas",
+                "Total gas used by transactions calling a package, by called package",
+                &["package"],
+                registry,
+            )
+            .unwrap(),
+            package_usage: Mutex::new(PackageUsage::default()),
+            total_gas_charged: register_int_counter_with_registry!(
+                "total_gas_charged",
+                "Total gas charged by executed certificates, before storage rebates",
+                registry,
+            )
+            .unwrap(),
+            gas_charged_per_tx: register_histogram_with_registry!(
+                "gas_charged_per_tx",
+                "Distribution of the gas charged per executed certificate",
+                POSITIVE_INT_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            cert_processing_latency: register_histogram_with_registry!(
+                "cert_processing_latency",
+                "Latency of executing a certificate to effects, in seconds",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            db_update_latency: register_histogram_with_registry!(
+                "db_update_latency",
+                "Latency of writing the effects of a certificate to the store, in seconds",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
             total_consensus_txns: register_int_counter_with_registry!(
                 "total_consensus_txns",
                 "Total number of consensus transactions received from narwhal",
@@ -271,6 +386,152 @@ impl AuthorityMetrics {
             .unwrap(),
         }
     }
+
+    fn record_rejection(&self, error: &SuiError) {
+        self.tx_rejected
+            .with_label_values(&[rejection_reason(error)])
+            .inc();
+    }
+
+    /// Records the Move calls made by an executed transaction which used `gas_used` gas. The gas is
+    /// attributed once to every package called. Only the `MAX_TRACKED_PACKAGES` packages which
+    /// used the most gas get their own label, to bound the metric cardinality.
+    fn record_package_calls(&self, kind: &TransactionKind, gas_used: u64) {
+        let mut calls: HashMap<ObjectID, u64> = HashMap::new();
+        for single in kind.single_transactions() {
+            if let SingleTransactionKind::Call(call) = single {
+                *calls.entry(call.package.0).or_default() += 1;
+            }
+        }
+        if calls.is_empty() {
+            return;
+        }
+        let mut usage = self.package_usage.lock();
+        for (package, count) in calls {
+            usage.record(
+                package,
+                count,
+                gas_used,
+                &self.package_calls,
+                &self.package_gas,
+            );
+        }
+    }
+}
+
+/// The calls made to, and the gas used by, the packages called, ranked by gas used to label
+/// only the top packages in the per-package metrics.
+#[derive(Default)]
+struct PackageUsage {
+    /// Calls and gas of the packages ranked, at most `MAX_PACKAGE_CANDIDATES`.
+    usage: HashMap<ObjectID, (u64, u64)>,
+    /// The packages with their own label, by gas used.
+    labeled: BTreeSet<(u64, ObjectID)>,
+    /// The other packages ranked, by gas used.
+    unlabeled: BTreeSet<(u64, ObjectID)>,
+    /// Calls and gas of all the packages without their own label, including forgotten ones.
+    other: (u64, u64),
+}
+
+impl PackageUsage {
+    fn record(
+        &mut self,
+        package: ObjectID,
+        calls: u64,
+        gas: u64,
+        calls_metric: &IntGaugeVec,
+        gas_metric: &IntGaugeVec,
+    ) {
+        if !self.usage.contains_key(&package) && self.usage.len() >= MAX_PACKAGE_CANDIDATES {
+            // The usage of the forgotten package stays counted in `other`.
+            if let Some(least) = self.unlabeled.iter().next().copied() {
+                self.unlabeled.remove(&least);
+                self.usage.remove(&least.1);
+            }
+        }
+        let entry = self.usage.entry(package).or_default();
+        let previous_gas = entry.1;
+        entry.0 += calls;
+        entry.1 += gas;
+        let (total_calls, total_gas) = *entry;
+
+        if self.labeled.remove(&(previous_gas, package)) {
+            self.labeled.insert((total_gas, package));
+        } else {
+            self.unlabeled.remove(&(previous_gas, package));
+            self.other.0 += calls;
+            self.other.1 += gas;
+            // The package takes the label of the labeled package which used the least gas, if
+            // it used more.
+            if self.labeled.len() >= MAX_TRACKED_PACKAGES {
+                match self.labeled.iter().next().copied() {
+                    Some(least) if least.0 < total_gas => {
+                        self.labeled.remove(&least);
+                        self.unlabeled.insert(least);
+                        self.other.0 += self.usage[&least.1].0;
+                        self.other.1 += least.0;
+                        let label = least.1.to_string();
+                        let _ = calls_metric.remove_label_values(&[&label]);
+                        let _ = gas_metric.remove_label_values(&[&label]);
+                    }
+                    _ => {
+                        self.unlabeled.insert((total_gas, package));
+                        self.set_other(calls_metric, gas_metric);
+                        return;
+                    }
+                }
+            }
+            self.labeled.insert((total_gas, package));
+            self.other.0 -= total_calls;
+            self.other.1 -= total_gas;
+            self.set_other(calls_metric, gas_metric);
+        }
+        let label = package.to_string();
+        calls_metric
+            .with_label_values(&[&label])
+            .set(total_calls as i64);
+        gas_metric
+            .with_label_values(&[&label])
+            .set(total_gas as i64);
+    }
+
+    fn set_other(&self, calls_metric: &IntGaugeVec, gas_metric: &IntGaugeVec) {
+        calls_metric
+            .with_label_values(&[OTHER_PACKAGES_LABEL])
+            .set(self.other.0 as i64);
+        gas_metric
+            .with_label_values(&[OTHER_PACKAGES_LABEL])
+            .set(self.other.1 as i64);
+    }
+}
+
+/// Maps a transaction rejection error to the `reason` label used by `tx_rejected`.
+fn rejection_reason(error: &SuiError) -> &'static str {
+    match error {
+        SuiError::ObjectErrors { errors } => {
+            errors.first().map_or("object_errors", rejection_reason)
+        }
+        SuiError::InvalidSignature { .. }
+        | SuiError::IncorrectSigner { .. }
+        | SuiError::UnknownSigner
+        | SuiError::InvalidMultiSigPublicKey { .. }
+        | SuiError::MultiSigThresholdNotMet { .. } => "invalid_signature",
+        SuiError::ConflictingTransaction { .. } | SuiError::ObjectLockedAtFutureEpoch { .. } => {
+            "conflicting_lock"
+        }
+        SuiError::TransactionLockDoesNotExist => "lock_does_not_exist",
+        SuiError::ObjectNotFound { .. } | SuiError::ObjectDeleted { .. } => "object_not_found",
+        SuiError::UnexpectedSequenceNumber { .. } => "object_version_mismatch",
+        SuiError::InsufficientGas { .. }
+        | SuiError::GasBudgetTooHigh { .. }
+        | SuiError::TransferInsufficientBalance { .. } => "insufficient_gas",
+        SuiError::ValidatorHaltedAtEpochEnd => "validator_halted",
+        SuiError::InvalidSystemTransaction => "invalid_system_transaction",
+        SuiError::WrongEpoch { .. } => "wrong_epoch",
+        SuiError::TransactionExpired { .. } => "transaction_expired",
+        SuiError::CertificateRequiresQuorum => "invalid_certificate",
+        _ => "other",
+    }
 }
 
 /// a Trait object for `signature::Signer` that is:
@@ -292,11 +553,13 @@ pub struct AuthorityState {
     // Epoch related information.
     /// Committee of this Sui instance.
     pub committee: ArcSwap<Committee>,
+    /// Protocol parameters of the current epoch.
+    protocol_config: ArcSwap<ProtocolConfig>,
     /// A global lock to halt all transaction/cert processing.
     halted: AtomicBool,
 
     /// Move native functions that are available to invoke
-    pub(crate) _native_functions: NativeFunctionTable,
+    pub(crate) native_functions: NativeFunctionTable,
     pub(crate) move_vm: Arc<MoveVM>,
 
     /// The database
@@ -320,6 +583,9 @@ pub struct AuthorityState {
     /// Keep as None if there is no need for this.
     pub(crate) batch_channels: BroadcastSender, // TODO: remove pub
 
+    /// The sender to notify event subscribers of the events of newly executed transactions.
+    event_channels: EventBroadcastSender,
+
     // The Transaction notifier ticketing engine.
     pub(crate) batch_notifier: Arc<authority_notifier::TransactionNotifier>, // TODO: remove pub
 
@@ -328,11 +594,43 @@ pub struct AuthorityState {
 
     pub metrics: Arc<AuthorityMetrics>,
 
+    /// The rate of executed certificates over the last minute, served to operators.
+    cert_throughput: ThroughputCounter,
+
+    /// The total supply of coin types, served to dashboards. None unless enabled in the config,
+    /// as computing a supply scans every object.
+    pub(crate) coin_supply: Option<CoinSupplyCache>,
+
     // Cache the latest checkpoint number to avoid expensive locking to access checkpoint store
     latest_checkpoint_num: AtomicU64,
 
     /// A channel to tell consensus to reconfigure.
     tx_reconfigure_consensus: Sender<(ConsensusKeyPair, ConsensusCommittee)>,
+
+    /// Test only: failures and latency injected into `handle_transaction`, along with the
+    /// number of calls made since they were set.
+    #[cfg(any(test, feature = "simulate-failure"))]
+    simulated_failure: Mutex<Option<(SimulatedFailure, u64)>>,
+}
+
+/// Test only: makes an authority flaky, to exercise clients against it. Calls to
+/// `handle_transaction` are delayed by `latency`, then `failure_rate` of them fail with `error`.
+/// Which calls fail is deterministic: with a rate of 0.5, every second call fails.
+#[cfg(any(test, feature = "simulate-failure"))]
+#[derive(Clone, Debug)]
+pub struct SimulatedFailure {
+    pub failure_rate: f64,
+    pub error: SuiError,
+    pub latency: Duration,
+}
+
+#[cfg(any(test, feature = "simulate-failure"))]
+impl SimulatedFailure {
+    /// Whether the call numbered `call` (starting from 0) fails, spreading failures evenly.
+    fn fails(&self, call: u64) -> bool {
+        let failures_until = |calls: u64| (calls as f64 * self.failure_rate).floor();
+        failures_until(call + 1) > failures_until(call)
+    }
 }
 
 /// The authority state encapsulates all state, drives execution, and ensures safety.
@@ -351,6 +649,11 @@ impl AuthorityState {
         self.batch_channels.subscribe()
     }
 
+    /// Get a broadcast receiver for the events of newly executed transactions
+    pub fn subscribe_events(&self) -> EventBroadcastReceiver {
+        self.event_channels.subscribe()
+    }
+
     pub fn epoch(&self) -> EpochId {
         self.committee.load().epoch
     }
@@ -359,6 +662,36 @@ impl AuthorityState {
         &self.epoch_store
     }
 
+    /// Return the protocol parameters of the current epoch, which transactions are checked
+    /// against.
+    pub fn get_protocol_config(&self) -> Arc<ProtocolConfig> {
+        self.protocol_config.load_full()
+    }
+
+    /// Test only: make `handle_transaction` slow or failing as described by `failure`, or behave
+    /// normally again when it is `None`.
+    #[cfg(any(test, feature = "simulate-failure"))]
+    pub fn set_simulated_failure(&self, failure: Option<SimulatedFailure>) {
+        *self.simulated_failure.lock() = failure.map(|failure| (failure, 0));
+    }
+
+    #[cfg(any(test, feature = "simulate-failure"))]
+    async fn simulate_failure(&self) -> SuiResult {
+        let (latency, error) = match &mut *self.simulated_failure.lock() {
+            Some((failure, calls)) => {
+                let fails = failure.fails(*calls);
+                *calls += 1;
+                (failure.latency, fails.then(|| failure.error.clone()))
+            }
+            None => return Ok(()),
+        };
+        tokio::time::sleep(latency).await;
+        match error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
     async fn handle_transaction_impl(
         &self,
         transaction: Transaction,
@@ -382,9 +715,24 @@ impl AuthorityState {
             return Err(SuiError::ValidatorHaltedAtEpochEnd);
         }
 
-        let (_gas_status, input_objects) =
-            transaction_input_checker::check_transaction_input(&self.database, &transaction)
-                .await?;
+        // Reject expired transactions before looking at their inputs, which are likely stale.
+        if let Some(expiration_epoch) = transaction.signed_data.data.expiration_epoch {
+            let current_epoch = self.epoch();
+            fp_ensure!(
+                current_epoch <= expiration_epoch,
+                SuiError::TransactionExpired {
+                    expiration_epoch,
+                    current_epoch,
+                }
+            );
+        }
+
+        let (_gas_status, input_objects) = transaction_input_checker::check_transaction_input(
+            &self.database,
+            &self.get_protocol_config(),
+            &transaction,
+        )
+        .await?;
 
         let owned_objects = input_objects.filter_owned_objects();
 
@@ -409,10 +757,13 @@ impl AuthorityState {
     ) -> Result<TransactionInfoResponse, SuiError> {
         let transaction_digest = *transaction.digest();
         debug!(tx_digest=?transaction_digest, "handle_transaction. Tx data: {:?}", transaction.signed_data.data);
+        #[cfg(any(test, feature = "simulate-failure"))]
+        self.simulate_failure().await?;
         self.metrics.tx_orders.inc();
         // Check the sender's signature.
         transaction.verify().map_err(|e| {
             self.metrics.signature_errors.inc();
+            self.metrics.record_rejection(&e);
             e
         })?;
 
@@ -426,6 +777,7 @@ impl AuthorityState {
                     self.metrics.tx_already_processed.inc();
                     Ok(self.make_transaction_info(&transaction_digest).await?)
                 } else {
+                    self.metrics.record_rejection(&err);
                     Err(err)
                 }
             }
@@ -515,6 +867,63 @@ impl AuthorityState {
             .tap_err(|e| debug!(?digest, "process_certificate failed: {}", e))
     }
 
+    /// Verify a certificate against the current committee, then execute it. The error tells a
+    /// certificate that failed verification apart from a valid one that failed to execute.
+    pub async fn verify_and_execute_certificate(
+        &self,
+        mut certificate: CertifiedTransaction,
+    ) -> Result<TransactionInfoResponse, CertificateExecutionError> {
+        certificate.verify(&self.committee.load()).map_err(|e| {
+            self.metrics.signature_errors.inc();
+            self.metrics.record_rejection(&e);
+            CertificateExecutionError::Verify(e)
+        })?;
+        certificate.is_verified = true;
+
+        self.handle_certificate(certificate)
+            .await
+            .map_err(CertificateExecutionError::Execute)
+    }
+
+    /// Execute the certificate, then wait for its transaction to be included in a checkpoint of
+    /// this authority. Returns the execution response along with the sequence number of that
+    /// checkpoint, or a `TimeoutError` if no checkpoint includes it within `timeout`.
+    pub async fn execute_transaction_and_wait_for_checkpoint(
+        &self,
+        certificate: CertifiedTransaction,
+        timeout: Duration,
+    ) -> SuiResult<(TransactionInfoResponse, CheckpointSequenceNumber)> {
+        let checkpoints =
+            self.checkpoints
+                .clone()
+                .ok_or_else(|| SuiError::UnsupportedFeatureError {
+                    error: "This authority does not produce checkpoints".to_string(),
+                })?;
+        let digest = *certificate.digest();
+        let response = self.handle_certificate(certificate).await?;
+        let effects = response
+            .signed_effects
+            .as_ref()
+            .ok_or(SuiError::TransactionNotFound { digest })?;
+        let execution_digests = ExecutionDigests::new(digest, effects.effects.digest());
+
+        let wait = async {
+            loop {
+                let seq = checkpoints
+                    .lock()
+                    .get_transaction_checkpoint(&execution_digests)?;
+                if let Some(seq) = seq {
+                    return SuiResult::Ok(seq);
+                }
+                tokio::time::sleep(CHECKPOINT_WAIT_POLL_INTERVAL).await;
+            }
+        };
+        let seq = tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| SuiError::TimeoutError)??;
+        Ok((response, seq))
+    }
+
     #[instrument(level = "trace", skip_all)]
     async fn check_shared_locks(
         &self,
@@ -541,25 +950,35 @@ impl AuthorityState {
         // (i) was not assigned a sequence number, or
         // (ii) has a different sequence number than the current one.
 
-        let lock_errors: Vec<_> = shared_object_refs
+        let contended: Vec<_> = shared_object_refs
             .iter()
-            .filter_map(|(object_id, version, _)| {
-                if !shared_locks.contains_key(object_id) {
-                    Some(SuiError::SharedObjectLockNotSetObject)
-                } else if shared_locks[object_id] != *version {
-                    Some(SuiError::UnexpectedSequenceNumber {
-                        object_id: *object_id,
-                        // This sequence number is the one attributed by consensus.
-                        expected_sequence: shared_locks[object_id],
-                        // This sequence number is the one we currently have in the database.
-                        given_sequence: *version,
-                    })
-                } else {
-                    None
-                }
+            .filter(|(object_id, version, _)| shared_locks.get(object_id) != Some(version))
+            .map(|(object_id, version, _)| (*object_id, shared_locks.get(object_id), *version))
+            .collect();
+        let lock_errors: Vec<_> = contended
+            .iter()
+            .map(|(object_id, expected, given)| match expected {
+                None => SuiError::SharedObjectLockNotSetObject,
+                Some(expected) => SuiError::UnexpectedSequenceNumber {
+                    object_id: *object_id,
+                    // This sequence number is the one attributed by consensus.
+                    expected_sequence: **expected,
+                    // This sequence number is the one we currently have in the database.
+                    given_sequence: *given,
+                },
             })
             .collect();
 
+        if !lock_errors.is_empty() {
+            self.metrics.shared_object_lock_errors.inc();
+            warn!(
+                tx_digest = ?transaction_digest,
+                // (object id, sequence number assigned by consensus, sequence number in the db)
+                ?contended,
+                "Shared object locks do not match the objects in the database"
+            );
+        }
+
         fp_ensure!(
             lock_errors.is_empty(),
             // NOTE: the error message here will say 'Error acquiring lock' but what it means is
@@ -588,8 +1007,10 @@ impl AuthorityState {
 
         if self.is_halted() && !certificate.signed_data.data.kind.is_system_tx() {
             tx_guard.release();
+            let err = SuiError::ValidatorHaltedAtEpochEnd;
+            self.metrics.record_rejection(&err);
             // TODO: Do we want to include the new validator set?
-            return Err(SuiError::ValidatorHaltedAtEpochEnd);
+            return Err(err);
         }
 
         // Check the certificate signatures.
@@ -598,6 +1019,7 @@ impl AuthorityState {
             .in_scope(|| certificate.verify(committee))
             .map_err(|e| {
                 self.metrics.signature_errors.inc();
+                self.metrics.record_rejection(&e);
                 e
             })?;
 
@@ -610,6 +1032,7 @@ impl AuthorityState {
                 Err(e) => {
                     debug!(name = ?self.name, ?digest, "Error preparing transaction: {}", e);
                     tx_guard.release();
+                    self.metrics.record_rejection(&e);
                     return Err(e);
                 }
                 Ok(res) => res,
@@ -620,16 +1043,26 @@ impl AuthorityState {
 
         // If commit_certificate returns an error, tx_guard will be dropped and the certificate
         // will be persisted in the log for later recovery.
-        self.commit_certificate(inner_temporary_store, certificate, &signed_effects)
+        let seq = self
+            .commit_certificate(inner_temporary_store, certificate, &signed_effects)
             .await
             .tap_err(|e| error!(?digest, "commit_certificate failed: {}", e))?;
 
         // commit_certificate finished, the tx is fully committed to the store.
         tx_guard.commit_tx();
 
+        // Subscribers too slow to keep up miss events and are told so when they next receive,
+        // execution never waits on them.
+        if !signed_effects.effects.events.is_empty() && self.event_channels.receiver_count() > 0 {
+            let _ = self
+                .event_channels
+                .send((seq, digest, signed_effects.effects.events.clone()));
+        }
+
         // Update metrics.
         self.metrics.total_effects.inc();
         self.metrics.total_certs.inc();
+        self.cert_throughput.record();
 
         if shared_object_count > 0 {
             self.metrics.shared_obj_tx.inc();
@@ -645,6 +1078,12 @@ impl AuthorityState {
             .batch_size
             .observe(certificate.signed_data.data.kind.batch_size() as f64);
 
+        let gas_charged = signed_effects.effects.gas_used.gas_used();
+        self.metrics.total_gas_charged.inc_by(gas_charged);
+        self.metrics.gas_charged_per_tx.observe(gas_charged as f64);
+        self.metrics
+            .record_package_calls(&certificate.signed_data.data.kind, gas_charged);
+
         Ok(TransactionInfoResponse {
             signed_transaction: self.database.get_transaction(&digest)?,
             certified_transaction: Some(certificate.clone()),
@@ -667,8 +1106,12 @@ impl AuthorityState {
         certificate: &CertifiedTransaction,
         transaction_digest: TransactionDigest,
     ) -> SuiResult<(InnerTemporaryStore, SignedTransactionEffects)> {
-        let (gas_status, input_objects) =
-            transaction_input_checker::check_transaction_input(&self.database, certificate).await?;
+        let (gas_status, input_objects) = transaction_input_checker::check_transaction_input(
+            &self.database,
+            &self.get_protocol_config(),
+            certificate,
+        )
+        .await?;
 
         // At this point we need to check if any shared objects need locks,
         // and whether they have them.
@@ -691,6 +1134,7 @@ impl AuthorityState {
         let transaction_dependencies = input_objects.transaction_dependencies();
         let temporary_store =
             TemporaryStore::new(self.database.clone(), input_objects, transaction_digest);
+        let execution_timer = self.metrics.cert_processing_latency.start_timer();
         let (inner_temp_store, effects, _execution_error) =
             execution_engine::execute_transaction_to_effects(
                 shared_object_refs,
@@ -699,17 +1143,152 @@ impl AuthorityState {
                 transaction_digest,
                 transaction_dependencies,
                 &self.move_vm,
-                &self._native_functions,
+                &self.native_functions,
                 gas_status,
                 self.epoch(),
             );
+        execution_timer.observe_duration();
 
-        // TODO: Distribute gas charge and rebate, which can be retrieved from effects.
+        // TODO: Distribute gas charge and rebate, which can be retrieved from effects. The charges
+        // are only observed through the `total_gas_charged` metrics for now: distributing them at
+        // epoch change needs per-epoch totals that every validator derives identically, i.e. from
+        // the certified checkpoint contents rather than from what this validator executed.
         let signed_effects = effects.to_sign_effects(self.epoch(), &self.name, &*self.secret);
 
         Ok((inner_temp_store, signed_effects))
     }
 
+    /// Re-execute an already executed certificate against the versions of the objects it read,
+    /// without committing anything, and return the resulting effects. The versions of the shared
+    /// objects are assigned by consensus and are not part of the certificate, they are taken from
+    /// the effects the certificate originally produced. Gas is metered with the parameters of the
+    /// epoch the certificate was signed in, and objects outside the inputs are read as they were
+    /// before the certificate executed.
+    pub async fn replay_certificate(
+        &self,
+        certificate: &CertifiedTransaction,
+        effects: &TransactionEffects,
+    ) -> SuiResult<TransactionEffects> {
+        let shared_versions: HashMap<_, _> = effects
+            .shared_objects
+            .iter()
+            .map(|(id, version, _)| (*id, *version))
+            .collect();
+
+        let mut input_versions = BTreeMap::new();
+        for kind in certificate.signed_data.data.input_objects()? {
+            match kind {
+                InputObjectKind::MovePackage(_) => (),
+                InputObjectKind::ImmOrOwnedMoveObject((id, version, _)) => {
+                    input_versions.insert(id, version);
+                }
+                InputObjectKind::SharedMoveObject(id) => {
+                    if let Some(version) = shared_versions.get(&id) {
+                        input_versions.insert(id, *version);
+                    }
+                }
+            }
+        }
+        self.replay_against_inputs(certificate, &input_versions, Some(effects))
+    }
+
+    /// Execute a certificate against an explicit snapshot of its inputs, without committing
+    /// anything, and return the resulting effects. `input_versions` gives the version to read of
+    /// every input object, whatever version the certificate references, so that the views of two
+    /// authorities disagreeing on effects can each be reproduced. Packages are immutable and are
+    /// read at their latest version unless listed.
+    pub async fn replay_effects_against_snapshot(
+        &self,
+        certificate: &CertifiedTransaction,
+        input_versions: &BTreeMap<ObjectID, SequenceNumber>,
+    ) -> SuiResult<TransactionEffects> {
+        let effects = self.database.get_effects(certificate.digest()).ok();
+        self.replay_against_inputs(certificate, input_versions, effects.as_ref())
+    }
+
+    /// Execute `certificate` against its inputs at `input_versions`. When the certificate was
+    /// executed, `effects` gives the versions it wrote, so that the objects it wrote are read as
+    /// they were before it.
+    fn replay_against_inputs(
+        &self,
+        certificate: &CertifiedTransaction,
+        input_versions: &BTreeMap<ObjectID, SequenceNumber>,
+        effects: Option<&TransactionEffects>,
+    ) -> SuiResult<TransactionEffects> {
+        let transaction_digest = *certificate.digest();
+        let data = &certificate.signed_data.data;
+
+        let mut objects = Vec::new();
+        for kind in data.input_objects()? {
+            let object_id = kind.object_id();
+            let object = match (&kind, input_versions.get(&object_id)) {
+                (_, Some(version)) => self.database.get_object_by_key(&object_id, *version)?,
+                (InputObjectKind::MovePackage(id), None) => self.database.get_object(id)?,
+                (_, None) => None,
+            }
+            .ok_or(SuiError::ObjectNotFound { object_id })?;
+            // Owned inputs are referenced at the snapshot version, which may not be the one the
+            // certificate references.
+            let kind = match kind {
+                InputObjectKind::ImmOrOwnedMoveObject(_) => {
+                    InputObjectKind::ImmOrOwnedMoveObject(object.compute_object_reference())
+                }
+                kind => kind,
+            };
+            objects.push((kind, object));
+        }
+        let input_objects = InputObjects::new(objects);
+
+        let epoch = certificate.auth_sign_info.epoch;
+        let gas_status = if data.kind.is_system_tx() {
+            SuiGasStatus::new_unmetered()
+        } else {
+            let storage_gas_price = self
+                .database
+                .get_sui_system_state_at_epoch(epoch)?
+                .parameters
+                .storage_gas_price;
+            let mut gas_status =
+                gas::start_gas_metering(data.gas_budget, data.gas_price, storage_gas_price)?;
+            if certificate.contains_shared_object() {
+                gas_status.charge_consensus()?;
+            }
+            gas_status
+        };
+
+        let written_versions: BTreeMap<_, _> = effects
+            .map(|effects| {
+                effects
+                    .all_mutated()
+                    .map(|(object_ref, _)| object_ref)
+                    .chain(&effects.deleted)
+                    .chain(&effects.wrapped)
+                    .map(|(id, version, _)| (*id, *version))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let shared_object_refs = input_objects.filter_shared_objects();
+        let transaction_dependencies = input_objects.transaction_dependencies();
+        let temporary_store = TemporaryStore::new(
+            PreExecutionStore::new(self.database.clone(), written_versions),
+            input_objects,
+            transaction_digest,
+        );
+        let (_inner_temp_store, replayed_effects, _execution_error) =
+            execution_engine::execute_transaction_to_effects(
+                shared_object_refs,
+                temporary_store,
+                data.clone(),
+                transaction_digest,
+                transaction_dependencies,
+                &self.move_vm,
+                &self.native_functions,
+                gas_status,
+                epoch,
+            );
+        Ok(replayed_effects)
+    }
+
     pub async fn check_tx_already_executed(
         &self,
         digest: &TransactionDigest,
@@ -841,6 +1420,39 @@ impl AuthorityState {
         Ok(())
     }
 
+    /// Flag the last `count` executed transactions whose effects were persisted without the
+    /// objects they wrote, e.g. because of a crash during a write. Run at startup.
+    pub fn report_inconsistent_effects(&self, count: u64) -> SuiResult {
+        for (digest, object_ref) in self.database.check_effects_consistency(count)? {
+            error!(
+                tx_digest = ?digest,
+                ?object_ref,
+                "Effects of an executed transaction refer to an object which is not stored"
+            );
+        }
+        Ok(())
+    }
+
+    /// Periodically prune the object versions and transaction effects beyond the retention
+    /// configured for each.
+    pub async fn run_store_pruning(&self, config: AuthorityStorePruningConfig) {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(config.pruning_interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            if let Some(num_versions) = config.num_latest_object_versions_to_retain {
+                if let Err(e) = self.database.prune_object_versions(num_versions) {
+                    error!("Failed to prune object versions: {e}");
+                }
+            }
+            if let Some(num_transactions) = config.num_latest_effects_to_retain {
+                if let Err(e) = self.database.prune_effects(num_transactions) {
+                    error!("Failed to prune transaction effects: {e}");
+                }
+            }
+        }
+    }
+
     pub fn unixtime_now_ms() -> u64 {
         let ts_ms = Utc::now().timestamp_millis();
         u64::try_from(ts_ms).expect("Travelling in time machine")
@@ -854,11 +1466,209 @@ impl AuthorityState {
             .await
     }
 
+    /// The info of the first transactions of `request`, in the same order, as many as fit in
+    /// `MAX_BATCH_RESPONSE_SIZE_BYTES`. Unknown transactions get a response holding nothing, as
+    /// from `handle_transaction_info_request`.
+    pub async fn handle_transaction_info_batch_request(
+        &self,
+        request: TransactionInfoBatchRequest,
+    ) -> Result<Vec<TransactionInfoResponse>, SuiError> {
+        fp_ensure!(
+            request.digests.len() <= MAX_TRANSACTION_INFO_BATCH_SIZE,
+            SuiError::TooManyItemsError {
+                count: request.digests.len() as u64,
+                limit: MAX_TRANSACTION_INFO_BATCH_SIZE as u64,
+            }
+        );
+        let mut responses = Vec::with_capacity(request.digests.len());
+        let mut size = 0;
+        for digest in &request.digests {
+            let response = self.make_transaction_info(digest).await?;
+            let response_size = bcs::serialized_size(&response)
+                .expect("Serializing a transaction info response cannot fail")
+                as u64;
+            // The other digests are left for the client to request again.
+            if !responses.is_empty() && size + response_size > MAX_BATCH_RESPONSE_SIZE_BYTES {
+                break;
+            }
+            size += response_size;
+            responses.push(response);
+        }
+        Ok(responses)
+    }
+
+    /// The events emitted by an executed transaction, in the order they were emitted. Unlike
+    /// `get_events_by_transaction`, this reads the stored effects, so needs no event store.
+    pub async fn handle_events_by_transaction_request(
+        &self,
+        request: EventsByTransactionRequest,
+    ) -> Result<Vec<Event>, SuiError> {
+        self.database.get_events(&request.transaction_digest)
+    }
+
+    /// Stream the events of the transactions executed from now on, as they are executed. If the
+    /// request has a start, the persisted events of that transaction and of those executed after
+    /// it are streamed first, read `EVENT_REPLAY_PAGE_SIZE` transactions at a time. The start
+    /// must be among the latest `MAX_EVENT_REPLAY_TRANSACTIONS` executed transactions. A
+    /// subscriber falling more than `broadcast_capacity` transactions behind is sent a
+    /// `SubscriptionItemsDroppedError` and the stream ends.
+    pub async fn handle_event_streaming(
+        &self,
+        request: EventSubscriptionRequest,
+    ) -> SuiResult<impl Stream<Item = SuiResult<EventResponseItem>>> {
+        // Subscribe before reading the history, so that no transaction is missed in between.
+        let subscriber = self.subscribe_events();
+
+        let mut replay = 0..0;
+        if let Some(start) = request.start {
+            let start_seq = self
+                .database
+                .get_transaction_sequence(&start)?
+                .ok_or(SuiError::TransactionNotFound { digest: start })?;
+            let end_seq = self.database.next_sequence_number()?;
+            let count = end_seq.saturating_sub(start_seq);
+            fp_ensure!(
+                count <= MAX_EVENT_REPLAY_TRANSACTIONS,
+                SuiError::TooManyItemsError {
+                    count,
+                    limit: MAX_EVENT_REPLAY_TRANSACTIONS,
+                }
+            );
+            replay = start_seq..end_seq;
+        }
+
+        struct EventStreamingLocals {
+            database: Arc<AuthorityStore>,
+            items: VecDeque<EventResponseItem>,
+            // The sequence numbers of the transactions whose persisted events are left to read.
+            replay: Range<TxSequenceNumber>,
+            // Transactions executed while the history was read are also received from the
+            // subscriber, and must not be streamed twice.
+            replayed: HashSet<TxSequenceNumber>,
+            subscriber: EventBroadcastReceiver,
+            exit: bool,
+        }
+
+        let local_state = EventStreamingLocals {
+            database: self.database.clone(),
+            items: VecDeque::new(),
+            replay,
+            replayed: HashSet::new(),
+            subscriber,
+            exit: false,
+        };
+
+        Ok(stream::unfold(local_state, |mut local_state| async move {
+            if local_state.exit {
+                return None;
+            }
+            loop {
+                if let Some(item) = local_state.items.pop_front() {
+                    return Some((Ok(item), local_state));
+                }
+                if !local_state.replay.is_empty() {
+                    let start = local_state.replay.start;
+                    let end = local_state.replay.end.min(start + EVENT_REPLAY_PAGE_SIZE);
+                    local_state.replay.start = end;
+                    let database = local_state.database.clone();
+                    let page = tokio::task::spawn_blocking(move || {
+                        Self::read_events_in_seq_range(&database, start, end)
+                    })
+                    .await
+                    .map_err(|e| SuiError::GenericAuthorityError {
+                        error: format!("Reading events failed: {e}"),
+                    });
+                    match page.and_then(|page| page) {
+                        Ok(page) => {
+                            for (seq, transaction_digest, events) in page {
+                                local_state.replayed.insert(seq);
+                                local_state.items.extend(events.into_iter().map(|event| {
+                                    EventResponseItem {
+                                        transaction_digest,
+                                        event,
+                                    }
+                                }));
+                            }
+                            continue;
+                        }
+                        Err(err) => {
+                            local_state.exit = true;
+                            return Some((Err(err), local_state));
+                        }
+                    }
+                }
+                let err = match local_state.subscriber.recv().await {
+                    Ok((seq, transaction_digest, events)) => {
+                        if !local_state.replayed.contains(&seq) {
+                            local_state.items.extend(events.into_iter().map(|event| {
+                                EventResponseItem {
+                                    transaction_digest,
+                                    event,
+                                }
+                            }));
+                        }
+                        continue;
+                    }
+                    Err(RecvError::Closed) => SuiError::SubscriptionServiceClosed,
+                    // Tell the subscriber it is too slow to consume events, and stop.
+                    Err(RecvError::Lagged(number_skipped)) => {
+                        SuiError::SubscriptionItemsDroppedError(number_skipped)
+                    }
+                };
+                local_state.exit = true;
+                return Some((Err(err), local_state));
+            }
+        }))
+    }
+
+    /// The persisted events of the transactions executed in the `start..end` sequence range,
+    /// skipping those which were pruned.
+    fn read_events_in_seq_range(
+        database: &AuthorityStore,
+        start: TxSequenceNumber,
+        end: TxSequenceNumber,
+    ) -> SuiResult<Vec<TransactionEvents>> {
+        let mut transactions = Vec::new();
+        for (seq, digests) in database.transactions_in_seq_range(start, end)? {
+            match database.get_events(&digests.transaction) {
+                Ok(events) => transactions.push((seq, digests.transaction, events)),
+                // The events of pruned transactions are no longer available.
+                Err(SuiError::TransactionNotFound { .. }) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(transactions)
+    }
+
     pub async fn handle_account_info_request(
         &self,
         request: AccountInfoRequest,
     ) -> Result<AccountInfoResponse, SuiError> {
-        self.make_account_info(request.account)
+        self.make_account_info(request)
+    }
+
+    pub async fn handle_account_info_page_request(
+        &self,
+        request: AccountInfoPageRequest,
+    ) -> Result<AccountInfoPageResponse, SuiError> {
+        // Ensure we are not doing too much work per request
+        let limit = request.limit.min(MAX_ITEMS_LIMIT) as usize;
+        if limit == 0 {
+            return Err(SuiError::ZeroPageLimit);
+        }
+        let (object_ids, next_cursor) = self
+            .get_owner_objects_page(
+                Owner::AddressOwner(request.account),
+                request.cursor,
+                limit,
+                request.type_filter,
+            )
+            .await?;
+        Ok(AccountInfoPageResponse {
+            object_ids: object_ids.into_iter().map(|id| id.into()).collect(),
+            owner: request.account,
+            next_cursor,
+        })
     }
 
     pub async fn handle_object_info_request(
@@ -873,7 +1683,8 @@ impl AuthorityState {
                     .await?
                     .next()
             }
-            ObjectInfoRequestKind::LatestObjectInfo(_) => {
+            ObjectInfoRequestKind::LatestObjectInfo(_)
+            | ObjectInfoRequestKind::ObjectProvenance => {
                 // Or get the latest object_reference and transaction entry.
                 self.get_latest_parent_entry(request.object_id).await?
             }
@@ -945,7 +1756,9 @@ impl AuthorityState {
                     _ => None,
                 }
             }
-            ObjectInfoRequestKind::PastObjectInfo(_) => None,
+            ObjectInfoRequestKind::PastObjectInfo(_) | ObjectInfoRequestKind::ObjectProvenance => {
+                None
+            }
         };
 
         Ok(ObjectInfoResponse {
@@ -955,6 +1768,42 @@ impl AuthorityState {
         })
     }
 
+    /// The result of `handle_object_info_request` for the first objects of `request`, in the
+    /// same order, as many as fit in `MAX_BATCH_RESPONSE_SIZE_BYTES`. Unknown objects get a
+    /// response holding nothing, and objects failing get their own error.
+    pub async fn handle_multi_object_info_request(
+        &self,
+        request: MultiObjectInfoRequest,
+    ) -> Result<Vec<SuiResult<ObjectInfoResponse>>, SuiError> {
+        fp_ensure!(
+            request.object_ids.len() <= MAX_MULTI_OBJECT_INFO_SIZE,
+            SuiError::TooManyItemsError {
+                count: request.object_ids.len() as u64,
+                limit: MAX_MULTI_OBJECT_INFO_SIZE as u64,
+            }
+        );
+        let mut responses = Vec::with_capacity(request.object_ids.len());
+        let mut size = 0;
+        for object_id in request.object_ids {
+            let response = self
+                .handle_object_info_request(ObjectInfoRequest {
+                    object_id,
+                    request_kind: request.request_kind.clone(),
+                })
+                .await;
+            let response_size = bcs::serialized_size(&response)
+                .expect("Serializing an object info response cannot fail")
+                as u64;
+            // The other objects are left for the client to request again.
+            if !responses.is_empty() && size + response_size > MAX_BATCH_RESPONSE_SIZE_BYTES {
+                break;
+            }
+            size += response_size;
+            responses.push(response);
+        }
+        Ok(responses)
+    }
+
     /// Handles a request for a batch info. It returns a sequence of
     /// [batches, transactions, batches, transactions] as UpdateItems, and a flag
     /// that if true indicates the request goes beyond the last batch in the
@@ -1031,6 +1880,46 @@ impl AuthorityState {
         Ok((items, (should_subscribe, start, end)))
     }
 
+    /// Handles a request for the batches and transactions before a sequence number. They are
+    /// the items of the matching batch info request, newest first.
+    pub async fn handle_reverse_batch_info_request(
+        &self,
+        request: ReverseBatchInfoRequest,
+    ) -> Result<ReverseBatchInfoResponse, SuiError> {
+        // Ensure the range contains some elements
+        if request.length == 0 {
+            return Err(SuiError::InvalidSequenceRangeError);
+        };
+
+        // Ensure we are not doing too much work per request
+        let length = std::cmp::min(request.length, MAX_ITEMS_LIMIT);
+
+        // If we do not have an end, stop at the latest batch.
+        let end = match request.end {
+            Some(end) => end,
+            None => {
+                self.last_batch()?
+                    .expect("Authority is always initialized with a batch")
+                    .data()
+                    .next_sequence_number
+            }
+        };
+        let start = end.saturating_sub(length);
+        if start == end {
+            return Ok(ReverseBatchInfoResponse { items: Vec::new() });
+        }
+
+        let (items, _) = self
+            .handle_batch_info_request(BatchInfoRequest {
+                start: Some(start),
+                length: end - start,
+            })
+            .await?;
+        Ok(ReverseBatchInfoResponse {
+            items: items.into_iter().rev().collect(),
+        })
+    }
+
     pub fn handle_checkpoint_request(
         &self,
         request: &CheckpointRequest,
@@ -1052,6 +1941,25 @@ impl AuthorityState {
         }
     }
 
+    /// Returns the certified checkpoint at `sequence_number` along with its contents, for
+    /// clients to check with `verify_certified_checkpoint`. Fails if this authority only holds
+    /// its own signature on that checkpoint so far.
+    pub fn get_certified_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> SuiResult<(CertifiedCheckpointSummary, CheckpointContents)> {
+        let request = CheckpointRequest::authenticated(Some(sequence_number), true);
+        match self.handle_checkpoint_request(&request)? {
+            CheckpointResponse::AuthenticatedCheckpoint {
+                checkpoint: Some(AuthenticatedCheckpoint::Certified(checkpoint)),
+                contents: Some(contents),
+            } => Ok((checkpoint, contents)),
+            _ => Err(SuiError::CheckpointingError {
+                error: format!("Checkpoint {sequence_number} is not certified on this authority"),
+            }),
+        }
+    }
+
     pub fn handle_epoch_request(&self, request: &EpochRequest) -> SuiResult<EpochResponse> {
         let epoch_info = match &request.epoch_id {
             Some(id) => self.epoch_store.get_authenticated_epoch(id)?,
@@ -1060,6 +1968,76 @@ impl AuthorityState {
         Ok(EpochResponse { epoch_info })
     }
 
+    pub fn handle_node_stats_request(
+        &self,
+        _request: &NodeStatsRequest,
+    ) -> SuiResult<NodeStatsResponse> {
+        Ok(NodeStatsResponse {
+            recent_tps: self.cert_throughput.rate(),
+            total_certificates: self.database.next_sequence_number()?,
+            batch_watermark: self
+                .last_batch()?
+                .map(|batch| batch.data().next_sequence_number),
+        })
+    }
+
+    /// The total balance of the `Coin<T>` objects stored for the currency T of the request.
+    ///
+    /// The supply is eventually consistent with the latest committed state. It is computed by
+    /// scanning the latest version of every object, then cached until a transaction touching
+    /// coins of the currency commits. Transactions committing during the scan may be missed, so
+    /// such a supply is served but not cached. The scan runs on a blocking thread, so as not to
+    /// stall the other requests.
+    pub async fn handle_coin_supply_request(
+        &self,
+        request: &CoinSupplyRequest,
+    ) -> SuiResult<CoinSupplyResponse> {
+        let cache = self
+            .coin_supply
+            .as_ref()
+            .ok_or_else(|| SuiError::UnsupportedFeatureError {
+                error: "coin supply is not enabled on this server".into(),
+            })?;
+        if let Some(supply) = cache.get(&request.coin_type) {
+            return Ok(supply);
+        }
+        let generation = cache.generation();
+        let database = self.database.clone();
+        let coin_type = request.coin_type.clone();
+        let (total_supply, coin_count) =
+            tokio::task::spawn_blocking(move || database.coin_supply(&coin_type))
+                .await
+                .map_err(|e| SuiError::GenericAuthorityError {
+                    error: format!("Coin supply scan failed: {e}"),
+                })??;
+        let supply = CoinSupplyResponse {
+            total_supply,
+            coin_count,
+        };
+        cache.insert(request.coin_type.clone(), supply.clone(), generation);
+        Ok(supply)
+    }
+
+    /// The currencies of the coins read or written by a transaction, whose supply it may change.
+    fn coin_types_touched(inner_temporary_store: &InnerTemporaryStore) -> BTreeSet<StructTag> {
+        inner_temporary_store
+            .objects
+            .values()
+            .chain(
+                inner_temporary_store
+                    .written
+                    .values()
+                    .map(|(_, object)| object),
+            )
+            .filter_map(|object| object.data.type_())
+            .filter(|type_| Coin::is_coin(type_))
+            .filter_map(|type_| match type_.type_params.first() {
+                Some(TypeTag::Struct(coin_type)) => Some(coin_type.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
     // TODO: This function takes both committee and genesis as parameter.
     // Technically genesis already contains committee information. Could consider merging them.
     pub async fn new(
@@ -1074,8 +2052,12 @@ impl AuthorityState {
         genesis: &Genesis,
         prometheus_registry: &prometheus::Registry,
         tx_reconfigure_consensus: Sender<(ConsensusKeyPair, ConsensusCommittee)>,
+        batch_config: AuthorityBatchConfig,
+        coin_supply: Option<CoinSupplyCache>,
     ) -> Self {
-        let (tx, _rx) = tokio::sync::broadcast::channel(BROADCAST_CAPACITY);
+        let (tx, _rx) = tokio::sync::broadcast::channel(batch_config.broadcast_capacity);
+        let (event_tx, _event_rx) =
+            tokio::sync::broadcast::channel(batch_config.broadcast_capacity);
         let native_functions =
             sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
         let move_vm = Arc::new(
@@ -1093,11 +2075,15 @@ impl AuthorityState {
                 .expect("Cannot bulk insert genesis objects");
         }
 
-        let committee = epoch_store
-            .get_latest_authenticated_epoch()
-            .epoch_info()
-            .committee()
-            .clone();
+        let latest_epoch = epoch_store.get_latest_authenticated_epoch();
+        store
+            .migrate_legacy_locks(latest_epoch.epoch())
+            .await
+            .expect("Cannot stamp the legacy transaction locks with the current epoch");
+        let committee = latest_epoch.epoch_info().committee().clone();
+        let protocol_config =
+            ProtocolConfig::get_for_version(latest_epoch.epoch_info().protocol_version())
+                .expect("The protocol version of the latest epoch must be supported");
 
         let event_handler = event_store.map(|es| Arc::new(EventHandler::new(store.clone(), es)));
 
@@ -1105,8 +2091,9 @@ impl AuthorityState {
             name,
             secret,
             committee: ArcSwap::from(Arc::new(committee)),
+            protocol_config: ArcSwap::from(Arc::new(protocol_config)),
             halted: AtomicBool::new(false),
-            _native_functions: native_functions,
+            native_functions,
             move_vm,
             database: store.clone(),
             indexes,
@@ -1118,14 +2105,19 @@ impl AuthorityState {
             checkpoints,
             epoch_store,
             batch_channels: tx,
+            event_channels: event_tx,
             batch_notifier: Arc::new(
                 authority_notifier::TransactionNotifier::new(store.clone())
                     .expect("Notifier cannot start."),
             ),
             consensus_guardrail: AtomicUsize::new(0),
             metrics: Arc::new(AuthorityMetrics::new(prometheus_registry)),
+            cert_throughput: ThroughputCounter::default(),
+            coin_supply,
             latest_checkpoint_num: AtomicU64::new(0),
             tx_reconfigure_consensus,
+            #[cfg(any(test, feature = "simulate-failure"))]
+            simulated_failure: Mutex::new(None),
         };
 
         // Process tx recovery log first, so that the batch and checkpoint recovery (below)
@@ -1234,6 +2226,8 @@ impl AuthorityState {
             genesis,
             &prometheus::Registry::new(),
             tx_reconfigure_consensus,
+            AuthorityBatchConfig::default(),
+            Some(CoinSupplyCache::default()),
         )
         .await
     }
@@ -1303,16 +2297,19 @@ impl AuthorityState {
             next_checkpoint,
             latest_epoch.epoch_info(),
         );
+        let protocol_config =
+            ProtocolConfig::get_for_version(signed_epoch.epoch_info.protocol_version())?;
         self.epoch_store
-            .epochs
+            .authenticated_epochs
             .insert(&cur_epoch, &AuthenticatedEpoch::Signed(signed_epoch))?;
         // TODO: Do we want to make it possible to subscribe to committee changes?
         self.committee.swap(Arc::new(new_committee));
+        self.protocol_config.swap(Arc::new(protocol_config));
         Ok(())
     }
 
     pub(crate) fn promote_signed_epoch_to_cert(&self, cert: CertifiedEpoch) -> SuiResult {
-        Ok(self.epoch_store.epochs.insert(
+        Ok(self.epoch_store.authenticated_epochs.insert(
             &cert.epoch_info.epoch(),
             &AuthenticatedEpoch::Certified(cert),
         )?)
@@ -1330,6 +2327,18 @@ impl AuthorityState {
         self.halted.store(false, Ordering::Relaxed);
     }
 
+    /// The native functions the Move VM of this authority supports, as `(address, module,
+    /// function)`, so that tooling can check the natives a package depends on are available.
+    pub fn native_functions(&self) -> Vec<(AccountAddress, Identifier, Identifier)> {
+        let mut natives: Vec<_> = self
+            .native_functions
+            .iter()
+            .map(|(address, module, function, _)| (*address, module.clone(), function.clone()))
+            .collect();
+        natives.sort();
+        natives
+    }
+
     pub fn db(&self) -> Arc<AuthorityStore> {
         self.database.clone()
     }
@@ -1354,10 +2363,24 @@ impl AuthorityState {
         self.database.get_sui_system_state_object()
     }
 
+    pub async fn get_reference_gas_price(&self) -> SuiResult<u64> {
+        Ok(self
+            .get_sui_system_state_object()
+            .await?
+            .effective_reference_gas_price(self.get_protocol_config().min_gas_price))
+    }
+
+    /// Summarize the current epoch, committee and gas parameters, combining the committee held by
+    /// this authority with the on-chain system state object.
+    pub async fn get_system_state(&self) -> SuiResult<SystemState> {
+        let system_state = self.get_sui_system_state_object().await?;
+        Ok(SystemState::new(&self.committee.load(), &system_state))
+    }
+
     pub async fn get_object_read(&self, object_id: &ObjectID) -> Result<ObjectRead, SuiError> {
         match self.database.get_latest_parent_entry(*object_id)? {
             None => Ok(ObjectRead::NotExists(*object_id)),
-            Some((obj_ref, _)) => {
+            Some((obj_ref, tx_digest)) => {
                 if obj_ref.2.is_alive() {
                     match self.database.get_object_by_key(object_id, obj_ref.1)? {
                         None => {
@@ -1375,7 +2398,10 @@ impl AuthorityState {
                         }
                     }
                 } else {
-                    Ok(ObjectRead::Deleted(obj_ref))
+                    Ok(ObjectRead::Deleted(ObjectTombstone {
+                        object_ref: obj_ref,
+                        deleted_by: tx_digest,
+                    }))
                 }
             }
         }
@@ -1385,6 +2411,27 @@ impl AuthorityState {
         self.database.get_owner_objects(owner)
     }
 
+    /// Up to `limit` objects owned by `owner` after `cursor`, in object id order, and the cursor
+    /// of the next page, as read by `AuthorityStore::get_owner_objects_page`.
+    pub async fn get_owner_objects_page(
+        &self,
+        owner: Owner,
+        cursor: Option<ObjectID>,
+        limit: usize,
+        type_filter: Option<StructTag>,
+    ) -> SuiResult<(Vec<ObjectInfo>, Option<ObjectID>)> {
+        // The scan reads up to MAX_OWNER_OBJECTS_SCANNED_PER_PAGE keys, and as many objects when
+        // filtering by type, so it is kept off the async executor.
+        let database = self.database.clone();
+        tokio::task::spawn_blocking(move || {
+            database.get_owner_objects_page(owner, cursor, limit, type_filter.as_ref())
+        })
+        .await
+        .map_err(|e| SuiError::GenericAuthorityError {
+            error: format!("Owned objects scan failed: {e}"),
+        })?
+    }
+
     pub fn get_total_transaction_number(&self) -> Result<u64, anyhow::Error> {
         QueryHelpers::get_total_transaction_number(&self.database)
     }
@@ -1411,6 +2458,35 @@ impl AuthorityState {
         QueryHelpers::get_transaction(&self.database, &digest)
     }
 
+    /// Return the authorities whose signatures form the certificate of a transaction.
+    pub fn get_transaction_auth_signers(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Vec<AuthorityName>> {
+        let certificate = match self.database.read_certificate(digest)? {
+            Some(certificate) => certificate,
+            // The transaction was signed by this authority, but has not been certified.
+            None if self.database.transaction_exists(digest)? => {
+                return Err(SuiError::CertificateNotfound {
+                    certificate_digest: *digest,
+                })
+            }
+            None => return Err(SuiError::TransactionNotFound { digest: *digest }),
+        };
+        let epoch = certificate.auth_sign_info.epoch;
+        let committee = self
+            .epoch_store
+            .get_authenticated_epoch(&epoch)?
+            .ok_or_else(|| SuiError::InvalidAuthenticatedEpoch(format!("Unknown epoch {epoch}")))?
+            .into_epoch_info()
+            .into_committee();
+        certificate
+            .auth_sign_info
+            .authorities(&committee)
+            .map(|name| name.copied())
+            .collect()
+    }
+
     fn get_indexes(&self) -> SuiResult<Arc<IndexStore>> {
         match &self.indexes {
             Some(i) => Ok(i.clone()),
@@ -1470,6 +2546,29 @@ impl AuthorityState {
         Ok(self.get_indexes()?.get_transactions_to_addr(address)?)
     }
 
+    /// The transactions `address` sent, or which left it owning an object they created or
+    /// mutated, with sequence number starting from `start`, at most `count`.
+    pub async fn get_transactions_touching_addr(
+        &self,
+        address: SuiAddress,
+        start: TxSequenceNumber,
+        count: u64,
+    ) -> Result<Vec<(TxSequenceNumber, TransactionDigest)>, anyhow::Error> {
+        fp_ensure!(
+            count <= MAX_TX_RANGE_SIZE,
+            SuiError::GatewayInvalidTxRangeQuery {
+                error: format!(
+                    "Number of transactions queried must not exceed {}, {} queried",
+                    MAX_TX_RANGE_SIZE, count
+                ),
+            }
+            .into()
+        );
+        Ok(self
+            .get_indexes()?
+            .get_transactions_touching_addr(address, start, count as usize)?)
+    }
+
     /// Returns a full handle to the event store, including inserts... so be careful!
     fn get_event_store(&self) -> Option<Arc<EventStoreType>> {
         self.event_handler
@@ -1614,13 +2713,18 @@ impl AuthorityState {
             .get_signed_transaction_info(transaction_digest)
     }
 
-    fn make_account_info(&self, account: SuiAddress) -> Result<AccountInfoResponse, SuiError> {
-        self.database
-            .get_owner_objects(Owner::AddressOwner(account))
-            .map(|object_ids| AccountInfoResponse {
-                object_ids: object_ids.into_iter().map(|id| id.into()).collect(),
-                owner: account,
-            })
+    fn make_account_info(
+        &self,
+        request: AccountInfoRequest,
+    ) -> Result<AccountInfoResponse, SuiError> {
+        let AccountInfoRequest { account } = request;
+        let object_ids = self
+            .database
+            .get_owner_objects(Owner::AddressOwner(account))?;
+        Ok(AccountInfoResponse {
+            object_ids: object_ids.into_iter().map(|id| id.into()).collect(),
+            owner: account,
+        })
     }
 
     // Helper function to manage transaction_locks
@@ -1633,19 +2737,19 @@ impl AuthorityState {
         signed_transaction: SignedTransaction,
     ) -> Result<(), SuiError> {
         self.database
-            .lock_and_write_transaction(mutable_input_objects, signed_transaction)
+            .lock_and_write_transaction(self.epoch(), mutable_input_objects, signed_transaction)
             .await
     }
 
     /// Update state and signals that a new transactions has been processed
-    /// to the batch maker service.
+    /// to the batch maker service. Returns the sequence number of the transaction.
     #[instrument(level = "trace", skip_all)]
     pub(crate) async fn commit_certificate(
         &self,
         inner_temporary_store: InnerTemporaryStore,
         certificate: &CertifiedTransaction,
         signed_effects: &SignedTransactionEffects,
-    ) -> SuiResult {
+    ) -> SuiResult<TxSequenceNumber> {
         if self.is_halted() && !certificate.signed_data.data.kind.is_system_tx() {
             // TODO: Here we should allow consensus transaction to continue.
             // TODO: Do we want to include the new validator set?
@@ -1657,6 +2761,12 @@ impl AuthorityState {
 
         let digest = certificate.digest();
         let effects_digest = &signed_effects.digest();
+        // Found before the store is consumed, to drop their cached supply once committed.
+        let coin_types_touched = self
+            .coin_supply
+            .as_ref()
+            .map(|_| Self::coin_types_touched(&inner_temporary_store));
+        let _db_update_timer = self.metrics.db_update_latency.start_timer();
         self.database
             .update_state(
                 inner_temporary_store,
@@ -1668,9 +2778,13 @@ impl AuthorityState {
             .await
             .tap_ok(|_| {
                 debug!(?digest, ?effects_digest, ?self.name, "commit_certificate finished");
-            })
+            })?;
+        if let (Some(cache), Some(coin_types)) = (&self.coin_supply, coin_types_touched) {
+            cache.invalidate(coin_types);
+        }
 
         // implicitly we drop the ticket here and that notifies the batch manager
+        Ok(seq)
     }
 
     /// Check whether a shared-object certificate has already been given shared-locks.
@@ -1736,6 +2850,35 @@ impl AuthorityState {
         self.database.get_latest_parent_entry(object_id)
     }
 
+    /// Returns the certificate of the transaction which created or last mutated the object, or
+    /// None if the object is unknown or was last written at genesis.
+    pub async fn get_object_provenance(
+        &self,
+        object_id: ObjectID,
+    ) -> Result<Option<CertifiedTransaction>, SuiError> {
+        match self.get_latest_parent_entry(object_id).await? {
+            Some((_, transaction_digest)) if transaction_digest != TransactionDigest::genesis() => {
+                self.read_certificate(&transaction_digest)
+                    .await?
+                    .ok_or(SuiError::CertificateNotfound {
+                        certificate_digest: transaction_digest,
+                    })
+                    .map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the owner of the object as of `version`, or None if the object did not exist, or
+    /// was deleted or wrapped, at that version. Versions newer than the latest one are an error.
+    pub async fn get_owner_at_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Owner>> {
+        self.database.get_owner_at_version(object_id, version)
+    }
+
     fn verify_narwhal_transaction(&self, certificate: &CertifiedTransaction) -> SuiResult {
         // Ensure the input is a shared object certificate. Remember that Byzantine authorities
         // may input anything into consensus.
@@ -1779,6 +2922,16 @@ impl ExecutionState for AuthorityState {
                     "handle_consensus_transaction UserTransaction",
                 );
 
+                // A certificate sequenced again was resubmitted, e.g. by a client retrying after
+                // timing out waiting for consensus.
+                if self
+                    .database
+                    .consensus_message_processed(certificate.digest())
+                    .map_err(NarwhalHandlerError::NodeError)?
+                {
+                    self.metrics.shared_object_retries.inc();
+                }
+
                 self.database
                     .persist_certificate_and_lock_shared_objects(*certificate, consensus_index)
                     // todo - potentially more errors from inside here needs to be mapped differently
@@ -1869,6 +3022,16 @@ pub enum NarwhalHandlerError {
     SkipNarwhalTransaction(SuiError),
 }
 
+#[derive(Eq, PartialEq, Clone, Debug, Error)]
+pub enum CertificateExecutionError {
+    /// The certificate signatures do not verify against the committee
+    #[error("Certificate verification failed: {0}")]
+    Verify(SuiError),
+    /// The certificate is valid, but could not be executed
+    #[error("Certificate execution failed: {0}")]
+    Execute(SuiError),
+}
+
 impl ExecutionStateError for NarwhalHandlerError {
     fn node_error(&self) -> bool {
         match self {
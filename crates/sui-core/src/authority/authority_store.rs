@@ -6,19 +6,23 @@ use narwhal_executor::ExecutionIndices;
 use rocksdb::Options;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use std::collections::BTreeMap;
 use std::iter;
 use std::path::Path;
 use std::sync::atomic::AtomicU64;
 use std::{fmt::Debug, path::PathBuf};
 use sui_storage::{
+    lock_service::LockDetails,
     mutex_table::{LockGuard, MutexTable},
     write_ahead_log::{DBWriteAheadLog, WriteAheadLog},
     LockService,
 };
 use sui_types::batch::{SignedBatch, TxSequenceNumber};
+use sui_types::coin::Coin;
 use sui_types::crypto::{AuthoritySignInfo, EmptySignInfo};
-use sui_types::object::{Owner, OBJECT_START_VERSION};
-use sui_types::{base_types::SequenceNumber, storage::ParentSync};
+use sui_types::event::Event;
+use sui_types::object::{ObjectTombstone, Owner, OBJECT_START_VERSION};
+use sui_types::{base_types::SequenceNumber, storage::ParentSync, struct_tag_matches};
 use tokio::sync::Notify;
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
 use tracing::{debug, error, info, trace};
@@ -35,9 +39,23 @@ pub struct CertLockGuard(LockGuard);
 const NUM_SHARDS: usize = 4096;
 const SHARD_SIZE: usize = 128;
 
+/// How many owned objects a page of `get_owner_objects_page` reads at most, whatever the number
+/// of objects of the filtered type among them.
+const MAX_OWNER_OBJECTS_SCANNED_PER_PAGE: usize = 10_000;
+
 /// The key where the latest consensus index is stored in the database.
 // TODO: Make a single table (e.g., called `variables`) storing all our lonely variables in one place.
 const LAST_CONSENSUS_INDEX_ADDR: u64 = 0;
+/// The key where the effects pruning watermark is stored in the database.
+const EFFECTS_PRUNING_WATERMARK_ADDR: u64 = 0;
+
+/// The maximum number of executed transactions examined, and so deleted, by one write batch when
+/// pruning effects.
+const MAX_EFFECTS_PRUNED_PER_STEP: usize = 1_000;
+
+/// The number of executed transactions indexed by one write batch when backfilling the index of
+/// executed transactions by digest.
+const EXECUTED_SEQUENCE_INDEX_BACKFILL_BATCH_SIZE: usize = 1_000;
 
 /// ALL_OBJ_VER determines whether we want to store all past
 /// versions of every object in the store. Authority doesn't store
@@ -56,6 +74,9 @@ pub struct SuiDataStore<S> {
     /// Internal vector of locks to manage concurrent writes to the database
     mutex_table: MutexTable<ObjectDigest>,
 
+    /// Set once the locks written before locks were stamped with an epoch have been stamped.
+    legacy_locks_migrated: tokio::sync::OnceCell<()>,
+
     // The next sequence number.
     next_pending_seq: AtomicU64,
     // A notifier for new pending certificates
@@ -89,14 +110,53 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .unwrap_or(0);
         let next_pending_seq = AtomicU64::new(pending_seq);
 
-        Self {
+        let store = Self {
             wal,
             lock_service,
             mutex_table: MutexTable::new(NUM_SHARDS, SHARD_SIZE),
+            legacy_locks_migrated: tokio::sync::OnceCell::new(),
             next_pending_seq,
             pending_notifier: Arc::new(Notify::new()),
             tables,
+        };
+        store
+            .backfill_executed_sequence_index()
+            .expect("Could not index the executed transactions");
+        store
+    }
+
+    /// Index the executed transactions of a store written before `executed_sequence_index`
+    /// existed. Executed transactions are indexed as they are sequenced and the backfill runs
+    /// in sequence order, so the index is complete if it holds the last executed transaction.
+    fn backfill_executed_sequence_index(&self) -> SuiResult {
+        let complete = match self.tables.executed_sequence.iter().skip_to_last().next() {
+            Some((_, digests)) => self
+                .tables
+                .executed_sequence_index
+                .contains_key(&digests.transaction)?,
+            None => true,
+        };
+        if complete {
+            return Ok(());
         }
+
+        let mut indexed = 0;
+        let mut entries = self.tables.executed_sequence.iter().peekable();
+        while entries.peek().is_some() {
+            let page: Vec<_> = entries
+                .by_ref()
+                .take(EXECUTED_SEQUENCE_INDEX_BACKFILL_BATCH_SIZE)
+                .map(|(seq, digests)| (digests.transaction, seq))
+                .collect();
+            indexed += page.len();
+            self.tables
+                .executed_sequence_index
+                .batch()
+                .insert_batch(&self.tables.executed_sequence_index, page)?
+                .write()?;
+        }
+        info!(indexed, "Indexed the executed transactions by digest");
+        Ok(())
     }
 
     pub async fn acquire_tx_guard(&self, cert: &CertifiedTransaction) -> SuiResult<CertTxGuard> {
@@ -140,6 +200,12 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             })
     }
 
+    /// Returns the events emitted by the transaction, in the order they were emitted, as
+    /// recorded in its effects
+    pub fn get_events(&self, transaction_digest: &TransactionDigest) -> SuiResult<Vec<Event>> {
+        Ok(self.get_effects(transaction_digest)?.events)
+    }
+
     /// Returns true if we have an effects structure for this transaction digest
     pub fn effects_exists(&self, transaction_digest: &TransactionDigest) -> SuiResult<bool> {
         self.tables
@@ -178,9 +244,30 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .unwrap_or(0))
     }
 
+    /// The serialized size of the certificate and effects of the transaction executed at `seq`,
+    /// as recorded when it was sequenced.
+    pub fn get_executed_transaction_size(&self, seq: TxSequenceNumber) -> SuiResult<Option<u64>> {
+        Ok(self.tables.executed_transaction_sizes.get(&seq)?)
+    }
+
+    /// The serialized size of an executed transaction, that is of its certificate and effects.
+    fn executed_transaction_size(
+        certificate: &CertifiedTransaction,
+        effects: &TransactionEffectsEnvelope<S>,
+    ) -> u64 {
+        let certificate_size =
+            bcs::serialized_size(certificate).expect("Serializing a certificate cannot fail");
+        let effects_size = bcs::serialized_size(effects).expect("Serializing effects cannot fail");
+        (certificate_size + effects_size) as u64
+    }
+
     #[cfg(test)]
     pub fn side_sequence(&self, seq: TxSequenceNumber, digest: &ExecutionDigests) {
         self.tables.executed_sequence.insert(&seq, digest).unwrap();
+        self.tables
+            .executed_sequence_index
+            .insert(&digest.transaction, &seq)
+            .unwrap();
     }
 
     #[cfg(test)]
@@ -198,10 +285,21 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
     /// index. If two instanced run concurrently, the indexes are guaranteed to not overlap
     /// although some certificates may be included twice in the `pending_execution`, and
     /// the same certificate may be written twice (but that is OK since it is valid.)
+    ///
+    /// Certificates added together are scheduled by decreasing priority, so that higher priority
+    /// ones are executed, and so sequenced and batched, first. Those not provided come last, and
+    /// those of equal priority keep their order.
     pub fn add_pending_certificates(
         &self,
-        certs: Vec<(TransactionDigest, Option<CertifiedTransaction>)>,
+        mut certs: Vec<(TransactionDigest, Option<CertifiedTransaction>)>,
     ) -> SuiResult<()> {
+        certs.sort_by_key(|(_, cert_opt)| {
+            std::cmp::Reverse(
+                cert_opt
+                    .as_ref()
+                    .map(|cert| cert.signed_data.data.priority()),
+            )
+        });
         let first_index = self
             .next_pending_seq
             .fetch_add(certs.len() as u64, Ordering::Relaxed);
@@ -278,6 +376,93 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .collect())
     }
 
+    /// Up to `limit` objects owned by `owner` after `cursor`, in object id order, along with a
+    /// cursor to continue from if more objects may follow. Only objects whose type matches
+    /// `type_filter` are returned if it is given. At most `MAX_OWNER_OBJECTS_SCANNED_PER_PAGE`
+    /// objects are read, so a filtered page can hold fewer than `limit` objects, or none, and
+    /// still have a next cursor.
+    pub fn get_owner_objects_page(
+        &self,
+        owner: Owner,
+        cursor: Option<ObjectID>,
+        limit: usize,
+        type_filter: Option<&StructTag>,
+    ) -> Result<(Vec<ObjectInfo>, Option<ObjectID>), SuiError> {
+        debug!(?owner, ?cursor, limit, "get_owner_objects_page");
+        let mut objects = Vec::new();
+        let mut last_scanned = None;
+        for (scanned, ((_, object_id), object_info)) in self
+            .tables
+            .owner_index
+            .iter()
+            .skip_to(&(owner, cursor.unwrap_or(ObjectID::ZERO)))?
+            .take_while(|((object_owner, _), _)| (object_owner == &owner))
+            .filter(|((_, object_id), _)| Some(*object_id) != cursor)
+            .enumerate()
+        {
+            if scanned == MAX_OWNER_OBJECTS_SCANNED_PER_PAGE {
+                // Continue after the last object read, whether it matched or not.
+                return Ok((objects, last_scanned));
+            }
+            last_scanned = Some(object_id);
+            if let Some(type_filter) = type_filter {
+                if !self.object_type_matches(&object_info, type_filter)? {
+                    continue;
+                }
+            }
+            objects.push(object_info);
+            // One more than the limit tells whether objects follow the page.
+            if objects.len() > limit {
+                break;
+            }
+        }
+        let next_cursor = if objects.len() > limit {
+            objects.truncate(limit);
+            objects.last().map(|object| object.object_id)
+        } else {
+            None
+        };
+        Ok((objects, next_cursor))
+    }
+
+    /// Whether the type of the object, read from its data, matches `type_filter`. Packages match
+    /// no type.
+    fn object_type_matches(
+        &self,
+        object_info: &ObjectInfo,
+        type_filter: &StructTag,
+    ) -> Result<bool, SuiError> {
+        let object = self.get_object_by_key(&object_info.object_id, object_info.version)?;
+        Ok(object
+            .as_ref()
+            .and_then(|object| object.data.type_())
+            .map_or(false, |tag| struct_tag_matches(type_filter, tag)))
+    }
+
+    /// The total balance and the number of the `Coin<coin_type>` objects, read from the latest
+    /// version of each object. This scans the index of every live object. The balances of all
+    /// the coins may not fit in a `u64`, so they are summed as a `u128`.
+    pub fn coin_supply(&self, coin_type: &StructTag) -> Result<(u128, u64), SuiError> {
+        let type_ = Coin::type_(coin_type.clone()).to_string();
+        let mut total_supply = 0;
+        let mut coin_count = 0;
+        for (_, object_info) in self.tables.owner_index.iter() {
+            if object_info.type_ != type_ {
+                continue;
+            }
+            let object = self
+                .get_object_by_key(&object_info.object_id, object_info.version)?
+                .ok_or(SuiError::ObjectNotFound {
+                    object_id: object_info.object_id,
+                })?;
+            if let Some(balance) = Coin::extract_balance_if_coin(&object)? {
+                total_supply += u128::from(balance);
+                coin_count += 1;
+            }
+        }
+        Ok((total_supply, coin_count))
+    }
+
     pub fn get_object_by_key(
         &self,
         object_id: &ObjectID,
@@ -334,20 +519,24 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         Ok(result)
     }
 
-    /// Get many objects by their (id, version number) key.
+    /// Get many objects by their (id, version number) key. The owned and immutable objects, whose
+    /// version is known, are read with a single multi-get.
     pub fn get_input_objects(
         &self,
         objects: &[InputObjectKind],
     ) -> Result<Vec<Option<Object>>, SuiError> {
-        let mut result = Vec::new();
+        let keys = objects.iter().filter_map(|kind| match kind {
+            InputObjectKind::ImmOrOwnedMoveObject(objref) => Some(ObjectKey(objref.0, objref.1)),
+            InputObjectKind::MovePackage(_) | InputObjectKind::SharedMoveObject(_) => None,
+        });
+        let mut versioned = self.tables.objects.multi_get(keys)?.into_iter();
+        let mut result = Vec::with_capacity(objects.len());
         for kind in objects {
             let obj = match kind {
                 InputObjectKind::MovePackage(id) | InputObjectKind::SharedMoveObject(id) => {
                     self.get_object(id)?
                 }
-                InputObjectKind::ImmOrOwnedMoveObject(objref) => {
-                    self.get_object_by_key(&objref.0, objref.1)?
-                }
+                InputObjectKind::ImmOrOwnedMoveObject(_) => versioned.next().flatten(),
             };
             result.push(obj);
         }
@@ -369,7 +558,7 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         // However we retry a couple times because the TX is written after the lock is acquired, so it might
         // just be a race.
         match transaction_option {
-            Some(tx_digest) => {
+            Some(LockDetails { tx_digest, .. }) => {
                 let mut retry_strategy = ExponentialBackoff::from_millis(2)
                     .factor(10)
                     .map(jitter)
@@ -552,19 +741,34 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         Ok(())
     }
 
+    /// Stamp the transaction locks written before locks were stamped with an epoch with the
+    /// current `epoch`, once per store. Runs before the first lock is acquired, and should run at
+    /// startup before locks are read.
+    pub async fn migrate_legacy_locks(&self, epoch: EpochId) -> SuiResult {
+        self.legacy_locks_migrated
+            .get_or_try_init(|| async {
+                self.lock_service.migrate_legacy_locks(epoch).await?;
+                Ok::<_, SuiError>(())
+            })
+            .await?;
+        Ok(())
+    }
+
     /// Acquires the transaction lock for a specific transaction, writing the transaction
     /// to the transaction column family if acquiring the lock succeeds.
-    /// The lock service is used to atomically acquire locks.
+    /// The lock service is used to atomically acquire locks, stamped with the given epoch.
     pub async fn lock_and_write_transaction(
         &self,
+        epoch: EpochId,
         owned_input_objects: &[ObjectRef],
         transaction: TransactionEnvelope<S>,
     ) -> Result<(), SuiError> {
         let tx_digest = *transaction.digest();
+        self.migrate_legacy_locks(epoch).await?;
 
         // Acquire the lock on input objects
         self.lock_service
-            .acquire_locks(owned_input_objects.to_owned(), tx_digest)
+            .acquire_locks(owned_input_objects.to_owned(), epoch, tx_digest)
             .await?;
 
         // TODO: we should have transaction insertion be atomic with lock acquisition, or retry.
@@ -613,6 +817,7 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             write_batch,
             inner_temporary_store,
             transaction_digest,
+            Self::executed_transaction_size(certificate, effects),
             proposed_seq,
             effects,
             effects_digest,
@@ -681,6 +886,7 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             write_batch,
             inner_temporary_store,
             transaction_digest,
+            Self::executed_transaction_size(&certificate, &effects),
             proposed_seq,
             &effects,
             effects_digest,
@@ -693,10 +899,20 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         write_batch: DBBatch,
         inner_temporary_store: InnerTemporaryStore,
         transaction_digest: &TransactionDigest,
+        transaction_size: u64,
         proposed_seq: TxSequenceNumber,
         effects: &TransactionEffectsEnvelope<S>,
         effects_digest: &TransactionEffectsDigest,
     ) -> SuiResult {
+        // The effects are written after the objects, and must only refer to objects written
+        // along with them, so that the effects of an executed transaction always match the
+        // objects in the store.
+        Self::check_effects_match_writes(
+            transaction_digest,
+            &effects.effects,
+            &inner_temporary_store.written,
+        )?;
+
         // Safe to unwrap since UpdateType::Transaction ensures we get a sequence number back.
         let assigned_seq = self
             .batch_update_objects(
@@ -743,12 +959,42 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             )]
             .into_iter(),
         )?;
+        let batch = batch.insert_batch(
+            &self.tables.executed_sequence_index,
+            [(*transaction_digest, assigned_seq)].into_iter(),
+        )?;
+        let batch = batch.insert_batch(
+            &self.tables.executed_transaction_sizes,
+            [(assigned_seq, transaction_size)].into_iter(),
+        )?;
 
         batch.write()?;
 
         Ok(())
     }
 
+    fn check_effects_match_writes(
+        transaction_digest: &TransactionDigest,
+        effects: &TransactionEffects,
+        written: &BTreeMap<ObjectID, (ObjectRef, Object)>,
+    ) -> SuiResult {
+        for (object_ref, _) in effects.all_mutated() {
+            match written.get(&object_ref.0) {
+                Some((written_ref, _)) if written_ref == object_ref => (),
+                written_ref => {
+                    return Err(SuiError::InconsistentEffects {
+                        digest: *transaction_digest,
+                        error: format!(
+                            "Effects refer to object {object_ref:?}, but {:?} was written",
+                            written_ref.map(|(object_ref, _)| object_ref)
+                        ),
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Helper function for updating the objects in the state
     async fn batch_update_objects(
         &self,
@@ -1024,6 +1270,222 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         }))
     }
 
+    /// Returns the latest parent entry of the object with a version lower than `version`, that is
+    /// the entry the object had before the transaction writing `version` executed.
+    pub fn get_parent_entry_before(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<(ObjectRef, TransactionDigest)>, SuiError> {
+        let bound = match version.decrement() {
+            Ok(bound) => bound,
+            Err(_) => return Ok(None),
+        };
+        let mut iterator =
+            self.tables
+                .parent_sync
+                .iter()
+                .skip_prior_to(&(object_id, bound, ObjectDigest::MAX))?;
+
+        Ok(iterator
+            .next()
+            .filter(|(obj_ref, _tx_digest)| obj_ref.0 == object_id))
+    }
+
+    /// Returns the tombstone of the object if its latest version was deleted or wrapped, recording
+    /// the version it was deleted at and the transaction that deleted it. Returns None if the
+    /// object is alive or unknown.
+    pub fn get_object_tombstone(&self, object_id: ObjectID) -> SuiResult<Option<ObjectTombstone>> {
+        Ok(self
+            .get_latest_parent_entry(object_id)?
+            .filter(|(obj_ref, _)| !obj_ref.2.is_alive())
+            .map(|(object_ref, deleted_by)| ObjectTombstone {
+                object_ref,
+                deleted_by,
+            }))
+    }
+
+    /// Returns the owner of the object as of `version`, that is the owner of the latest version
+    /// of the object no later than `version`, read from the historical object state.
+    ///
+    /// Returns None if the object did not exist yet at that version, or had been deleted or
+    /// wrapped. Returns an ObjectVersionPruned error if the parent_sync index knows of the
+    /// version but the object state itself is no longer stored, and an ObjectVersionNotYetKnown
+    /// error if the version is newer than the latest version of the live object, whose owner
+    /// may still change before it.
+    pub fn get_owner_at_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Owner>> {
+        if let Some((latest_ref, _)) = self.get_latest_parent_entry(object_id)? {
+            if latest_ref.2.is_alive() && version > latest_ref.1 {
+                return Err(SuiError::ObjectVersionNotYetKnown {
+                    object_id,
+                    version,
+                    latest_version: latest_ref.1,
+                });
+            }
+        }
+
+        let entry = self
+            .tables
+            .parent_sync
+            .iter()
+            // Make the max possible entry for this object ID at this version.
+            .skip_prior_to(&(object_id, version, ObjectDigest::MAX))?
+            .next();
+
+        match entry {
+            Some(((id, seq, digest), _)) if id == object_id && digest.is_alive() => {
+                match self.get_object_by_key(&object_id, seq)? {
+                    Some(object) => Ok(Some(object.owner)),
+                    None => Err(SuiError::ObjectVersionPruned {
+                        object_id,
+                        version: seq,
+                    }),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Delete all but the latest `num_versions_to_retain` versions of every object, always
+    /// keeping at least the latest one. Every older version was taken as input by a transaction
+    /// whose effects have been written, so no transaction needs it to be retried. Returns the
+    /// number of versions deleted.
+    ///
+    /// Reverting a transaction at the end of an epoch reads the versions it took as input, so
+    /// validators reverting transactions must retain enough versions to cover them.
+    pub fn prune_object_versions(&self, num_versions_to_retain: u64) -> SuiResult<usize> {
+        let num_versions_to_retain = num_versions_to_retain.max(1) as usize;
+        let mut to_delete = Vec::new();
+        // The retained versions of the current object, oldest first.
+        let mut versions: VecDeque<ObjectKey> = VecDeque::new();
+        for key in self.tables.objects.keys() {
+            if versions.front().map_or(false, |front| front.0 != key.0) {
+                versions.clear();
+            }
+            versions.push_back(key);
+            if versions.len() > num_versions_to_retain {
+                to_delete.extend(versions.pop_front());
+            }
+        }
+
+        let pruned = to_delete.len();
+        let write_batch = self.tables.objects.batch();
+        write_batch
+            .delete_batch(&self.tables.objects, to_delete)?
+            .write()?;
+        debug!(pruned, "Pruned object versions");
+        Ok(pruned)
+    }
+
+    /// Delete the effects, along with the certificate, of the transactions executed before the
+    /// latest `num_transactions_to_retain`. Returns the number of transactions pruned.
+    ///
+    /// Without its effects a certificate looks unexecuted and could be executed again, so a
+    /// transaction is only pruned once the version of its gas object it took as input has been
+    /// pruned, which makes executing it again fail. Certificates still pending execution or
+    /// holding shared object locks are kept as well.
+    ///
+    /// Pruning resumes from a persisted watermark, the sequence number of the first transaction
+    /// not known to be pruned, and deletes at most `MAX_EFFECTS_PRUNED_PER_STEP` transactions per
+    /// write batch. The watermark stops at the first transaction kept, and a call stops after the
+    /// step which kept one so that it is retried on the next call.
+    ///
+    /// The executed sequence, and so the batches, keep referring to pruned transactions: their
+    /// digests are still streamed to followers but their info is no longer served, so a follower
+    /// further behind than the retention has to sync those transactions from another source.
+    pub fn prune_effects(&self, num_transactions_to_retain: u64) -> SuiResult<usize> {
+        let end = self
+            .next_sequence_number()?
+            .saturating_sub(num_transactions_to_retain);
+        let pending: HashSet<_> = self.tables.pending_execution.values().collect();
+        let mut watermark = self.effects_pruning_watermark()?;
+
+        let mut pruned = 0;
+        loop {
+            let mut to_delete = Vec::new();
+            let mut first_kept = None;
+            let mut next_seq = watermark;
+            for (seq, digests) in self
+                .tables
+                .executed_sequence
+                .iter()
+                .skip_to(&watermark)?
+                .take_while(|(seq, _)| *seq < end)
+                .take(MAX_EFFECTS_PRUNED_PER_STEP)
+            {
+                next_seq = seq + 1;
+                if self.is_prunable(&digests.transaction, &pending)? {
+                    to_delete.push(digests.transaction);
+                } else if first_kept.is_none() {
+                    first_kept = Some(seq);
+                }
+            }
+            if next_seq == watermark {
+                // Every transaction before the end was examined.
+                break;
+            }
+
+            pruned += to_delete.len();
+            watermark = first_kept.unwrap_or(next_seq);
+            let mut write_batch = self.tables.effects.batch();
+            write_batch = write_batch.delete_batch(&self.tables.certificates, to_delete.iter())?;
+            write_batch = write_batch.delete_batch(&self.tables.effects, to_delete.iter())?;
+            write_batch = write_batch.insert_batch(
+                &self.tables.effects_pruning_watermark,
+                iter::once((EFFECTS_PRUNING_WATERMARK_ADDR, watermark)),
+            )?;
+            write_batch.write()?;
+            if first_kept.is_some() {
+                break;
+            }
+        }
+        debug!(pruned, watermark, "Pruned transaction effects");
+        Ok(pruned)
+    }
+
+    /// The sequence number of the first executed transaction which is not known to be pruned.
+    pub fn effects_pruning_watermark(&self) -> SuiResult<TxSequenceNumber> {
+        Ok(self
+            .tables
+            .effects_pruning_watermark
+            .get(&EFFECTS_PRUNING_WATERMARK_ADDR)?
+            .unwrap_or(0))
+    }
+
+    /// Whether the effects and certificate of an executed transaction can be deleted, see
+    /// `prune_effects`.
+    fn is_prunable(
+        &self,
+        digest: &TransactionDigest,
+        pending: &HashSet<TransactionDigest>,
+    ) -> SuiResult<bool> {
+        if pending.contains(digest) || self.holds_shared_locks(digest)? {
+            return Ok(false);
+        }
+        match self.tables.certificates.get(digest)? {
+            Some(certificate) => {
+                let gas = certificate.signed_data.data.gas_payment_object_ref();
+                Ok(!self.tables.objects.contains_key(&ObjectKey::from(gas))?)
+            }
+            // The certificate is gone already, only the effects are left to prune.
+            None => Ok(true),
+        }
+    }
+
+    fn holds_shared_locks(&self, transaction_digest: &TransactionDigest) -> SuiResult<bool> {
+        Ok(self
+            .tables
+            .assigned_object_versions
+            .iter()
+            .skip_to(&(*transaction_digest, ObjectID::ZERO))?
+            .next()
+            .map_or(false, |((digest, _), _)| digest == *transaction_digest))
+    }
+
     /// Remove the shared objects locks.
     pub fn remove_shared_objects_locks(
         &self,
@@ -1073,6 +1535,14 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         Ok(())
     }
 
+    /// Whether the certificate was already sequenced by consensus and its shared objects locked.
+    pub fn consensus_message_processed(&self, digest: &TransactionDigest) -> SuiResult<bool> {
+        Ok(self
+            .tables
+            .consensus_message_processed
+            .contains_key(digest)?)
+    }
+
     /// Lock a sequence number for the shared objects of the input transaction. Also update the
     /// last consensus index.
     /// This function must only be called from the consensus task (i.e. from handle_consensus_transaction).
@@ -1086,11 +1556,7 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
 
         // Ensure that we only advance next_object_versions exactly once for every cert received from
         // consensus.
-        if self
-            .tables
-            .consensus_message_processed
-            .contains_key(&transaction_digest)?
-        {
+        if self.consensus_message_processed(&transaction_digest)? {
             return Ok(());
         }
 
@@ -1172,6 +1638,17 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         write_batch.write().map_err(SuiError::from)
     }
 
+    /// Returns the sequence number the transaction was executed at, if it was executed.
+    pub fn get_transaction_sequence(
+        &self,
+        transaction_digest: &TransactionDigest,
+    ) -> SuiResult<Option<TxSequenceNumber>> {
+        Ok(self
+            .tables
+            .executed_sequence_index
+            .get(transaction_digest)?)
+    }
+
     pub fn transactions_in_seq_range(
         &self,
         start: u64,
@@ -1186,6 +1663,52 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .collect())
     }
 
+    /// Check that the objects created, mutated and unwrapped by the last `count` executed
+    /// transactions are stored at the versions their effects state. Objects are written before
+    /// the effects that refer to them, so a mismatch means the store was left inconsistent, e.g.
+    /// by a partial write. Returns the transactions and object references which do not match.
+    ///
+    /// Pruned history is not a mismatch: transactions whose effects were pruned are skipped, as
+    /// are object versions missing while a later version of the object is stored.
+    ///
+    /// Executed effects may already have been shared with other authorities and clients, so
+    /// mismatches are only reported here rather than repaired.
+    pub fn check_effects_consistency(
+        &self,
+        count: u64,
+    ) -> SuiResult<Vec<(TransactionDigest, ObjectRef)>> {
+        let end = self.next_sequence_number()?;
+        let mut mismatches = vec![];
+        for (_, digests) in self.transactions_in_seq_range(end.saturating_sub(count), end)? {
+            let effects = match self.tables.effects.get(&digests.transaction)? {
+                Some(effects) => effects.effects,
+                None => continue,
+            };
+            for (object_ref, _) in effects.all_mutated() {
+                let stored_ref = self
+                    .get_object_by_key(&object_ref.0, object_ref.1)?
+                    .map(|object| object.compute_object_reference());
+                if stored_ref.as_ref() != Some(object_ref)
+                    && !(stored_ref.is_none() && self.has_later_version(object_ref)?)
+                {
+                    mismatches.push((digests.transaction, *object_ref));
+                }
+            }
+        }
+        Ok(mismatches)
+    }
+
+    /// Whether a version of the object later than `object_ref` is stored.
+    fn has_later_version(&self, object_ref: &ObjectRef) -> SuiResult<bool> {
+        Ok(self
+            .tables
+            .objects
+            .iter()
+            .skip_to(&ObjectKey(object_ref.0, object_ref.1.increment()))?
+            .next()
+            .map_or(false, |(key, _)| key.0 == object_ref.0))
+    }
+
     /// Retrieves batches including transactions within a range.
     ///
     /// This function returns all signed batches that enclose the requested transaction
@@ -1299,6 +1822,25 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .map_err(SuiError::from)
     }
 
+    /// Return the sequence number of the last transaction gossip synced from the peer, or `None`
+    /// if nothing was synced from it yet.
+    pub fn gossip_synced_sequence(
+        &self,
+        peer: &AuthorityName,
+    ) -> SuiResult<Option<TxSequenceNumber>> {
+        Ok(self.tables.gossip_synced_sequence.get(peer)?)
+    }
+
+    /// Record that gossip synced every transaction from the peer up to `seq`.
+    pub fn set_gossip_synced_sequence(
+        &self,
+        peer: &AuthorityName,
+        seq: TxSequenceNumber,
+    ) -> SuiResult {
+        self.tables.gossip_synced_sequence.insert(peer, &seq)?;
+        Ok(())
+    }
+
     pub fn get_transaction(
         &self,
         transaction_digest: &TransactionDigest,
@@ -1329,13 +1871,54 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         let sui_system_object = self
             .get_object(&SUI_SYSTEM_STATE_OBJECT_ID)?
             .expect("Sui System State object must always exist");
+        Ok(Self::to_sui_system_state(&sui_system_object))
+    }
+
+    /// Returns the Sui system state as it was at the end of `epoch`. The system parameters only
+    /// change at epoch boundaries, so this holds the parameters in effect during the epoch. The
+    /// epoch of the system state object only grows with its version, so the last version still
+    /// in `epoch` is found by binary search over the versions.
+    pub fn get_sui_system_state_at_epoch(&self, epoch: EpochId) -> SuiResult<SuiSystemState>
+    where
+        S: Eq + Serialize + for<'de> Deserialize<'de>,
+    {
+        let latest_version = self
+            .get_object(&SUI_SYSTEM_STATE_OBJECT_ID)?
+            .expect("Sui System State object must always exist")
+            .version();
+        let mut low = OBJECT_START_VERSION.value();
+        let mut high = latest_version.value();
+        let mut found = None;
+        while low <= high {
+            let middle = low + (high - low) / 2;
+            let object = self
+                .get_object_by_key(&SUI_SYSTEM_STATE_OBJECT_ID, middle.into())?
+                .ok_or(SuiError::ObjectNotFound {
+                    object_id: SUI_SYSTEM_STATE_OBJECT_ID,
+                })?;
+            let state = Self::to_sui_system_state(&object);
+            if state.epoch <= epoch {
+                found = Some(state);
+                low = middle + 1;
+            } else {
+                high = middle - 1;
+            }
+        }
+        match found {
+            Some(state) if state.epoch == epoch => Ok(state),
+            _ => Err(SuiError::ObjectNotFound {
+                object_id: SUI_SYSTEM_STATE_OBJECT_ID,
+            }),
+        }
+    }
+
+    fn to_sui_system_state(sui_system_object: &Object) -> SuiSystemState {
         let move_object = sui_system_object
             .data
             .try_as_move()
             .expect("Sui System State object must be a Move object");
-        let result = bcs::from_bytes::<SuiSystemState>(move_object.contents())
-            .expect("Sui System State object deserialization cannot fail");
-        Ok(result)
+        bcs::from_bytes::<SuiSystemState>(move_object.contents())
+            .expect("Sui System State object deserialization cannot fail")
     }
 }
 
@@ -1383,6 +1966,46 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> ParentSync for SuiDa
     }
 }
 
+/// A view of the store as it was just before a transaction executed, to replay it. The parent
+/// entry of an object the transaction wrote is the one preceding the version it wrote, other
+/// objects are read at their latest entry. Packages are immutable and are read as they are now.
+pub struct PreExecutionStore<S> {
+    store: Arc<SuiDataStore<S>>,
+    written_versions: BTreeMap<ObjectID, SequenceNumber>,
+}
+
+impl<S> PreExecutionStore<S> {
+    /// `written_versions` holds the version the transaction wrote of each object, as recorded in
+    /// its effects.
+    pub fn new(
+        store: Arc<SuiDataStore<S>>,
+        written_versions: BTreeMap<ObjectID, SequenceNumber>,
+    ) -> Self {
+        Self {
+            store,
+            written_versions,
+        }
+    }
+}
+
+impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> BackingPackageStore
+    for PreExecutionStore<S>
+{
+    fn get_package(&self, package_id: &ObjectID) -> SuiResult<Option<Object>> {
+        self.store.get_package(package_id)
+    }
+}
+
+impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> ParentSync for PreExecutionStore<S> {
+    fn get_latest_parent_entry_ref(&self, object_id: ObjectID) -> SuiResult<Option<ObjectRef>> {
+        let entry = match self.written_versions.get(&object_id) {
+            Some(version) => self.store.get_parent_entry_before(object_id, *version)?,
+            None => self.store.get_latest_parent_entry(object_id)?,
+        };
+        Ok(entry.map(|(obj_ref, _)| obj_ref))
+    }
+}
+
 impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> ModuleResolver for SuiDataStore<S> {
     type Error = SuiError;
 
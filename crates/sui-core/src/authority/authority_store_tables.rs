@@ -90,14 +90,31 @@ pub struct AuthorityStoreTables<S> {
     /// A sequence on all executed certificates and effects.
     pub executed_sequence: DBMap<TxSequenceNumber, ExecutionDigests>,
 
+    /// The sequence number each executed transaction was assigned in `executed_sequence`, by
+    /// transaction digest. Written in the same batch as `executed_sequence`.
+    pub(crate) executed_sequence_index: DBMap<TransactionDigest, TxSequenceNumber>,
+
+    /// The serialized size of the certificate and effects of each executed transaction, by its
+    /// sequence number in `executed_sequence`. Written in the same batch as `executed_sequence`,
+    /// so that the batch service can size its batches without reading the transactions back.
+    pub(crate) executed_transaction_sizes: DBMap<TxSequenceNumber, u64>,
+
     /// A sequence of batches indexing into the sequence of executed transactions.
     pub batches: DBMap<TxSequenceNumber, SignedBatch>,
 
+    /// The sequence number of the last transaction gossip synced from the batch stream of each
+    /// peer, so that following the peer resumes after it rather than refetching past transactions.
+    pub(crate) gossip_synced_sequence: DBMap<AuthorityName, TxSequenceNumber>,
+
     /// The following table is used to store a single value (the corresponding key is a constant). The value
     /// represents the index of the latest consensus message this authority processed. This field is written
     /// by a single process acting as consensus (light) client. It is used to ensure the authority processes
     /// every message output by consensus (and in the right order).
     pub(crate) last_consensus_index: DBMap<u64, ExecutionIndices>,
+
+    /// A single value (the key is a constant): the sequence number of the first executed
+    /// transaction whose effects are not known to be pruned, where pruning them resumes from.
+    pub(crate) effects_pruning_watermark: DBMap<u64, TxSequenceNumber>,
 }
 
 // These functions are used to initialize the DB tables
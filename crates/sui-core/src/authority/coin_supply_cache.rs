@@ -0,0 +1,130 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::language_storage::StructTag;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use sui_types::messages::CoinSupplyResponse;
+
+/// Caches the total supply of coin types, which is computed by scanning every object.
+///
+/// The supply of a coin type is dropped when a transaction touching coins of that type commits,
+/// so a cached supply includes every transaction committed before it was computed. A supply
+/// computed while a transaction touching coins of its type commits is not cached, as it may have
+/// missed its writes.
+#[derive(Default)]
+pub struct CoinSupplyCache {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// The supply of each coin type T, for `Coin<T>` objects.
+    supplies: HashMap<StructTag, CoinSupplyResponse>,
+    /// Counts the invalidations, to tell whether one happened while a supply was computed.
+    generation: u64,
+    /// The generation of the last invalidation of each coin type since the last clear.
+    invalidated_at: HashMap<StructTag, u64>,
+    /// The generation of the last clear.
+    cleared_at: u64,
+}
+
+impl CoinSupplyCache {
+    pub(crate) fn get(&self, coin_type: &StructTag) -> Option<CoinSupplyResponse> {
+        self.inner.lock().supplies.get(coin_type).cloned()
+    }
+
+    /// To be read before computing a supply, and given back to `insert`.
+    pub(crate) fn generation(&self) -> u64 {
+        self.inner.lock().generation
+    }
+
+    /// Cache a supply computed since `generation`, unless coins of its type were touched in the
+    /// meantime.
+    pub(crate) fn insert(
+        &self,
+        coin_type: StructTag,
+        supply: CoinSupplyResponse,
+        generation: u64,
+    ) -> bool {
+        let mut inner = self.inner.lock();
+        let invalidated = inner.cleared_at > generation
+            || inner
+                .invalidated_at
+                .get(&coin_type)
+                .map_or(false, |invalidated_at| *invalidated_at > generation);
+        if invalidated {
+            return false;
+        }
+        inner.supplies.insert(coin_type, supply);
+        true
+    }
+
+    /// Drop the supplies of `coin_types`, after their coins were written.
+    pub(crate) fn invalidate(&self, coin_types: impl IntoIterator<Item = StructTag>) {
+        let mut coin_types = coin_types.into_iter().peekable();
+        if coin_types.peek().is_none() {
+            return;
+        }
+        let mut inner = self.inner.lock();
+        inner.generation += 1;
+        let generation = inner.generation;
+        for coin_type in coin_types {
+            inner.supplies.remove(&coin_type);
+            inner.invalidated_at.insert(coin_type, generation);
+        }
+    }
+
+    /// Drop every supply, e.g. after transactions were reverted.
+    pub(crate) fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.generation += 1;
+        inner.cleared_at = inner.generation;
+        inner.supplies.clear();
+        inner.invalidated_at.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::{coin::Coin, gas_coin::GAS};
+
+    #[test]
+    fn test_coin_supply_cache() {
+        let cache = CoinSupplyCache::default();
+        let supply = |total_supply| CoinSupplyResponse {
+            total_supply,
+            coin_count: 1,
+        };
+
+        let generation = cache.generation();
+        assert!(cache.insert(GAS::type_(), supply(10), generation));
+        assert_eq!(cache.get(&GAS::type_()), Some(supply(10)));
+
+        // Touching no coin keeps the supplies.
+        cache.invalidate(std::iter::empty());
+        assert_eq!(cache.generation(), generation);
+        assert_eq!(cache.get(&GAS::type_()), Some(supply(10)));
+
+        // A supply computed while coins were touched is not cached.
+        let generation = cache.generation();
+        cache.invalidate([GAS::type_()]);
+        assert_eq!(cache.get(&GAS::type_()), None);
+        assert!(!cache.insert(GAS::type_(), supply(20), generation));
+        assert_eq!(cache.get(&GAS::type_()), None);
+
+        // Touching coins of another type keeps a supply computed meanwhile.
+        let generation = cache.generation();
+        cache.invalidate([Coin::type_(GAS::type_())]);
+        assert!(cache.insert(GAS::type_(), supply(25), generation));
+        assert_eq!(cache.get(&GAS::type_()), Some(supply(25)));
+
+        // Clearing drops every supply, including those being computed.
+        let generation = cache.generation();
+        cache.clear();
+        assert_eq!(cache.get(&GAS::type_()), None);
+        assert!(!cache.insert(GAS::type_(), supply(30), generation));
+        assert!(cache.insert(GAS::type_(), supply(30), cache.generation()));
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use std::time::Instant;
+
+/// The number of seconds over which the throughput of an authority is averaged.
+pub const THROUGHPUT_WINDOW_SECS: u64 = 60;
+
+/// Counts events, e.g. executed certificates, in one-second buckets to report their rate over a
+/// sliding window of the last seconds.
+pub struct ThroughputCounter {
+    start: Instant,
+    window_secs: u64,
+    /// A ring of `(second since start, count)` buckets. It holds one more bucket than the window,
+    /// so that counting the current second never overwrites a second still in the window.
+    buckets: Mutex<Vec<(u64, u64)>>,
+}
+
+impl ThroughputCounter {
+    pub fn new(window_secs: u64) -> Self {
+        assert!(window_secs > 0, "The throughput window must not be empty");
+        Self {
+            start: Instant::now(),
+            window_secs,
+            buckets: Mutex::new(vec![(0, 0); window_secs as usize + 1]),
+        }
+    }
+
+    /// Count an event happening now.
+    pub fn record(&self) {
+        self.record_at(self.start.elapsed().as_secs());
+    }
+
+    /// The events per second, over the last complete seconds of the window.
+    pub fn rate(&self) -> f64 {
+        self.rate_at(self.start.elapsed().as_secs())
+    }
+
+    fn record_at(&self, second: u64) {
+        let mut buckets = self.buckets.lock();
+        let len = buckets.len() as u64;
+        let bucket = &mut buckets[(second % len) as usize];
+        if bucket.0 != second {
+            *bucket = (second, 0);
+        }
+        bucket.1 += 1;
+    }
+
+    fn rate_at(&self, now: u64) -> f64 {
+        // Only complete seconds are counted, and fewer of them exist right after startup.
+        let window = self.window_secs.min(now);
+        if window == 0 {
+            return 0.0;
+        }
+        let count: u64 = self
+            .buckets
+            .lock()
+            .iter()
+            .filter(|(second, _)| *second < now && *second >= now - window)
+            .map(|(_, count)| count)
+            .sum();
+        count as f64 / window as f64
+    }
+}
+
+impl Default for ThroughputCounter {
+    fn default() -> Self {
+        Self::new(THROUGHPUT_WINDOW_SECS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throughput_counter() {
+        let counter = ThroughputCounter::new(3);
+        assert_eq!(counter.rate_at(0), 0.0);
+
+        for _ in 0..4 {
+            counter.record_at(0);
+        }
+        // The current second is not complete yet.
+        assert_eq!(counter.rate_at(0), 0.0);
+        assert_eq!(counter.rate_at(1), 4.0);
+
+        counter.record_at(1);
+        counter.record_at(2);
+        assert_eq!(counter.rate_at(3), 2.0);
+
+        // The first second falls out of the window, and its bucket is reused.
+        counter.record_at(4);
+        assert_eq!(counter.rate_at(4), 2.0 / 3.0);
+        assert_eq!(counter.rate_at(5), 2.0 / 3.0);
+
+        // Nothing happened in the last three seconds.
+        assert_eq!(counter.rate_at(8), 0.0);
+    }
+}
@@ -7,7 +7,12 @@ use crate::authority_active::checkpoint_driver::CheckpointMetrics;
 use std::sync::Arc;
 use std::time::Duration;
 
-use sui_types::crypto::AccountKeyPair;
+use sui_types::base_types::dbg_addr;
+use sui_types::batch::UpdateItem;
+use sui_types::crypto::{AccountKeyPair, Signature};
+use sui_types::messages::{
+    SingleTransactionKind, Transaction, TransactionData, TransactionKind, TransferObject,
+};
 use sui_types::{crypto::get_key_pair, messages::ExecutionStatus, object::Object};
 
 //use super::super::AuthorityState;
@@ -18,6 +23,8 @@ use crate::authority_aggregator::authority_aggregator_tests::{
 use crate::checkpoints::checkpoint_tests::checkpoint_tests_setup;
 use crate::test_utils::wait_for_tx;
 
+use super::execute_pending;
+
 use tracing::info;
 
 #[tokio::test(flavor = "current_thread", start_paused = true)]
@@ -277,3 +284,116 @@ async fn test_parent_cert_exec() {
         .signed_effects
         .unwrap();
 }
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn test_execute_pending_batches_by_priority() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    // Each transaction transfers its own object with its own gas, so they are independent.
+    let priorities = [1, 3, 2, 3];
+    let objects: Vec<_> = priorities
+        .iter()
+        .map(|_| {
+            (
+                Object::with_owner_for_testing(sender),
+                Object::with_owner_for_testing(sender),
+            )
+        })
+        .collect();
+    let (aggregator, authorities, _) = init_local_authorities(
+        4,
+        objects
+            .iter()
+            .flat_map(|(object, gas)| vec![object.clone(), gas.clone()])
+            .collect(),
+    )
+    .await;
+    let authority_clients: Vec<_> = authorities
+        .iter()
+        .map(|a| &aggregator.authority_clients[&a.name])
+        .collect();
+
+    // Certify the transactions without the 4th authority executing any of them.
+    let mut certificates = Vec::new();
+    for ((object, gas), priority) in objects.iter().zip(priorities) {
+        let object_ref = get_latest_ref(authority_clients[0], object.id()).await;
+        let gas_ref = get_latest_ref(authority_clients[0], gas.id()).await;
+        let data = TransactionData::new_with_gas_price(
+            TransactionKind::Single(SingleTransactionKind::TransferObject(TransferObject {
+                recipient,
+                object_ref,
+            })),
+            sender,
+            gas_ref,
+            10000,
+            priority,
+        );
+        let signature = Signature::new(&data, &sender_key);
+        let transaction = Transaction::new(data, signature);
+        for &authority_client in &authority_clients[..3] {
+            do_transaction(authority_client, &transaction).await;
+        }
+        certificates.push(
+            extract_cert(
+                &authority_clients,
+                &aggregator.committee,
+                transaction.digest(),
+            )
+            .await,
+        );
+    }
+
+    let active_state = Arc::new(
+        ActiveAuthority::new_with_ephemeral_storage_for_test(
+            authorities[3].clone(),
+            aggregator.clone(),
+        )
+        .unwrap(),
+    );
+    let batch_state = authorities[3].clone();
+    let _join = tokio::task::spawn(async move {
+        batch_state
+            .run_batch_service(priorities.len() as u64, Duration::from_secs(10000))
+            .await
+    });
+    let mut rx = authorities[3].subscribe_batch();
+    tokio::task::yield_now().await;
+
+    // Schedule the certificates together, and let the execution driver execute them.
+    authorities[3]
+        .database
+        .add_pending_certificates(
+            certificates
+                .iter()
+                .map(|cert| (*cert.digest(), Some(cert.clone())))
+                .collect(),
+        )
+        .unwrap();
+    assert!(execute_pending(&active_state).await.unwrap());
+    assert!(authorities[3]
+        .database
+        .get_pending_digests()
+        .unwrap()
+        .is_empty());
+
+    // Higher priority transactions come first in the batch, and those of equal priority keep
+    // their order.
+    let mut batched = Vec::new();
+    loop {
+        match rx.recv().await.unwrap() {
+            UpdateItem::Transaction((_, digests)) => batched.push(digests.transaction),
+            UpdateItem::Batch(batch) => {
+                assert_eq!(batch.data().size, priorities.len() as u64);
+                break;
+            }
+        }
+    }
+    let expected: Vec<_> = [1, 3, 2, 0]
+        .iter()
+        .map(|i| *certificates[*i].digest())
+        .collect();
+    assert_eq!(batched, expected);
+
+    authorities[3].batch_notifier.close();
+    _join.await.expect("No issues ending task.").expect("ok");
+}
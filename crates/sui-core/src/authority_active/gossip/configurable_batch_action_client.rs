@@ -9,20 +9,25 @@ use crate::authority_client::{AuthorityAPI, BatchInfoResponseItemStream};
 use crate::epoch::epoch_store::EpochStore;
 use crate::safe_client::SafeClient;
 use async_trait::async_trait;
+use futures::StreamExt;
+use parking_lot::Mutex;
 use std::borrow::Borrow;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
+use std::iter;
 use std::sync::Arc;
 use std::sync::Once;
+use std::time::Duration;
 use sui_adapter::genesis;
 use sui_types::base_types::*;
 use sui_types::batch::{AuthorityBatch, SignedBatch, UpdateItem};
-use sui_types::committee::Committee;
+use sui_types::committee::{Committee, StakeUnit};
 use sui_types::crypto::{get_key_pair, AuthorityKeyPair};
-use sui_types::error::SuiError;
+use sui_types::error::{SuiError, SuiResult};
 use sui_types::messages::{
-    AccountInfoRequest, AccountInfoResponse, BatchInfoRequest, BatchInfoResponseItem,
-    CertifiedTransaction, EpochRequest, EpochResponse, ObjectInfoRequest, ObjectInfoResponse,
-    Transaction, TransactionInfoRequest, TransactionInfoResponse,
+    AccountInfoPageRequest, AccountInfoPageResponse, AccountInfoRequest, AccountInfoResponse,
+    BatchInfoRequest, BatchInfoResponseItem, CertifiedTransaction, EpochRequest, EpochResponse,
+    ObjectInfoRequest, ObjectInfoResponse, Transaction, TransactionInfoRequest,
+    TransactionInfoResponse,
 };
 use sui_types::messages_checkpoint::{CheckpointRequest, CheckpointResponse};
 use sui_types::object::Object;
@@ -45,18 +50,49 @@ pub struct TestBatch {
 pub enum BatchAction {
     EmitError(),
     EmitUpdateItem(),
+    /// Fail one batch stream request to every authority with the error, e.g. to simulate a
+    /// network failure, rather than emitting an error within the stream.
+    ReturnError(SuiError),
+    /// Emit the error within every batch stream, after the items of the preceding actions.
+    EmitErrorItem(SuiError),
+    /// Stall the batch stream for `before`, then perform the `then` action.
+    Delay {
+        before: Duration,
+        then: Box<BatchAction>,
+    },
+}
+
+/// The methods of `AuthorityAPI`, used to select which method an error is injected into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ClientMethod {
+    HandleTransaction,
+    HandleCertificate,
+    HandleAccountInfoRequest,
+    HandleAccountInfoPageRequest,
+    HandleObjectInfoRequest,
+    HandleTransactionInfoRequest,
+    HandleBatchStream,
+    HandleCheckpoint,
+    HandleEpoch,
 }
 
 #[derive(Clone)]
 pub enum BatchActionInternal {
     EmitError(),
     EmitUpdateItem(TestBatch),
+    EmitErrorItem(SuiError),
+    Delay(Duration, Box<BatchActionInternal>),
 }
 
 #[derive(Clone)]
 pub struct ConfigurableBatchActionClient {
     state: Arc<AuthorityState>,
     pub action_sequence_internal: Vec<BatchActionInternal>,
+    // Errors returned by the next calls to each method, instead of handling them.
+    // Shared between clones, so that errors can be injected into the clients of an aggregator.
+    injected_errors: Arc<Mutex<BTreeMap<ClientMethod, VecDeque<SuiError>>>>,
+    // The batch stream requests received, shared between clones like the injected errors.
+    batch_stream_requests: Arc<Mutex<Vec<BatchInfoRequest>>>,
 }
 
 impl ConfigurableBatchActionClient {
@@ -76,6 +112,8 @@ impl ConfigurableBatchActionClient {
         ConfigurableBatchActionClient {
             state: Arc::new(state),
             action_sequence_internal: Vec::new(),
+            injected_errors: Default::default(),
+            batch_stream_requests: Default::default(),
         }
     }
 
@@ -83,6 +121,34 @@ impl ConfigurableBatchActionClient {
     pub fn register_action_sequence(&mut self, actions: Vec<BatchActionInternal>) {
         self.action_sequence_internal = actions;
     }
+
+    /// Make the next `times` calls to `method` fail with `error` before reaching the authority.
+    #[cfg(test)]
+    pub fn inject_error(&self, method: ClientMethod, error: SuiError, times: usize) {
+        self.injected_errors
+            .lock()
+            .entry(method)
+            .or_default()
+            .extend(iter::repeat(error).take(times));
+    }
+
+    /// Take the batch stream requests received since the last call.
+    #[cfg(test)]
+    pub fn take_batch_stream_requests(&self) -> Vec<BatchInfoRequest> {
+        std::mem::take(&mut *self.batch_stream_requests.lock())
+    }
+
+    fn check_injected_error(&self, method: ClientMethod) -> SuiResult {
+        match self
+            .injected_errors
+            .lock()
+            .get_mut(&method)
+            .and_then(VecDeque::pop_front)
+        {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
 }
 
 #[async_trait]
@@ -91,6 +157,7 @@ impl AuthorityAPI for ConfigurableBatchActionClient {
         &self,
         transaction: Transaction,
     ) -> Result<TransactionInfoResponse, SuiError> {
+        self.check_injected_error(ClientMethod::HandleTransaction)?;
         let state = self.state.clone();
         state.handle_transaction(transaction).await
     }
@@ -99,6 +166,7 @@ impl AuthorityAPI for ConfigurableBatchActionClient {
         &self,
         certificate: CertifiedTransaction,
     ) -> Result<TransactionInfoResponse, SuiError> {
+        self.check_injected_error(ClientMethod::HandleCertificate)?;
         let state = self.state.clone();
         state.handle_certificate(certificate).await
     }
@@ -107,16 +175,30 @@ impl AuthorityAPI for ConfigurableBatchActionClient {
         &self,
         _request: AccountInfoRequest,
     ) -> Result<AccountInfoResponse, SuiError> {
+        self.check_injected_error(ClientMethod::HandleAccountInfoRequest)?;
         Ok(AccountInfoResponse {
             object_ids: vec![],
             owner: Default::default(),
         })
     }
 
+    async fn handle_account_info_page_request(
+        &self,
+        request: AccountInfoPageRequest,
+    ) -> Result<AccountInfoPageResponse, SuiError> {
+        self.check_injected_error(ClientMethod::HandleAccountInfoPageRequest)?;
+        Ok(AccountInfoPageResponse {
+            object_ids: vec![],
+            owner: request.account,
+            next_cursor: None,
+        })
+    }
+
     async fn handle_object_info_request(
         &self,
         request: ObjectInfoRequest,
     ) -> Result<ObjectInfoResponse, SuiError> {
+        self.check_injected_error(ClientMethod::HandleObjectInfoRequest)?;
         let state = self.state.clone();
         state.handle_object_info_request(request).await
     }
@@ -126,19 +208,24 @@ impl AuthorityAPI for ConfigurableBatchActionClient {
         &self,
         request: TransactionInfoRequest,
     ) -> Result<TransactionInfoResponse, SuiError> {
+        self.check_injected_error(ClientMethod::HandleTransactionInfoRequest)?;
         self.state.handle_transaction_info_request(request).await
     }
 
     /// Handle Batch information requests for this authority.
     async fn handle_batch_stream(
         &self,
-        _request: BatchInfoRequest,
+        request: BatchInfoRequest,
     ) -> Result<BatchInfoResponseItemStream, SuiError> {
+        self.check_injected_error(ClientMethod::HandleBatchStream)?;
+        let start = request.start.unwrap_or(0);
+        self.batch_stream_requests.lock().push(request);
         let mut last_batch = AuthorityBatch::initial();
         let actions = &self.action_sequence_internal;
         let secret = self.state.secret.clone();
         let name = self.state.name;
-        let mut items: Vec<Result<BatchInfoResponseItem, SuiError>> = Vec::new();
+        // Each item is emitted after the delay it is paired with.
+        let mut items: Vec<(Duration, Result<BatchInfoResponseItem, SuiError>)> = Vec::new();
         let mut seq = 0;
         let zero_batch = SignedBatch::new(
             self.state.epoch(),
@@ -146,17 +233,29 @@ impl AuthorityAPI for ConfigurableBatchActionClient {
             &*secret,
             name,
         );
-        items.push(Ok(BatchInfoResponseItem(UpdateItem::Batch(zero_batch))));
-        let _ = actions.iter().for_each(|action| {
+        items.push((
+            Duration::ZERO,
+            Ok(BatchInfoResponseItem(UpdateItem::Batch(zero_batch))),
+        ));
+        for mut action in actions {
+            // The delays of nested actions add up before the first item of the innermost one.
+            let mut delay = Duration::ZERO;
+            while let BatchActionInternal::Delay(before, then) = action {
+                delay += *before;
+                action = then.as_ref();
+            }
             match action {
                 BatchActionInternal::EmitUpdateItem(test_batch) => {
                     let mut transactions = Vec::new();
                     for digest in test_batch.digests.clone() {
                         transactions.push((seq, digest));
                         // Safe client requires batches arrive first
-                        items.push(Ok(BatchInfoResponseItem(UpdateItem::Transaction((
-                            seq, digest,
-                        )))));
+                        items.push((
+                            std::mem::take(&mut delay),
+                            Ok(BatchInfoResponseItem(UpdateItem::Transaction((
+                                seq, digest,
+                            )))),
+                        ));
                         seq += 1;
                     }
                     // batch size of 1
@@ -169,32 +268,65 @@ impl AuthorityAPI for ConfigurableBatchActionClient {
                             &*secret,
                             name,
                         );
-                        Ok(BatchInfoResponseItem(UpdateItem::Batch(item)))
+                        (
+                            std::mem::take(&mut delay),
+                            Ok(BatchInfoResponseItem(UpdateItem::Batch(item))),
+                        )
                     });
                 }
                 BatchActionInternal::EmitError() => unsafe {
                     if SHOULD_FAIL {
                         fix();
-                        items.push(Err(SuiError::GenericAuthorityError {
-                            error: "Synthetic authority error".to_string(),
-                        }))
+                        items.push((
+                            delay,
+                            Err(SuiError::GenericAuthorityError {
+                                error: "Synthetic authority error".to_string(),
+                            }),
+                        ))
                     }
                 },
+                BatchActionInternal::EmitErrorItem(error) => {
+                    items.push((delay, Err(error.clone())));
+                }
+                BatchActionInternal::Delay(..) => unreachable!("Delays were unwrapped above"),
             };
-        });
+        }
 
-        Ok(Box::pin(tokio_stream::iter(items)))
+        // Like an authority, stream from the last batch before the requested start, so that the
+        // transactions before it are not streamed again.
+        let first_item = items
+            .iter()
+            .rposition(|(_, item)| {
+                matches!(
+                    item,
+                    Ok(BatchInfoResponseItem(UpdateItem::Batch(batch)))
+                        if batch.data().next_sequence_number <= start
+                )
+            })
+            .unwrap_or(0);
+        items.drain(..first_item);
+
+        Ok(Box::pin(tokio_stream::iter(items).then(
+            |(delay, item)| async move {
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                item
+            },
+        )))
     }
 
     async fn handle_checkpoint(
         &self,
         request: CheckpointRequest,
     ) -> Result<CheckpointResponse, SuiError> {
+        self.check_injected_error(ClientMethod::HandleCheckpoint)?;
         let state = self.state.clone();
         state.handle_checkpoint_request(&request)
     }
 
     async fn handle_epoch(&self, request: EpochRequest) -> Result<EpochResponse, SuiError> {
+        self.check_injected_error(ClientMethod::HandleEpoch)?;
         let state = self.state.clone();
         state.handle_epoch_request(&request)
     }
@@ -207,32 +339,40 @@ pub async fn init_configurable_authorities(
     AuthorityAggregator<ConfigurableBatchActionClient>,
     Vec<Arc<AuthorityState>>,
     Vec<ExecutionDigests>,
+) {
+    init_configurable_authorities_with_stakes(authority_action, vec![1; 4]).await
+}
+
+/// Like `init_configurable_authorities`, with an authority for each of the stakes. The
+/// transactions of the actions are only certified when every stake is equal.
+#[cfg(test)]
+pub async fn init_configurable_authorities_with_stakes(
+    authority_action: Vec<BatchAction>,
+    stakes: Vec<StakeUnit>,
+) -> (
+    AuthorityAggregator<ConfigurableBatchActionClient>,
+    Vec<Arc<AuthorityState>>,
+    Vec<ExecutionDigests>,
 ) {
     use fastcrypto::traits::KeyPair;
     use sui_types::crypto::AccountKeyPair;
 
     use crate::safe_client::SafeClientMetrics;
 
-    let authority_count = 4;
     let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
     let mut gas_objects = Vec::new();
     for _i in 0..authority_action.len() {
         gas_objects.push(Object::with_owner_for_testing(addr1));
     }
-    let genesis_objects = vec![
-        gas_objects.clone(),
-        gas_objects.clone(),
-        gas_objects.clone(),
-        gas_objects.clone(),
-    ];
+    let genesis_objects = vec![gas_objects.clone(); stakes.len()];
 
     // Create committee.
     let mut key_pairs = Vec::new();
     let mut voting_rights = BTreeMap::new();
-    for _ in 0..authority_count {
+    for stake in stakes {
         let (_, key_pair): (_, AuthorityKeyPair) = get_key_pair();
         let authority_name = key_pair.public().into();
-        voting_rights.insert(authority_name, 1);
+        voting_rights.insert(authority_name, stake);
         key_pairs.push((authority_name, key_pair));
     }
     let committee = Committee::new(0, voting_rights).unwrap();
@@ -261,9 +401,18 @@ pub async fn init_configurable_authorities(
     // create a batch action internal sequence.
     let mut to_be_executed_digests = Vec::new();
     let mut batch_action_internal = Vec::new();
+    let mut injected_batch_stream_errors = Vec::new();
     let framework_obj_ref = genesis::get_framework_object_ref();
 
     for (action, gas_object) in authority_action.iter().zip(gas_objects) {
+        let mut delays = Vec::new();
+        let mut action = action;
+        while let BatchAction::Delay { before, then } = action {
+            delays.push(*before);
+            action = then.as_ref();
+        }
+        let internal_actions_before = batch_action_internal.len();
+
         if let BatchAction::EmitUpdateItem() = action {
             let temp_client = clients[0].borrow();
             let gas_ref = get_latest_ref(temp_client, gas_object.id()).await;
@@ -292,6 +441,25 @@ pub async fn init_configurable_authorities(
         if let BatchAction::EmitError() = action {
             batch_action_internal.push(BatchActionInternal::EmitError());
         }
+        if let BatchAction::EmitErrorItem(error) = action {
+            batch_action_internal.push(BatchActionInternal::EmitErrorItem(error.clone()));
+        }
+        if let BatchAction::ReturnError(error) = action {
+            assert!(
+                delays.is_empty(),
+                "Errors returned instead of a batch stream cannot be delayed"
+            );
+            injected_batch_stream_errors.push(error.clone());
+        }
+
+        // Wrap the internal action in the delays around it, innermost first.
+        if batch_action_internal.len() > internal_actions_before {
+            let mut internal_action = batch_action_internal.pop().unwrap();
+            for before in delays.into_iter().rev() {
+                internal_action = BatchActionInternal::Delay(before, Box::new(internal_action));
+            }
+            batch_action_internal.push(internal_action);
+        }
     }
 
     // Create BtreeMap of names to clients.
@@ -326,6 +494,17 @@ pub async fn init_configurable_authorities(
         executed_digests.push(ExecutionDigests::new(digest, effects_digest));
     }
 
+    // Inject the errors only now, so that they do not interfere with setting up the authorities.
+    for client in authority_clients.values() {
+        for error in &injected_batch_stream_errors {
+            client.authority_client().inject_error(
+                ClientMethod::HandleBatchStream,
+                error.clone(),
+                1,
+            );
+        }
+    }
+
     let authority_clients = authority_clients
         .into_iter()
         .map(|(name, client)| (name, client.authority_client().clone()))
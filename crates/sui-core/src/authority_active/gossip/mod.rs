@@ -12,6 +12,7 @@ use prometheus::{
     register_histogram_with_registry, register_int_counter_with_registry,
     register_int_gauge_with_registry, Histogram, IntCounter, IntGauge, Registry,
 };
+use rand::{seq::SliceRandom, Rng};
 use std::future::Future;
 use std::ops::Deref;
 use std::{collections::HashSet, sync::Arc, time::Duration};
@@ -41,6 +42,8 @@ pub(crate) struct Follower<A> {
 
 const REQUEST_FOLLOW_NUM_DIGESTS: u64 = 100_000;
 const REFRESH_FOLLOWER_PERIOD_SECS: u64 = 60;
+/// The probability of selecting a peer to follow uniformly at random rather than by stake.
+const RANDOM_PEER_SELECTION_PROBABILITY: f64 = 0.2;
 
 use super::ActiveAuthority;
 
@@ -188,6 +191,9 @@ async fn follower_process<A, Handler: DigestHandler<A> + Clone>(
                 select_gossip_peer(local_active.state.name, peer_names.clone(), &local_active)
                     .await;
             if name_result.is_err() {
+                // Give the peers some time to become available, rather than retrying right away.
+                tokio::time::sleep(Duration::from_millis(10 * committee.num_members() as u64))
+                    .await;
                 continue;
             }
             let name = name_result.unwrap();
@@ -256,6 +262,9 @@ async fn wait_for_one_gossip_task_to_finish<A>(
     peer_names.remove(&finished_name);
 }
 
+/// Select a peer to follow, that is not already followed and can be contacted now. Peers are
+/// usually selected by stake, so that the authorities holding most of the stake are followed,
+/// but sometimes uniformly at random, so that every peer is followed from time to time.
 pub async fn select_gossip_peer<A>(
     my_name: AuthorityName,
     peer_names: HashSet<AuthorityName>,
@@ -264,24 +273,32 @@ pub async fn select_gossip_peer<A>(
 where
     A: AuthorityAPI + Send + Sync + 'static + Clone,
 {
-    // Make sure we exit loop by limiting the number of tries to choose peer
-    // where n is the total number of committee members.
-    let mut tries_remaining = active_authority.state.committee.load().num_members();
-    while tries_remaining > 0 {
-        let name = *active_authority.state.committee.load().sample();
-        if peer_names.contains(&name)
-            || name == my_name
-            || !active_authority.can_contact(name).await
+    let committee = active_authority.state.committee.load().deref().clone();
+    let mut candidates = Vec::new();
+    for (name, stake) in committee.members() {
+        if *name != my_name
+            && !peer_names.contains(name)
+            && active_authority.can_contact(*name).await
         {
-            tries_remaining -= 1;
-            tokio::time::sleep(Duration::from_millis(10)).await;
-            continue;
+            candidates.push((*name, *stake));
         }
-        return Ok(name);
     }
-    Err(SuiError::GenericAuthorityError {
-        error: "Could not connect to any peer".to_string(),
-    })
+
+    let mut rng = rand::thread_rng();
+    let selected = if rng.gen_bool(RANDOM_PEER_SELECTION_PROBABILITY) {
+        candidates.choose(&mut rng)
+    } else {
+        // Fails if no candidate holds any stake, and then any of them is as good as another.
+        candidates
+            .choose_weighted(&mut rng, |(_, stake)| *stake)
+            .ok()
+            .or_else(|| candidates.choose(&mut rng))
+    };
+    selected
+        .map(|(name, _)| *name)
+        .ok_or_else(|| SuiError::GenericAuthorityError {
+            error: "Could not connect to any peer".to_string(),
+        })
 }
 
 #[async_trait]
@@ -391,6 +408,16 @@ where
         (peer_name, result)
     }
 
+    /// The sequence number to follow the peer from: the one after the last transaction synced
+    /// from it, or `None` to start at its latest batch on first contact.
+    fn resume_sequence(&self) -> SuiResult<Option<TxSequenceNumber>> {
+        Ok(self
+            .state
+            .database
+            .gossip_synced_sequence(&self.peer_name)?
+            .map(|seq| seq + 1))
+    }
+
     async fn follow_peer_for_duration<'a, Handler: DigestHandler<A>>(
         &self,
         duration: Duration,
@@ -402,7 +429,7 @@ where
         let mut results = FuturesOrdered::new();
 
         let req = BatchInfoRequest {
-            start: None,
+            start: self.resume_sequence()?,
             length: REQUEST_FOLLOW_NUM_DIGESTS,
         };
         let mut streamx = Box::pin(self.client.handle_batch_stream(req).await?);
@@ -452,7 +479,7 @@ where
                             self.client.metrics_total_times_reconnect_follower_stream.inc();
                             tokio::time::sleep(Duration::from_secs(REFRESH_FOLLOWER_PERIOD_SECS / 12)).await;
                             let req = BatchInfoRequest {
-                                start: None,
+                                start: self.resume_sequence()?,
                                 length: REQUEST_FOLLOW_NUM_DIGESTS,
                             };
                             streamx = Box::pin(self.client.handle_batch_stream(req).await?);
@@ -463,6 +490,8 @@ where
                 result = &mut results.next() , if !results.is_empty() => {
                     let (seq, digests) = result.unwrap()?;
                     trace!(?peer, ?seq, ?digests, "digest handler finished");
+                    // The results complete in order, so every transaction up to here was synced.
+                    self.state.database.set_gossip_synced_sequence(&peer, seq)?;
                 }
             };
         }
@@ -3,10 +3,12 @@
 
 use super::*;
 use crate::authority_active::gossip::configurable_batch_action_client::{
-    init_configurable_authorities, BatchAction, ConfigurableBatchActionClient,
+    init_configurable_authorities, init_configurable_authorities_with_stakes, BatchAction,
+    ConfigurableBatchActionClient,
 };
 use crate::authority_active::MAX_RETRY_DELAY_MS;
 use crate::authority_aggregator::AuthorityAggregator;
+use std::collections::BTreeMap;
 use std::time::Duration;
 use tokio::task::JoinHandle;
 
@@ -66,6 +68,86 @@ pub async fn test_gossip_error() {
     }
 }
 
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+pub async fn test_gossip_no_network() {
+    let network_error =
+        SuiError::RpcError("Synthetic network error".to_string(), "Unavailable", None);
+    let action_sequence = vec![
+        BatchAction::ReturnError(network_error.clone()),
+        BatchAction::ReturnError(network_error),
+        BatchAction::EmitUpdateItem(),
+        BatchAction::EmitUpdateItem(),
+    ];
+
+    let (net, states, digests) = init_configurable_authorities(action_sequence).await;
+
+    let _active_authorities = start_gossip_process(states.clone(), net.clone()).await;
+    // Every peer fails twice before it can be followed, so wait out the failure back-offs.
+    tokio::time::sleep(Duration::from_millis(4 * MAX_RETRY_DELAY_MS)).await;
+
+    // The failures were seen by the gossip processes, which retried them.
+    let gossip_errors: u64 = states
+        .iter()
+        .map(|state| state.metrics.gossip_task_error_count.get())
+        .sum();
+    assert!(gossip_errors > 0);
+
+    for client in net.clone_inner_clients().values() {
+        for digest in &digests {
+            let result = client
+                .handle_transaction_info_request(TransactionInfoRequest {
+                    transaction_digest: digest.transaction,
+                })
+                .await
+                .unwrap();
+            assert!(result.certified_transaction.is_some());
+        }
+    }
+}
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+pub async fn test_gossip_stalled_stream_error() {
+    let stream_error = SuiError::GenericAuthorityError {
+        error: "Synthetic stream error".to_string(),
+    };
+    // Every stream stalls between its items, and fails once they were all emitted.
+    let action_sequence = vec![
+        BatchAction::EmitUpdateItem(),
+        BatchAction::Delay {
+            before: Duration::from_secs(1),
+            then: Box::new(BatchAction::EmitUpdateItem()),
+        },
+        BatchAction::Delay {
+            before: Duration::from_secs(5),
+            then: Box::new(BatchAction::EmitErrorItem(stream_error)),
+        },
+    ];
+
+    let (net, states, digests) = init_configurable_authorities(action_sequence).await;
+
+    let _active_authorities = start_gossip_process(states.clone(), net.clone()).await;
+    tokio::time::sleep(Duration::from_millis(4 * MAX_RETRY_DELAY_MS)).await;
+
+    // The stream errors were seen by the gossip processes, which retried them.
+    let gossip_errors: u64 = states
+        .iter()
+        .map(|state| state.metrics.gossip_task_error_count.get())
+        .sum();
+    assert!(gossip_errors > 0);
+
+    for client in net.clone_inner_clients().values() {
+        for digest in &digests {
+            let result = client
+                .handle_transaction_info_request(TransactionInfoRequest {
+                    transaction_digest: digest.transaction,
+                })
+                .await
+                .unwrap();
+            assert!(result.certified_transaction.is_some());
+        }
+    }
+}
+
 #[tokio::test(flavor = "current_thread", start_paused = true)]
 pub async fn test_gossip_after_revert() {
     telemetry_subscribers::init_for_testing();
@@ -151,6 +233,94 @@ pub async fn test_gossip_after_revert() {
     assert_eq!(all_batch_item_counts.iter().filter(|c| *c == &2).count(), 1);
 }
 
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+pub async fn test_gossip_resumes_after_restart() {
+    let action_sequence = vec![BatchAction::EmitUpdateItem(), BatchAction::EmitUpdateItem()];
+    let (net, states, _digests) = init_configurable_authorities(action_sequence).await;
+
+    // Follow a peer that streams both transactions.
+    let state = states[0].clone();
+    let (peer, peer_client) = net
+        .clone_inner_clients()
+        .into_iter()
+        .find(|(name, client)| *name != state.name && !client.action_sequence_internal.is_empty())
+        .unwrap();
+    assert_eq!(state.database.gossip_synced_sequence(&peer).unwrap(), None);
+
+    let active =
+        ActiveAuthority::new_with_ephemeral_storage_for_test(state.clone(), net.clone()).unwrap();
+    let (_, result) = Follower::new(peer, &active)
+        .start(
+            Duration::from_secs(10),
+            GossipDigestHandler::new(active.gossip_metrics.clone()),
+        )
+        .await;
+    result.unwrap();
+    assert!(active.gossip_metrics.total_tx_received.get() > 0);
+    assert_eq!(
+        state.database.gossip_synced_sequence(&peer).unwrap(),
+        Some(1)
+    );
+
+    // Once restarted, the authority resumes following the peer after the synced transactions.
+    peer_client.take_batch_stream_requests();
+    let active =
+        ActiveAuthority::new_with_ephemeral_storage_for_test(state.clone(), net.clone()).unwrap();
+    let (_, result) = Follower::new(peer, &active)
+        .start(
+            Duration::from_secs(10),
+            GossipDigestHandler::new(active.gossip_metrics.clone()),
+        )
+        .await;
+    result.unwrap();
+
+    let requests = peer_client.take_batch_stream_requests();
+    assert!(!requests.is_empty());
+    assert!(requests.iter().all(|request| request.start == Some(2)));
+    assert_eq!(active.gossip_metrics.total_tx_received.get(), 0);
+}
+
+#[tokio::test]
+pub async fn test_gossip_peer_selection_by_stake() {
+    // One authority holds most of the stake.
+    let (net, states, _) =
+        init_configurable_authorities_with_stakes(vec![], vec![1, 1, 1, 7]).await;
+    let committee = states[0].committee.load().deref().clone();
+    let heavy = *committee
+        .names()
+        .max_by_key(|name| committee.weight(name))
+        .unwrap();
+    let state = states.iter().find(|state| state.name != heavy).unwrap();
+    let active = ActiveAuthority::new_with_ephemeral_storage_for_test(state.clone(), net).unwrap();
+
+    let mut selections = BTreeMap::new();
+    for _ in 0..1000 {
+        let name = select_gossip_peer(state.name, HashSet::new(), &active)
+            .await
+            .unwrap();
+        *selections.entry(name).or_insert(0) += 1;
+    }
+
+    // The authority never follows itself, and prefers the peer with the most stake, while still
+    // following the others from time to time.
+    assert!(!selections.contains_key(&state.name));
+    assert_eq!(selections.len(), 3);
+    for (name, count) in &selections {
+        if *name != heavy {
+            assert!(selections[&heavy] > 2 * count);
+        }
+    }
+
+    // Peers already followed are not selected again.
+    let followed = HashSet::from([heavy]);
+    for _ in 0..100 {
+        let name = select_gossip_peer(state.name, followed.clone(), &active)
+            .await
+            .unwrap();
+        assert!(name != heavy && name != state.name);
+    }
+}
+
 async fn start_gossip_process(
     states: Vec<Arc<AuthorityState>>,
     net: AuthorityAggregator<ConfigurableBatchActionClient>,
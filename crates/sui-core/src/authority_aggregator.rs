@@ -3,13 +3,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::authority_client::AuthorityAPI;
+use crate::equivocation::{EquivocationDetected, EquivocationDetector};
 use crate::safe_client::{SafeClient, SafeClientMetrics};
 use async_trait::async_trait;
 
 use futures::{future, future::BoxFuture, stream::FuturesUnordered, StreamExt};
+use move_core_types::language_storage::StructTag;
 use move_core_types::value::MoveStructLayout;
 use sui_types::crypto::AuthoritySignature;
-use sui_types::object::{Object, ObjectFormatOptions, ObjectRead};
+use sui_types::object::{Object, ObjectFormatOptions, ObjectRead, ObjectTombstone};
 use sui_types::{
     base_types::*,
     committee::Committee,
@@ -26,6 +28,7 @@ use prometheus::{
     register_histogram_with_registry, register_int_counter_with_registry, Histogram, IntCounter,
 };
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::future::Future;
 use std::string::ToString;
 use std::sync::Arc;
 use std::time::Duration;
@@ -34,6 +37,7 @@ use tokio::sync::mpsc::Receiver;
 use tokio::time::{sleep, timeout};
 
 use crate::epoch::epoch_store::EpochStore;
+use sui_config::gateway::SubmitRetryConfig;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use tap::TapFallible;
 
@@ -89,6 +93,7 @@ pub struct AuthAggMetrics {
     pub num_good_stake: Histogram,
     pub num_bad_stake: Histogram,
     pub total_quorum_once_timeout: IntCounter,
+    pub total_equivocations_detected: IntCounter,
 }
 
 // Override default Prom buckets for positive numbers in 0-50k range
@@ -134,6 +139,12 @@ impl AuthAggMetrics {
                 registry,
             )
             .unwrap(),
+            total_equivocations_detected: register_int_counter_with_registry!(
+                "total_equivocations_detected",
+                "Total number of times an authority was seen signing conflicting transactions",
+                registry,
+            )
+            .unwrap(),
         }
     }
 
@@ -152,8 +163,13 @@ pub struct AuthorityAggregator<A> {
     // Metrics
     pub metrics: AuthAggMetrics,
     pub timeouts: TimeoutConfig,
+    /// Retries of transactions and certificates submitted to an authority on network errors.
+    /// Disabled by default.
+    pub submit_retry: SubmitRetryConfig,
     // Store here for clone during re-config
     pub safe_client_metrics: SafeClientMetrics,
+    /// Shared by clones, so that conflicting locks are detected across requests.
+    equivocation_detector: Arc<EquivocationDetector>,
 }
 
 impl<A> AuthorityAggregator<A> {
@@ -200,10 +216,89 @@ impl<A> AuthorityAggregator<A> {
                 .collect(),
             metrics,
             timeouts,
+            submit_retry: SubmitRetryConfig::disabled(),
             safe_client_metrics,
+            equivocation_detector: Arc::new(EquivocationDetector::default()),
+        }
+    }
+
+    /// Return the proofs of the most recent authorities seen signing conflicting transactions
+    /// for the same owned object version in the same epoch.
+    pub fn equivocations(&self) -> Vec<EquivocationDetected> {
+        self.equivocation_detector.equivocations()
+    }
+
+    /// Record that `authority` signed `signed_transaction` locking the owned object
+    /// `object_ref`, to detect the authority signing another transaction for the same object
+    /// version in the same epoch.
+    fn observe_lock(
+        &self,
+        authority: AuthorityName,
+        object_ref: ObjectRef,
+        signed_transaction: &SignedTransaction,
+    ) {
+        if let Some(equivocation) =
+            self.equivocation_detector
+                .observe_lock(authority, object_ref, signed_transaction)
+        {
+            self.record_equivocation(equivocation);
+        }
+    }
+
+    /// Record that `authority` signed `signed_transaction`, locking each owned object it takes
+    /// as input. Immutable objects take no lock, so a conflict over an object that is neither
+    /// paid as gas nor transferred is only recorded once the object is shown to be owned.
+    async fn observe_signed_transaction(
+        &self,
+        authority: AuthorityName,
+        signed_transaction: &SignedTransaction,
+    ) {
+        let inputs = match signed_transaction.signed_data.data.input_objects() {
+            Ok(inputs) => inputs,
+            Err(_) => return,
+        };
+        for input in inputs {
+            let object_ref = match input {
+                InputObjectKind::ImmOrOwnedMoveObject(object_ref) => object_ref,
+                _ => continue,
+            };
+            let equivocation = match self.equivocation_detector.observe_lock(
+                authority,
+                object_ref,
+                signed_transaction,
+            ) {
+                Some(equivocation) => equivocation,
+                None => continue,
+            };
+            if equivocation.is_known_owned() || self.is_owned_object(authority, object_ref).await {
+                self.record_equivocation(equivocation);
+            }
         }
     }
 
+    /// Whether `authority` holds `object_ref` as an owned object. The object digest commits to
+    /// the owner, so the authority cannot misreport it.
+    async fn is_owned_object(&self, authority: AuthorityName, object_ref: ObjectRef) -> bool {
+        let request = ObjectInfoRequest {
+            object_id: object_ref.0,
+            request_kind: ObjectInfoRequestKind::PastObjectInfoDebug(object_ref.1, None),
+        };
+        match self.authority_clients[&authority]
+            .handle_object_info_request(request)
+            .await
+        {
+            Ok(response) => response.object().map_or(false, |object| {
+                object.compute_object_reference() == object_ref && !object.is_immutable()
+            }),
+            Err(_) => false,
+        }
+    }
+
+    fn record_equivocation(&self, equivocation: EquivocationDetected) {
+        self.equivocation_detector.record(equivocation);
+        self.metrics.total_equivocations_detected.inc();
+    }
+
     pub fn clone_client(&self, name: &AuthorityName) -> SafeClient<A>
     where
         A: Clone,
@@ -976,6 +1071,10 @@ where
                         (None, None, None)
                     };
 
+                if let Some(lock) = &signed_transaction_option {
+                    self.observe_lock(name, object_ref, lock);
+                }
+
                 // Update the map with the information from this authority
                 let entry = object_map
                     .entry((object_ref, transaction_digest))
@@ -1219,6 +1318,151 @@ where
             .await
     }
 
+    /// Ask authorities for the ids of the objects owned by `address`, without syncing them. As
+    /// with `sync_all_owned_objects`, the ids are only as trustworthy as the authorities that
+    /// reported them, use `sync_all_given_objects` to get the objects themselves.
+    pub async fn get_owned_object_ids(
+        &self,
+        address: SuiAddress,
+        timeout_after_quorum: Duration,
+    ) -> Result<BTreeSet<ObjectID>, SuiError> {
+        let (object_map, _authority_list) = self
+            .get_all_owned_objects(address, timeout_after_quorum)
+            .await?;
+        Ok(object_map.keys().map(|object_ref| object_ref.0).collect())
+    }
+
+    /// Ask authorities for a page of up to `limit` ids of the objects owned by `address` after
+    /// `cursor`, in object id order, only of the type matching `type_filter` if given, along with
+    /// the cursor of the next page. As with `get_owned_object_ids`, the ids are only as
+    /// trustworthy as the authorities that reported them.
+    pub async fn get_owned_object_ids_page(
+        &self,
+        address: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: u64,
+        type_filter: Option<StructTag>,
+        timeout_after_quorum: Duration,
+    ) -> Result<(Vec<ObjectID>, Option<ObjectID>), SuiError> {
+        #[derive(Default)]
+        struct OwnedObjectPageState {
+            good_weight: StakeUnit,
+            bad_weight: StakeUnit,
+            object_ids: BTreeSet<ObjectID>,
+            // The smallest next cursor returned: past it, some authorities have not been read.
+            covered_until: Option<ObjectID>,
+            errors: Vec<(AuthorityName, SuiError)>,
+        }
+        let request = AccountInfoPageRequest {
+            account: address,
+            cursor,
+            limit,
+            type_filter,
+        };
+        let threshold = self.committee.quorum_threshold();
+        let validity = self.committee.validity_threshold();
+        let final_state = self
+            .quorum_map_then_reduce_with_timeout(
+                OwnedObjectPageState::default(),
+                |_name, client| {
+                    let request = request.clone();
+                    Box::pin(async move { client.handle_account_info_page_request(request).await })
+                },
+                |mut state, name, weight, result| {
+                    Box::pin(async move {
+                        state.good_weight += weight;
+                        match result {
+                            Ok(AccountInfoPageResponse {
+                                object_ids,
+                                next_cursor,
+                                ..
+                            }) => {
+                                state
+                                    .object_ids
+                                    .extend(object_ids.into_iter().map(|object_ref| object_ref.0));
+                                if let Some(next_cursor) = next_cursor {
+                                    state.covered_until = Some(match state.covered_until {
+                                        Some(covered_until) => covered_until.min(next_cursor),
+                                        None => next_cursor,
+                                    });
+                                }
+                            }
+                            Err(err) => {
+                                state.errors.push((name, err));
+                                state.bad_weight += weight;
+                                if state.bad_weight > validity {
+                                    return Err(SuiError::TooManyIncorrectAuthorities {
+                                        errors: state.errors,
+                                        action: "get_owned_object_ids_page",
+                                    });
+                                }
+                            }
+                        };
+
+                        if state.good_weight < threshold {
+                            Ok(ReduceOutput::Continue(state))
+                        } else {
+                            Ok(ReduceOutput::ContinueWithTimeout(
+                                state,
+                                timeout_after_quorum,
+                            ))
+                        }
+                    })
+                },
+                self.timeouts.pre_quorum_timeout,
+            )
+            .await?;
+
+        // Only the objects up to the smallest next cursor were read from every authority which
+        // responded, the next page starts after them.
+        let covered_until = final_state.covered_until;
+        let mut object_ids: Vec<_> = final_state
+            .object_ids
+            .into_iter()
+            .filter(|object_id| covered_until.map_or(true, |until| *object_id <= until))
+            .collect();
+        let next_cursor = if object_ids.len() > limit as usize {
+            object_ids.truncate(limit as usize);
+            object_ids.last().copied()
+        } else {
+            covered_until
+        };
+        Ok((object_ids, next_cursor))
+    }
+
+    /// Submit a transaction or a certificate to an authority, retrying with an exponential
+    /// backoff as long as the submission fails with a network error, up to the configured number
+    /// of retries. Any other error, or an answer from the authority, is returned at once.
+    async fn submit_with_retries<F, Fut>(
+        &self,
+        name: AuthorityName,
+        submit: F,
+    ) -> Result<TransactionInfoResponse, SuiError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<TransactionInfoResponse, SuiError>>,
+    {
+        let mut delay = self.submit_retry.base_delay;
+        let mut retries = 0;
+        loop {
+            match submit().await {
+                Err(err) if err.is_transport_error() && retries < self.submit_retry.max_retries => {
+                    retries += 1;
+                    debug!(
+                        authority =? name,
+                        retries,
+                        ?delay,
+                        error =? err,
+                        "Retrying submission after a network error"
+                    );
+                    sleep(delay).await;
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+
     /// Submits the transaction to a quorum of validators to make a certificate.
     pub async fn process_transaction(
         &self,
@@ -1260,10 +1504,13 @@ where
         let state = self
             .quorum_map_then_reduce_with_timeout(
                 state,
-                |_name, client| {
-                    Box::pin(
-                        async move { client.handle_transaction(transaction_ref.clone()).await },
-                    )
+                |name, client| {
+                    Box::pin(async move {
+                        self.submit_with_retries(name, || {
+                            client.handle_transaction(transaction_ref.clone())
+                        })
+                        .await
+                    })
                 },
                 |mut state, name, weight, result| {
                     Box::pin(async move {
@@ -1289,6 +1536,8 @@ where
                             }) => {
                                 let tx_digest = inner_signed_transaction.digest();
                                 debug!(tx_digest = ?tx_digest, ?name, weight, "Received signed transaction from validator handle_transaction");
+                                self.observe_signed_transaction(name, &inner_signed_transaction)
+                                    .await;
                                 state.signatures.push((
                                     name,
                                     inner_signed_transaction.auth_sign_info.signature,
@@ -1446,10 +1695,10 @@ where
                         // - we try to update the authority with the cert, and on error return Err.
                         // - we try to re-process the certificate and return the result.
 
-                        let res =
-                            client.handle_certificate(cert_ref.clone())
-                                .instrument(tracing::trace_span!("handle_certificate", authority =? name))
-                                .await;
+                        let res = self
+                            .submit_with_retries(name, || client.handle_certificate(cert_ref.clone()))
+                            .instrument(tracing::trace_span!("handle_certificate", authority =? name))
+                            .await;
 
                         if res.is_ok() {
                             debug!(
@@ -1608,6 +1857,25 @@ where
         Ok((new_certificate, response))
     }
 
+    /// Debug only: send the transaction to a single authority and return its response as is.
+    /// This intentionally bypasses quorum aggregation and the response checks of `SafeClient`,
+    /// so it must not be used to make any decision about the state of the network.
+    pub async fn handle_transaction_with_authority(
+        &self,
+        authority: &AuthorityName,
+        transaction: Transaction,
+    ) -> SuiResult<TransactionInfoResponse> {
+        let client = self.authority_clients.get(authority).ok_or_else(|| {
+            SuiError::GenericAuthorityError {
+                error: format!("Authority {authority} is not in the committee"),
+            }
+        })?;
+        client
+            .authority_client()
+            .handle_transaction(transaction)
+            .await
+    }
+
     pub async fn get_object_info_execute(&self, object_id: ObjectID) -> SuiResult<ObjectRead> {
         let (object_map, cert_map) = self.get_object_by_id(object_id).await?;
         let mut object_ref_stack: Vec<_> = object_map.into_iter().collect();
@@ -1648,7 +1916,10 @@ where
                     }
                     None => {
                         // TODO: Figure out how to find out object being wrapped instead of deleted.
-                        return Ok(ObjectRead::Deleted(obj_ref));
+                        return Ok(ObjectRead::Deleted(ObjectTombstone {
+                            object_ref: obj_ref,
+                            deleted_by: tx_digest,
+                        }));
                     }
                 };
             }
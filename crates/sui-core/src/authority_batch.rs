@@ -56,6 +56,10 @@ pub type BroadcastReceiver = tokio::sync::broadcast::Receiver<UpdateItem>;
 
 pub type BroadcastPair = (BroadcastSender, BroadcastReceiver);
 
+/// Default upper bound on the serialized size of the transactions in a single batch. A batch is
+/// closed once it reaches this size, even if `min_batch_size` items are not yet accumulated.
+pub const DEFAULT_MAX_BATCH_SIZE_BYTES: u64 = 16 * 1024 * 1024;
+
 impl crate::authority::AuthorityState {
     pub fn last_batch(&self) -> Result<Option<SignedBatch>, SuiError> {
         let last_batch = self
@@ -131,6 +135,23 @@ impl crate::authority::AuthorityState {
         &self,
         min_batch_size: u64,
         max_delay: Duration,
+    ) -> SuiResult<()> {
+        self.run_batch_service_with_size_limit(
+            min_batch_size,
+            DEFAULT_MAX_BATCH_SIZE_BYTES,
+            max_delay,
+        )
+        .await
+    }
+
+    /// Run the batch service, closing a batch when it holds `min_batch_size` items, when the
+    /// serialized size of the certificates and effects of its transactions reaches
+    /// `max_batch_size_bytes`, or after `max_delay`, whichever comes first.
+    pub async fn run_batch_service_with_size_limit(
+        &self,
+        min_batch_size: u64,
+        max_batch_size_bytes: u64,
+        max_delay: Duration,
     ) -> SuiResult<()> {
         debug!("Batch service started");
         // This assumes we have initialized the database with a batch.
@@ -160,6 +181,8 @@ impl crate::authority::AuthorityState {
         // of transactions in order, following the last batch. The loose transactions holds
         // transactions we may have received out of order.
         let mut current_batch: Vec<(TxSequenceNumber, ExecutionDigests)> = Vec::new();
+        // The serialized size of the transactions in the current_batch.
+        let mut current_batch_bytes: u64 = 0;
 
         while !exit {
             // Reset the flags.
@@ -180,10 +203,21 @@ impl crate::authority::AuthorityState {
                         },
                         Some((seq, tx_digest)) => {
                             // Add to batch and broadcast
+                            // A transaction whose size is unknown does not count towards the
+                            // size limit, rather than stopping the service.
+                            current_batch_bytes += match self.db().get_executed_transaction_size(seq) {
+                                Ok(size) => size.unwrap_or(0),
+                                Err(err) => {
+                                    error!(?seq, "Could not read the size of an executed transaction: {}", err);
+                                    0
+                                }
+                            };
                             current_batch.push((seq, tx_digest));
                             let _ = self.batch_channels.send(UpdateItem::Transaction((seq, tx_digest)));
 
-                            if current_batch.len() as TxSequenceNumber >= min_batch_size {
+                            if current_batch.len() as TxSequenceNumber >= min_batch_size
+                                || current_batch_bytes >= max_batch_size_bytes
+                            {
                                 make_batch = true;
                             }
                         }
@@ -231,6 +265,7 @@ impl crate::authority::AuthorityState {
                 // A new batch is actually made, so we reset the conditions.
                 prev_batch = new_batch.into_data();
                 current_batch.clear();
+                current_batch_bytes = 0;
 
                 // We rest the interval here to ensure that blocks
                 // are made either when they are full or old enough.
@@ -45,6 +45,12 @@ pub trait AuthorityAPI {
         request: AccountInfoRequest,
     ) -> Result<AccountInfoResponse, SuiError>;
 
+    /// Handle requests for a page of the objects owned by an account.
+    async fn handle_account_info_page_request(
+        &self,
+        request: AccountInfoPageRequest,
+    ) -> Result<AccountInfoPageResponse, SuiError>;
+
     /// Handle Object information requests for this account.
     async fn handle_object_info_request(
         &self,
@@ -75,6 +81,8 @@ pub type BatchInfoResponseItemStream = BoxStream<'static, Result<BatchInfoRespon
 #[derive(Clone)]
 pub struct NetworkAuthorityClient {
     client: ValidatorClient<tonic::transport::Channel>,
+    /// Used for bulk methods, whose responses may be compressed by the server.
+    bulk_client: ValidatorClient<tonic::transport::Channel>,
     metrics: Arc<NetworkAuthorityClientMetrics>,
 }
 
@@ -103,14 +111,54 @@ impl NetworkAuthorityClient {
         metrics: Arc<NetworkAuthorityClientMetrics>,
     ) -> Self {
         Self {
-            client: ValidatorClient::new(channel),
+            client: ValidatorClient::new(channel.clone()),
+            bulk_client: ValidatorClient::new(channel).accept_gzip(),
             metrics,
         }
     }
 
+    /// Whether to accept compressed responses from bulk methods, i.e. account info, batch
+    /// streams and checkpoints. Enabled by default, other methods are never compressed.
+    pub fn with_bulk_compression(mut self, enabled: bool) -> Self {
+        self.bulk_client = if enabled {
+            self.client.clone().accept_gzip()
+        } else {
+            self.client.clone()
+        };
+        self
+    }
+
+    /// The recent activity of the authority, reported to operators.
+    pub async fn handle_node_stats(
+        &self,
+        request: NodeStatsRequest,
+    ) -> Result<NodeStatsResponse, SuiError> {
+        self.client()
+            .node_stats(request)
+            .await
+            .map(tonic::Response::into_inner)
+            .map_err(Into::into)
+    }
+
+    /// The total balance of the coins of a currency, if the authority serves it.
+    pub async fn handle_coin_supply(
+        &self,
+        request: CoinSupplyRequest,
+    ) -> Result<CoinSupplyResponse, SuiError> {
+        self.client()
+            .coin_supply(request)
+            .await
+            .map(tonic::Response::into_inner)
+            .map_err(Into::into)
+    }
+
     fn client(&self) -> ValidatorClient<tonic::transport::Channel> {
         self.client.clone()
     }
+
+    fn bulk_client(&self) -> ValidatorClient<tonic::transport::Channel> {
+        self.bulk_client.clone()
+    }
 }
 
 #[async_trait]
@@ -183,7 +231,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .start_timer();
 
         let response = self
-            .client()
+            .bulk_client()
             .account_info(request)
             .await
             .map(tonic::Response::into_inner)
@@ -194,6 +242,17 @@ impl AuthorityAPI for NetworkAuthorityClient {
         response
     }
 
+    async fn handle_account_info_page_request(
+        &self,
+        request: AccountInfoPageRequest,
+    ) -> Result<AccountInfoPageResponse, SuiError> {
+        self.bulk_client()
+            .account_info_page(request)
+            .await
+            .map(tonic::Response::into_inner)
+            .map_err(Into::into)
+    }
+
     async fn handle_object_info_request(
         &self,
         request: ObjectInfoRequest,
@@ -247,7 +306,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .observe(request.start.unwrap_or(0) as f64);
 
         let stream = self
-            .client()
+            .bulk_client()
             .batch_info(request)
             .await
             .map(tonic::Response::into_inner)?
@@ -264,7 +323,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
         let timer = self.metrics.handle_checkpoint_request_latency.start_timer();
 
         let response = self
-            .client()
+            .bulk_client()
             .checkpoint(request)
             .await
             .map(tonic::Response::into_inner)
@@ -284,9 +343,12 @@ impl AuthorityAPI for NetworkAuthorityClient {
     }
 }
 
+/// Clients of the active validators of `sui_system_state`, accepting compressed responses from
+/// bulk methods if `bulk_compression` is set.
 pub fn make_network_authority_client_sets_from_system_state(
     sui_system_state: &SuiSystemState,
     network_config: &Config,
+    bulk_compression: bool,
     network_metrics: Arc<NetworkAuthorityClientMetrics>,
 ) -> anyhow::Result<BTreeMap<AuthorityPublicKeyBytes, NetworkAuthorityClient>> {
     let mut authority_clients = BTreeMap::new();
@@ -295,7 +357,8 @@ pub fn make_network_authority_client_sets_from_system_state(
         let channel = network_config
             .connect_lazy(&address)
             .map_err(|err| anyhow!(err.to_string()))?;
-        let client = NetworkAuthorityClient::new(channel, network_metrics.clone());
+        let client = NetworkAuthorityClient::new(channel, network_metrics.clone())
+            .with_bulk_compression(bulk_compression);
         let name: &[u8] = &validator.metadata.name;
         let public_key_bytes = AuthorityPublicKeyBytes::from_bytes(name)?;
         authority_clients.insert(public_key_bytes, client);
@@ -303,9 +366,12 @@ pub fn make_network_authority_client_sets_from_system_state(
     Ok(authority_clients)
 }
 
+/// Clients of the genesis validators, accepting compressed responses from bulk methods if
+/// `bulk_compression` is set.
 pub fn make_network_authority_client_sets_from_genesis(
     genesis: &Genesis,
     network_config: &Config,
+    bulk_compression: bool,
     network_metrics: Arc<NetworkAuthorityClientMetrics>,
 ) -> anyhow::Result<BTreeMap<AuthorityPublicKeyBytes, NetworkAuthorityClient>> {
     let mut authority_clients = BTreeMap::new();
@@ -313,7 +379,8 @@ pub fn make_network_authority_client_sets_from_genesis(
         let channel = network_config
             .connect_lazy(validator.network_address())
             .map_err(|err| anyhow!(err.to_string()))?;
-        let client = NetworkAuthorityClient::new(channel, network_metrics.clone());
+        let client = NetworkAuthorityClient::new(channel, network_metrics.clone())
+            .with_bulk_compression(bulk_compression);
         authority_clients.insert(validator.protocol_key(), client);
     }
     Ok(authority_clients)
@@ -395,6 +462,14 @@ impl AuthorityAPI for LocalAuthorityClient {
         state.handle_account_info_request(request).await
     }
 
+    async fn handle_account_info_page_request(
+        &self,
+        request: AccountInfoPageRequest,
+    ) -> Result<AccountInfoPageResponse, SuiError> {
+        let state = self.state.clone();
+        state.handle_account_info_page_request(request).await
+    }
+
     async fn handle_object_info_request(
         &self,
         request: ObjectInfoRequest,
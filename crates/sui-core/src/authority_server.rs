@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    authority::AuthorityState,
+    authority::{AuthorityState, CertificateExecutionError},
     consensus_adapter::{
         CheckpointConsensusAdapter, CheckpointSender, ConsensusAdapter, ConsensusAdapterMetrics,
         ConsensusListener, ConsensusListenerMessage,
@@ -15,7 +15,7 @@ use async_trait::async_trait;
 use fastcrypto::ed25519::Ed25519KeyPair as ConsensusKeyPair;
 use fastcrypto::traits::KeyPair;
 use futures::{stream::BoxStream, TryStreamExt};
-use multiaddr::Multiaddr;
+use multiaddr::{Multiaddr, Protocol};
 use narwhal_config::Committee as ConsensusCommittee;
 use prometheus::Registry;
 use std::{io, sync::Arc, time::Duration};
@@ -25,7 +25,7 @@ use sui_network::{
     tonic,
 };
 
-use sui_types::{error::*, messages::*};
+use sui_types::{error::*, event::Event, messages::*};
 use tokio::{
     sync::mpsc::{channel, Receiver, Sender},
     task::JoinHandle,
@@ -79,6 +79,7 @@ pub struct AuthorityServer {
     consensus_adapter: ConsensusAdapter,
     min_batch_size: u64,
     max_delay: Duration,
+    grpc_compression: bool,
 }
 
 impl AuthorityServer {
@@ -103,9 +104,17 @@ impl AuthorityServer {
             consensus_adapter,
             min_batch_size: MIN_BATCH_SIZE,
             max_delay: Duration::from_millis(MAX_DELAY_MILLIS),
+            grpc_compression: true,
         }
     }
 
+    /// Whether to compress responses with gzip for clients that accept it, as configured by
+    /// `NodeConfig::grpc_compression`. Enabled by default.
+    pub fn with_grpc_compression(mut self, enabled: bool) -> Self {
+        self.grpc_compression = enabled;
+        self
+    }
+
     /// Create a batch subsystem, register it with the authority state, and
     /// launch a task that manages it. Return the join handle of this task.
     pub async fn spawn_batch_subsystem(
@@ -128,25 +137,35 @@ impl AuthorityServer {
         self.spawn_with_bind_address(address).await
     }
 
+    /// Serve on `address`, which is either a TCP address, e.g. `/ip4/127.0.0.1/tcp/0/http`, or
+    /// a Unix domain socket, e.g. `/unix/<path>/http`, for clients running on the same host.
     pub async fn spawn_with_bind_address(
         self,
         address: Multiaddr,
     ) -> Result<AuthorityServerHandle, io::Error> {
+        #[cfg(unix)]
+        remove_stale_unix_socket(&address)?;
+
         // Start the batching subsystem
         let _join_handle = self
             .spawn_batch_subsystem(self.min_batch_size, self.max_delay)
             .await;
 
+        let mut validator_server = ValidatorServer::new(ValidatorService {
+            state: self.state,
+            consensus_adapter: Arc::new(self.consensus_adapter),
+            _checkpoint_consensus_handle: None,
+        });
+        if self.grpc_compression {
+            // Only responses to clients accepting gzip are compressed.
+            validator_server = validator_server.send_gzip().accept_gzip();
+        }
         let mut server = mysten_network::config::Config::new()
             .server_builder()
-            .add_service(ValidatorServer::new(ValidatorService {
-                state: self.state,
-                consensus_adapter: Arc::new(self.consensus_adapter),
-                _checkpoint_consensus_handle: None,
-            }))
+            .add_service(validator_server)
             .bind(&address)
             .await
-            .unwrap();
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
         let local_addr = server.local_addr().to_owned();
         info!("Listening to traffic on {local_addr}");
         let handle = AuthorityServerHandle {
@@ -158,6 +177,23 @@ impl AuthorityServer {
     }
 }
 
+/// Remove the socket file a previous server left behind at a Unix domain socket address, as
+/// binding to an existing file fails. Files which are not sockets are left alone.
+#[cfg(unix)]
+fn remove_stale_unix_socket(address: &Multiaddr) -> Result<(), io::Error> {
+    use std::os::unix::fs::FileTypeExt;
+
+    if let Some(Protocol::Unix(path)) = address.iter().next() {
+        let path = std::path::Path::new(path.as_ref());
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if metadata.file_type().is_socket() {
+                std::fs::remove_file(path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 pub struct ValidatorService {
     state: Arc<AuthorityState>,
     consensus_adapter: Arc<ConsensusAdapter>,
@@ -260,7 +296,7 @@ impl ValidatorService {
 
         transaction
             .verify()
-            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+            .map_err(|e| e.to_status(tonic::Code::InvalidArgument))?;
         //TODO This is really really bad, we should have different types for signature-verified transactions
         transaction.is_verified = true;
 
@@ -277,7 +313,7 @@ impl ValidatorService {
             .handle_transaction(transaction)
             .instrument(span)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(tonic::Status::from)?;
 
         Ok(tonic::Response::new(info))
     }
@@ -291,7 +327,7 @@ impl ValidatorService {
         // 1) Verify certificate
         certificate
             .verify(&state.committee.load())
-            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+            .map_err(|e| e.to_status(tonic::Code::InvalidArgument))?;
         //TODO This is really really bad, we should have different types for signature verified transactions
         certificate.is_verified = true;
 
@@ -300,7 +336,7 @@ impl ValidatorService {
         if let Some(response) = state
             .check_tx_already_executed(digest)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?
+            .map_err(tonic::Status::from)?
         {
             return Ok(tonic::Response::new(response));
         }
@@ -311,12 +347,12 @@ impl ValidatorService {
             && !state
                 .transaction_shared_locks_exist(&certificate)
                 .await
-                .map_err(|e| tonic::Status::internal(e.to_string()))?
+                .map_err(tonic::Status::from)?
         {
             consensus_adapter
                 .submit(&state.name, &certificate)
                 .await
-                .map_err(|e| tonic::Status::internal(e.to_string()))?;
+                .map_err(tonic::Status::from)?;
         }
 
         // 4) Execute the certificate.
@@ -327,11 +363,15 @@ impl ValidatorService {
             tx_kind = certificate.signed_data.data.kind_as_str()
         );
 
+        // The certificate was verified in 1), so only execution can fail here.
         let response = state
-            .handle_certificate(certificate)
+            .verify_and_execute_certificate(certificate)
             .instrument(span)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(|e| match e {
+                CertificateExecutionError::Verify(e) => e.to_status(tonic::Code::InvalidArgument),
+                CertificateExecutionError::Execute(e) => tonic::Status::from(e),
+            })?;
 
         Ok(tonic::Response::new(response))
     }
@@ -378,7 +418,22 @@ impl Validator for ValidatorService {
             .state
             .handle_account_info_request(request)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(tonic::Status::from)?;
+
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn account_info_page(
+        &self,
+        request: tonic::Request<AccountInfoPageRequest>,
+    ) -> Result<tonic::Response<AccountInfoPageResponse>, tonic::Status> {
+        let request = request.into_inner();
+
+        let response = self
+            .state
+            .handle_account_info_page_request(request)
+            .await
+            .map_err(tonic::Status::from)?;
 
         Ok(tonic::Response::new(response))
     }
@@ -393,7 +448,22 @@ impl Validator for ValidatorService {
             .state
             .handle_object_info_request(request)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(tonic::Status::from)?;
+
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn multi_object_info(
+        &self,
+        request: tonic::Request<MultiObjectInfoRequest>,
+    ) -> Result<tonic::Response<Vec<SuiResult<ObjectInfoResponse>>>, tonic::Status> {
+        let request = request.into_inner();
+
+        let response = self
+            .state
+            .handle_multi_object_info_request(request)
+            .await
+            .map_err(tonic::Status::from)?;
 
         Ok(tonic::Response::new(response))
     }
@@ -408,11 +478,60 @@ impl Validator for ValidatorService {
             .state
             .handle_transaction_info_request(request)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(tonic::Status::from)?;
+
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn transaction_info_batch(
+        &self,
+        request: tonic::Request<TransactionInfoBatchRequest>,
+    ) -> Result<tonic::Response<Vec<TransactionInfoResponse>>, tonic::Status> {
+        let request = request.into_inner();
+
+        let response = self
+            .state
+            .handle_transaction_info_batch_request(request)
+            .await
+            .map_err(tonic::Status::from)?;
+
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn events_by_transaction(
+        &self,
+        request: tonic::Request<EventsByTransactionRequest>,
+    ) -> Result<tonic::Response<Vec<Event>>, tonic::Status> {
+        let request = request.into_inner();
+
+        let response = self
+            .state
+            .handle_events_by_transaction_request(request)
+            .await
+            .map_err(tonic::Status::from)?;
 
         Ok(tonic::Response::new(response))
     }
 
+    type SubscribeEventsStream = BoxStream<'static, Result<EventResponseItem, tonic::Status>>;
+
+    async fn subscribe_events(
+        &self,
+        request: tonic::Request<EventSubscriptionRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeEventsStream>, tonic::Status> {
+        let request = request.into_inner();
+
+        let xstream = self
+            .state
+            .handle_event_streaming(request)
+            .await
+            .map_err(tonic::Status::from)?;
+
+        let response = xstream.map_err(tonic::Status::from);
+
+        Ok(tonic::Response::new(Box::pin(response)))
+    }
+
     type FollowTxStreamStream = BoxStream<'static, Result<BatchInfoResponseItem, tonic::Status>>;
 
     async fn batch_info(
@@ -425,13 +544,28 @@ impl Validator for ValidatorService {
             .state
             .handle_batch_streaming(request)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(tonic::Status::from)?;
 
-        let response = xstream.map_err(|e| tonic::Status::internal(e.to_string()));
+        let response = xstream.map_err(tonic::Status::from);
 
         Ok(tonic::Response::new(Box::pin(response)))
     }
 
+    async fn reverse_batch_info(
+        &self,
+        request: tonic::Request<ReverseBatchInfoRequest>,
+    ) -> Result<tonic::Response<ReverseBatchInfoResponse>, tonic::Status> {
+        let request = request.into_inner();
+
+        let response = self
+            .state
+            .handle_reverse_batch_info_request(request)
+            .await
+            .map_err(tonic::Status::from)?;
+
+        Ok(tonic::Response::new(response))
+    }
+
     async fn checkpoint(
         &self,
         request: tonic::Request<CheckpointRequest>,
@@ -441,7 +575,7 @@ impl Validator for ValidatorService {
         let response = self
             .state
             .handle_checkpoint_request(&request)
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(tonic::Status::from)?;
 
         return Ok(tonic::Response::new(response));
     }
@@ -455,8 +589,37 @@ impl Validator for ValidatorService {
         let response = self
             .state
             .handle_epoch_request(&request)
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(tonic::Status::from)?;
 
         return Ok(tonic::Response::new(response));
     }
+
+    async fn node_stats(
+        &self,
+        request: tonic::Request<NodeStatsRequest>,
+    ) -> Result<tonic::Response<NodeStatsResponse>, tonic::Status> {
+        let request = request.into_inner();
+
+        let response = self
+            .state
+            .handle_node_stats_request(&request)
+            .map_err(tonic::Status::from)?;
+
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn coin_supply(
+        &self,
+        request: tonic::Request<CoinSupplyRequest>,
+    ) -> Result<tonic::Response<CoinSupplyResponse>, tonic::Status> {
+        let request = request.into_inner();
+
+        let response = self
+            .state
+            .handle_coin_supply_request(&request)
+            .await
+            .map_err(tonic::Status::from)?;
+
+        Ok(tonic::Response::new(response))
+    }
 }
@@ -757,6 +757,15 @@ impl CheckpointStore {
 
     // Helper read functions
 
+    /// Returns the sequence number of the checkpoint that includes the given transaction, if it
+    /// has been checkpointed.
+    pub fn get_transaction_checkpoint(
+        &self,
+        digest: &ExecutionDigests,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(self.tables.transactions_to_checkpoint.get(digest)?)
+    }
+
     /// Return the seq number of the next checkpoint.
     pub fn next_checkpoint(&mut self) -> CheckpointSequenceNumber {
         self.get_locals().next_checkpoint
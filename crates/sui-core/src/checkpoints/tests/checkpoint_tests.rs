@@ -3,13 +3,14 @@
 
 use super::*;
 use crate::{
-    authority::{AuthorityState, AuthorityStore},
+    authority::{AuthorityState, AuthorityStore, GatewayStore},
     authority_active::execution_driver::PendCertificateForExecutionNoop,
     authority_aggregator::{
         authority_aggregator_tests::transfer_coin_transaction, AuthorityAggregator,
     },
     authority_batch::batch_tests::init_state_parameters_from_rng,
     authority_client::LocalAuthorityClient,
+    gateway_state::{GatewayAPI, GatewayMetrics, GatewayState},
     safe_client::SafeClientMetrics,
 };
 use rand::prelude::StdRng;
@@ -23,7 +24,7 @@ use sui_types::{
         SuiAuthoritySignature,
     },
     messages::{CertifiedTransaction, ExecutionStatus},
-    messages_checkpoint::CheckpointRequest,
+    messages_checkpoint::{verify_certified_checkpoint, CheckpointRequest},
     object::Object,
     utils::{make_committee_key, make_committee_key_num},
     waypoint::GlobalCheckpoint,
@@ -1950,3 +1951,91 @@ async fn test_no_more_fragments() {
         .attempt_to_construct_checkpoint(&setup.committee)
         .is_ok());
 }
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn test_get_and_verify_certified_checkpoint() {
+    let mut setup = checkpoint_tests_setup(5, Duration::from_millis(500), true).await;
+
+    let t = setup.transactions.pop().unwrap();
+    let (_cert, effects) = setup
+        .aggregator
+        .execute_transaction(&t)
+        .await
+        .expect("All ok.");
+    let transactions = [ExecutionDigests::new(*t.digest(), effects.effects.digest())];
+
+    // Wait for a batch to go through, so that the transaction can be checkpointed.
+    tokio::time::sleep(Duration::from_secs(5)).await;
+
+    // Certify checkpoint 0 with the signatures of the authorities that executed the transaction.
+    let mut signed_checkpoints = Vec::new();
+    let mut signers = Vec::new();
+    for auth in &setup.authorities {
+        if auth
+            .checkpoint
+            .lock()
+            .sign_new_checkpoint(0, 0, transactions.iter(), TestCausalOrderPendCertNoop)
+            .is_err()
+        {
+            continue;
+        }
+        if let Some(AuthenticatedCheckpoint::Signed(signed)) =
+            auth.checkpoint.lock().get_checkpoint(0).unwrap()
+        {
+            signed_checkpoints.push(signed);
+            signers.push(auth);
+        }
+    }
+    let checkpoint_cert =
+        CertifiedCheckpointSummary::aggregate(signed_checkpoints, &setup.committee).unwrap();
+    for auth in &signers {
+        auth.checkpoint
+            .lock()
+            .promote_signed_checkpoint_to_cert(&checkpoint_cert, &setup.committee)
+            .unwrap();
+    }
+
+    // The authority serves the certified checkpoint, which verifies against the committee.
+    let (checkpoint, contents) = signers[0].authority.get_certified_checkpoint(0).unwrap();
+    assert_eq!(checkpoint.summary, checkpoint_cert.summary);
+    assert!(contents.iter().eq(transactions.iter()));
+    verify_certified_checkpoint(&checkpoint, 0, &setup.committee, Some(&contents)).unwrap();
+
+    // It is not accepted in place of another checkpoint, nor with other contents.
+    assert!(verify_certified_checkpoint(&checkpoint, 1, &setup.committee, None).is_err());
+    let other_contents = CheckpointContents::new_with_causally_ordered_transactions(
+        [ExecutionDigests::random()].into_iter(),
+    );
+    assert!(
+        verify_certified_checkpoint(&checkpoint, 0, &setup.committee, Some(&other_contents))
+            .is_err()
+    );
+
+    // Nor does it verify against a committee of another epoch.
+    let mut next_committee = setup.committee.clone();
+    next_committee.epoch += 1;
+    assert!(verify_certified_checkpoint(&checkpoint, 0, &next_committee, None).is_err());
+
+    // The gateway fetches the same certified checkpoint from the authorities.
+    let gateway = GatewayState::new_with_authorities(
+        Arc::new(GatewayStore::open(
+            &tempfile::tempdir().unwrap().into_path(),
+            None,
+        )),
+        setup.aggregator.clone(),
+        GatewayMetrics::new_for_tests(),
+    )
+    .unwrap();
+    let (checkpoint, contents) = gateway.get_certified_checkpoint(0).await.unwrap();
+    assert_eq!(checkpoint.summary, checkpoint_cert.summary);
+    verify_certified_checkpoint(&checkpoint, 0, &setup.committee, Some(&contents)).unwrap();
+
+    // An authority that never signed it has no certified checkpoint to serve.
+    assert!(setup
+        .authorities
+        .iter()
+        .filter(|auth| signers
+            .iter()
+            .all(|s| s.authority.name != auth.authority.name))
+        .all(|auth| auth.authority.get_certified_checkpoint(0).is_err()));
+}
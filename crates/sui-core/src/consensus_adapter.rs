@@ -3,6 +3,7 @@
 
 use crate::checkpoints::CheckpointStore;
 use crate::checkpoints::ConsensusSender;
+use crate::priority_semaphore::PrioritySemaphore;
 use bytes::Bytes;
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
@@ -80,6 +81,11 @@ pub struct ConsensusAdapterMetrics {
 }
 
 const MAX_DELAY_MULTIPLIER: u64 = 100;
+
+/// How many certificates may be in the process of being sent to consensus at once. Further
+/// certificates wait, and are let through by priority.
+const MAX_PENDING_SUBMISSIONS: usize = 1_000;
+
 fn weighted_average_half(old_average: u64, new_value: u64) -> u64 {
     (500 * old_average + 500 * new_value) / 1000
 }
@@ -217,6 +223,9 @@ pub struct ConsensusAdapter {
     /// same transaction.
     delay_ms: AtomicU64,
 
+    /// Bounds the certificates being sent to consensus, preferring higher priority ones when full.
+    submit_permits: PrioritySemaphore,
+
     /// A structure to register metrics
     opt_metrics: OptArcConsensusAdapterMetrics,
 }
@@ -240,6 +249,7 @@ impl ConsensusAdapter {
             tx_consensus_listener,
             max_delay,
             delay_ms: AtomicU64::new(max_delay.as_millis() as u64),
+            submit_permits: PrioritySemaphore::new(MAX_PENDING_SUBMISSIONS),
             opt_metrics,
         }
     }
@@ -289,6 +299,14 @@ impl ConsensusAdapter {
         let now = Instant::now();
         let should_submit = Self::should_submit(certificate);
         if should_submit {
+            // When too many certificates are being sent to consensus, send the higher priority
+            // ones first. Consensus does not order by priority, so this is only a hint. The permit
+            // is released once the certificate is sent, so waiting for it to be sequenced does
+            // not hold back other submissions.
+            let _permit = self
+                .submit_permits
+                .acquire(certificate.signed_data.data.priority())
+                .await;
             self.consensus_client
                 .clone()
                 .submit_transaction(TransactionProto { transaction: bytes })
@@ -2,23 +2,68 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use rocksdb::Options;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use sui_storage::default_db_options;
 use sui_types::base_types::ObjectID;
 use sui_types::committee::{Committee, EpochId};
+use sui_types::crypto::{AuthoritySignInfo, AuthorityStrongQuorumSignInfo, EmptySignInfo};
 use sui_types::error::SuiResult;
-use sui_types::messages::{AuthenticatedEpoch, GenesisEpoch};
+use sui_types::messages::{AuthenticatedEpoch, EpochEnvelop, EpochInfoV1, GenesisEpoch};
+use tracing::info;
 use typed_store::rocks::DBMap;
 use typed_store::traits::DBMapTableUtil;
 use typed_store::Map;
 use typed_store_macros::DBMapUtils;
 
+/// The number of epochs moved by one write batch when migrating the legacy epochs.
+const LEGACY_EPOCHS_MIGRATED_PER_BATCH: usize = 100;
+
 #[derive(DBMapUtils)]
 pub struct EpochStore {
     /// Map from each epoch ID to the epoch information. The epoch is either signed by this node,
     /// or is certified (signed by a quorum).
     #[default_options_override_fn = "epochs_table_default_config"]
-    pub(crate) epochs: DBMap<EpochId, AuthenticatedEpoch>,
+    pub(crate) authenticated_epochs: DBMap<EpochId, AuthenticatedEpoch>,
+
+    /// The epochs stored before the epoch information recorded the protocol version. They are
+    /// moved to `authenticated_epochs` when the store is opened, after which this table is empty.
+    #[default_options_override_fn = "epochs_table_default_config"]
+    epochs: DBMap<EpochId, LegacyAuthenticatedEpoch>,
+}
+
+/// `AuthenticatedEpoch` as it was stored before the epoch information recorded the protocol
+/// version.
+#[derive(Serialize, Deserialize)]
+enum LegacyAuthenticatedEpoch {
+    Genesis(LegacyEpochEnvelop<EmptySignInfo>),
+    Signed(LegacyEpochEnvelop<AuthoritySignInfo>),
+    Certified(LegacyEpochEnvelop<AuthorityStrongQuorumSignInfo>),
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyEpochEnvelop<S> {
+    epoch_info: EpochInfoV1,
+    auth_sign_info: S,
+}
+
+impl<S> From<LegacyEpochEnvelop<S>> for EpochEnvelop<S> {
+    fn from(epoch: LegacyEpochEnvelop<S>) -> Self {
+        Self {
+            epoch_info: epoch.epoch_info.into(),
+            auth_sign_info: epoch.auth_sign_info,
+        }
+    }
+}
+
+impl From<LegacyAuthenticatedEpoch> for AuthenticatedEpoch {
+    fn from(epoch: LegacyAuthenticatedEpoch) -> Self {
+        match epoch {
+            LegacyAuthenticatedEpoch::Genesis(epoch) => Self::Genesis(epoch.into()),
+            LegacyAuthenticatedEpoch::Signed(epoch) => Self::Signed(epoch.into()),
+            LegacyAuthenticatedEpoch::Certified(epoch) => Self::Certified(epoch.into()),
+        }
+    }
 }
 
 // These functions are used to initialize the DB tables
@@ -29,6 +74,9 @@ fn epochs_table_default_config() -> Options {
 impl EpochStore {
     pub fn new(path: PathBuf, genesis_committee: &Committee, db_options: Option<Options>) -> Self {
         let epoch_store = Self::open_tables_read_write(path, db_options, None);
+        epoch_store
+            .migrate_legacy_epochs()
+            .expect("Migrating the legacy epochs must not fail");
         if epoch_store.database_is_empty() {
             epoch_store
                 .init_genesis_epoch(genesis_committee.clone())
@@ -44,10 +92,43 @@ impl EpochStore {
         Self::new(path, genesis_committee, None)
     }
 
+    /// Move the epochs stored before the epoch information recorded the protocol version to
+    /// `authenticated_epochs`. They all ran the genesis protocol version, whose epoch information
+    /// is signed in the legacy layout, so their signatures stay valid.
+    fn migrate_legacy_epochs(&self) -> SuiResult {
+        let mut migrated = 0;
+        loop {
+            let legacy: Vec<_> = self
+                .epochs
+                .iter()
+                .take(LEGACY_EPOCHS_MIGRATED_PER_BATCH)
+                .collect();
+            if legacy.is_empty() {
+                break;
+            }
+            migrated += legacy.len();
+            let epoch_ids: Vec<_> = legacy.iter().map(|(epoch_id, _)| *epoch_id).collect();
+            self.authenticated_epochs
+                .batch()
+                .insert_batch(
+                    &self.authenticated_epochs,
+                    legacy
+                        .into_iter()
+                        .map(|(epoch_id, epoch)| (epoch_id, AuthenticatedEpoch::from(epoch))),
+                )?
+                .delete_batch(&self.epochs, epoch_ids)?
+                .write()?;
+        }
+        if migrated > 0 {
+            info!(migrated, "Migrated the legacy epochs");
+        }
+        Ok(())
+    }
+
     pub fn init_genesis_epoch(&self, genesis_committee: Committee) -> SuiResult {
         assert_eq!(genesis_committee.epoch, 0);
         let epoch_data = AuthenticatedEpoch::Genesis(GenesisEpoch::new(genesis_committee));
-        self.epochs.insert(&0, &epoch_data)?;
+        self.authenticated_epochs.insert(&0, &epoch_data)?;
         Ok(())
     }
 
@@ -55,11 +136,11 @@ impl EpochStore {
         &self,
         epoch_id: &EpochId,
     ) -> SuiResult<Option<AuthenticatedEpoch>> {
-        Ok(self.epochs.get(epoch_id)?)
+        Ok(self.authenticated_epochs.get(epoch_id)?)
     }
 
     pub fn get_latest_authenticated_epoch(&self) -> AuthenticatedEpoch {
-        self.epochs
+        self.authenticated_epochs
             .iter()
             .skip_to_last()
             .next()
@@ -70,6 +151,6 @@ impl EpochStore {
     }
 
     fn database_is_empty(&self) -> bool {
-        self.epochs.iter().next().is_none()
+        self.authenticated_epochs.iter().next().is_none()
     }
 }
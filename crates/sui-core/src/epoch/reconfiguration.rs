@@ -79,6 +79,9 @@ where
                     .database
                     .revert_state_update(&tx_digest.transaction)?;
             }
+            if let Some(coin_supply) = &self.state.coin_supply {
+                coin_supply.clear();
+            }
 
             // Delete any extra certificates now unprocessed.
             checkpoints.tables.extra_transactions.clear()?;
@@ -158,8 +161,10 @@ where
 
         let advance_epoch_tx = SignedTransaction::new_change_epoch(
             next_epoch,
-            0, // TODO: fill in storage_charge
-            0, // TODO: fill in computation_charge
+            // TODO: fill in the storage and computation charges once they are summed over the
+            // certified checkpoints of the epoch, which is the same set on every validator.
+            0,
+            0,
             self.state.name,
             &*self.state.secret,
         );
@@ -151,7 +151,7 @@ async fn test_start_epoch_change() {
         tx_digest,
         BTreeSet::new(),
         &state.move_vm,
-        &state._native_functions,
+        &state.native_functions,
         SuiGasStatus::new_with_budget(1000, 1.into(), 1.into()),
         state.epoch(),
     );
@@ -0,0 +1,170 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, VecDeque};
+
+use parking_lot::Mutex;
+use sui_types::{
+    base_types::{AuthorityName, ObjectRef},
+    committee::{Committee, EpochId},
+    error::{SuiError, SuiResult},
+    fp_ensure,
+    messages::{InputObjectKind, SignedTransaction, SingleTransactionKind, TransferObject},
+};
+use tracing::error;
+
+/// How many signed object locks are remembered to compare later responses against. The oldest
+/// locks are forgotten first.
+pub const MAX_TRACKED_LOCKS: usize = 100_000;
+
+/// How many equivocations are kept to be returned by the detector. The oldest are dropped first.
+pub const MAX_RECORDED_EQUIVOCATIONS: usize = 1_000;
+
+/// Proof that an authority signed two different transactions taking the same version of an
+/// owned object as input in the same epoch. An honest authority locks each owned object version
+/// on a single transaction per epoch, so this is Byzantine behavior.
+#[derive(Clone, Debug)]
+pub struct EquivocationDetected {
+    pub epoch: EpochId,
+    pub authority: AuthorityName,
+    pub object_ref: ObjectRef,
+    pub first: SignedTransaction,
+    pub second: SignedTransaction,
+}
+
+impl EquivocationDetected {
+    /// Check that both transactions are signed by `authority` in `epoch` and take `object_ref` as
+    /// input. Whether `object_ref` is an owned object rather than an immutable one cannot be told
+    /// from the transactions, unless one of them pays gas with it or transfers it.
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        fp_ensure!(
+            self.first.digest() != self.second.digest(),
+            SuiError::from("Equivocation proof must hold two different transactions")
+        );
+        for signed_transaction in [&self.first, &self.second] {
+            fp_ensure!(
+                signed_transaction.auth_sign_info.authority == self.authority,
+                SuiError::from(
+                    "Equivocation proof holds a transaction signed by another authority"
+                )
+            );
+            fp_ensure!(
+                signed_transaction.auth_sign_info.epoch == self.epoch,
+                SuiError::from("Equivocation proof holds a transaction signed in another epoch")
+            );
+            fp_ensure!(
+                takes_owned_input(signed_transaction, &self.object_ref),
+                SuiError::from("Equivocation proof holds a transaction not taking the object")
+            );
+            signed_transaction.verify(committee)?;
+        }
+        Ok(())
+    }
+
+    /// Whether `object_ref` is known to be an owned object from the transactions alone, that is
+    /// one of them pays gas with it or transfers it. Immutable objects can be neither.
+    pub fn is_known_owned(&self) -> bool {
+        [&self.first, &self.second]
+            .into_iter()
+            .any(|signed_transaction| {
+                let data = &signed_transaction.signed_data.data;
+                data.gas_payment_object_ref() == &self.object_ref
+                    || data.kind.single_transactions().any(|kind| {
+                        matches!(
+                            kind,
+                            SingleTransactionKind::TransferObject(TransferObject { object_ref, .. })
+                                if object_ref == &self.object_ref
+                        )
+                    })
+            })
+    }
+}
+
+fn takes_owned_input(signed_transaction: &SignedTransaction, object_ref: &ObjectRef) -> bool {
+    signed_transaction
+        .signed_data
+        .data
+        .input_objects()
+        .map(|inputs| inputs.contains(&InputObjectKind::ImmOrOwnedMoveObject(*object_ref)))
+        .unwrap_or(false)
+}
+
+/// Remembers the transaction each authority signed for each owned object version it was seen
+/// locking in each epoch, and reports an [`EquivocationDetected`] when an authority is seen
+/// signing another transaction for the same object version in the same epoch.
+#[derive(Default)]
+pub(crate) struct EquivocationDetector {
+    inner: Mutex<DetectorState>,
+}
+
+type LockKey = (EpochId, AuthorityName, ObjectRef);
+
+#[derive(Default)]
+struct DetectorState {
+    locks: HashMap<LockKey, SignedTransaction>,
+    /// Insertion order of `locks`, to forget the oldest locks first.
+    order: VecDeque<LockKey>,
+    equivocations: VecDeque<EquivocationDetected>,
+}
+
+impl EquivocationDetector {
+    /// Record that `signed_transaction`, already verified to be signed by `authority`, locks
+    /// `object_ref`. Returns the equivocation if the authority had signed another transaction
+    /// locking the same object version in the same epoch. The equivocation is not recorded, as
+    /// the caller may first need to check that the object is owned, see [`Self::record`].
+    pub fn observe_lock(
+        &self,
+        authority: AuthorityName,
+        object_ref: ObjectRef,
+        signed_transaction: &SignedTransaction,
+    ) -> Option<EquivocationDetected> {
+        if !takes_owned_input(signed_transaction, &object_ref) {
+            return None;
+        }
+        let epoch = signed_transaction.auth_sign_info.epoch;
+        let mut guard = self.inner.lock();
+        let state = &mut *guard;
+        let key = (epoch, authority, object_ref);
+        match state.locks.get(&key) {
+            Some(first) if first.digest() == signed_transaction.digest() => None,
+            Some(first) => Some(EquivocationDetected {
+                epoch,
+                authority,
+                object_ref,
+                first: first.clone(),
+                second: signed_transaction.clone(),
+            }),
+            None => {
+                if state.order.len() >= MAX_TRACKED_LOCKS {
+                    if let Some(oldest) = state.order.pop_front() {
+                        state.locks.remove(&oldest);
+                    }
+                }
+                state.order.push_back(key);
+                state.locks.insert(key, signed_transaction.clone());
+                None
+            }
+        }
+    }
+
+    /// Keep `equivocation` among the most recent ones returned by [`Self::equivocations`].
+    pub fn record(&self, equivocation: EquivocationDetected) {
+        error!(
+            epoch = equivocation.epoch,
+            authority = ?equivocation.authority,
+            object_ref = ?equivocation.object_ref,
+            first = ?equivocation.first.digest(),
+            second = ?equivocation.second.digest(),
+            "Authority signed conflicting transactions for the same object version"
+        );
+        let mut state = self.inner.lock();
+        if state.equivocations.len() >= MAX_RECORDED_EQUIVOCATIONS {
+            state.equivocations.pop_front();
+        }
+        state.equivocations.push_back(equivocation);
+    }
+
+    pub fn equivocations(&self) -> Vec<EquivocationDetected> {
+        self.inner.lock().equivocations.iter().cloned().collect()
+    }
+}
@@ -4,7 +4,9 @@
 
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Bound;
 use std::path::Path;
+use std::str::FromStr;
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::time::Duration;
@@ -12,18 +14,22 @@ use std::time::Duration;
 use anyhow::anyhow;
 use async_trait::async_trait;
 use futures::future;
+use lru::LruCache;
 use move_binary_format::access::ModuleAccess;
 use move_bytecode_utils::module_cache::SyncModuleCache;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
+use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
+use parking_lot::{Mutex, RwLock};
 use prometheus::{
     register_histogram_with_registry, register_int_counter_with_registry, Histogram, IntCounter,
     Registry,
 };
 use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
-use tracing::{debug, error, trace, Instrument};
+use tracing::{debug, error, trace, warn, Instrument};
 
-use sui_adapter::adapter::resolve_and_type_check;
+use sui_adapter::adapter::{self, resolve_and_type_check};
+use sui_adapter::temporary_store::TemporaryStore;
 use sui_config::gateway::GatewayConfig;
 use sui_config::ValidatorInfo;
 use sui_types::gas_coin::GasCoin;
@@ -32,16 +38,24 @@ use sui_types::{
     base_types::*,
     coin,
     committee::Committee,
+    crypto::{sha3_hash, SignableBytes},
     error::{SuiError, SuiResult},
     fp_ensure,
     messages::*,
+    messages_checkpoint::{
+        verify_certified_checkpoint, CertifiedCheckpointSummary, CheckpointContents,
+        CheckpointRequest, CheckpointResponse, CheckpointSequenceNumber,
+    },
     object::{Object, ObjectRead},
-    SUI_FRAMEWORK_ADDRESS,
+    protocol_config::{ProtocolConfig, GENESIS_PROTOCOL_VERSION},
+    sui_serde::Base64,
+    MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS,
 };
 
 use crate::authority::ResolverWrapper;
 use crate::authority_aggregator::AuthAggMetrics;
 use crate::authority_client::{NetworkAuthorityClient, NetworkAuthorityClientMetrics};
+use crate::execution_engine;
 use crate::safe_client::SafeClientMetrics;
 use crate::transaction_input_checker;
 use crate::{
@@ -50,15 +64,19 @@ use crate::{
 };
 use sui_json::{resolve_move_function_args, SuiJsonCallArg, SuiJsonValue};
 use sui_json_rpc_types::{
-    GetObjectDataResponse, GetRawObjectDataResponse, MoveCallParams, RPCTransactionRequestParams,
-    SuiData, SuiObject, SuiObjectInfo, SuiParsedMergeCoinResponse, SuiParsedPublishResponse,
-    SuiParsedSplitCoinResponse, SuiParsedTransactionResponse, SuiTransactionEffects,
-    SuiTransactionResponse, SuiTypeTag, TransferObjectParams,
+    GetObjectDataResponse, GetRawObjectDataResponse, MoveCallParams, OwnedObjectsPage,
+    RPCTransactionRequestParams, SimulatedTransactionEffects, SuiCheckpointedTransactionResponse,
+    SuiData, SuiObject, SuiObjectInfo, SuiParsedData, SuiParsedMergeCoinResponse,
+    SuiParsedPublishResponse, SuiParsedSplitCoinResponse, SuiParsedTransactionResponse,
+    SuiTransactionEffects, SuiTransactionResponse, SuiTypeTag, SyncProgress, SystemState,
+    TransferObjectParams,
 };
 use sui_types::error::SuiError::ConflictingTransaction;
 
 use crate::epoch::epoch_store::EpochStore;
 use tap::TapFallible;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::Semaphore;
 
 #[cfg(test)]
 #[path = "unit_tests/gateway_state_tests.rs"]
@@ -72,6 +90,16 @@ pub type GatewayTxSeqNumber = u64;
 
 /// Number of times to retry failed TX
 const MAX_NUM_TX_RETRIES: usize = 5;
+/// How often to poll authorities for new checkpoints while waiting for a transaction to be
+/// checkpointed.
+const CHECKPOINT_WAIT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// The longest a call to `wait_for_transaction` waits, whatever timeout it asks for.
+pub const MAX_WAIT_FOR_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(60);
+/// How many calls to `wait_for_transaction` may wait at once. Further calls are refused.
+pub const MAX_TRANSACTION_WAITERS: usize = 1000;
+/// How many committed transaction digests a waiter may fall behind before missing some, after
+/// which it checks the store again.
+const COMMITTED_TRANSACTIONS_CAPACITY: usize = 1000;
 
 /// Prometheus metrics which can be displayed in Grafana, queried and alerted on
 #[derive(Clone)]
@@ -85,6 +113,7 @@ pub struct GatewayMetrics {
     num_tx_mergecoin: IntCounter,
     total_tx_retries: IntCounter,
     shared_obj_tx: IntCounter,
+    num_package_fetches: IntCounter,
     pub total_tx_certificates: IntCounter,
     pub transaction_latency: Histogram,
 }
@@ -153,6 +182,12 @@ impl GatewayMetrics {
                 registry,
             )
             .unwrap(),
+            num_package_fetches: register_int_counter_with_registry!(
+                "gateway_num_package_fetches",
+                "Number of packages fetched from validators, rather than from the package cache",
+                registry,
+            )
+            .unwrap(),
             transaction_latency: register_histogram_with_registry!(
                 "transaction_latency",
                 "Latency of execute_transaction_impl",
@@ -168,6 +203,56 @@ impl GatewayMetrics {
     }
 }
 
+/// How many packages the package cache of a gateway holds by default.
+pub const DEFAULT_PACKAGE_CACHE_SIZE: usize = 1_000;
+
+/// Packages fetched from the authorities, least recently used first out. A package never changes
+/// at a given version, so entries are keyed by version and never invalidated. Each gateway has its
+/// own cache, which may be shared with others through [`GatewayState::with_package_cache`].
+pub struct PackageCache {
+    inner: Mutex<PackageCacheInner>,
+}
+
+struct PackageCacheInner {
+    packages: LruCache<(ObjectID, SequenceNumber), Object>,
+    /// The latest cached version of each package, so it can be found by id alone.
+    latest_versions: HashMap<ObjectID, SequenceNumber>,
+}
+
+impl PackageCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(PackageCacheInner {
+                packages: LruCache::new(capacity),
+                latest_versions: HashMap::new(),
+            }),
+        }
+    }
+
+    /// The latest cached version of the package.
+    pub fn get(&self, package_id: &ObjectID) -> Option<Object> {
+        let mut inner = self.inner.lock();
+        let version = *inner.latest_versions.get(package_id)?;
+        inner.packages.get(&(*package_id, version)).cloned()
+    }
+
+    /// Cache `object` if it is a package, other objects may change and are ignored.
+    pub fn insert(&self, object: &Object) {
+        if !object.is_package() {
+            return;
+        }
+        let key = (object.id(), object.version());
+        let mut inner = self.inner.lock();
+        if let Some((evicted, _)) = inner.packages.push(key, object.clone()) {
+            if evicted != key && inner.latest_versions.get(&evicted.0) == Some(&evicted.1) {
+                inner.latest_versions.remove(&evicted.0);
+            }
+        }
+        let latest = inner.latest_versions.entry(key.0).or_insert(key.1);
+        *latest = (*latest).max(key.1);
+    }
+}
+
 pub struct GatewayState<A> {
     authorities: AuthorityAggregator<A>,
     store: Arc<GatewayStore>,
@@ -179,6 +264,20 @@ pub struct GatewayState<A> {
     next_tx_seq_number: AtomicU64,
     metrics: GatewayMetrics,
     module_cache: SyncModuleCache<ResolverWrapper<GatewayStore>>,
+    /// Used to execute transactions locally for dry runs, they are never committed.
+    native_functions: NativeFunctionTable,
+    move_vm: Arc<MoveVM>,
+    /// Resolves names given in place of addresses, see `GatewayAPI::resolve_name`.
+    name_resolver: RwLock<Option<Arc<dyn NameResolver>>>,
+    /// Object mapping names to addresses, used when no name resolver is set.
+    name_registry: Option<ObjectID>,
+    /// Protocol parameters of the epoch of the committee, fetched from the authorities when
+    /// first needed.
+    protocol_config: RwLock<Option<Arc<ProtocolConfig>>>,
+    package_cache: Arc<PackageCache>,
+    /// Digests of the transactions committed to the store, for `wait_for_transaction`.
+    committed_transactions: broadcast::Sender<TransactionDigest>,
+    transaction_waiters: Semaphore,
 }
 
 impl<A> GatewayState<A> {
@@ -213,15 +312,35 @@ impl<A> GatewayState<A> {
         metrics: GatewayMetrics,
     ) -> SuiResult<Self> {
         let next_tx_seq_number = AtomicU64::new(gateway_store.next_sequence_number()?);
+        let native_functions =
+            sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
+        let move_vm = Arc::new(
+            adapter::new_move_vm(native_functions.clone())
+                .expect("We defined natives to not fail here"),
+        );
         Ok(Self {
             store: gateway_store.clone(),
             authorities,
             next_tx_seq_number,
             metrics,
             module_cache: SyncModuleCache::new(ResolverWrapper(gateway_store)),
+            native_functions,
+            move_vm,
+            name_resolver: RwLock::new(None),
+            name_registry: None,
+            protocol_config: RwLock::new(None),
+            package_cache: Arc::new(PackageCache::new(DEFAULT_PACKAGE_CACHE_SIZE)),
+            committed_transactions: broadcast::channel(COMMITTED_TRANSACTIONS_CAPACITY).0,
+            transaction_waiters: Semaphore::new(MAX_TRANSACTION_WAITERS),
         })
     }
 
+    /// Use `package_cache`, e.g. shared with other gateways, rather than a cache of its own.
+    pub fn with_package_cache(mut self, package_cache: Arc<PackageCache>) -> Self {
+        self.package_cache = package_cache;
+        self
+    }
+
     // Given a list of inputs from a transaction, fetch the objects
     // from the db.
     async fn read_objects_from_store(
@@ -255,12 +374,15 @@ impl GatewayState<NetworkAuthorityClient> {
         let network_metrics = NetworkAuthorityClientMetrics::new(prometheus_registry);
         let authority_clients = Self::make_authority_clients(config, network_metrics);
 
-        Ok(Arc::new(GatewayState::new(
+        let mut state = GatewayState::new(
             &config.db_folder_path,
             committee,
             authority_clients,
             prometheus_registry,
-        )?))
+        )?;
+        state.name_registry = config.name_registry;
+        state.authorities.submit_retry = config.submit_retry.clone();
+        Ok(Arc::new(state))
     }
 
     pub fn make_committee(config: &GatewayConfig) -> SuiResult<Committee> {
@@ -290,14 +412,69 @@ impl GatewayState<NetworkAuthorityClient> {
     }
 }
 
+/// Resolves human-readable names, e.g. `alice.sui`, to the addresses they are registered to.
+#[async_trait]
+pub trait NameResolver: Send + Sync {
+    async fn resolve_name(&self, name: &str) -> Result<SuiAddress, anyhow::Error>;
+}
+
 // Operations are considered successful when they successfully reach a quorum of authorities.
 #[async_trait]
 pub trait GatewayAPI {
+    /// Execute the transaction on a quorum of authorities.
+    ///
+    /// Once this returns, the objects written by the transaction are committed to the local
+    /// store, so that any following read through this gateway, e.g. `get_object` or
+    /// `get_objects_owned_by_address`, reflects them.
     async fn execute_transaction(
         &self,
         tx: Transaction,
     ) -> Result<SuiTransactionResponse, anyhow::Error>;
 
+    /// Execute the transaction, then wait for it to be included in a certified checkpoint.
+    /// Returns the response along with the sequence number of that checkpoint, or an error if
+    /// no checkpoint includes the transaction within `timeout`.
+    async fn execute_transaction_and_wait_for_checkpoint(
+        &self,
+        tx: Transaction,
+        timeout: Duration,
+    ) -> Result<SuiCheckpointedTransactionResponse, anyhow::Error>;
+
+    /// Get the certified checkpoint at `sequence_number` along with its contents, from any
+    /// authority. Both are checked against the committee before being returned, and clients can
+    /// check them again with `verify_certified_checkpoint` without trusting the gateway.
+    async fn get_certified_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<(CertifiedCheckpointSummary, CheckpointContents), anyhow::Error>;
+
+    /// Debug only: submit the transaction to the given authority alone, without quorum
+    /// aggregation, and return its response verbatim. Nothing is recorded by the gateway.
+    async fn execute_transaction_on_authority(
+        &self,
+        tx: Transaction,
+        authority: AuthorityName,
+    ) -> Result<TransactionInfoResponse, anyhow::Error>;
+
+    /// Execute already built transaction bytes (as returned by the transaction builder API)
+    /// locally against the objects known to the gateway, and return the resulting effects.
+    /// Nothing is signed, submitted to authorities or committed.
+    async fn dry_run_transaction_bytes(
+        &self,
+        tx_bytes: Base64,
+    ) -> Result<SuiTransactionEffects, anyhow::Error>;
+
+    /// Dry run transaction bytes as `dry_run_transaction_bytes` does, as if the given input
+    /// objects had the given state rather than their actual one. Each override must have the ID
+    /// and version of an input of the transaction. This is a what-if simulation only: nothing is
+    /// signed, submitted or committed, and the overrides are not stored. The ids of the objects
+    /// the simulation creates are synthetic.
+    async fn simulate_with_overrides(
+        &self,
+        tx_bytes: Base64,
+        overrides: Vec<Object>,
+    ) -> Result<SimulatedTransactionEffects, anyhow::Error>;
+
     /// Send an object to a Sui address. The object's type must allow public transfers
     async fn public_transfer_object(
         &self,
@@ -323,6 +500,17 @@ pub trait GatewayAPI {
     /// this method doesn't guarantee data correctness, caller will have to handle potential byzantine authority
     async fn sync_account_state(&self, account_addr: SuiAddress) -> Result<(), anyhow::Error>;
 
+    /// Synchronise at most `limit` of the objects owned by account_addr, in object id order,
+    /// starting after `cursor`. Syncing from the returned cursor until none is returned syncs
+    /// the whole account in steps, so that the progress of large accounts can be reported. The
+    /// cursor is all a step resumes from, the owned objects being listed again at every step.
+    async fn sync_account_state_incremental(
+        &self,
+        account_addr: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<SyncProgress, anyhow::Error>;
+
     /// Call move functions in the module in the given package, with args supplied
     async fn move_call(
         &self,
@@ -400,14 +588,27 @@ pub trait GatewayAPI {
         gas_budget: u64,
     ) -> Result<TransactionData, anyhow::Error>;
 
+    /// Send several objects to the same Sui address in a single Batch Transaction, so either all
+    /// of them are transferred or none is. Every object must be owned by the signer, and have a
+    /// type allowing public transfers.
+    async fn batch_transfer_objects(
+        &self,
+        signer: SuiAddress,
+        object_ids: Vec<ObjectID>,
+        recipient: SuiAddress,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> Result<TransactionData, anyhow::Error>;
+
     /// Get the object data
     async fn get_object(&self, object_id: ObjectID)
         -> Result<GetObjectDataResponse, anyhow::Error>;
 
-    /// Get the object data
+    /// Get the object data, with the fields of a Move object decoded if `decode_fields` is set.
     async fn get_raw_object(
         &self,
         object_id: ObjectID,
+        decode_fields: bool,
     ) -> Result<GetRawObjectDataResponse, anyhow::Error>;
 
     /// Get refs of all objects we own from local cache.
@@ -421,6 +622,23 @@ pub trait GatewayAPI {
         object_id: ObjectID,
     ) -> Result<Vec<SuiObjectInfo>, anyhow::Error>;
 
+    /// Get a page of at most `limit` objects owned by an address after `cursor`, in object id
+    /// order, only those whose type matches `type_filter` if given. The page is listed by the
+    /// authorities and its objects synced from them, rather than read from local cache.
+    async fn get_objects_owned_by_address_page(
+        &self,
+        account_addr: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: usize,
+        type_filter: Option<StructTag>,
+    ) -> Result<OwnedObjectsPage, anyhow::Error>;
+
+    /// Return the number of objects owned by an address, per object type.
+    async fn get_owned_object_summary(
+        &self,
+        account_addr: SuiAddress,
+    ) -> Result<BTreeMap<String, u64>, anyhow::Error>;
+
     /// Get the total number of transactions ever happened in history.
     fn get_total_transaction_number(&self) -> Result<u64, anyhow::Error>;
 
@@ -443,12 +661,107 @@ pub trait GatewayAPI {
         &self,
         digest: TransactionDigest,
     ) -> Result<SuiTransactionResponse, anyhow::Error>;
+
+    /// Wait up to `timeout`, bounded by `MAX_WAIT_FOR_TRANSACTION_TIMEOUT`, for a transaction to
+    /// be committed, and return it. Returns at once if it was committed already.
+    async fn wait_for_transaction(
+        &self,
+        digest: TransactionDigest,
+        timeout: Duration,
+    ) -> Result<SuiTransactionResponse, anyhow::Error>;
+
+    /// Return the authorities whose signatures form the certificate of a transaction.
+    async fn get_transaction_auth_signers(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<Vec<AuthorityName>, anyhow::Error>;
+
+    /// Return the BCS serialized `TransactionEffects` of a transaction, so that external
+    /// verifiers can recompute the effects digest.
+    async fn get_raw_effects(&self, digest: TransactionDigest) -> Result<Base64, anyhow::Error>;
+
+    /// Return the current epoch, committee and gas parameters of the network.
+    async fn get_system_state(&self) -> Result<SystemState, anyhow::Error>;
+
+    /// Return the gas price transactions should use by default.
+    async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
+
+    /// Return the protocol parameters of the current epoch, as recorded by the authorities.
+    async fn get_protocol_config(&self) -> Result<ProtocolConfig, anyhow::Error>;
+
+    /// Return the owner of an object as of `version`, or None if the object did not exist, or
+    /// was deleted or wrapped, at that version. Only history known to the gateway is available,
+    /// and versions newer than the latest one are an error.
+    async fn get_owner_at_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Owner>, anyhow::Error>;
+
+    /// Resolve names with `resolver` rather than the name registry of the gateway config.
+    fn set_name_resolver(&self, resolver: Arc<dyn NameResolver>);
+
+    /// Return the address `name` is registered to, by the name resolver if one is set, or else
+    /// by the name registry object of the gateway config.
+    async fn resolve_name(&self, name: &str) -> Result<SuiAddress, anyhow::Error>;
 }
 
 impl<A> GatewayState<A>
 where
     A: AuthorityAPI + Send + Sync + 'static + Clone,
 {
+    /// Return the protocol parameters transactions are checked against, fetching them from the
+    /// authorities if they are not cached yet.
+    async fn protocol_config(&self) -> SuiResult<Arc<ProtocolConfig>> {
+        if let Some(protocol_config) = self.protocol_config.read().clone() {
+            return Ok(protocol_config);
+        }
+        let protocol_config = Arc::new(self.fetch_protocol_config().await?);
+        *self.protocol_config.write() = Some(protocol_config.clone());
+        Ok(protocol_config)
+    }
+
+    /// Read the protocol version of the current epoch. The genesis epoch runs the genesis
+    /// protocol version. The version of a later epoch is read from the first authority returning
+    /// the epoch certified by a quorum of the previous committee, which the safe client verifies:
+    /// an epoch signed by a single authority is not trusted with it.
+    async fn fetch_protocol_config(&self) -> SuiResult<ProtocolConfig> {
+        let epoch_id = self.authorities.committee.epoch;
+        if epoch_id == 0 {
+            return ProtocolConfig::get_for_version(GENESIS_PROTOCOL_VERSION);
+        }
+        let mut last_error = None;
+        for (name, client) in &self.authorities.authority_clients {
+            match client
+                .handle_epoch(EpochRequest {
+                    epoch_id: Some(epoch_id),
+                })
+                .await
+            {
+                Ok(EpochResponse {
+                    epoch_info: Some(AuthenticatedEpoch::Certified(epoch)),
+                }) => return ProtocolConfig::get_for_version(epoch.epoch_info.protocol_version()),
+                Ok(EpochResponse {
+                    epoch_info: Some(_),
+                }) => {
+                    debug!(authority = ?name, epoch_id, "Authority has not certified the epoch");
+                }
+                Ok(EpochResponse { epoch_info: None }) => {
+                    debug!(authority = ?name, epoch_id, "Authority does not know the epoch");
+                }
+                Err(err) => {
+                    debug!(authority = ?name, ?err, "Failed to get the epoch from authority");
+                    last_error = Some(err);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            SuiError::from(
+                format!("No authority returned the certified info of epoch {epoch_id}").as_str(),
+            )
+        }))
+    }
+
     pub async fn get_framework_object_ref(&self) -> Result<ObjectRef, anyhow::Error> {
         Ok(self
             .get_object_ref(&ObjectID::from(SUI_FRAMEWORK_ADDRESS))
@@ -462,7 +775,11 @@ where
     /// And when it's out-of-dated in the rare case, we need to be able to understand the error
     /// returned from validators and update the object locally so that the wallet can retry.
     async fn get_object_internal(&self, object_id: &ObjectID) -> SuiResult<Object> {
+        if let Some(package) = self.package_cache.get(object_id) {
+            return Ok(package);
+        }
         if let Ok(Some(o)) = self.store.get_object(object_id) {
+            self.package_cache.insert(&o);
             if o.is_immutable() {
                 // If an object is immutable, it can never be mutated and hence is guaranteed to
                 // be up-to-date. No need to download from validators.
@@ -475,6 +792,10 @@ where
             .into_object()?;
         let obj_ref = object.compute_object_reference();
         debug!(?object_id, ?obj_ref, "Fetched object from validators");
+        if object.is_package() {
+            self.metrics.num_package_fetches.inc();
+            self.package_cache.insert(&object);
+        }
         Ok(object)
     }
 
@@ -589,15 +910,22 @@ where
             "Setting transaction lock"
         );
         self.store
-            .lock_and_write_transaction(mutable_input_objects, transaction)
+            .lock_and_write_transaction(
+                self.authorities.committee.epoch,
+                mutable_input_objects,
+                transaction,
+            )
             .await
     }
 
     /// Make sure all objects in the input exist in the gateway store.
     /// If any object does not exist in the store, give it a chance
     /// to download from authorities.
-    async fn sync_input_objects_with_authorities(&self, transaction: &Transaction) -> SuiResult {
-        let input_objects = transaction.signed_data.data.input_objects()?;
+    async fn sync_input_objects_with_authorities(
+        &self,
+        transaction: &TransactionData,
+    ) -> SuiResult {
+        let input_objects = transaction.input_objects()?;
         let mut objects = self.read_objects_from_store(&input_objects).await?;
         for (object_opt, kind) in objects.iter_mut().zip(&input_objects) {
             if object_opt.is_none() {
@@ -674,6 +1002,8 @@ where
                 effects.digest(),
             )
             .await?;
+        // Fails only when nobody is waiting.
+        let _ = self.committed_transactions.send(*tx_digest);
 
         Ok((new_certificate, effects))
     }
@@ -686,7 +1016,7 @@ where
     ) -> SuiResult<(InputObjects, Vec<ObjectRef>)> {
         transaction.verify()?;
 
-        self.sync_input_objects_with_authorities(transaction)
+        self.sync_input_objects_with_authorities(&transaction.signed_data.data)
             .await?;
 
         // Getting the latest system state for gas information
@@ -695,8 +1025,13 @@ where
         self.download_object_from_authorities(SUI_SYSTEM_STATE_OBJECT_ID)
             .await?;
 
-        let (_gas_status, input_objects) =
-            transaction_input_checker::check_transaction_input(&self.store, transaction).await?;
+        let protocol_config = self.protocol_config().await?;
+        let (_gas_status, input_objects) = transaction_input_checker::check_transaction_input(
+            &self.store,
+            &protocol_config,
+            transaction,
+        )
+        .await?;
 
         let owned_objects = input_objects.filter_owned_objects();
         if let Err(err) = self
@@ -782,16 +1117,36 @@ where
         }
     }
 
+    /// Read the latest version of an object known to the authorities, and store it locally.
+    ///
+    /// If the local store holds a newer version, e.g. because this gateway just executed a
+    /// transaction which some of the authorities answering have not yet processed, the local
+    /// version is returned instead. Together with `execute_transaction` only returning once its
+    /// effects are committed to the local store, this guarantees that reads through the gateway
+    /// observe the writes of the transactions it executed.
     async fn download_object_from_authorities(&self, object_id: ObjectID) -> SuiResult<ObjectRead> {
         let result = self.authorities.get_object_info_execute(object_id).await?;
-        if let ObjectRead::Exists(obj_ref, object, _) = &result {
+        if let ObjectRead::Exists(obj_ref, object, layout) = &result {
             let local_object = self.store.get_object(&object_id)?;
             let should_update = match local_object {
                 None => true, // Local store doesn't have it.
                 Some(local_obj) => {
                     let local_obj_ref = local_obj.compute_object_reference();
                     match local_obj_ref.1.cmp(&obj_ref.1) {
-                        Ordering::Greater => false, // Local version is more up-to-date
+                        Ordering::Greater => {
+                            // Local version is more up-to-date. The type of an object never
+                            // changes, so the layout of the downloaded version applies.
+                            debug!(
+                                ?local_obj_ref,
+                                ?obj_ref,
+                                "Authorities returned a stale object, using the local version"
+                            );
+                            return Ok(ObjectRead::Exists(
+                                local_obj_ref,
+                                local_obj,
+                                layout.clone(),
+                            ));
+                        }
                         Ordering::Less => true,
                         Ordering::Equal => {
                             if local_obj_ref.2 != obj_ref.2 {
@@ -967,11 +1322,7 @@ where
         );
 
         Ok(SuiParsedTransactionResponse::Publish(
-            SuiParsedPublishResponse {
-                package,
-                created_objects,
-                updated_gas,
-            },
+            SuiParsedPublishResponse::new(package, created_objects, updated_gas),
         ))
     }
 
@@ -1323,6 +1674,168 @@ where
         });
     }
 
+    async fn execute_transaction_and_wait_for_checkpoint(
+        &self,
+        tx: Transaction,
+        timeout: Duration,
+    ) -> Result<SuiCheckpointedTransactionResponse, anyhow::Error> {
+        let tx_digest = *tx.digest();
+        let authorities: BTreeSet<_> = self.authorities.committee.names().cloned().collect();
+
+        // A checkpoint certified before the transaction is executed cannot include it, so the
+        // search starts from the latest checkpoint known beforehand. If the transaction was
+        // already executed earlier, its checkpoint may predate this one and is not found.
+        let request = CheckpointRequest::authenticated(None, false);
+        let mut next_checkpoint = match self
+            .authorities
+            .handle_checkpoint_request(&request, &authorities, Some(timeout))
+            .await?
+        {
+            CheckpointResponse::AuthenticatedCheckpoint {
+                checkpoint: Some(checkpoint),
+                ..
+            } => *checkpoint.summary().sequence_number(),
+            _ => 0,
+        };
+
+        let response = self.execute_transaction(tx).await?;
+
+        let wait = async {
+            loop {
+                match self
+                    .authorities
+                    .get_certified_checkpoint(next_checkpoint, true, &authorities, Some(timeout))
+                    .await
+                {
+                    Ok((_, Some(contents))) => {
+                        if contents
+                            .iter()
+                            .any(|digests| digests.transaction == tx_digest)
+                        {
+                            return next_checkpoint;
+                        }
+                        next_checkpoint += 1;
+                    }
+                    // The checkpoint is not certified yet.
+                    _ => tokio::time::sleep(CHECKPOINT_WAIT_POLL_INTERVAL).await,
+                }
+            }
+        };
+        let checkpoint = tokio::time::timeout(timeout, wait).await.map_err(|_| {
+            anyhow!(
+                "Transaction {tx_digest:?} was executed but not checkpointed within {timeout:?}"
+            )
+        })?;
+        Ok(SuiCheckpointedTransactionResponse {
+            response,
+            checkpoint,
+        })
+    }
+
+    async fn get_certified_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<(CertifiedCheckpointSummary, CheckpointContents), anyhow::Error> {
+        let authorities: BTreeSet<_> = self.authorities.committee.names().cloned().collect();
+        let (checkpoint, contents) = self
+            .authorities
+            .get_certified_checkpoint(sequence_number, true, &authorities, None)
+            .await?;
+        let contents = contents
+            .ok_or_else(|| anyhow!("No contents returned for checkpoint {sequence_number}"))?;
+        verify_certified_checkpoint(
+            &checkpoint,
+            sequence_number,
+            &self.authorities.committee,
+            Some(&contents),
+        )?;
+        Ok((checkpoint, contents))
+    }
+
+    async fn execute_transaction_on_authority(
+        &self,
+        tx: Transaction,
+        authority: AuthorityName,
+    ) -> Result<TransactionInfoResponse, anyhow::Error> {
+        warn!(tx_digest = ?tx.digest(), ?authority, "Debug: bypassing quorum, submitting to one authority");
+        Ok(self
+            .authorities
+            .handle_transaction_with_authority(&authority, tx)
+            .await?)
+    }
+
+    async fn dry_run_transaction_bytes(
+        &self,
+        tx_bytes: Base64,
+    ) -> Result<SuiTransactionEffects, anyhow::Error> {
+        Ok(self
+            .simulate_with_overrides(tx_bytes, vec![])
+            .await?
+            .effects)
+    }
+
+    async fn simulate_with_overrides(
+        &self,
+        tx_bytes: Base64,
+        overrides: Vec<Object>,
+    ) -> Result<SimulatedTransactionEffects, anyhow::Error> {
+        let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?).map_err(|e| {
+            anyhow!("Cannot deserialize transaction bytes into TransactionData: {e}")
+        })?;
+        // The transaction is not signed, so it has no digest yet: the one executed transactions
+        // get hashes the signed data. We derive a synthetic digest from the data alone, so the
+        // ids of the objects the simulation creates differ from those execution assigns.
+        let transaction_digest = TransactionDigest::new(sha3_hash(&data));
+        debug!(
+            tx_digest = ?transaction_digest,
+            num_overrides = overrides.len(),
+            "Received dry run request"
+        );
+
+        self.sync_input_objects_with_authorities(&data).await?;
+        let protocol_config = self.protocol_config().await?;
+        // The overrides are checked, and executed, in place of the stored objects, so that
+        // e.g. a gas coin can be simulated with a balance it does not have. The store is
+        // untouched.
+        let (gas_status, input_objects) = transaction_input_checker::check_transaction_data_input(
+            &self.store,
+            &protocol_config,
+            &data,
+            &overrides,
+        )
+        .await?;
+        for object in &overrides {
+            if !input_objects.contains_object(object.id(), object.version()) {
+                return Err(anyhow!(
+                    "Override of object {} at version {} does not match an input of the transaction",
+                    object.id(),
+                    object.version()
+                ));
+            }
+        }
+
+        let shared_object_refs = input_objects.filter_shared_objects();
+        let transaction_dependencies = input_objects.transaction_dependencies();
+        let temporary_store =
+            TemporaryStore::new(self.store.clone(), input_objects, transaction_digest);
+        let (_inner_temp_store, effects, _execution_error) =
+            execution_engine::execute_transaction_to_effects(
+                shared_object_refs,
+                temporary_store,
+                data,
+                transaction_digest,
+                transaction_dependencies,
+                &self.move_vm,
+                &self.native_functions,
+                gas_status,
+                self.authorities.committee.epoch,
+            );
+        Ok(SimulatedTransactionEffects {
+            effects: SuiTransactionEffects::try_from(effects, &self.module_cache)?,
+            synthetic_object_ids: true,
+        })
+    }
+
     async fn public_transfer_object(
         &self,
         signer: SuiAddress,
@@ -1401,6 +1914,63 @@ where
         ))
     }
 
+    async fn batch_transfer_objects(
+        &self,
+        signer: SuiAddress,
+        object_ids: Vec<ObjectID>,
+        recipient: SuiAddress,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> Result<TransactionData, anyhow::Error> {
+        fp_ensure!(
+            !object_ids.is_empty(),
+            SuiError::InvalidBatchTransaction {
+                error: "Batch transfer must transfer at least one object".to_owned(),
+            }
+            .into()
+        );
+        let mut all_tx_kind = vec![];
+        let mut used_object_ids = BTreeSet::new();
+        for object_id in object_ids {
+            fp_ensure!(
+                used_object_ids.insert(object_id),
+                SuiError::DuplicateObjectRefInput.into()
+            );
+            let object = self.get_object_internal(&object_id).await?;
+            match object.owner {
+                Owner::AddressOwner(owner) if owner == signer => {}
+                Owner::Shared => {
+                    return Err(anyhow!(
+                        "Object {object_id} is shared and cannot be transferred"
+                    ))
+                }
+                Owner::Immutable => {
+                    return Err(anyhow!(
+                        "Object {object_id} is immutable and cannot be transferred"
+                    ))
+                }
+                owner => {
+                    return Err(anyhow!(
+                        "Object {object_id} is owned by {owner}, not by the signer {signer}"
+                    ))
+                }
+            }
+            all_tx_kind.push(SingleTransactionKind::TransferObject(TransferObject {
+                recipient,
+                object_ref: object.compute_object_reference(),
+            }));
+        }
+        let gas = self
+            .choose_gas_for_address(signer, gas_budget, gas, used_object_ids)
+            .await?;
+        Ok(TransactionData::new(
+            TransactionKind::Batch(all_tx_kind),
+            signer,
+            gas,
+            gas_budget,
+        ))
+    }
+
     // TODO: Get rid of the sync API.
     // https://github.com/MystenLabs/sui/issues/1045
     async fn sync_account_state(&self, account_addr: SuiAddress) -> Result<(), anyhow::Error> {
@@ -1432,6 +2002,68 @@ where
         Ok(())
     }
 
+    async fn sync_account_state_incremental(
+        &self,
+        account_addr: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<SyncProgress, anyhow::Error> {
+        if limit == 0 {
+            return Err(anyhow!("Cannot sync zero objects at a time"));
+        }
+        // Every step lists the owned objects afresh and resumes after the cursor, so that no
+        // state is kept between the steps of a sync.
+        let owned_ids = self
+            .authorities
+            .get_owned_object_ids(account_addr, Duration::from_secs(60))
+            .await?;
+        let remaining: Vec<_> = match cursor {
+            Some(cursor) => owned_ids
+                .range((Bound::Excluded(cursor), Bound::Unbounded))
+                .copied()
+                .collect(),
+            None => owned_ids.iter().copied().collect(),
+        };
+        let step = &remaining[..limit.min(remaining.len())];
+        debug!(
+            ?account_addr,
+            ?cursor,
+            num_objects = step.len(),
+            "Syncing account objects from validators"
+        );
+
+        let (active_object_certs, deleted_refs_certs) =
+            self.authorities.sync_all_given_objects(step).await?;
+        let processed_transactions = active_object_certs
+            .iter()
+            .filter_map(|(_, _, cert)| cert.as_ref())
+            .chain(
+                deleted_refs_certs
+                    .iter()
+                    .filter_map(|(_, cert)| cert.as_ref()),
+            )
+            .map(|cert| *cert.digest())
+            .collect::<HashSet<_>>()
+            .len();
+        for (object, _option_layout, _option_cert) in active_object_certs {
+            self.store
+                .insert_object_direct(object.compute_object_reference(), &object)
+                .await?;
+        }
+
+        let next_cursor = if step.len() < remaining.len() {
+            step.last().copied()
+        } else {
+            None
+        };
+        Ok(SyncProgress {
+            synced_objects: owned_ids.len() - remaining.len() + step.len(),
+            total_objects: owned_ids.len(),
+            processed_transactions,
+            next_cursor,
+        })
+    }
+
     async fn move_call(
         &self,
         signer: SuiAddress,
@@ -1588,9 +2220,10 @@ where
     async fn get_raw_object(
         &self,
         object_id: ObjectID,
+        decode_fields: bool,
     ) -> Result<GetRawObjectDataResponse, anyhow::Error> {
         let result = self.download_object_from_authorities(object_id).await?;
-        Ok(result.try_into()?)
+        GetRawObjectDataResponse::try_from_read(result, decode_fields)
     }
 
     async fn get_objects_owned_by_address(
@@ -1619,6 +2252,55 @@ where
         Ok(refs)
     }
 
+    async fn get_objects_owned_by_address_page(
+        &self,
+        account_addr: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: usize,
+        type_filter: Option<StructTag>,
+    ) -> Result<OwnedObjectsPage, anyhow::Error> {
+        if limit == 0 {
+            return Err(anyhow!("Cannot get zero objects at a time"));
+        }
+        let (object_ids, next_cursor) = self
+            .authorities
+            .get_owned_object_ids_page(
+                account_addr,
+                cursor,
+                limit as u64,
+                type_filter,
+                Duration::from_secs(60),
+            )
+            .await?;
+        let (active_object_certs, _deleted_refs_certs) =
+            self.authorities.sync_all_given_objects(&object_ids).await?;
+        let mut data = Vec::new();
+        for (object, _option_layout, _option_cert) in active_object_certs {
+            let object_ref = object.compute_object_reference();
+            self.store.insert_object_direct(object_ref, &object).await?;
+            // The object may have been transferred since the authorities listed it.
+            if object.owner == Owner::AddressOwner(account_addr) {
+                data.push(SuiObjectInfo::from(ObjectInfo::new(&object_ref, &object)));
+            }
+        }
+        data.sort_by_key(|object| object.object_id);
+        Ok(OwnedObjectsPage { data, next_cursor })
+    }
+
+    async fn get_owned_object_summary(
+        &self,
+        account_addr: SuiAddress,
+    ) -> Result<BTreeMap<String, u64>, anyhow::Error> {
+        let mut summary = BTreeMap::new();
+        for info in self
+            .store
+            .get_owner_objects(Owner::AddressOwner(account_addr))?
+        {
+            *summary.entry(info.type_).or_default() += 1;
+        }
+        Ok(summary)
+    }
+
     fn get_total_transaction_number(&self) -> Result<u64, anyhow::Error> {
         QueryHelpers::get_total_transaction_number(&self.store)
     }
@@ -1651,4 +2333,122 @@ where
             parsed_data: None,
         })
     }
+
+    async fn wait_for_transaction(
+        &self,
+        digest: TransactionDigest,
+        timeout: Duration,
+    ) -> Result<SuiTransactionResponse, anyhow::Error> {
+        let _permit = self
+            .transaction_waiters
+            .try_acquire()
+            .map_err(|_| anyhow!("Too many callers are waiting for transactions, retry later"))?;
+        let timeout = timeout.min(MAX_WAIT_FOR_TRANSACTION_TIMEOUT);
+        // Subscribe before checking the store, so that a commit in between is not missed.
+        let mut committed = self.committed_transactions.subscribe();
+        let wait = async {
+            if self.store.effects_exists(&digest)? {
+                return Ok(());
+            }
+            loop {
+                match committed.recv().await {
+                    Ok(committed_digest) if committed_digest != digest => continue,
+                    // The digests missed by a lagging waiter may include this one.
+                    Err(RecvError::Lagged(_)) if !self.store.effects_exists(&digest)? => continue,
+                    _ => return Ok::<_, SuiError>(()),
+                }
+            }
+        };
+        tokio::time::timeout(timeout, wait).await.map_err(|_| {
+            anyhow!("Transaction {digest:?} was not committed within {timeout:?}")
+        })??;
+        self.get_transaction(digest).await
+    }
+
+    async fn get_transaction_auth_signers(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<Vec<AuthorityName>, anyhow::Error> {
+        let (cert, _) = QueryHelpers::get_transaction(&self.store, &digest)?;
+        // The gateway only knows the current committee.
+        let committee = &self.authorities.committee;
+        if cert.auth_sign_info.epoch != committee.epoch {
+            return Err(SuiError::InvalidAuthenticatedEpoch(format!(
+                "Transaction {digest:?} was certified in epoch {}, current epoch is {}",
+                cert.auth_sign_info.epoch, committee.epoch
+            ))
+            .into());
+        }
+        Ok(cert
+            .auth_sign_info
+            .authorities(committee)
+            .map(|name| name.copied())
+            .collect::<SuiResult<_>>()?)
+    }
+
+    async fn get_raw_effects(&self, digest: TransactionDigest) -> Result<Base64, anyhow::Error> {
+        let (_, effects) = QueryHelpers::get_transaction(&self.store, &digest)?;
+        Ok(Base64::from_bytes(&bcs::to_bytes(&effects)?))
+    }
+
+    async fn get_system_state(&self) -> Result<SystemState, anyhow::Error> {
+        // Always refresh the system state object, the local copy is only updated when
+        // transactions are prepared.
+        self.download_object_from_authorities(SUI_SYSTEM_STATE_OBJECT_ID)
+            .await?;
+        let system_state = self.store.get_sui_system_state_object()?;
+        Ok(SystemState::new(&self.authorities.committee, &system_state))
+    }
+
+    async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
+        self.download_object_from_authorities(SUI_SYSTEM_STATE_OBJECT_ID)
+            .await?;
+        let min_gas_price = self.protocol_config().await?.min_gas_price;
+        Ok(self
+            .store
+            .get_sui_system_state_object()?
+            .effective_reference_gas_price(min_gas_price))
+    }
+
+    async fn get_protocol_config(&self) -> Result<ProtocolConfig, anyhow::Error> {
+        // Always ask the authorities, the cached config is only used to check transactions.
+        let protocol_config = Arc::new(self.fetch_protocol_config().await?);
+        *self.protocol_config.write() = Some(protocol_config.clone());
+        Ok((*protocol_config).clone())
+    }
+
+    async fn get_owner_at_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Owner>, anyhow::Error> {
+        Ok(self.store.get_owner_at_version(object_id, version)?)
+    }
+
+    fn set_name_resolver(&self, resolver: Arc<dyn NameResolver>) {
+        *self.name_resolver.write() = Some(resolver);
+    }
+
+    async fn resolve_name(&self, name: &str) -> Result<SuiAddress, anyhow::Error> {
+        let resolver = self.name_resolver.read().clone();
+        if let Some(resolver) = resolver {
+            return resolver.resolve_name(name).await;
+        }
+        let registry = self
+            .name_registry
+            .ok_or_else(|| anyhow!("No name resolver or name registry is configured"))?;
+        // The registry holds a `names: VecMap<String, address>` field.
+        let fields = match self.get_sui_object::<SuiParsedData>(&registry).await?.data {
+            SuiParsedData::MoveObject(object) => object.fields.to_json_value()?,
+            SuiParsedData::Package(_) => {
+                return Err(anyhow!("Name registry {registry} is not a Move object"))
+            }
+        };
+        let address = fields["names"]["contents"]
+            .as_array()
+            .and_then(|entries| entries.iter().find(|entry| entry["key"] == name))
+            .and_then(|entry| entry["value"].as_str())
+            .ok_or_else(|| anyhow!("Name {name} is not registered in {registry}"))?;
+        Ok(SuiAddress::from_str(address)?)
+    }
 }
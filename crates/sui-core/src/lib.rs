@@ -10,6 +10,7 @@ pub mod authority_server;
 pub mod checkpoints;
 pub mod consensus_adapter;
 pub mod epoch;
+pub mod equivocation;
 pub mod event_handler;
 pub mod execution_engine;
 pub mod gateway_state;
@@ -22,4 +23,5 @@ pub mod transaction_streamer;
 pub mod test_utils;
 
 mod node_sync;
+mod priority_semaphore;
 mod query_helpers;
@@ -0,0 +1,166 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use parking_lot::Mutex;
+use tokio::sync::oneshot;
+
+/// A semaphore whose waiters are let through by priority, highest first, rather than in arrival
+/// order. Waiters of equal priority are let through in arrival order. While permits are available
+/// it behaves like a plain semaphore, so priorities only matter once it is saturated.
+pub(crate) struct PrioritySemaphore {
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    available: usize,
+    waiting: BinaryHeap<Waiter>,
+    next_seq: u64,
+}
+
+struct Waiter {
+    priority: u64,
+    seq: u64,
+    wake: oneshot::Sender<()>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    /// The greatest waiter has the highest priority, and among those the lowest sequence number.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+/// Holds one permit of a [`PrioritySemaphore`] until dropped.
+pub(crate) struct PriorityPermit<'a> {
+    semaphore: &'a PrioritySemaphore,
+}
+
+impl Drop for PriorityPermit<'_> {
+    fn drop(&mut self) {
+        self.semaphore.release();
+    }
+}
+
+/// A waiter that may be dropped, e.g. because its task was cancelled, after being handed a
+/// permit but before taking it. The permit is then passed on.
+struct Waiting<'a> {
+    semaphore: &'a PrioritySemaphore,
+    woken: oneshot::Receiver<()>,
+}
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        if self.woken.try_recv().is_ok() {
+            self.semaphore.release();
+        }
+    }
+}
+
+impl PrioritySemaphore {
+    pub fn new(permits: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                available: permits,
+                waiting: BinaryHeap::new(),
+                next_seq: 0,
+            }),
+        }
+    }
+
+    pub async fn acquire(&self, priority: u64) -> PriorityPermit<'_> {
+        let woken = {
+            let mut inner = self.inner.lock();
+            if inner.available > 0 && inner.waiting.is_empty() {
+                inner.available -= 1;
+                return PriorityPermit { semaphore: self };
+            }
+            let (wake, woken) = oneshot::channel();
+            let seq = inner.next_seq;
+            inner.next_seq += 1;
+            inner.waiting.push(Waiter {
+                priority,
+                seq,
+                wake,
+            });
+            woken
+        };
+        let mut waiting = Waiting {
+            semaphore: self,
+            woken,
+        };
+        // Waiters are only removed from the queue to be woken, so the sender is never dropped
+        // without sending.
+        let _ = (&mut waiting.woken).await;
+        PriorityPermit { semaphore: self }
+    }
+
+    /// Hand the released permit to the highest priority waiter still waiting.
+    fn release(&self) {
+        let mut inner = self.inner.lock();
+        while let Some(waiter) = inner.waiting.pop() {
+            if waiter.wake.send(()).is_ok() {
+                return;
+            }
+        }
+        inner.available += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_priority_semaphore_lets_higher_priority_through_first() {
+        let semaphore = Arc::new(PrioritySemaphore::new(1));
+        let permit = semaphore.acquire(0).await;
+
+        // Queue waiters while saturated, in an order different from their priorities.
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(4);
+        let mut handles = Vec::new();
+        for (name, priority) in [("low", 1), ("high-1", 10), ("mid", 5), ("high-2", 10)] {
+            let semaphore = semaphore.clone();
+            let sender = sender.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = semaphore.acquire(priority).await;
+                sender.send(name).await.unwrap();
+            }));
+            // Let the waiter enqueue itself before the next one.
+            while semaphore.inner.lock().waiting.len() < handles.len() {
+                tokio::task::yield_now().await;
+            }
+        }
+        drop(permit);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        let mut order = Vec::new();
+        while let Ok(name) = receiver.try_recv() {
+            order.push(name);
+        }
+        // Equal priorities keep their arrival order.
+        assert_eq!(order, vec!["high-1", "high-2", "mid", "low"]);
+        assert_eq!(semaphore.inner.lock().available, 1);
+    }
+}
@@ -9,7 +9,7 @@ use sui_types::messages::{CertifiedTransaction, TransactionEffects};
 use sui_types::{base_types::*, batch::TxSequenceNumber, error::SuiError, fp_ensure};
 use tracing::debug;
 
-const MAX_TX_RANGE_SIZE: u64 = 4096;
+pub(crate) const MAX_TX_RANGE_SIZE: u64 = 4096;
 
 pub struct QueryHelpers<S> {
     _s: std::marker::PhantomData<S>,
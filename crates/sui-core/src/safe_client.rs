@@ -481,6 +481,15 @@ where
             .await
     }
 
+    pub async fn handle_account_info_page_request(
+        &self,
+        request: AccountInfoPageRequest,
+    ) -> Result<AccountInfoPageResponse, SuiError> {
+        self.authority_client
+            .handle_account_info_page_request(request)
+            .await
+    }
+
     pub async fn handle_object_info_request(
         &self,
         request: ObjectInfoRequest,
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::authority::SuiDataStore;
+use move_core_types::language_storage::TypeTag;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -15,31 +16,57 @@ use sui_types::{
         InputObjectKind, InputObjects, SingleTransactionKind, TransactionData, TransactionEnvelope,
     },
     object::{Object, Owner},
+    protocol_config::ProtocolConfig,
 };
 use tracing::instrument;
 
 #[instrument(level = "trace", skip_all)]
 pub async fn check_transaction_input<S, T>(
     store: &SuiDataStore<S>,
+    protocol_config: &ProtocolConfig,
     transaction: &TransactionEnvelope<T>,
 ) -> Result<(SuiGasStatus<'static>, InputObjects), SuiError>
 where
     S: Eq + Debug + Serialize + for<'de> Deserialize<'de>,
 {
-    transaction.signed_data.data.kind.validity_check()?;
+    check_transaction_data_input(store, protocol_config, &transaction.signed_data.data, &[]).await
+}
+
+/// Same as [`check_transaction_input`], for transaction data that has not been signed. Used to
+/// execute transactions locally without submitting them, e.g. for dry runs. The input objects
+/// with the ID and version of one of `overrides` are checked, and returned, as that object
+/// rather than the stored one.
+#[instrument(level = "trace", skip_all)]
+pub async fn check_transaction_data_input<S>(
+    store: &SuiDataStore<S>,
+    protocol_config: &ProtocolConfig,
+    data: &TransactionData,
+    overrides: &[Object],
+) -> Result<(SuiGasStatus<'static>, InputObjects), SuiError>
+where
+    S: Eq + Debug + Serialize + for<'de> Deserialize<'de>,
+{
+    data.kind.validity_check()?;
+    check_protocol_limits(protocol_config, data)?;
 
     let mut gas_status = check_gas(
         store,
-        transaction.gas_payment_object_ref().0,
-        transaction.signed_data.data.gas_budget,
-        transaction.signed_data.data.gas_price,
-        &transaction.signed_data.data.kind,
+        protocol_config,
+        data.gas_payment_object_ref().0,
+        data.gas_budget,
+        data.gas_price,
+        &data.kind,
+        overrides,
     )
     .await?;
 
-    let input_objects = check_objects(store, &transaction.signed_data.data).await?;
+    let input_objects = check_objects(store, protocol_config, data, overrides).await?;
 
-    if transaction.contains_shared_object() {
+    if data
+        .kind
+        .single_transactions()
+        .any(|s| s.contains_shared_object())
+    {
         // It's important that we do this here to make sure there is enough
         // gas to cover shared objects, before we lock all objects.
         gas_status.charge_consensus()?;
@@ -48,6 +75,69 @@ where
     Ok((gas_status, input_objects))
 }
 
+/// Check the transaction against the size limits of the protocol that do not depend on its
+/// input objects. The number of input objects is checked in [`check_objects`].
+fn check_protocol_limits(protocol_config: &ProtocolConfig, data: &TransactionData) -> SuiResult {
+    let size = bcs::serialized_size(data).expect("Transaction data must serialize") as u64;
+    fp_ensure!(
+        size <= protocol_config.max_tx_size_bytes,
+        SuiError::TransactionTooLarge {
+            size,
+            limit: protocol_config.max_tx_size_bytes,
+        }
+    );
+
+    let batch_size = data.kind.batch_size() as u64;
+    fp_ensure!(
+        batch_size <= protocol_config.max_transactions_in_batch,
+        SuiError::InvalidBatchTransaction {
+            error: format!(
+                "Batch transaction has {batch_size} transactions, more than the maximum of {}",
+                protocol_config.max_transactions_in_batch
+            ),
+        }
+    );
+
+    if let Some(max_depth) = protocol_config.max_type_argument_depth {
+        for single in data.kind.single_transactions() {
+            if let SingleTransactionKind::Call(call) = single {
+                for type_argument in &call.type_arguments {
+                    let depth = type_tag_depth(type_argument);
+                    fp_ensure!(
+                        depth <= max_depth,
+                        SuiError::TypeArgumentDepthExceeded {
+                            depth,
+                            limit: max_depth,
+                        }
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The nesting depth of `tag`, computed without recursion so that an over-deep tag cannot
+/// overflow the stack while being checked.
+fn type_tag_depth(tag: &TypeTag) -> u64 {
+    let mut max_depth = 0;
+    let mut stack = vec![(tag, 1)];
+    while let Some((tag, depth)) = stack.pop() {
+        max_depth = max_depth.max(depth);
+        match tag {
+            TypeTag::Vector(inner) => stack.push((inner.as_ref(), depth + 1)),
+            TypeTag::Struct(struct_tag) => stack.extend(
+                struct_tag
+                    .type_params
+                    .iter()
+                    .map(|param| (param, depth + 1)),
+            ),
+            _ => (),
+        }
+    }
+    max_depth
+}
+
 /// Checking gas budget by fetching the gas object only from the store,
 /// and check whether the balance and budget satisfies the miminum requirement.
 /// Returns the gas object (to be able to reuse it latter) and a gas status
@@ -55,10 +145,12 @@ where
 #[instrument(level = "trace", skip_all)]
 async fn check_gas<S>(
     store: &SuiDataStore<S>,
+    protocol_config: &ProtocolConfig,
     gas_payment_id: ObjectID,
     gas_budget: u64,
     computation_gas_price: u64,
     tx_kind: &TransactionKind,
+    overrides: &[Object],
 ) -> SuiResult<SuiGasStatus<'static>>
 where
     S: Eq + Debug + Serialize + for<'de> Deserialize<'de>,
@@ -66,7 +158,15 @@ where
     if tx_kind.is_system_tx() {
         Ok(SuiGasStatus::new_unmetered())
     } else {
-        let gas_object = store.get_object(&gas_payment_id)?;
+        fp_ensure!(
+            computation_gas_price >= protocol_config.min_gas_price,
+            SuiError::GasPriceTooLow {
+                gas_price: computation_gas_price,
+                min_gas_price: protocol_config.min_gas_price,
+            }
+        );
+
+        let gas_object = apply_override(store.get_object(&gas_payment_id)?, overrides);
         let gas_object = gas_object.ok_or(SuiError::ObjectNotFound {
             object_id: gas_payment_id,
         })?;
@@ -100,15 +200,28 @@ where
 #[instrument(level = "trace", skip_all)]
 async fn check_objects<S>(
     store: &SuiDataStore<S>,
+    protocol_config: &ProtocolConfig,
     transaction: &TransactionData,
+    overrides: &[Object],
 ) -> Result<InputObjects, SuiError>
 where
     S: Eq + Debug + Serialize + for<'de> Deserialize<'de>,
 {
     let input_objects = transaction.input_objects()?;
+    fp_ensure!(
+        input_objects.len() as u64 <= protocol_config.max_input_objects,
+        SuiError::TooManyInputObjects {
+            count: input_objects.len() as u64,
+            limit: protocol_config.max_input_objects,
+        }
+    );
 
     // These IDs act as authenticators that can own other objects.
-    let objects = store.get_input_objects(&input_objects)?;
+    let objects: Vec<_> = store
+        .get_input_objects(&input_objects)?
+        .into_iter()
+        .map(|object| apply_override(object, overrides))
+        .collect();
 
     // Constructing the list of objects that could be used to authenticate other
     // objects. Any mutable object (either shared or owned) can be used to
@@ -185,6 +298,17 @@ where
     Ok(InputObjects::new(all_objects))
 }
 
+/// Replace a stored object by the override with its ID and version, if any.
+fn apply_override(object: Option<Object>, overrides: &[Object]) -> Option<Object> {
+    object.map(|object| {
+        overrides
+            .iter()
+            .find(|o| o.id() == object.id() && o.version() == object.version())
+            .cloned()
+            .unwrap_or(object)
+    })
+}
+
 /// The logic to check one object against a reference, and return the object if all is well
 /// or an error if not.
 fn check_one_object(
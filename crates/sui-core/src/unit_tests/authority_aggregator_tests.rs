@@ -8,6 +8,7 @@ use move_core_types::{account_address::AccountAddress, ident_str};
 use move_package::BuildConfig;
 use signature::Signer;
 
+use sui_config::gateway::SubmitRetryConfig;
 use sui_config::genesis::Genesis;
 use sui_config::ValidatorInfo;
 use sui_types::crypto::{
@@ -20,7 +21,7 @@ use sui_types::messages::*;
 use sui_types::object::{Object, GAS_VALUE_FOR_TESTING};
 
 use super::*;
-use crate::authority::AuthorityState;
+use crate::authority::{AuthorityState, SimulatedFailure};
 use crate::authority_client::{
     AuthorityAPI, BatchInfoResponseItemStream, LocalAuthorityClient,
     LocalAuthorityClientFaultConfig,
@@ -613,6 +614,36 @@ async fn test_get_all_owned_objects() {
     assert_eq!(1, owned_object.len());
 }
 
+#[tokio::test]
+async fn test_get_owned_object_ids_page() {
+    let (addr1, _): (_, AccountKeyPair) = get_key_pair();
+    let (addr2, _): (_, AccountKeyPair) = get_key_pair();
+    let mut objects: Vec<_> = (0..5)
+        .map(|_| Object::with_owner_for_testing(addr1))
+        .collect();
+    objects.push(Object::with_owner_for_testing(addr2));
+    let mut owned_ids: Vec<_> = objects[..5].iter().map(|object| object.id()).collect();
+    owned_ids.sort();
+
+    let (authorities, _, _) = init_local_authorities(4, objects).await;
+
+    let mut paged_ids = vec![];
+    let mut cursor = None;
+    loop {
+        let (object_ids, next_cursor) = authorities
+            .get_owned_object_ids_page(addr1, cursor, 2, None, Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert!(object_ids.len() <= 2);
+        paged_ids.extend(object_ids);
+        cursor = next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(paged_ids, owned_ids);
+}
+
 #[tokio::test]
 async fn test_sync_all_owned_objects() {
     let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
@@ -882,6 +913,293 @@ async fn test_process_transaction_fault_fail() {
     .is_err());
 }
 
+#[tokio::test]
+async fn test_handle_transaction_with_authority() {
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let (addr2, _): (_, AccountKeyPair) = get_key_pair();
+    let gas_object1 = Object::with_owner_for_testing(addr1);
+    let gas_object2 = Object::with_owner_for_testing(addr1);
+    let (mut authorities, _, _) =
+        init_local_authorities(4, vec![gas_object1.clone(), gas_object2.clone()]).await;
+    let names: Vec<_> = authorities.authority_clients.keys().cloned().collect();
+
+    let tx = transfer_coin_transaction(
+        addr1,
+        &key1,
+        addr2,
+        gas_object1.compute_object_reference(),
+        gas_object2.compute_object_reference(),
+    );
+
+    // Only the targeted authority signs the transaction, no certificate is formed.
+    let response = authorities
+        .handle_transaction_with_authority(&names[0], tx.clone())
+        .await
+        .unwrap();
+    assert!(response.signed_transaction.is_some());
+    assert!(response.certified_transaction.is_none());
+    for (i, client) in authorities.authority_clients.values().enumerate() {
+        let info = client
+            .handle_transaction_info_request((*tx.digest()).into())
+            .await
+            .unwrap();
+        assert_eq!(info.signed_transaction.is_some(), i == 0);
+    }
+
+    // Errors from the targeted authority are returned as is.
+    get_local_client(&mut authorities, 1)
+        .fault_config
+        .fail_before_handle_transaction = true;
+    assert_eq!(
+        authorities
+            .handle_transaction_with_authority(&names[1], tx.clone())
+            .await
+            .unwrap_err(),
+        SuiError::GenericAuthorityError {
+            error: "Mock error before handle_transaction".to_owned()
+        }
+    );
+
+    // Authorities outside of the committee are rejected.
+    let unknown: AuthorityName = get_key_pair::<AuthorityKeyPair>().1.public().into();
+    assert!(authorities
+        .handle_transaction_with_authority(&unknown, tx)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_quorum_with_flaky_authority() {
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let (addr2, _): (_, AccountKeyPair) = get_key_pair();
+    let gas_objects: Vec<_> = (0..4)
+        .map(|_| Object::with_owner_for_testing(addr1))
+        .collect();
+    let (authorities, states, _) = init_local_authorities(4, gas_objects.clone()).await;
+    let transfer = |object: &Object, gas: &Object| {
+        transfer_coin_transaction(
+            addr1,
+            &key1,
+            addr2,
+            object.compute_object_reference(),
+            gas.compute_object_reference(),
+        )
+    };
+
+    // One authority fails every other transaction, and another one is slow.
+    states[0].set_simulated_failure(Some(SimulatedFailure {
+        failure_rate: 0.5,
+        error: SuiError::from("Simulated failure"),
+        latency: Duration::ZERO,
+    }));
+    states[1].set_simulated_failure(Some(SimulatedFailure {
+        failure_rate: 0.0,
+        error: SuiError::from("Simulated failure"),
+        latency: Duration::from_millis(500),
+    }));
+    for (object, gas) in [
+        (&gas_objects[0], &gas_objects[1]),
+        (&gas_objects[2], &gas_objects[3]),
+    ] {
+        let cert = authorities
+            .process_transaction(transfer(object, gas))
+            .await
+            .unwrap();
+        authorities.process_certificate(cert).await.unwrap();
+    }
+
+    // Once a second authority fails, no quorum can be formed.
+    let (object, gas) = (
+        Object::with_owner_for_testing(addr1),
+        Object::with_owner_for_testing(addr1),
+    );
+    for state in &states {
+        state.insert_genesis_object(object.clone()).await;
+        state.insert_genesis_object(gas.clone()).await;
+    }
+    for state in &states[..2] {
+        state.set_simulated_failure(Some(SimulatedFailure {
+            failure_rate: 1.0,
+            error: SuiError::from("Simulated failure"),
+            latency: Duration::ZERO,
+        }));
+    }
+    let tx = transfer(&object, &gas);
+    assert!(authorities.process_transaction(tx.clone()).await.is_err());
+
+    // And transactions go through again once the authorities recover.
+    for state in &states {
+        state.set_simulated_failure(None);
+    }
+    authorities.process_transaction(tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_submit_retries_on_network_errors() {
+    #[derive(Clone)]
+    struct FlakyAuthorityClient {
+        inner: LocalAuthorityClient,
+        error: SuiError,
+        // How many of the next submissions fail with `error`.
+        failures: Arc<Mutex<usize>>,
+        submissions: Arc<Mutex<usize>>,
+    }
+
+    impl FlakyAuthorityClient {
+        fn submit(&self) -> Result<(), SuiError> {
+            *self.submissions.lock().unwrap() += 1;
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(self.error.clone());
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AuthorityAPI for FlakyAuthorityClient {
+        async fn handle_transaction(
+            &self,
+            transaction: Transaction,
+        ) -> Result<TransactionInfoResponse, SuiError> {
+            self.submit()?;
+            self.inner.handle_transaction(transaction).await
+        }
+
+        async fn handle_certificate(
+            &self,
+            certificate: CertifiedTransaction,
+        ) -> Result<TransactionInfoResponse, SuiError> {
+            self.submit()?;
+            self.inner.handle_certificate(certificate).await
+        }
+
+        async fn handle_account_info_request(
+            &self,
+            request: AccountInfoRequest,
+        ) -> Result<AccountInfoResponse, SuiError> {
+            self.inner.handle_account_info_request(request).await
+        }
+
+        async fn handle_account_info_page_request(
+            &self,
+            request: AccountInfoPageRequest,
+        ) -> Result<AccountInfoPageResponse, SuiError> {
+            self.inner.handle_account_info_page_request(request).await
+        }
+
+        async fn handle_object_info_request(
+            &self,
+            request: ObjectInfoRequest,
+        ) -> Result<ObjectInfoResponse, SuiError> {
+            self.inner.handle_object_info_request(request).await
+        }
+
+        async fn handle_transaction_info_request(
+            &self,
+            request: TransactionInfoRequest,
+        ) -> Result<TransactionInfoResponse, SuiError> {
+            self.inner.handle_transaction_info_request(request).await
+        }
+
+        async fn handle_batch_stream(
+            &self,
+            request: BatchInfoRequest,
+        ) -> Result<BatchInfoResponseItemStream, SuiError> {
+            self.inner.handle_batch_stream(request).await
+        }
+
+        async fn handle_checkpoint(
+            &self,
+            request: CheckpointRequest,
+        ) -> Result<CheckpointResponse, SuiError> {
+            self.inner.handle_checkpoint(request).await
+        }
+
+        async fn handle_epoch(&self, request: EpochRequest) -> Result<EpochResponse, SuiError> {
+            self.inner.handle_epoch(request).await
+        }
+    }
+
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let (addr2, _): (_, AccountKeyPair) = get_key_pair();
+    let gas_objects: Vec<_> = (0..4)
+        .map(|_| Object::with_owner_for_testing(addr1))
+        .collect();
+    let (authorities, _, _) = init_local_authorities(4, gas_objects.clone()).await;
+    let transfer = |object: &Object, gas: &Object| {
+        transfer_coin_transaction(
+            addr1,
+            &key1,
+            addr2,
+            object.compute_object_reference(),
+            gas.compute_object_reference(),
+        )
+    };
+    // Every authority fails its first `failures` submissions with `error`.
+    let flaky_authorities = |error: &SuiError, failures: usize, max_retries: u32| {
+        let submissions = Arc::new(Mutex::new(0));
+        let clients = authorities
+            .authority_clients
+            .iter()
+            .map(|(name, client)| {
+                let client = FlakyAuthorityClient {
+                    inner: client.authority_client().clone(),
+                    error: error.clone(),
+                    failures: Arc::new(Mutex::new(failures)),
+                    submissions: submissions.clone(),
+                };
+                (*name, client)
+            })
+            .collect();
+        let mut aggregator = AuthorityAggregator::new_with_timeouts(
+            authorities.committee.clone(),
+            Arc::new(EpochStore::new_for_testing(&authorities.committee)),
+            clients,
+            AuthAggMetrics::new_for_tests(),
+            SafeClientMetrics::new_for_tests(),
+            authorities.timeouts.clone(),
+        );
+        aggregator.submit_retry = SubmitRetryConfig {
+            max_retries,
+            base_delay: Duration::from_millis(10),
+        };
+        (aggregator, submissions)
+    };
+    let network_error = SuiError::RpcError(
+        "Connection reset".to_owned(),
+        sui_network::tonic::Code::Unavailable.description(),
+        None,
+    );
+
+    // Network errors are retried until the authorities answer.
+    let (aggregator, _) = flaky_authorities(&network_error, 2, 3);
+    let cert = aggregator
+        .process_transaction(transfer(&gas_objects[0], &gas_objects[1]))
+        .await
+        .unwrap();
+    aggregator.process_certificate(cert).await.unwrap();
+
+    // But only up to the configured number of retries.
+    let (aggregator, _) = flaky_authorities(&network_error, 2, 1);
+    assert!(aggregator
+        .process_transaction(transfer(&gas_objects[2], &gas_objects[3]))
+        .await
+        .is_err());
+
+    // Errors returned by the authorities are never retried.
+    let authority_error = SuiError::GenericAuthorityError {
+        error: "Authority error".to_owned(),
+    };
+    let (aggregator, submissions) = flaky_authorities(&authority_error, 1, 3);
+    assert!(aggregator
+        .process_transaction(transfer(&gas_objects[2], &gas_objects[3]))
+        .await
+        .is_err());
+    assert!(*submissions.lock().unwrap() <= 4);
+}
+
 #[tokio::test(start_paused = true)]
 async fn test_quorum_once_with_timeout() {
     telemetry_subscribers::init_for_testing();
@@ -918,6 +1236,13 @@ async fn test_quorum_once_with_timeout() {
             unreachable!();
         }
 
+        async fn handle_account_info_page_request(
+            &self,
+            _request: AccountInfoPageRequest,
+        ) -> Result<AccountInfoPageResponse, SuiError> {
+            unreachable!();
+        }
+
         /// Handle Object information requests for this account.
         async fn handle_object_info_request(
             &self,
@@ -1045,3 +1370,179 @@ async fn test_quorum_once_with_timeout() {
             .collect::<Vec<Duration>>()
     );
 }
+
+#[tokio::test]
+async fn test_equivocation_detected() {
+    /// Signs every transaction it is given, without locking the objects it takes as input.
+    #[derive(Clone)]
+    struct EquivocatingAuthority {
+        name: AuthorityName,
+        secret: Arc<AuthorityKeyPair>,
+    }
+
+    #[async_trait]
+    impl AuthorityAPI for EquivocatingAuthority {
+        async fn handle_transaction(
+            &self,
+            transaction: Transaction,
+        ) -> Result<TransactionInfoResponse, SuiError> {
+            Ok(TransactionInfoResponse {
+                signed_transaction: Some(SignedTransaction::new(
+                    0,
+                    transaction,
+                    self.name,
+                    &*self.secret,
+                )),
+                certified_transaction: None,
+                signed_effects: None,
+            })
+        }
+
+        async fn handle_certificate(
+            &self,
+            _certificate: CertifiedTransaction,
+        ) -> Result<TransactionInfoResponse, SuiError> {
+            unreachable!()
+        }
+
+        async fn handle_account_info_request(
+            &self,
+            _request: AccountInfoRequest,
+        ) -> Result<AccountInfoResponse, SuiError> {
+            unreachable!();
+        }
+
+        async fn handle_account_info_page_request(
+            &self,
+            _request: AccountInfoPageRequest,
+        ) -> Result<AccountInfoPageResponse, SuiError> {
+            unreachable!();
+        }
+
+        async fn handle_object_info_request(
+            &self,
+            _request: ObjectInfoRequest,
+        ) -> Result<ObjectInfoResponse, SuiError> {
+            unreachable!();
+        }
+
+        async fn handle_transaction_info_request(
+            &self,
+            _request: TransactionInfoRequest,
+        ) -> Result<TransactionInfoResponse, SuiError> {
+            unreachable!();
+        }
+
+        async fn handle_batch_stream(
+            &self,
+            _request: BatchInfoRequest,
+        ) -> Result<BatchInfoResponseItemStream, SuiError> {
+            unreachable!();
+        }
+
+        async fn handle_checkpoint(
+            &self,
+            _request: CheckpointRequest,
+        ) -> Result<CheckpointResponse, SuiError> {
+            unreachable!();
+        }
+
+        async fn handle_epoch(&self, _request: EpochRequest) -> Result<EpochResponse, SuiError> {
+            unreachable!()
+        }
+    }
+
+    let mut authorities = BTreeMap::new();
+    let mut clients = BTreeMap::new();
+    for _ in 0..4 {
+        let (_, secret): (_, AuthorityKeyPair) = get_key_pair();
+        let name: AuthorityName = secret.public().into();
+        authorities.insert(name, 1);
+        clients.insert(
+            name,
+            EquivocatingAuthority {
+                name,
+                secret: Arc::new(secret),
+            },
+        );
+    }
+    let committee = Committee::new(0, authorities).unwrap();
+    let epoch_store = Arc::new(EpochStore::new_for_testing(&committee));
+    let agg = AuthorityAggregator::new(
+        committee.clone(),
+        epoch_store,
+        clients,
+        AuthAggMetrics::new_for_tests(),
+        SafeClientMetrics::new_for_tests(),
+    );
+
+    // Two transfers of different objects, paying with the same version of the same gas object,
+    // then a transfer of the first object paying with another gas object.
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas = Object::with_owner_for_testing(sender).compute_object_reference();
+    let other_gas = Object::with_owner_for_testing(sender).compute_object_reference();
+    let object = Object::with_owner_for_testing(sender).compute_object_reference();
+    let transfer = |object_ref: ObjectRef, gas: ObjectRef| {
+        let data = TransactionData::new_transfer(dbg_addr(2), object_ref, sender, gas, 1000);
+        let signature = Signature::new(&data, &sender_key);
+        Transaction::new(data, signature)
+    };
+    let tx1 = transfer(object, gas);
+    let tx2 = transfer(
+        Object::with_owner_for_testing(sender).compute_object_reference(),
+        gas,
+    );
+    let tx3 = transfer(object, other_gas);
+
+    agg.process_transaction(tx1.clone()).await.unwrap();
+    assert!(agg.equivocations().is_empty());
+    agg.process_transaction(tx2.clone()).await.unwrap();
+    agg.process_transaction(tx3.clone()).await.unwrap();
+
+    // Each quorum holds at least 3 of the 4 authorities, so at least 2 signed both transactions
+    // of each conflicting pair.
+    let equivocations = agg.equivocations();
+    assert_eq!(
+        agg.metrics.total_equivocations_detected.get(),
+        equivocations.len() as u64
+    );
+    for equivocation in &equivocations {
+        equivocation.verify(&committee).unwrap();
+        assert_eq!(equivocation.epoch, 0);
+        assert_eq!(equivocation.first.digest(), tx1.digest());
+    }
+    let conflicts_over = |object_ref: ObjectRef, second: &Transaction| {
+        equivocations
+            .iter()
+            .filter(|e| e.object_ref == object_ref && e.second.digest() == second.digest())
+            .count()
+    };
+    assert!(conflicts_over(gas, &tx2) >= 2);
+    assert!(conflicts_over(object, &tx3) >= 2);
+}
+
+#[test]
+fn test_equivocation_detector_keys_locks_by_epoch() {
+    let (_, secret): (_, AuthorityKeyPair) = get_key_pair();
+    let authority: AuthorityName = secret.public().into();
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas = Object::with_owner_for_testing(sender).compute_object_reference();
+    let sign = |epoch| {
+        let object_ref = Object::with_owner_for_testing(sender).compute_object_reference();
+        let data = TransactionData::new_transfer(dbg_addr(2), object_ref, sender, gas, 1000);
+        let signature = Signature::new(&data, &sender_key);
+        SignedTransaction::new(epoch, Transaction::new(data, signature), authority, &secret)
+    };
+
+    let detector = EquivocationDetector::default();
+    assert!(detector.observe_lock(authority, gas, &sign(0)).is_none());
+    // Locks are released at the end of the epoch, so signing again in the next one is honest.
+    assert!(detector.observe_lock(authority, gas, &sign(1)).is_none());
+    let equivocation = detector.observe_lock(authority, gas, &sign(1)).unwrap();
+    assert_eq!(equivocation.epoch, 1);
+    assert!(equivocation.is_known_owned());
+    // Conflicts are only kept once recorded.
+    assert!(detector.equivocations().is_empty());
+    detector.record(equivocation);
+    assert_eq!(detector.equivocations().len(), 1);
+}
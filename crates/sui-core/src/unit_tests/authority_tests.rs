@@ -12,6 +12,7 @@ use move_core_types::{
     account_address::AccountAddress, ident_str, identifier::Identifier, language_storage::TypeTag,
 };
 use narwhal_executor::ExecutionIndices;
+use prometheus::{core::Collector, Opts};
 use rand::{
     distributions::{Distribution, Uniform},
     prelude::StdRng,
@@ -29,12 +30,18 @@ use sui_types::{
     base_types::dbg_addr,
     crypto::{get_key_pair, Signature},
     crypto::{AccountKeyPair, AuthorityKeyPair, KeypairTraits},
+    gas_coin::{GasCoin, GAS},
     messages::Transaction,
-    object::{Owner, GAS_VALUE_FOR_TESTING, OBJECT_START_VERSION},
+    object::{MoveObject, Owner, GAS_VALUE_FOR_TESTING, OBJECT_START_VERSION},
+    protocol_config::GENESIS_PROTOCOL_VERSION,
     sui_system_state::SuiSystemState,
-    SUI_SYSTEM_STATE_OBJECT_ID,
+    MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
+};
+use sui_types::{
+    crypto::AuthorityPublicKeyBytes, messages_checkpoint::CheckpointContents, object::Data,
 };
-use sui_types::{crypto::AuthorityPublicKeyBytes, object::Data};
+
+use crate::authority_active::execution_driver::PendCertificateForExecutionNoop;
 
 use tracing::info;
 
@@ -745,6 +752,117 @@ async fn test_handle_transfer_transaction_double_spend() {
     compare_transaction_info_responses(&signed_transaction, &double_spend_signed_transaction);
 }
 
+#[tokio::test]
+async fn test_handle_transaction_expiration_epoch() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let ids: Vec<_> = (0..6).map(|_| (sender, ObjectID::random())).collect();
+    let authority_state = init_state_with_ids(ids.clone()).await;
+    let mut object_refs = vec![];
+    for (_, id) in &ids {
+        let object = authority_state.get_object(id).await.unwrap().unwrap();
+        object_refs.push(object.compute_object_reference());
+    }
+    let transfer_expiring_at = |refs: &[ObjectRef], expiration_epoch| {
+        let mut data = TransactionData::new_transfer(recipient, refs[0], sender, refs[1], 10000);
+        data.expiration_epoch = expiration_epoch;
+        let signature = Signature::new(&data, &sender_key);
+        Transaction::new(data, signature)
+    };
+
+    // A transaction may be signed up to and including its expiration epoch.
+    authority_state
+        .handle_transaction(transfer_expiring_at(&object_refs[0..2], Some(0)))
+        .await
+        .unwrap();
+
+    let mut committee = authority_state.clone_committee();
+    let voting_rights = committee.voting_rights.drain(..).collect();
+    authority_state
+        .sign_new_epoch_and_update_committee(Committee::new(1, voting_rights).unwrap(), 0)
+        .unwrap();
+
+    let result = authority_state
+        .handle_transaction(transfer_expiring_at(&object_refs[2..4], Some(0)))
+        .await;
+    assert!(matches!(
+        result,
+        Err(SuiError::TransactionExpired {
+            expiration_epoch: 0,
+            current_epoch: 1,
+        })
+    ));
+    authority_state
+        .handle_transaction(transfer_expiring_at(&object_refs[2..4], Some(1)))
+        .await
+        .unwrap();
+
+    // Transactions without an expiration are unaffected.
+    authority_state
+        .handle_transaction(transfer_expiring_at(&object_refs[4..6], None))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_handle_transfer_transaction_conflicting_lock_metrics() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let transfer_transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        dbg_addr(2),
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+    let conflicting_transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        dbg_addr(3),
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+
+    let rejected = authority_state
+        .metrics
+        .tx_rejected
+        .with_label_values(&["conflicting_lock"]);
+    let rejected_before = rejected.get();
+
+    authority_state
+        .handle_transaction(transfer_transaction)
+        .await
+        .unwrap();
+    assert_eq!(rejected.get(), rejected_before);
+
+    let result = authority_state
+        .handle_transaction(conflicting_transaction)
+        .await;
+    assert!(result.is_err());
+    assert_eq!(rejected.get(), rejected_before + 1);
+    assert_eq!(
+        authority_state
+            .metrics
+            .tx_rejected
+            .with_label_values(&["invalid_signature"])
+            .get(),
+        0
+    );
+}
+
 #[tokio::test]
 async fn test_handle_transfer_sui_with_amount_insufficient_gas() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -1013,6 +1131,204 @@ async fn test_handle_confirmation_transaction_ok() {
     );
 }
 
+#[tokio::test]
+async fn test_verify_and_execute_certificate() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // A certificate whose sender signature does not verify.
+    let (_unknown_address, unknown_key): (_, AccountKeyPair) = get_key_pair();
+    let mut bad_signature_certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    bad_signature_certificate.signed_data.tx_signature =
+        Signature::new(&bad_signature_certificate.signed_data.data, &unknown_key);
+    assert!(matches!(
+        authority_state
+            .verify_and_execute_certificate(bad_signature_certificate)
+            .await,
+        Err(CertificateExecutionError::Verify(_))
+    ));
+
+    // A valid certificate transferring an object the authority does not know.
+    let unknown_object_certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        (
+            ObjectID::random(),
+            OBJECT_START_VERSION,
+            ObjectDigest::random(),
+        ),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    assert!(matches!(
+        authority_state
+            .verify_and_execute_certificate(unknown_object_certificate)
+            .await,
+        Err(CertificateExecutionError::Execute(_))
+    ));
+
+    let certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    let info = authority_state
+        .verify_and_execute_certificate(certificate)
+        .await
+        .unwrap();
+    info.signed_effects.unwrap().effects.status.unwrap();
+}
+
+#[tokio::test]
+async fn test_get_owner_at_version() {
+    let TransferFixture {
+        authority_state,
+        sender,
+        recipient,
+        object,
+        certificate: certified_transfer_transaction,
+        ..
+    } = init_transfer_fixture().await;
+    let object_id = object.id();
+    let initial_version = object.version();
+    authority_state
+        .handle_certificate(certified_transfer_transaction)
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap()
+        .effects
+        .status
+        .unwrap();
+    let transferred_version = initial_version.increment();
+
+    // The object did not exist before its initial version.
+    assert_eq!(
+        authority_state
+            .get_owner_at_version(object_id, SequenceNumber::from(0))
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        authority_state
+            .get_owner_at_version(object_id, initial_version)
+            .await
+            .unwrap(),
+        Some(Owner::AddressOwner(sender))
+    );
+    assert_eq!(
+        authority_state
+            .get_owner_at_version(object_id, transferred_version)
+            .await
+            .unwrap(),
+        Some(Owner::AddressOwner(recipient))
+    );
+    // Versions after the latest one are not known yet.
+    assert!(matches!(
+        authority_state
+            .get_owner_at_version(object_id, transferred_version.increment())
+            .await,
+        Err(SuiError::ObjectVersionNotYetKnown { latest_version, .. })
+            if latest_version == transferred_version
+    ));
+
+    // Once the historical object state is gone, the owner is reported as unavailable.
+    authority_state
+        .database
+        .tables
+        .objects
+        .remove(&object.compute_object_reference().into())
+        .unwrap();
+    assert!(matches!(
+        authority_state
+            .get_owner_at_version(object_id, initial_version)
+            .await,
+        Err(SuiError::ObjectVersionPruned { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_prune_object_versions_retains_effects() {
+    let TransferFixture {
+        authority_state,
+        object,
+        gas_object,
+        certificate: certified_transfer_transaction,
+        ..
+    } = init_transfer_fixture().await;
+    let object_id = object.id();
+    let gas_object_id = gas_object.id();
+    let digest = *certified_transfer_transaction.digest();
+    authority_state
+        .handle_certificate(certified_transfer_transaction)
+        .await
+        .unwrap();
+    let database = &authority_state.database;
+
+    // The certificate could still be executed again while its gas input is stored.
+    assert_eq!(database.prune_effects(0).unwrap(), 0);
+    assert!(database.effects_exists(&digest).unwrap());
+    // Pruning resumes from the kept transaction.
+    let seq = database.next_sequence_number().unwrap() - 1;
+    assert_eq!(database.effects_pruning_watermark().unwrap(), seq);
+
+    // Only the versions the transfer took as input are pruned.
+    assert_eq!(database.prune_object_versions(1).unwrap(), 2);
+    for (id, version) in [
+        (object_id, object.version()),
+        (gas_object_id, gas_object.version()),
+    ] {
+        assert!(database.get_object_by_key(&id, version).unwrap().is_none());
+        assert!(database
+            .get_object_by_key(&id, version.increment())
+            .unwrap()
+            .is_some());
+    }
+
+    // The effects and certificate remain queryable.
+    assert_eq!(
+        database.get_effects(&digest).unwrap().transaction_digest,
+        digest
+    );
+    assert!(database.read_certificate(&digest).unwrap().is_some());
+
+    // Once the gas input is gone they can be pruned as well.
+    assert_eq!(database.prune_effects(0).unwrap(), 1);
+    assert!(!database.effects_exists(&digest).unwrap());
+    assert!(database.read_certificate(&digest).unwrap().is_none());
+    assert_eq!(database.effects_pruning_watermark().unwrap(), seq + 1);
+
+    // Pruned transactions are not examined again.
+    assert_eq!(database.prune_effects(0).unwrap(), 0);
+    assert_eq!(database.effects_pruning_watermark().unwrap(), seq + 1);
+}
+
 struct LimitedPoll<F: Future> {
     inner: Pin<Box<F>>,
     count: u64,
@@ -1189,79 +1505,909 @@ async fn test_handle_confirmation_transaction_idempotent() {
 }
 
 #[tokio::test]
-async fn test_move_call_mutable_object_not_mutated() {
-    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
-    let gas_object_id = ObjectID::random();
-    let (authority_state, pkg_ref) =
-        init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
-
-    let effects = create_move_object(
-        &pkg_ref,
-        &authority_state,
-        &gas_object_id,
-        &sender,
-        &sender_key,
-    )
-    .await
-    .unwrap();
-    assert!(effects.status.is_ok());
-    assert_eq!((effects.created.len(), effects.mutated.len()), (1, 1));
-    let (new_object_id1, seq1, _) = effects.created[0].0;
-
-    let effects = create_move_object(
-        &pkg_ref,
-        &authority_state,
-        &gas_object_id,
-        &sender,
-        &sender_key,
-    )
-    .await
-    .unwrap();
-    assert!(effects.status.is_ok());
-    assert_eq!((effects.created.len(), effects.mutated.len()), (1, 1));
-    let (new_object_id2, seq2, _) = effects.created[0].0;
+async fn test_handle_transaction_info_batch_request() {
+    let TransferFixture {
+        authority_state,
+        certificate: certified_transfer_transaction,
+        ..
+    } = init_transfer_fixture().await;
+    let digest = *certified_transfer_transaction.digest();
+    authority_state
+        .handle_certificate(certified_transfer_transaction)
+        .await
+        .unwrap();
 
-    let effects = call_move(
-        &authority_state,
-        &gas_object_id,
-        &sender,
-        &sender_key,
-        &pkg_ref,
-        "object_basics",
-        "update",
-        vec![],
-        vec![
-            TestCallArg::Object(new_object_id1),
-            TestCallArg::Object(new_object_id2),
-        ],
-    )
-    .await
-    .unwrap();
-    assert!(effects.status.is_ok());
-    assert_eq!((effects.created.len(), effects.mutated.len()), (0, 3));
-    // Verify that both objects' version increased, even though only one object was updated.
+    let unknown = TransactionDigest::random();
+    let responses = authority_state
+        .handle_transaction_info_batch_request(TransactionInfoBatchRequest {
+            digests: vec![unknown, digest, unknown],
+        })
+        .await
+        .unwrap();
+    assert_eq!(responses.len(), 3);
     assert_eq!(
-        authority_state
-            .get_object(&new_object_id1)
-            .await
-            .unwrap()
+        responses[1]
+            .signed_effects
+            .as_ref()
             .unwrap()
-            .version(),
-        seq1.increment()
+            .effects
+            .transaction_digest,
+        digest
     );
+    for response in [&responses[0], &responses[2]] {
+        assert!(response.signed_transaction.is_none());
+        assert!(response.certified_transaction.is_none());
+        assert!(response.signed_effects.is_none());
+    }
+
+    let result = authority_state
+        .handle_transaction_info_batch_request(TransactionInfoBatchRequest {
+            digests: vec![digest; MAX_TRANSACTION_INFO_BATCH_SIZE + 1],
+        })
+        .await;
+    assert!(matches!(result, Err(SuiError::TooManyItemsError { .. })));
     assert_eq!(
-        authority_state
-            .get_object(&new_object_id2)
-            .await
-            .unwrap()
-            .unwrap()
-            .version(),
-        seq2.increment()
+        sui_network::tonic::Status::from(result.unwrap_err()).code(),
+        sui_network::tonic::Code::ResourceExhausted
     );
 }
 
 #[tokio::test]
-async fn test_move_call_insufficient_gas() {
+async fn test_handle_account_info_request_pages() {
+    let (sender, _): (_, AccountKeyPair) = get_key_pair();
+    let mut object_ids: Vec<_> = (0..5).map(|_| ObjectID::random()).collect();
+    let authority_state = init_state_with_ids(object_ids.iter().map(|id| (sender, *id))).await;
+    object_ids.sort();
+
+    // Account info requests return all objects at once.
+    let response = authority_state
+        .handle_account_info_request(sender.into())
+        .await
+        .unwrap();
+    let mut ids: Vec<_> = response.object_ids.iter().map(|oref| oref.0).collect();
+    ids.sort();
+    assert_eq!(ids, object_ids);
+
+    let mut paged_ids = vec![];
+    let mut cursor = None;
+    loop {
+        let response = authority_state
+            .handle_account_info_page_request(AccountInfoPageRequest {
+                account: sender,
+                cursor,
+                limit: 2,
+                type_filter: None,
+            })
+            .await
+            .unwrap();
+        assert!(response.object_ids.len() <= 2);
+        paged_ids.extend(response.object_ids.iter().map(|oref| oref.0));
+        cursor = response.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(paged_ids, object_ids);
+
+    let result = authority_state
+        .handle_account_info_page_request(AccountInfoPageRequest {
+            account: sender,
+            cursor: None,
+            limit: 0,
+            type_filter: None,
+        })
+        .await;
+    assert!(matches!(result, Err(SuiError::ZeroPageLimit)));
+}
+
+#[tokio::test]
+async fn test_handle_account_info_page_request_by_type() {
+    let (sender, _): (_, AccountKeyPair) = get_key_pair();
+    let object_of_type = |type_: &str| {
+        let id = ObjectID::random();
+        let object = unsafe {
+            sui_types::object::MoveObject::new_from_execution(
+                sui_types::parse_sui_struct_tag(type_).unwrap(),
+                true,
+                OBJECT_START_VERSION,
+                None,
+                GasCoin::new(id, GAS_VALUE_FOR_TESTING).to_bcs_bytes(),
+            )
+        };
+        Object::new_move(
+            object,
+            Owner::AddressOwner(sender),
+            TransactionDigest::genesis(),
+        )
+    };
+    let gas_coins = vec![
+        Object::with_owner_for_testing(sender),
+        Object::with_owner_for_testing(sender),
+    ];
+    let other_coin = object_of_type("0x2::example::Coin<0x2::example::EXAMPLE>");
+    let nft = object_of_type("0x2::devnet_nft::DevNetNFT");
+    let authority_state = init_state_with_objects(
+        gas_coins
+            .iter()
+            .chain([&other_coin, &nft])
+            .cloned()
+            .collect::<Vec<_>>(),
+    )
+    .await;
+
+    // Pages only hold objects of the type.
+    let object_ids_of_type = |type_: &str| {
+        let type_filter = sui_types::parse_sui_struct_tag(type_).unwrap();
+        let authority_state = &authority_state;
+        async move {
+            let mut ids = vec![];
+            let mut cursor = None;
+            loop {
+                let response = authority_state
+                    .handle_account_info_page_request(AccountInfoPageRequest {
+                        account: sender,
+                        cursor,
+                        limit: 1,
+                        type_filter: Some(type_filter.clone()),
+                    })
+                    .await
+                    .unwrap();
+                assert!(response.object_ids.len() <= 1);
+                ids.extend(response.object_ids.iter().map(|oref| oref.0));
+                cursor = response.next_cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+            ids
+        }
+    };
+    let mut gas_coin_ids: Vec<_> = gas_coins.iter().map(|object| object.id()).collect();
+    gas_coin_ids.sort();
+
+    // A type with type arguments only matches that instantiation.
+    assert_eq!(
+        object_ids_of_type("0x2::coin::Coin<0x2::sui::SUI>").await,
+        gas_coin_ids
+    );
+    // A type without type arguments matches every instantiation.
+    assert_eq!(object_ids_of_type("0x2::coin::Coin").await, gas_coin_ids);
+    assert_eq!(
+        object_ids_of_type("0x2::example::Coin").await,
+        vec![other_coin.id()]
+    );
+    assert_eq!(
+        object_ids_of_type("0x2::devnet_nft::DevNetNFT").await,
+        vec![nft.id()]
+    );
+    assert!(object_ids_of_type("0x2::devnet_nft::Other")
+        .await
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_handle_events_by_transaction_request() {
+    let TransferFixture {
+        authority_state,
+        object,
+        certificate: certified_transfer_transaction,
+        ..
+    } = init_transfer_fixture().await;
+    let object_id = object.id();
+    let digest = *certified_transfer_transaction.digest();
+    let effects = authority_state
+        .handle_certificate(certified_transfer_transaction)
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap()
+        .effects;
+
+    let events = authority_state
+        .handle_events_by_transaction_request(EventsByTransactionRequest {
+            transaction_digest: digest,
+        })
+        .await
+        .unwrap();
+    assert_eq!(events, effects.events);
+    assert!(events.iter().any(|event| matches!(
+        event,
+        Event::TransferObject { object_id: id, .. } if *id == object_id
+    )));
+
+    let result = authority_state
+        .handle_events_by_transaction_request(EventsByTransactionRequest {
+            transaction_digest: TransactionDigest::random(),
+        })
+        .await;
+    assert!(matches!(result, Err(SuiError::TransactionNotFound { .. })));
+    // An unknown transaction is reported to the client as not found.
+    assert_eq!(
+        sui_network::tonic::Status::from(result.unwrap_err()).code(),
+        sui_network::tonic::Code::NotFound
+    );
+}
+
+#[tokio::test]
+async fn test_handle_event_streaming() {
+    use futures::StreamExt;
+
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let object_ids = [ObjectID::random(), ObjectID::random()];
+    let gas_object_ids = [ObjectID::random(), ObjectID::random()];
+    let authority_state = init_state_with_ids(
+        object_ids
+            .iter()
+            .chain(gas_object_ids.iter())
+            .map(|id| (sender, *id)),
+    )
+    .await;
+
+    async fn transfer(
+        authority_state: &AuthorityState,
+        sender: SuiAddress,
+        sender_key: &AccountKeyPair,
+        object_id: ObjectID,
+        gas_object_id: ObjectID,
+    ) -> (TransactionDigest, Vec<Event>) {
+        let object = authority_state.get_object(&object_id).await.unwrap();
+        let gas_object = authority_state.get_object(&gas_object_id).await.unwrap();
+        let certificate = init_certified_transfer_transaction(
+            sender,
+            sender_key,
+            dbg_addr(2),
+            object.unwrap().compute_object_reference(),
+            gas_object.unwrap().compute_object_reference(),
+            authority_state,
+        );
+        let digest = *certificate.digest();
+        let effects = authority_state
+            .handle_certificate(certificate)
+            .await
+            .unwrap()
+            .signed_effects
+            .unwrap()
+            .effects;
+        assert!(!effects.events.is_empty());
+        (digest, effects.events)
+    }
+
+    // Events of transactions executed before subscribing are not streamed without a start.
+    let (digest1, events1) = transfer(
+        &authority_state,
+        sender,
+        &sender_key,
+        object_ids[0],
+        gas_object_ids[0],
+    )
+    .await;
+    let mut live = Box::pin(
+        authority_state
+            .handle_event_streaming(EventSubscriptionRequest { start: None })
+            .await
+            .unwrap(),
+    );
+    let (digest2, events2) = transfer(
+        &authority_state,
+        sender,
+        &sender_key,
+        object_ids[1],
+        gas_object_ids[1],
+    )
+    .await;
+    for event in &events2 {
+        assert_eq!(
+            live.next().await.unwrap().unwrap(),
+            EventResponseItem {
+                transaction_digest: digest2,
+                event: event.clone(),
+            }
+        );
+    }
+
+    // With a start, the persisted events of that transaction and of those executed after it
+    // come first, in execution order.
+    let replay = authority_state
+        .handle_event_streaming(EventSubscriptionRequest {
+            start: Some(digest1),
+        })
+        .await
+        .unwrap();
+    let expected: Vec<_> = events1
+        .into_iter()
+        .map(|event| (digest1, event))
+        .chain(events2.into_iter().map(|event| (digest2, event)))
+        .map(|(transaction_digest, event)| {
+            Ok::<_, SuiError>(EventResponseItem {
+                transaction_digest,
+                event,
+            })
+        })
+        .collect();
+    let replayed: Vec<_> = replay.take(expected.len()).collect().await;
+    assert_eq!(replayed, expected);
+
+    let result = authority_state
+        .handle_event_streaming(EventSubscriptionRequest {
+            start: Some(TransactionDigest::random()),
+        })
+        .await;
+    assert!(matches!(result, Err(SuiError::TransactionNotFound { .. })));
+
+    // A subscriber lagging past the capacity of the channel, which is rounded up to a power of
+    // two, is told so and its stream ends. Sending never waits for it.
+    for seq in 0..2 * AuthorityBatchConfig::default().broadcast_capacity as u64 {
+        let _ = authority_state
+            .event_channels
+            .send((seq, TransactionDigest::random(), vec![]));
+    }
+    assert!(matches!(
+        live.next().await,
+        Some(Err(SuiError::SubscriptionItemsDroppedError(_)))
+    ));
+    assert!(live.next().await.is_none());
+
+    // Only the latest transactions can be replayed.
+    let next_seq = authority_state.database.next_sequence_number().unwrap();
+    authority_state.database.side_sequence(
+        next_seq + MAX_EVENT_REPLAY_TRANSACTIONS,
+        &ExecutionDigests::random(),
+    );
+    let result = authority_state
+        .handle_event_streaming(EventSubscriptionRequest {
+            start: Some(digest1),
+        })
+        .await;
+    assert!(matches!(result, Err(SuiError::TooManyItemsError { .. })));
+}
+
+#[tokio::test]
+async fn test_gas_charged_once_per_certificate() {
+    let TransferFixture {
+        authority_state,
+        certificate: certified_transfer_transaction,
+        ..
+    } = init_transfer_fixture().await;
+
+    let info = authority_state
+        .handle_certificate(certified_transfer_transaction.clone())
+        .await
+        .unwrap();
+    let gas_used = info.signed_effects.unwrap().effects.gas_used;
+    assert!(gas_used.gas_used() > 0);
+    assert_eq!(
+        authority_state.metrics.total_gas_charged.get(),
+        gas_used.gas_used()
+    );
+
+    // Processing the certificate again charges nothing more.
+    authority_state
+        .handle_certificate(certified_transfer_transaction)
+        .await
+        .unwrap();
+    assert_eq!(
+        authority_state.metrics.total_gas_charged.get(),
+        gas_used.gas_used()
+    );
+}
+
+#[tokio::test]
+async fn test_execute_transaction_and_wait_for_checkpoint() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+
+    // The transaction is executed, but no checkpoint includes it.
+    assert_eq!(
+        authority_state
+            .execute_transaction_and_wait_for_checkpoint(
+                certificate.clone(),
+                Duration::from_millis(200)
+            )
+            .await
+            .unwrap_err(),
+        SuiError::TimeoutError
+    );
+    let effects = authority_state
+        .handle_certificate(certificate.clone())
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap();
+    let execution_digests = ExecutionDigests::new(*certificate.digest(), effects.effects.digest());
+
+    // Checkpoint the transaction while the authority waits for it.
+    let checkpoints = authority_state.checkpoints().unwrap();
+    let checkpoint_task = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let mut checkpoints = checkpoints.lock();
+        checkpoints
+            .handle_internal_batch(1, &[(0, execution_digests)])
+            .unwrap();
+        checkpoints
+            .update_new_checkpoint(
+                0,
+                &CheckpointContents::new_with_causally_ordered_transactions(
+                    [execution_digests].into_iter(),
+                ),
+                PendCertificateForExecutionNoop,
+            )
+            .unwrap();
+    });
+
+    let (info, checkpoint) = authority_state
+        .execute_transaction_and_wait_for_checkpoint(certificate, Duration::from_secs(10))
+        .await
+        .unwrap();
+    checkpoint_task.await.unwrap();
+    assert_eq!(checkpoint, 0);
+    assert_eq!(
+        info.signed_effects.unwrap().effects.digest(),
+        execution_digests.effects
+    );
+}
+
+#[tokio::test]
+async fn test_get_transaction_auth_signers() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+    let digest = *transaction.digest();
+    assert_eq!(
+        authority_state
+            .get_transaction_auth_signers(&digest)
+            .unwrap_err(),
+        SuiError::TransactionNotFound { digest }
+    );
+
+    // Signed but not certified.
+    authority_state
+        .handle_transaction(transaction.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        authority_state
+            .get_transaction_auth_signers(&digest)
+            .unwrap_err(),
+        SuiError::CertificateNotfound {
+            certificate_digest: digest
+        }
+    );
+
+    let certificate = init_certified_transaction(transaction, &authority_state);
+    authority_state
+        .handle_certificate(certificate)
+        .await
+        .unwrap();
+    let signers = authority_state
+        .get_transaction_auth_signers(&digest)
+        .unwrap();
+    let committee = authority_state.committee.load();
+    let stake: u64 = signers.iter().map(|name| committee.weight(name)).sum();
+    assert!(signers.contains(&authority_state.name));
+    assert!(stake >= committee.quorum_threshold());
+}
+
+#[tokio::test]
+async fn test_effects_consistency_check() {
+    let TransferFixture {
+        authority_state,
+        object,
+        gas_object,
+        certificate,
+        ..
+    } = init_transfer_fixture().await;
+    let object_id = object.id();
+    let effects = authority_state
+        .handle_certificate(certificate.clone())
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap()
+        .effects;
+    let database = &authority_state.database;
+    assert!(database.check_effects_consistency(10).unwrap().is_empty());
+
+    // Simulate a partial write, where the effects were persisted but the transferred object was
+    // not.
+    let transferred = effects
+        .mutated
+        .iter()
+        .find(|(object_ref, _)| object_ref.0 == object_id)
+        .unwrap()
+        .0;
+    let mut transferred_object = database
+        .get_object_by_key(&object_id, transferred.1)
+        .unwrap()
+        .unwrap();
+    database
+        .tables
+        .objects
+        .remove(&authority_store::ObjectKey(object_id, transferred.1))
+        .unwrap();
+    assert_eq!(
+        database.check_effects_consistency(10).unwrap(),
+        vec![(*certificate.digest(), transferred)]
+    );
+
+    // A missing version is not a mismatch once a later version of the object is stored, as it
+    // was pruned after being superseded.
+    transferred_object
+        .data
+        .try_as_move_mut()
+        .unwrap()
+        .increment_version();
+    database
+        .tables
+        .objects
+        .insert(
+            &authority_store::ObjectKey(object_id, transferred.1.increment()),
+            &transferred_object,
+        )
+        .unwrap();
+    assert!(database.check_effects_consistency(10).unwrap().is_empty());
+
+    // Transactions whose effects were pruned are skipped.
+    let gas = effects.gas_object.0;
+    database
+        .tables
+        .objects
+        .remove(&authority_store::ObjectKey(gas.0, gas.1))
+        .unwrap();
+    assert_eq!(
+        database.check_effects_consistency(10).unwrap(),
+        vec![(*certificate.digest(), gas)]
+    );
+    database
+        .tables
+        .effects
+        .remove(certificate.digest())
+        .unwrap();
+    assert!(database.check_effects_consistency(10).unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_get_input_objects() {
+    let objects: Vec<_> = (0..50)
+        .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), dbg_addr(1)))
+        .collect();
+    let authority_state = init_state_with_objects(objects.clone()).await;
+
+    let mut inputs: Vec<_> = objects
+        .iter()
+        .map(|o| InputObjectKind::ImmOrOwnedMoveObject(o.compute_object_reference()))
+        .collect();
+    inputs.insert(20, InputObjectKind::SharedMoveObject(ObjectID::random()));
+    inputs.insert(30, InputObjectKind::SharedMoveObject(objects[40].id()));
+    let (missing_id, missing_version, missing_digest) = objects[0].compute_object_reference();
+    inputs.push(InputObjectKind::ImmOrOwnedMoveObject((
+        missing_id,
+        missing_version.increment(),
+        missing_digest,
+    )));
+
+    // The objects are returned in the order of the inputs, read by version or as their latest.
+    let fetched = authority_state.database.get_input_objects(&inputs).unwrap();
+    assert_eq!(fetched.len(), inputs.len());
+    assert_eq!(fetched[0].as_ref(), Some(&objects[0]));
+    assert!(fetched[20].is_none());
+    assert_eq!(fetched[21].as_ref(), Some(&objects[20]));
+    assert_eq!(fetched[30].as_ref(), Some(&objects[40]));
+    assert_eq!(fetched[31].as_ref(), Some(&objects[29]));
+    assert_eq!(fetched[51].as_ref(), Some(&objects[49]));
+    assert!(fetched[52].is_none());
+}
+
+#[tokio::test]
+async fn test_move_call_mutable_object_not_mutated() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, pkg_ref) =
+        init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
+
+    let effects = create_move_object(
+        &pkg_ref,
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok());
+    assert_eq!((effects.created.len(), effects.mutated.len()), (1, 1));
+    let (new_object_id1, seq1, _) = effects.created[0].0;
+
+    let effects = create_move_object(
+        &pkg_ref,
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok());
+    assert_eq!((effects.created.len(), effects.mutated.len()), (1, 1));
+    let (new_object_id2, seq2, _) = effects.created[0].0;
+
+    let effects = call_move(
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+        &pkg_ref,
+        "object_basics",
+        "update",
+        vec![],
+        vec![
+            TestCallArg::Object(new_object_id1),
+            TestCallArg::Object(new_object_id2),
+        ],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok());
+    assert_eq!((effects.created.len(), effects.mutated.len()), (0, 3));
+    // Verify that both objects' version increased, even though only one object was updated.
+    assert_eq!(
+        authority_state
+            .get_object(&new_object_id1)
+            .await
+            .unwrap()
+            .unwrap()
+            .version(),
+        seq1.increment()
+    );
+    assert_eq!(
+        authority_state
+            .get_object(&new_object_id2)
+            .await
+            .unwrap()
+            .unwrap()
+            .version(),
+        seq2.increment()
+    );
+}
+
+#[tokio::test]
+async fn test_package_call_metrics() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, pkg_ref) =
+        init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
+
+    let label = pkg_ref.0.to_string();
+    let calls = authority_state
+        .metrics
+        .package_calls
+        .with_label_values(&[&label]);
+    let gas = authority_state
+        .metrics
+        .package_gas
+        .with_label_values(&[&label]);
+    assert_eq!(calls.get(), 0);
+
+    let mut total_gas = 0;
+    for i in 1..=3 {
+        let effects = create_move_object(
+            &pkg_ref,
+            &authority_state,
+            &gas_object_id,
+            &sender,
+            &sender_key,
+        )
+        .await
+        .unwrap();
+        assert!(effects.status.is_ok());
+        total_gas += effects.gas_used.gas_used();
+        assert_eq!(calls.get(), i);
+        assert_eq!(gas.get() as u64, total_gas);
+    }
+}
+
+#[test]
+fn test_package_usage_labels_top_packages() {
+    let calls = IntGaugeVec::new(Opts::new("calls", "calls"), &["package"]).unwrap();
+    let gas = IntGaugeVec::new(Opts::new("gas", "gas"), &["package"]).unwrap();
+    let mut usage = PackageUsage::default();
+    for _ in 0..MAX_TRACKED_PACKAGES {
+        usage.record(ObjectID::random(), 1, 10, &calls, &gas);
+    }
+
+    // A package which used less gas than all the labeled ones is counted as other.
+    let package = ObjectID::random();
+    usage.record(package, 1, 5, &calls, &gas);
+    assert!(!usage.labeled.iter().any(|(_, labeled)| *labeled == package));
+    assert_eq!(gas.with_label_values(&[OTHER_PACKAGES_LABEL]).get(), 5);
+
+    // Once it used more, it takes the label of one which used the least.
+    usage.record(package, 2, 10, &calls, &gas);
+    assert_eq!(calls.with_label_values(&[&package.to_string()]).get(), 3);
+    assert_eq!(gas.with_label_values(&[&package.to_string()]).get(), 15);
+    assert_eq!(calls.with_label_values(&[OTHER_PACKAGES_LABEL]).get(), 1);
+    assert_eq!(gas.with_label_values(&[OTHER_PACKAGES_LABEL]).get(), 10);
+    assert_eq!(
+        gas.collect()[0].get_metric().len(),
+        MAX_TRACKED_PACKAGES + 1
+    );
+
+    // The packages ranked are bounded, the forgotten ones staying counted as other.
+    for _ in 0..MAX_PACKAGE_CANDIDATES {
+        usage.record(ObjectID::random(), 1, 1, &calls, &gas);
+    }
+    assert_eq!(usage.usage.len(), MAX_PACKAGE_CANDIDATES);
+    assert_eq!(
+        gas.with_label_values(&[OTHER_PACKAGES_LABEL]).get(),
+        10 + MAX_PACKAGE_CANDIDATES as i64
+    );
+}
+
+#[tokio::test]
+async fn test_protocol_config_limits_are_enforced() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, pkg_ref) =
+        init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
+    let gas_object_ref = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .compute_object_reference();
+
+    let config = authority_state.get_protocol_config();
+    assert_eq!(config.version, GENESIS_PROTOCOL_VERSION);
+    assert_eq!(
+        authority_state
+            .epoch_store()
+            .get_latest_authenticated_epoch()
+            .epoch_info()
+            .protocol_version(),
+        config.version
+    );
+
+    let random_object_ref = || {
+        (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::new([0; 32]),
+        )
+    };
+    let call = |arguments| {
+        SingleTransactionKind::Call(MoveCall {
+            package: pkg_ref,
+            module: ident_str!("object_basics").to_owned(),
+            function: ident_str!("create").to_owned(),
+            type_arguments: vec![],
+            arguments,
+        })
+    };
+    let handle = |kind, gas_price| {
+        let data =
+            TransactionData::new_with_gas_price(kind, sender, gas_object_ref, 10000, gas_price);
+        let signature = Signature::new(&data, &sender_key);
+        authority_state.handle_transaction(Transaction::new(data, signature))
+    };
+
+    let too_large = vec![0; config.max_tx_size_bytes as usize];
+    let kind = TransactionKind::Single(call(vec![CallArg::Pure(too_large)]));
+    assert!(matches!(
+        handle(kind, 1).await,
+        Err(SuiError::TransactionTooLarge { limit, .. }) if limit == config.max_tx_size_bytes
+    ));
+
+    let kind = TransactionKind::Batch(
+        (0..=config.max_transactions_in_batch)
+            .map(|_| {
+                SingleTransactionKind::TransferObject(TransferObject {
+                    recipient: dbg_addr(2),
+                    object_ref: random_object_ref(),
+                })
+            })
+            .collect(),
+    );
+    assert!(matches!(
+        handle(kind, 1).await,
+        Err(SuiError::InvalidBatchTransaction { .. })
+    ));
+
+    // With the package and the gas object, this is one input object over the limit.
+    let kind = TransactionKind::Single(call(
+        (1..config.max_input_objects)
+            .map(|_| CallArg::Object(ObjectArg::ImmOrOwnedObject(random_object_ref())))
+            .collect(),
+    ));
+    assert!(matches!(
+        handle(kind, 1).await,
+        Err(SuiError::TooManyInputObjects { count, limit })
+            if count == config.max_input_objects + 1 && limit == config.max_input_objects
+    ));
+
+    // Type arguments are limited from protocol version 2 on, where a type argument nested one
+    // level too deep is rejected before it is ever executed.
+    assert_eq!(config.max_type_argument_depth, None);
+    let config = Arc::new(ProtocolConfig::get_for_version(2).unwrap());
+    authority_state.protocol_config.store(config.clone());
+    let max_depth = config.max_type_argument_depth.unwrap();
+    let mut type_argument = TypeTag::U64;
+    for _ in 0..max_depth {
+        type_argument = TypeTag::Vector(Box::new(type_argument));
+    }
+    let kind = TransactionKind::Single(SingleTransactionKind::Call(MoveCall {
+        package: pkg_ref,
+        module: ident_str!("object_basics").to_owned(),
+        function: ident_str!("create").to_owned(),
+        type_arguments: vec![TypeTag::Bool, type_argument],
+        arguments: vec![],
+    }));
+    assert!(matches!(
+        handle(kind, 1).await,
+        Err(SuiError::TypeArgumentDepthExceeded { depth, limit })
+            if depth == max_depth + 1 && limit == max_depth
+    ));
+
+    let kind = TransactionKind::Single(call(vec![
+        CallArg::Pure(16u64.to_le_bytes().to_vec()),
+        CallArg::Pure(bcs::to_bytes(&AccountAddress::from(sender)).unwrap()),
+    ]));
+    assert_eq!(
+        handle(kind.clone(), config.min_gas_price - 1)
+            .await
+            .unwrap_err(),
+        SuiError::GasPriceTooLow {
+            gas_price: config.min_gas_price - 1,
+            min_gas_price: config.min_gas_price,
+        }
+    );
+    handle(kind, config.min_gas_price).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_move_call_insufficient_gas() {
     // This test attempts to trigger a transaction execution that would fail due to insufficient gas.
     // We want to ensure that even though the transaction failed to execute, all objects
     // are mutated properly.
@@ -1462,76 +2608,374 @@ async fn test_get_latest_parent_entry() {
         &gas_object_id,
         &sender,
         &sender_key,
-        &pkg_ref,
-        "object_basics",
-        "update",
-        vec![],
+        &pkg_ref,
+        "object_basics",
+        "update",
+        vec![],
+        vec![
+            TestCallArg::Object(new_object_id1),
+            TestCallArg::Object(new_object_id2),
+        ],
+    )
+    .await
+    .unwrap();
+
+    // Check entry for object to be deleted is returned
+    let (obj_ref, tx) = authority_state
+        .get_latest_parent_entry(new_object_id1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(obj_ref.0, new_object_id1);
+    assert_eq!(obj_ref.1, SequenceNumber::from(2));
+    assert_eq!(effects.transaction_digest, tx);
+
+    let effects = call_move(
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+        &pkg_ref,
+        "object_basics",
+        "delete",
+        vec![],
+        vec![TestCallArg::Object(new_object_id1)],
+    )
+    .await
+    .unwrap();
+
+    // Test get_latest_parent_entry function
+
+    // The objects just after the gas object also returns None
+    let mut x = gas_object_id.to_vec();
+    let last_index = x.len() - 1;
+    // Prevent overflow
+    x[last_index] = u8::MAX - x[last_index];
+    let unknown_object_id: ObjectID = x.try_into().unwrap();
+    assert!(authority_state
+        .get_latest_parent_entry(unknown_object_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Check gas object is returned.
+    let (obj_ref, tx) = authority_state
+        .get_latest_parent_entry(gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(obj_ref.0, gas_object_id);
+    assert_eq!(obj_ref.1, SequenceNumber::from(4));
+    assert_eq!(effects.transaction_digest, tx);
+
+    // Check entry for deleted object is returned
+    let (obj_ref, tx) = authority_state
+        .get_latest_parent_entry(new_object_id1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(obj_ref.0, new_object_id1);
+    assert_eq!(obj_ref.1, SequenceNumber::from(3));
+    assert_eq!(obj_ref.2, ObjectDigest::OBJECT_DIGEST_DELETED);
+    assert_eq!(effects.transaction_digest, tx);
+}
+
+#[tokio::test]
+async fn test_get_object_provenance() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, pkg_ref) =
+        init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
+
+    // Genesis objects and unknown objects have no provenance.
+    assert!(authority_state
+        .get_object_provenance(gas_object_id)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(authority_state
+        .get_object_provenance(ObjectID::random())
+        .await
+        .unwrap()
+        .is_none());
+
+    let effects = create_move_object(
+        &pkg_ref,
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+    )
+    .await
+    .unwrap();
+    let (new_object_id, _, _) = effects.created[0].0;
+
+    let certificate = authority_state
+        .get_object_provenance(new_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(*certificate.digest(), effects.transaction_digest);
+
+    // The same certificate is returned by an object info request, without the object.
+    let response = authority_state
+        .handle_object_info_request(ObjectInfoRequest::object_provenance_request(new_object_id))
+        .await
+        .unwrap();
+    assert_eq!(
+        response.parent_certificate.unwrap().digest(),
+        certificate.digest()
+    );
+    assert_eq!(
+        response.requested_object_reference.unwrap().0,
+        new_object_id
+    );
+    assert!(response.object_and_lock.is_none());
+}
+
+#[tokio::test]
+async fn test_handle_multi_object_info_request() {
+    let sender = dbg_addr(1);
+    let (object_id1, object_id2) = (ObjectID::random(), ObjectID::random());
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id1), (sender, object_id2)]).await;
+    let unknown = ObjectID::random();
+
+    let responses = authority_state
+        .handle_multi_object_info_request(MultiObjectInfoRequest {
+            object_ids: vec![object_id2, unknown, object_id1],
+            request_kind: ObjectInfoRequestKind::LatestObjectInfo(Some(
+                ObjectFormatOptions::default(),
+            )),
+        })
+        .await
+        .unwrap();
+    assert_eq!(responses.len(), 3);
+    for (response, object_id) in [(&responses[0], object_id2), (&responses[2], object_id1)] {
+        let object_and_lock = response.as_ref().unwrap().object_and_lock.as_ref().unwrap();
+        assert_eq!(object_and_lock.object.id(), object_id);
+        assert!(object_and_lock.layout.is_some());
+    }
+    let response = responses[1].as_ref().unwrap();
+    assert!(response.requested_object_reference.is_none());
+    assert!(response.object_and_lock.is_none());
+
+    let result = authority_state
+        .handle_multi_object_info_request(MultiObjectInfoRequest {
+            object_ids: vec![object_id1; MAX_MULTI_OBJECT_INFO_SIZE + 1],
+            request_kind: ObjectInfoRequestKind::LatestObjectInfo(None),
+        })
+        .await;
+    assert!(matches!(result, Err(SuiError::TooManyItemsError { .. })));
+    assert_eq!(
+        sui_network::tonic::Status::from(result.unwrap_err()).code(),
+        sui_network::tonic::Code::ResourceExhausted
+    );
+
+    // Only as many responses as fit in a gRPC message are returned.
+    let mut contents = ObjectID::random().to_vec();
+    contents.resize(256 * 1024, 0);
+    let large_object = Object::new_move(
+        MoveObject::new_gas_coin(OBJECT_START_VERSION, contents),
+        Owner::AddressOwner(sender),
+        TransactionDigest::genesis(),
+    );
+    let large_object_id = large_object.id();
+    authority_state.insert_genesis_object(large_object).await;
+    let responses = authority_state
+        .handle_multi_object_info_request(MultiObjectInfoRequest {
+            object_ids: vec![large_object_id; 20],
+            request_kind: ObjectInfoRequestKind::LatestObjectInfo(None),
+        })
+        .await
+        .unwrap();
+    assert!(!responses.is_empty() && responses.len() < 20);
+    let size: usize = responses
+        .iter()
+        .map(|response| bcs::serialized_size(response).unwrap())
+        .sum();
+    assert!(size as u64 <= MAX_BATCH_RESPONSE_SIZE_BYTES);
+}
+
+#[tokio::test]
+async fn test_handle_multi_object_info_request_isolates_failures() {
+    let TransferFixture {
+        authority_state,
+        object,
+        certificate,
+        ..
+    } = init_transfer_fixture().await;
+    let digest = *certificate.digest();
+    authority_state
+        .handle_certificate(certificate)
+        .await
+        .unwrap();
+    // Without the certificate that last wrote it, the info of the object cannot be served.
+    authority_state
+        .database
+        .tables
+        .certificates
+        .remove(&digest)
+        .unwrap();
+
+    let responses = authority_state
+        .handle_multi_object_info_request(MultiObjectInfoRequest {
+            object_ids: vec![object.id(), ObjectID::random()],
+            request_kind: ObjectInfoRequestKind::LatestObjectInfo(None),
+        })
+        .await
+        .unwrap();
+    assert_eq!(responses.len(), 2);
+    assert!(matches!(
+        responses[0],
+        Err(SuiError::CertificateNotfound { .. })
+    ));
+    assert!(responses[1].as_ref().unwrap().object_and_lock.is_none());
+}
+
+#[tokio::test]
+async fn test_handle_node_stats_request() {
+    let TransferFixture {
+        authority_state,
+        certificate,
+        ..
+    } = init_transfer_fixture().await;
+
+    let before = authority_state
+        .handle_node_stats_request(&NodeStatsRequest {})
+        .unwrap();
+    authority_state
+        .handle_certificate(certificate)
+        .await
+        .unwrap();
+    let after = authority_state
+        .handle_node_stats_request(&NodeStatsRequest {})
+        .unwrap();
+    assert_eq!(after.total_certificates, before.total_certificates + 1);
+}
+
+#[tokio::test]
+async fn test_handle_coin_supply_request() {
+    let TransferFixture {
+        authority_state,
+        certificate,
+        ..
+    } = init_transfer_fixture().await;
+    let request = CoinSupplyRequest {
+        coin_type: GAS::type_(),
+    };
+    let cache = authority_state.coin_supply.as_ref().unwrap();
+
+    // The objects of the test are coins, on top of those of genesis.
+    let before = authority_state
+        .handle_coin_supply_request(&request)
+        .await
+        .unwrap();
+    assert!(before.coin_count >= 2);
+    assert!(before.total_supply >= u128::from(2 * GAS_VALUE_FOR_TESTING));
+    assert_eq!(cache.get(&GAS::type_()), Some(before.clone()));
+
+    // Paying for gas changes the supply, so it is computed again.
+    let effects = authority_state
+        .handle_certificate(certificate)
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap()
+        .effects;
+    assert_eq!(cache.get(&GAS::type_()), None);
+    let after = authority_state
+        .handle_coin_supply_request(&request)
+        .await
+        .unwrap();
+    assert_eq!(after.coin_count, before.coin_count);
+    assert_eq!(
+        after.total_supply as i128,
+        before.total_supply as i128 - i128::from(effects.gas_used.net_gas_usage())
+    );
+
+    // No coin of an unknown currency is stored.
+    let unknown = authority_state
+        .handle_coin_supply_request(&CoinSupplyRequest {
+            coin_type: StructTag {
+                address: SUI_FRAMEWORK_ADDRESS,
+                module: ident_str!("unknown").to_owned(),
+                name: ident_str!("UNKNOWN").to_owned(),
+                type_params: vec![],
+            },
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        unknown,
+        CoinSupplyResponse {
+            total_supply: 0,
+            coin_count: 0,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_get_object_tombstone() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let coin_id = ObjectID::random();
+    let coin_to_merge_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state = init_state_with_ids(vec![
+        (sender, coin_id),
+        (sender, coin_to_merge_id),
+        (sender, gas_object_id),
+    ])
+    .await;
+    assert!(authority_state
+        .database
+        .get_object_tombstone(coin_to_merge_id)
+        .unwrap()
+        .is_none());
+
+    // Merging the coins deletes the coin merged in.
+    let package_object_ref = authority_state.get_framework_object_ref().await.unwrap();
+    let effects = call_move(
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+        &package_object_ref,
+        "coin",
+        "join",
+        vec![GAS::type_tag()],
         vec![
-            TestCallArg::Object(new_object_id1),
-            TestCallArg::Object(new_object_id2),
+            TestCallArg::Object(coin_id),
+            TestCallArg::Object(coin_to_merge_id),
         ],
     )
     .await
     .unwrap();
+    assert!(effects.status.is_ok());
+    let deleted_ref = effects.deleted[0];
+    assert_eq!(deleted_ref.0, coin_to_merge_id);
 
-    // Check entry for object to be deleted is returned
-    let (obj_ref, tx) = authority_state
-        .get_latest_parent_entry(new_object_id1)
-        .await
+    let tombstone = authority_state
+        .database
+        .get_object_tombstone(coin_to_merge_id)
         .unwrap()
         .unwrap();
-    assert_eq!(obj_ref.0, new_object_id1);
-    assert_eq!(obj_ref.1, SequenceNumber::from(2));
-    assert_eq!(effects.transaction_digest, tx);
-
-    let effects = call_move(
-        &authority_state,
-        &gas_object_id,
-        &sender,
-        &sender_key,
-        &pkg_ref,
-        "object_basics",
-        "delete",
-        vec![],
-        vec![TestCallArg::Object(new_object_id1)],
-    )
-    .await
-    .unwrap();
-
-    // Test get_latest_parent_entry function
-
-    // The objects just after the gas object also returns None
-    let mut x = gas_object_id.to_vec();
-    let last_index = x.len() - 1;
-    // Prevent overflow
-    x[last_index] = u8::MAX - x[last_index];
-    let unknown_object_id: ObjectID = x.try_into().unwrap();
+    assert_eq!(tombstone.object_ref, deleted_ref);
+    assert_eq!(tombstone.deleted_by, effects.transaction_digest);
     assert!(authority_state
-        .get_latest_parent_entry(unknown_object_id)
-        .await
+        .database
+        .get_object_tombstone(coin_id)
         .unwrap()
         .is_none());
 
-    // Check gas object is returned.
-    let (obj_ref, tx) = authority_state
-        .get_latest_parent_entry(gas_object_id)
-        .await
-        .unwrap()
-        .unwrap();
-    assert_eq!(obj_ref.0, gas_object_id);
-    assert_eq!(obj_ref.1, SequenceNumber::from(4));
-    assert_eq!(effects.transaction_digest, tx);
-
-    // Check entry for deleted object is returned
-    let (obj_ref, tx) = authority_state
-        .get_latest_parent_entry(new_object_id1)
+    match authority_state
+        .get_object_read(&coin_to_merge_id)
         .await
         .unwrap()
-        .unwrap();
-    assert_eq!(obj_ref.0, new_object_id1);
-    assert_eq!(obj_ref.1, SequenceNumber::from(3));
-    assert_eq!(obj_ref.2, ObjectDigest::OBJECT_DIGEST_DELETED);
-    assert_eq!(effects.transaction_digest, tx);
+    {
+        ObjectRead::Deleted(read_tombstone) => assert_eq!(read_tombstone, tombstone),
+        other => panic!("Expected the object to be deleted, got {other}"),
+    }
 }
 
 #[tokio::test]
@@ -1694,6 +3138,208 @@ async fn test_change_epoch_transaction() {
     assert_eq!(sui_system_object.epoch, 1);
 }
 
+#[tokio::test]
+async fn test_get_system_state() {
+    let authority_state = init_state().await;
+    let committee = authority_state.clone_committee();
+
+    let system_state = authority_state.get_system_state().await.unwrap();
+    assert_eq!(system_state.epoch, committee.epoch);
+    assert_eq!(system_state.committee.len(), committee.num_members());
+    assert_eq!(system_state.total_stake, committee.total_votes);
+    assert_eq!(system_state.min_gas_budget, *sui_types::gas::MIN_GAS_BUDGET);
+    assert_eq!(system_state.max_gas_budget, *sui_types::gas::MAX_GAS_BUDGET);
+
+    let sui_system_object = authority_state.get_sui_system_state_object().await.unwrap();
+    assert_eq!(
+        system_state.reference_gas_price,
+        sui_system_object.reference_gas_price
+    );
+    assert_eq!(
+        system_state.storage_gas_price,
+        sui_system_object.parameters.storage_gas_price
+    );
+}
+
+#[tokio::test]
+async fn test_native_functions() {
+    let authority_state = init_state().await;
+    let natives = authority_state.native_functions();
+
+    for (address, module, function) in [
+        (SUI_FRAMEWORK_ADDRESS, "object", "delete_impl"),
+        (SUI_FRAMEWORK_ADDRESS, "event", "emit"),
+        (SUI_FRAMEWORK_ADDRESS, "transfer", "transfer_internal"),
+        (MOVE_STDLIB_ADDRESS, "bcs", "to_bytes"),
+    ] {
+        let native = (
+            address,
+            Identifier::new(module).unwrap(),
+            Identifier::new(function).unwrap(),
+        );
+        assert!(natives.contains(&native), "Missing native {native:?}");
+    }
+}
+
+#[tokio::test]
+async fn test_replay_certificate() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let other_object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state = init_state_with_ids(vec![
+        (sender, object_id),
+        (sender, other_object_id),
+        (sender, gas_object_id),
+    ])
+    .await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let certified_transfer_transaction = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    let digest = *certified_transfer_transaction.digest();
+    authority_state
+        .handle_certificate(certified_transfer_transaction)
+        .await
+        .unwrap();
+
+    // Pay for another transfer with the same gas object, so that the gas object moves past the
+    // version the first transaction read.
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let other_object = authority_state
+        .get_object(&other_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let other_transfer_transaction = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        other_object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    authority_state
+        .handle_certificate(other_transfer_transaction)
+        .await
+        .unwrap();
+
+    // Replaying a known-good transaction reproduces the stored effects exactly, reading its
+    // inputs at the versions it read and metering gas with the parameters of its epoch.
+    let (cert, effects) = authority_state.get_transaction(digest).await.unwrap();
+    let replayed_effects = authority_state
+        .replay_certificate(&cert, &effects)
+        .await
+        .unwrap();
+    assert!(effects.diff(&replayed_effects).is_empty());
+    assert_eq!(effects, replayed_effects);
+
+    // Replaying does not change the state.
+    let new_object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(new_object.owner, recipient);
+    assert_eq!(new_object.version(), object.version().increment());
+
+    // The system state is known for the epochs the authority went through only.
+    let system_state = authority_state
+        .database
+        .get_sui_system_state_at_epoch(cert.auth_sign_info.epoch)
+        .unwrap();
+    assert_eq!(system_state.epoch, cert.auth_sign_info.epoch);
+    assert!(authority_state
+        .database
+        .get_sui_system_state_at_epoch(cert.auth_sign_info.epoch + 1)
+        .is_err());
+
+    // The diff reports the fields that differ.
+    let mut tampered_effects = effects.clone();
+    tampered_effects.gas_used.computation_cost += 1;
+    let diff = effects.diff(&tampered_effects);
+    assert_eq!(diff.len(), 1);
+    assert!(diff[0].starts_with("gas_used:"));
+}
+
+#[tokio::test]
+async fn test_replay_effects_against_snapshot() {
+    let TransferFixture {
+        authority_state,
+        object,
+        gas_object,
+        certificate: certified_transfer_transaction,
+        ..
+    } = init_transfer_fixture().await;
+    let object_id = object.id();
+    let gas_object_id = gas_object.id();
+    let digest = *certified_transfer_transaction.digest();
+    authority_state
+        .handle_certificate(certified_transfer_transaction)
+        .await
+        .unwrap();
+    let (cert, effects) = authority_state.get_transaction(digest).await.unwrap();
+
+    // The snapshot the certificate was executed against reproduces its effects.
+    let snapshot = BTreeMap::from([
+        (object_id, object.version()),
+        (gas_object_id, gas_object.version()),
+    ]);
+    let replayed_effects = authority_state
+        .replay_effects_against_snapshot(&cert, &snapshot)
+        .await
+        .unwrap();
+    assert_eq!(effects, replayed_effects);
+
+    // A snapshot with a different version of the object produces different effects.
+    let divergent_snapshot = BTreeMap::from([
+        (object_id, object.version().increment()),
+        (gas_object_id, gas_object.version()),
+    ]);
+    let divergent_effects = authority_state
+        .replay_effects_against_snapshot(&cert, &divergent_snapshot)
+        .await
+        .unwrap();
+    assert!(!effects.diff(&divergent_effects).is_empty());
+    // The object version written by the transaction itself was read.
+    assert!(divergent_effects.dependencies.contains(&digest));
+
+    // Every input object must be in the snapshot, at a version the store has.
+    let missing_snapshot = BTreeMap::from([(gas_object_id, gas_object.version())]);
+    assert!(authority_state
+        .replay_effects_against_snapshot(&cert, &missing_snapshot)
+        .await
+        .is_err());
+    let unknown_snapshot = BTreeMap::from([
+        (object_id, object.version().increment().increment()),
+        (gas_object_id, gas_object.version()),
+    ]);
+    assert!(authority_state
+        .replay_effects_against_snapshot(&cert, &unknown_snapshot)
+        .await
+        .is_err());
+}
+
 #[tokio::test]
 async fn test_transfer_sui_no_amount() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -1999,6 +3645,54 @@ pub fn init_transfer_transaction(
     Transaction::new(data, signature)
 }
 
+/// An authority holding an object and a gas object of `sender`, with a certificate
+/// transferring the object to `recipient` paid with the gas object, not executed yet.
+#[cfg(test)]
+struct TransferFixture {
+    authority_state: AuthorityState,
+    sender: SuiAddress,
+    recipient: SuiAddress,
+    object: Object,
+    gas_object: Object,
+    certificate: CertifiedTransaction,
+}
+
+#[cfg(test)]
+async fn init_transfer_fixture() -> TransferFixture {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    TransferFixture {
+        authority_state,
+        sender,
+        recipient,
+        object,
+        gas_object,
+        certificate,
+    }
+}
+
 #[cfg(test)]
 fn init_certified_transfer_transaction(
     sender: SuiAddress,
@@ -2014,7 +3708,7 @@ fn init_certified_transfer_transaction(
 }
 
 #[cfg(test)]
-fn init_certified_transaction(
+pub fn init_certified_transaction(
     transaction: Transaction,
     authority_state: &AuthorityState,
 ) -> CertifiedTransaction {
@@ -2218,6 +3912,77 @@ async fn shared_object() {
     assert_eq!(shared_object_version, SequenceNumber::from(2));
 }
 
+#[tokio::test]
+async fn test_shared_object_lock_contention_metrics() {
+    let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+    let gas_object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let gas_object_ref = gas_object.compute_object_reference();
+    let other_gas_object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let other_gas_object_ref = other_gas_object.compute_object_reference();
+
+    let shared_object_id = ObjectID::random();
+    let shared_object = {
+        use sui_types::gas_coin::GasCoin;
+        use sui_types::object::MoveObject;
+
+        let content = GasCoin::new(shared_object_id, 10);
+        let obj = MoveObject::new_gas_coin(OBJECT_START_VERSION, content.to_bcs_bytes());
+        Object::new_move(obj, Owner::Shared, TransactionDigest::genesis())
+    };
+
+    let authority =
+        init_state_with_objects(vec![gas_object, other_gas_object, shared_object]).await;
+
+    async fn send_consensus(authority: &AuthorityState, cert: &CertifiedTransaction) {
+        authority
+            .handle_consensus_transaction(
+                &narwhal_consensus::ConsensusOutput {
+                    certificate: narwhal_types::Certificate::default(),
+                    consensus_index: narwhal_types::SequenceNumber::default(),
+                },
+                ExecutionIndices::default(),
+                ConsensusTransaction::new_certificate_message(&authority.name, cert.clone()),
+            )
+            .await
+            .unwrap();
+    }
+
+    // Sequence two conflicting transactions on the shared object.
+    let mut certificates = vec![];
+    for (gas_object_ref, value) in [(gas_object_ref, 16), (other_gas_object_ref, 17)] {
+        let certificate = make_test_transaction(
+            &sender,
+            &keypair,
+            shared_object_id,
+            &gas_object_ref,
+            &[&authority],
+            value,
+        )
+        .await;
+        send_consensus(&authority, &certificate).await;
+        certificates.push(certificate);
+    }
+    assert_eq!(authority.metrics.shared_object_lock_errors.get(), 0);
+    assert_eq!(authority.metrics.shared_object_retries.get(), 0);
+
+    // The second transaction was assigned a version of the shared object which does not exist yet.
+    let result = authority.handle_certificate(certificates[1].clone()).await;
+    assert!(matches!(result, Err(SuiError::ObjectErrors { .. })));
+    assert_eq!(authority.metrics.shared_object_lock_errors.get(), 1);
+
+    // Sequencing the first transaction again counts as a retry, without assigning new versions.
+    send_consensus(&authority, &certificates[0]).await;
+    assert_eq!(authority.metrics.shared_object_retries.get(), 1);
+
+    for certificate in &certificates {
+        authority
+            .handle_certificate(certificate.clone())
+            .await
+            .unwrap();
+    }
+    assert_eq!(authority.metrics.shared_object_lock_errors.get(), 1);
+}
+
 #[tokio::test]
 async fn test_consensus_message_processed() {
     telemetry_subscribers::init_for_testing();
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use fastcrypto::traits::KeyPair;
+use move_core_types::identifier::Identifier;
 use rand::{prelude::StdRng, SeedableRng};
 use sui_types::committee::Committee;
 use sui_types::crypto::get_key_pair;
@@ -9,6 +10,7 @@ use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::AccountKeyPair;
 use sui_types::crypto::AuthorityKeyPair;
 use sui_types::crypto::AuthorityPublicKeyBytes;
+use sui_types::crypto::Signature;
 use sui_types::messages_checkpoint::CheckpointRequest;
 use sui_types::messages_checkpoint::CheckpointResponse;
 
@@ -28,9 +30,10 @@ use std::env;
 use std::fs;
 use std::sync::Arc;
 use sui_types::messages::{
-    AccountInfoRequest, AccountInfoResponse, BatchInfoRequest, BatchInfoResponseItem,
-    CertifiedTransaction, EpochRequest, EpochResponse, ObjectInfoRequest, ObjectInfoResponse,
-    Transaction, TransactionInfoRequest, TransactionInfoResponse,
+    AccountInfoPageRequest, AccountInfoPageResponse, AccountInfoRequest, AccountInfoResponse,
+    BatchInfoRequest, BatchInfoResponseItem, CallArg, CertifiedTransaction, EpochRequest,
+    EpochResponse, ObjectInfoRequest, ObjectInfoResponse, ReverseBatchInfoRequest, Transaction,
+    TransactionData, TransactionInfoRequest, TransactionInfoResponse,
 };
 
 pub(crate) fn init_state_parameters_from_rng<R>(
@@ -72,6 +75,8 @@ pub(crate) async fn init_state(
         &sui_config::genesis::Genesis::get_default_genesis(),
         &prometheus::Registry::new(),
         tx_reconfigure_consensus,
+        Default::default(),
+        None,
     )
     .await
 }
@@ -352,6 +357,104 @@ async fn test_batch_manager_drop_out_of_order() {
     _join.await.expect("No errors in task").expect("ok");
 }
 
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn test_batch_manager_closes_batch_on_size() {
+    // Create a random directory to store the DB
+    let dir = env::temp_dir();
+    let path = dir.join(format!("DB_{:?}", ObjectID::random()));
+    fs::create_dir(&path).unwrap();
+
+    // Create an authority
+    let store = Arc::new(AuthorityStore::open(&path, None));
+
+    // Make a test key pair
+    let seed = [1u8; 32];
+    let (committee, _, authority_key) =
+        init_state_parameters_from_rng(&mut StdRng::from_seed(seed));
+    let authority_state = Arc::new(init_state(committee, authority_key, store.clone()).await);
+
+    // Make a few large transactions, all of the same size.
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let random_object_ref = || {
+        (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::random(),
+        )
+    };
+    let mut transactions = vec![];
+    for _ in 0..4 {
+        let data = TransactionData::new_move_call(
+            sender,
+            random_object_ref(),
+            Identifier::new("module").unwrap(),
+            Identifier::new("function").unwrap(),
+            vec![],
+            random_object_ref(),
+            vec![CallArg::Pure(vec![0; 10_000])],
+            1000,
+        );
+        let signature = Signature::new(&data, &sender_key);
+        let certificate =
+            init_certified_transaction(Transaction::new(data, signature), &authority_state);
+        transactions.push((
+            ExecutionDigests::new(*certificate.digest(), TransactionEffectsDigest::random()),
+            bcs::serialized_size(&certificate).unwrap() as u64,
+        ));
+    }
+    let transaction_size = transactions[0].1;
+    assert!(transaction_size > 10_000);
+
+    let inner_state = authority_state.clone();
+    let _join = tokio::task::spawn(async move {
+        inner_state
+            // Make sure that a batch will not be formed due to time or count, but will be
+            // formed once the size of 3 transactions is reached.
+            .run_batch_service_with_size_limit(
+                1000,
+                3 * transaction_size,
+                Duration::from_millis(10000),
+            )
+            .await
+    });
+    let mut rx = authority_state.subscribe_batch();
+
+    for (transaction, size) in &transactions {
+        let t = authority_state.batch_notifier.ticket().expect("ok");
+        store
+            .tables
+            .executed_transaction_sizes
+            .insert(&t.seq(), size)
+            .unwrap();
+        store.side_sequence(t.seq(), transaction);
+        drop(t);
+    }
+
+    for seq in 0..3 {
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            UpdateItem::Transaction((s, _)) if s == seq
+        ));
+    }
+    // The batch closes on size, well before reaching the count threshold.
+    match rx.recv().await.unwrap() {
+        UpdateItem::Batch(batch) => {
+            assert_eq!(batch.data().size, 3);
+            assert_eq!(batch.data().next_sequence_number, 3);
+        }
+        _ => panic!("Expected a batch"),
+    }
+    assert!(matches!(
+        rx.recv().await.unwrap(),
+        UpdateItem::Transaction((3, _))
+    ));
+
+    // When we close the sending channel we also also end the service task
+    authority_state.batch_notifier.close();
+
+    _join.await.expect("No errors in task").expect("ok");
+}
+
 #[tokio::test]
 async fn test_handle_move_order_with_batch() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -507,6 +610,101 @@ async fn test_batch_store_retrieval() {
     _join.await.expect("No errors in task").expect("ok");
 }
 
+#[tokio::test]
+async fn test_batch_info_request_reverse() {
+    let dir = env::temp_dir();
+    let path = dir.join(format!("DB_{:?}", ObjectID::random()));
+    fs::create_dir(&path).unwrap();
+    let store = Arc::new(AuthorityStore::open(&path, None));
+
+    let seed = [1u8; 32];
+    let (committee, _, authority_key) =
+        init_state_parameters_from_rng(&mut StdRng::from_seed(seed));
+    let authority_state = Arc::new(init_state(committee, authority_key, store.clone()).await);
+
+    let inner_state = authority_state.clone();
+    let _join = tokio::task::spawn(async move {
+        inner_state
+            .run_batch_service(10, Duration::from_secs(6000))
+            .await
+    });
+
+    for _i in 0u64..50 {
+        let t0 = &authority_state.batch_notifier.ticket().expect("ok");
+        store
+            .tables
+            .executed_sequence
+            .insert(&t0.seq(), &ExecutionDigests::random())
+            .expect("Failed to write.");
+    }
+    while authority_state
+        .last_batch()
+        .unwrap()
+        .unwrap()
+        .data()
+        .next_sequence_number
+        < 50
+    {
+        tokio::task::yield_now().await;
+    }
+
+    // Walk back from the latest batch.
+    let reversed = authority_state
+        .handle_reverse_batch_info_request(ReverseBatchInfoRequest {
+            end: None,
+            length: 25,
+        })
+        .await
+        .unwrap()
+        .items;
+    assert!(matches!(
+        reversed.first(),
+        Some(UpdateItem::Batch(batch)) if batch.data().next_sequence_number == 50
+    ));
+
+    // Each batch comes before its transactions, newest first.
+    let mut batch_range = None;
+    let mut last_seq = u64::MAX;
+    for item in &reversed {
+        match item {
+            UpdateItem::Batch(batch) => {
+                batch_range =
+                    Some(batch.data().initial_sequence_number..batch.data().next_sequence_number);
+            }
+            UpdateItem::Transaction((seq, _)) => {
+                assert!(*seq < last_seq);
+                assert!(batch_range.as_ref().unwrap().contains(seq));
+                last_seq = *seq;
+            }
+        }
+    }
+    assert!(last_seq <= 25);
+
+    // The same items as the forward request over the same range.
+    let (forward, _) = authority_state
+        .handle_batch_info_request(BatchInfoRequest {
+            start: Some(25),
+            length: 25,
+        })
+        .await
+        .unwrap();
+    let sequence_numbers = |items: &mut dyn Iterator<Item = &UpdateItem>| {
+        items
+            .map(|item| match item {
+                UpdateItem::Batch(batch) => batch.data().next_sequence_number,
+                UpdateItem::Transaction((seq, _)) => *seq,
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        sequence_numbers(&mut reversed.iter()),
+        sequence_numbers(&mut forward.iter().rev())
+    );
+
+    authority_state.batch_notifier.close();
+    _join.await.expect("No errors in task").expect("ok");
+}
+
 #[derive(Clone)]
 struct TrustworthyAuthorityClient(Arc<Mutex<AuthorityState>>);
 
@@ -544,6 +742,17 @@ impl AuthorityAPI for TrustworthyAuthorityClient {
         })
     }
 
+    async fn handle_account_info_page_request(
+        &self,
+        _request: AccountInfoPageRequest,
+    ) -> Result<AccountInfoPageResponse, SuiError> {
+        Ok(AccountInfoPageResponse {
+            object_ids: vec![],
+            owner: Default::default(),
+            next_cursor: None,
+        })
+    }
+
     async fn handle_object_info_request(
         &self,
         _request: ObjectInfoRequest,
@@ -663,6 +872,17 @@ impl AuthorityAPI for ByzantineAuthorityClient {
         })
     }
 
+    async fn handle_account_info_page_request(
+        &self,
+        _request: AccountInfoPageRequest,
+    ) -> Result<AccountInfoPageResponse, SuiError> {
+        Ok(AccountInfoPageResponse {
+            object_ids: vec![],
+            owner: Default::default(),
+            next_cursor: None,
+        })
+    }
+
     async fn handle_object_info_request(
         &self,
         _request: ObjectInfoRequest,
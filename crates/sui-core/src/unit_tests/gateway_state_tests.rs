@@ -14,6 +14,7 @@ use sui_types::crypto::{AccountKeyPair, Signature};
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages::Transaction;
 use sui_types::object::{Object, GAS_VALUE_FOR_TESTING};
+use sui_types::protocol_config::GENESIS_PROTOCOL_VERSION;
 use sui_types::{crypto::get_key_pair, object::Owner};
 
 use crate::authority_aggregator::authority_aggregator_tests::{
@@ -107,6 +108,217 @@ async fn test_public_transfer_object() {
     assert_eq!(gateway.get_total_transaction_number().unwrap(), 1);
 }
 
+#[tokio::test]
+async fn test_read_your_writes() {
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let (addr2, _key2): (_, AccountKeyPair) = get_key_pair();
+
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+
+    let genesis_objects = vec![coin_object.clone(), gas_object.clone()];
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    public_transfer_object(
+        &gateway,
+        addr1,
+        &key1,
+        coin_object.id(),
+        gas_object.id(),
+        addr2,
+    )
+    .await
+    .unwrap();
+
+    // Reads right after the transaction observe its writes.
+    let owned = gateway.get_objects_owned_by_address(addr2).await.unwrap();
+    assert_eq!(owned.len(), 1);
+    assert_eq!(owned[0].object_id, coin_object.id());
+    let coin = gateway
+        .get_object(coin_object.id())
+        .await
+        .unwrap()
+        .into_object()
+        .unwrap();
+    assert_eq!(coin.owner, Owner::AddressOwner(addr2));
+    assert_eq!(coin.reference.version, coin_object.version().increment());
+
+    // A newer local version, written by a transaction the authorities have not caught up with,
+    // is not replaced by the stale version they return.
+    let mut newer = gateway
+        .store()
+        .get_object(&coin_object.id())
+        .unwrap()
+        .unwrap();
+    newer.data.try_as_move_mut().unwrap().increment_version();
+    gateway
+        .store()
+        .insert_object_direct(newer.compute_object_reference(), &newer)
+        .await
+        .unwrap();
+    let coin = gateway
+        .get_object(coin_object.id())
+        .await
+        .unwrap()
+        .into_object()
+        .unwrap();
+    assert_eq!(coin.reference.version, newer.version());
+}
+
+#[tokio::test]
+async fn test_get_protocol_config() {
+    let (addr1, _key1): (_, AccountKeyPair) = get_key_pair();
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let gateway = create_gateway_state(vec![gas_object]).await;
+
+    let config = gateway.get_protocol_config().await.unwrap();
+    assert_eq!(
+        config,
+        ProtocolConfig::get_for_version(GENESIS_PROTOCOL_VERSION).unwrap()
+    );
+    for client in gateway.authorities.authority_clients.values() {
+        assert_eq!(
+            config,
+            *client.authority_client().state.get_protocol_config()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_dry_run_transaction_bytes() {
+    let (addr1, _key1): (_, AccountKeyPair) = get_key_pair();
+    let (addr2, _key2): (_, AccountKeyPair) = get_key_pair();
+
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+
+    let genesis_objects = vec![coin_object.clone(), gas_object.clone()];
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    let data = gateway
+        .public_transfer_object(
+            addr1,
+            coin_object.id(),
+            Some(gas_object.id()),
+            GAS_VALUE_FOR_TESTING / 10,
+            addr2,
+        )
+        .await
+        .unwrap();
+    let effects = gateway
+        .dry_run_transaction_bytes(Base64::from_bytes(&data.to_bytes()))
+        .await
+        .unwrap();
+    assert!(effects.status.is_ok());
+    assert_eq!(effects.mutated.len(), 2);
+    assert_eq!(
+        effects.mutated_excluding_gas().next().unwrap().owner,
+        Owner::AddressOwner(addr2)
+    );
+
+    // Nothing was committed.
+    let coin = gateway
+        .store()
+        .get_object(&coin_object.id())
+        .unwrap()
+        .unwrap();
+    assert_eq!(coin.owner, Owner::AddressOwner(addr1));
+    assert_eq!(gateway.get_total_transaction_number().unwrap(), 0);
+
+    let err = gateway
+        .dry_run_transaction_bytes(Base64::from_bytes(b"not a transaction"))
+        .await
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Cannot deserialize transaction bytes into TransactionData"));
+}
+
+#[tokio::test]
+async fn test_simulate_with_overrides() {
+    let (addr1, _key1): (_, AccountKeyPair) = get_key_pair();
+
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+
+    let genesis_objects = vec![coin_object.clone(), gas_object.clone()];
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    // Split off more than the coin holds.
+    let data = gateway
+        .split_coin(
+            addr1,
+            coin_object.id(),
+            vec![GAS_VALUE_FOR_TESTING * 2],
+            Some(gas_object.id()),
+            GAS_VALUE_FOR_TESTING / 10,
+        )
+        .await
+        .unwrap();
+    let tx_bytes = Base64::from_bytes(&data.to_bytes());
+    let effects = gateway
+        .dry_run_transaction_bytes(tx_bytes.clone())
+        .await
+        .unwrap();
+    assert!(effects.status.is_err());
+
+    // As if the coin held enough, the split succeeds.
+    let richer_coin =
+        Object::with_id_owner_gas_for_testing(coin_object.id(), addr1, GAS_VALUE_FOR_TESTING * 3);
+    let simulated = gateway
+        .simulate_with_overrides(tx_bytes.clone(), vec![richer_coin])
+        .await
+        .unwrap();
+    assert!(simulated.synthetic_object_ids);
+    assert!(simulated.effects.status.is_ok());
+    assert_eq!(simulated.effects.created.len(), 1);
+
+    // The override was not stored.
+    let coin = gateway
+        .store()
+        .get_object(&coin_object.id())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        GasCoin::try_from(&coin).unwrap().value(),
+        GAS_VALUE_FOR_TESTING
+    );
+    assert_eq!(gateway.get_total_transaction_number().unwrap(), 0);
+
+    // Overrides must replace an input of the transaction.
+    let err = gateway
+        .simulate_with_overrides(tx_bytes, vec![Object::with_owner_for_testing(addr1)])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("does not match an input"));
+
+    // The inputs are checked with the overrides, so a gas coin can be given the balance a
+    // budget higher than the stored one requires.
+    let data = gateway
+        .split_coin(
+            addr1,
+            coin_object.id(),
+            vec![1],
+            Some(gas_object.id()),
+            GAS_VALUE_FOR_TESTING * 2,
+        )
+        .await
+        .unwrap();
+    let tx_bytes = Base64::from_bytes(&data.to_bytes());
+    let err = gateway
+        .dry_run_transaction_bytes(tx_bytes.clone())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Gas balance is"));
+    let richer_gas =
+        Object::with_id_owner_gas_for_testing(gas_object.id(), addr1, GAS_VALUE_FOR_TESTING * 10);
+    let simulated = gateway
+        .simulate_with_overrides(tx_bytes, vec![richer_gas])
+        .await
+        .unwrap();
+    assert!(simulated.effects.status.is_ok());
+}
+
 #[tokio::test]
 async fn test_move_call() {
     let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
@@ -130,6 +342,73 @@ async fn test_move_call() {
     assert_eq!(effects.created[0].owner, Owner::AddressOwner(addr1));
 }
 
+#[tokio::test]
+async fn test_package_cache_shared_across_gateways() {
+    let (addr1, _): (_, AccountKeyPair) = get_key_pair();
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let (authorities, _, pkg_ref) = init_local_authorities(4, vec![gas_object.clone()]).await;
+    let metrics = GatewayMetrics::new_for_tests();
+    let package_cache = Arc::new(PackageCache::new(DEFAULT_PACKAGE_CACHE_SIZE));
+
+    for _ in 0..2 {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let gateway = GatewayState::new_with_authorities(
+            Arc::new(GatewayStore::open(&path, None)),
+            authorities.clone(),
+            metrics.clone(),
+        )
+        .unwrap()
+        .with_package_cache(package_cache.clone());
+        gateway.sync_account_state(addr1).await.unwrap();
+        gateway
+            .move_call(
+                addr1,
+                pkg_ref.0,
+                "object_basics".to_string(),
+                "create".to_string(),
+                vec![],
+                vec![
+                    SuiJsonValue::new(json!(100)).unwrap(),
+                    SuiJsonValue::new(json!(addr1)).unwrap(),
+                ],
+                Some(gas_object.id()),
+                10000,
+            )
+            .await
+            .unwrap();
+    }
+
+    // The second gateway found the package in the cache filled by the first one.
+    assert_eq!(metrics.num_package_fetches.get(), 1);
+    assert!(package_cache.get(&pkg_ref.0).is_some());
+}
+
+#[test]
+fn test_package_cache_evicts_least_recently_used() {
+    let package_cache = PackageCache::new(1);
+    let stdlib = Object::new_package(
+        sui_framework::get_move_stdlib(),
+        TransactionDigest::genesis(),
+    );
+    let framework = Object::new_package(
+        sui_framework::get_sui_framework(),
+        TransactionDigest::genesis(),
+    );
+
+    // Objects other than packages are not cached.
+    let coin = Object::with_owner_for_testing(dbg_addr(1));
+    package_cache.insert(&coin);
+    assert!(package_cache.get(&coin.id()).is_none());
+
+    package_cache.insert(&stdlib);
+    assert_eq!(package_cache.get(&stdlib.id()), Some(stdlib.clone()));
+
+    // The least recently used package is evicted to make room for another one.
+    package_cache.insert(&framework);
+    assert!(package_cache.get(&stdlib.id()).is_none());
+    assert_eq!(package_cache.get(&framework.id()), Some(framework));
+}
+
 #[tokio::test]
 async fn test_publish() {
     let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
@@ -351,6 +630,92 @@ async fn test_recent_transactions() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_get_raw_effects() -> Result<(), anyhow::Error> {
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let (addr2, _): (_, AccountKeyPair) = get_key_pair();
+
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let genesis_objects = vec![coin_object.clone(), gas_object.clone()];
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    let response = public_transfer_object(
+        &gateway,
+        addr1,
+        &key1,
+        coin_object.id(),
+        gas_object.id(),
+        addr2,
+    )
+    .await?;
+    let digest = response.certificate.transaction_digest;
+
+    let raw_effects = gateway.get_raw_effects(digest).await?;
+    let effects: TransactionEffects = bcs::from_bytes(&raw_effects.to_vec()?)?;
+    let (_, stored_effects) = QueryHelpers::get_transaction(&gateway.store, &digest)?;
+    assert_eq!(effects, stored_effects);
+    assert_eq!(effects.transaction_digest, digest);
+    assert_eq!(effects.digest(), stored_effects.digest());
+
+    // Unknown transactions have no effects.
+    assert!(gateway
+        .get_raw_effects(TransactionDigest::random())
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_wait_for_transaction() -> Result<(), anyhow::Error> {
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let (addr2, _): (_, AccountKeyPair) = get_key_pair();
+
+    let coin_object = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let genesis_objects = vec![coin_object.clone(), gas_object.clone()];
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    let data = gateway
+        .public_transfer_object(
+            addr1,
+            coin_object.id(),
+            Some(gas_object.id()),
+            GAS_VALUE_FOR_TESTING / 10,
+            addr2,
+        )
+        .await?;
+    let transaction = Transaction::new(data.clone(), key1.sign(&data.to_bytes()));
+    let digest = *transaction.digest();
+
+    // The transaction is committed shortly after the waiter starts waiting.
+    let (waited, executed) = tokio::join!(
+        gateway.wait_for_transaction(digest, Duration::from_secs(30)),
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            gateway.execute_transaction(transaction).await
+        }
+    );
+    let waited = waited?;
+    assert_eq!(waited.certificate.transaction_digest, digest);
+    assert_eq!(waited.effects, executed?.effects);
+
+    // Once committed, the transaction is returned at once.
+    let committed = gateway
+        .wait_for_transaction(digest, Duration::from_millis(1))
+        .await?;
+    assert_eq!(committed.certificate.transaction_digest, digest);
+
+    // Unknown transactions time out.
+    assert!(gateway
+        .wait_for_transaction(TransactionDigest::random(), Duration::from_millis(100))
+        .await
+        .is_err());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_equivocation_resilient() {
     telemetry_subscribers::init_for_testing();
@@ -615,6 +980,45 @@ async fn test_get_owner_object() {
     assert!(objects.is_empty())
 }
 
+#[tokio::test]
+async fn test_get_owned_object_summary() {
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let gas_objects: Vec<_> = (0..3)
+        .map(|_| Object::with_owner_for_testing(addr1))
+        .collect();
+    let (gateway, pkg_ref) = create_gateway_state_with_object_basics_ref(gas_objects.clone()).await;
+
+    for gas_object in &gas_objects[..2] {
+        let tx = crate_object_move_transaction(
+            addr1,
+            &key1,
+            addr1,
+            100,
+            pkg_ref,
+            gas_object.compute_object_reference(),
+        );
+        let effects = gateway.execute_transaction(tx).await.unwrap().effects;
+        assert!(effects.status.is_ok());
+    }
+
+    let summary = gateway.get_owned_object_summary(addr1).await.unwrap();
+    assert_eq!(summary.len(), 2);
+    assert_eq!(summary[&GasCoin::type_().to_string()], 3);
+    let (object_type, count) = summary
+        .iter()
+        .find(|(type_, _)| **type_ != GasCoin::type_().to_string())
+        .unwrap();
+    assert!(object_type.ends_with("::object_basics::Object"));
+    assert_eq!(*count, 2);
+
+    let (addr2, _): (_, AccountKeyPair) = get_key_pair();
+    assert!(gateway
+        .get_owned_object_summary(addr2)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
 #[tokio::test]
 async fn test_multiple_gateways() {
     let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
@@ -719,3 +1123,59 @@ async fn test_batch_transaction() {
     assert!(effects.created.is_empty());
     assert_eq!(effects.mutated.len(), 3);
 }
+
+#[tokio::test]
+async fn test_batch_transfer_objects() {
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let (addr2, _key2): (_, AccountKeyPair) = get_key_pair();
+
+    let objects: Vec<_> = (0..3)
+        .map(|_| Object::with_owner_for_testing(addr1))
+        .collect();
+    let gas_object = Object::with_owner_for_testing(addr1);
+
+    let mut genesis_objects = objects.clone();
+    genesis_objects.push(gas_object.clone());
+    let gateway = create_gateway_state(genesis_objects).await;
+    let object_ids: Vec<_> = objects.iter().map(|o| o.id()).collect();
+
+    // Objects not owned by the signer, or listed twice, are rejected before anything is signed.
+    let mut duplicated = object_ids.clone();
+    duplicated.push(object_ids[0]);
+    assert!(gateway
+        .batch_transfer_objects(addr1, duplicated, addr2, Some(gas_object.id()), 5000)
+        .await
+        .is_err());
+    assert!(gateway
+        .batch_transfer_objects(addr2, object_ids.clone(), addr1, None, 5000)
+        .await
+        .is_err());
+
+    let data = gateway
+        .batch_transfer_objects(
+            addr1,
+            object_ids.clone(),
+            addr2,
+            Some(gas_object.id()),
+            5000,
+        )
+        .await
+        .unwrap();
+    let signature = key1.sign(&data.to_bytes());
+    let effects = gateway
+        .execute_transaction(Transaction::new(data, signature))
+        .await
+        .unwrap()
+        .effects;
+    assert!(effects.status.is_ok());
+    assert_eq!(effects.mutated.len(), 4);
+
+    let owned: HashSet<_> = gateway
+        .get_objects_owned_by_address(addr2)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|info| info.object_id)
+        .collect();
+    assert_eq!(owned, object_ids.into_iter().collect());
+}
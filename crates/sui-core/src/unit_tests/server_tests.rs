@@ -3,7 +3,7 @@
 
 use super::*;
 use crate::{
-    authority::authority_tests::init_state_with_object_id,
+    authority::authority_tests::{init_state_with_ids, init_state_with_object_id},
     authority_client::{
         AuthorityAPI, LocalAuthorityClient, LocalAuthorityClientFaultConfig,
         NetworkAuthorityClient, NetworkAuthorityClientMetrics,
@@ -12,8 +12,9 @@ use crate::{
 };
 use futures::StreamExt;
 use std::sync::Arc;
+use sui_network::api::ValidatorClient;
 use sui_types::{
-    base_types::{dbg_addr, dbg_object_id, ExecutionDigests},
+    base_types::{dbg_addr, dbg_object_id, ExecutionDigests, ObjectID},
     batch::UpdateItem,
     object::ObjectFormatOptions,
 };
@@ -90,6 +91,140 @@ async fn test_simple_request() {
     client.handle_object_info_request(req).await.unwrap();
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn test_simple_request_over_unix_socket() {
+    let sender = dbg_addr(1);
+    let object_id = dbg_object_id(1);
+    let authority_state = init_state_with_object_id(sender, object_id).await;
+
+    let consensus_address = "/ip4/127.0.0.1/tcp/0/http".parse().unwrap();
+    let (tx_consensus_listener, _rx_consensus_listener) = tokio::sync::mpsc::channel(1);
+
+    // The human-readable multiaddr format cannot hold a path with separators, so the socket
+    // address is built by hand.
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("authority.sock");
+    let address = Multiaddr::empty()
+        .with(Protocol::Unix(path.to_str().unwrap().to_owned().into()))
+        .with(Protocol::Http);
+    // A socket left behind by a previous server does not prevent binding.
+    std::os::unix::net::UnixListener::bind(&path).unwrap();
+
+    let server = AuthorityServer::new(
+        address.clone(),
+        Arc::new(authority_state),
+        consensus_address,
+        tx_consensus_listener,
+    );
+    let server_handle = server.spawn().await.unwrap();
+    assert_eq!(server_handle.address(), &address);
+
+    let client = NetworkAuthorityClient::connect(
+        server_handle.address(),
+        Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
+    )
+    .await
+    .unwrap();
+
+    let req = ObjectInfoRequest::latest_object_info_request(
+        object_id,
+        Some(ObjectFormatOptions::default()),
+    );
+    let response = client.handle_object_info_request(req).await.unwrap();
+    assert_eq!(
+        response.object().unwrap().owner,
+        sui_types::object::Owner::AddressOwner(sender)
+    );
+}
+
+#[tokio::test]
+async fn test_compressed_account_info_round_trip() {
+    let sender = dbg_addr(1);
+    let authority_state =
+        init_state_with_ids((0..2_000).map(|_| (sender, ObjectID::random()))).await;
+
+    let consensus_address = "/ip4/127.0.0.1/tcp/0/http".parse().unwrap();
+    let (tx_consensus_listener, _rx_consensus_listener) = tokio::sync::mpsc::channel(1);
+
+    let server = AuthorityServer::new(
+        "/ip4/127.0.0.1/tcp/0/http".parse().unwrap(),
+        Arc::new(authority_state),
+        consensus_address,
+        tx_consensus_listener,
+    );
+    let server_handle = server.spawn().await.unwrap();
+    let channel = mysten_network::client::connect(server_handle.address())
+        .await
+        .unwrap();
+    let request = AccountInfoRequest::from(sender);
+
+    // The server only compresses the response when the client accepts it.
+    let response = ValidatorClient::new(channel.clone())
+        .accept_gzip()
+        .account_info(request.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        response
+            .metadata()
+            .get("grpc-encoding")
+            .unwrap()
+            .to_str()
+            .unwrap(),
+        "gzip"
+    );
+    let compressed = response.into_inner();
+
+    let response = ValidatorClient::new(channel.clone())
+        .account_info(request.clone())
+        .await
+        .unwrap();
+    assert!(response.metadata().get("grpc-encoding").is_none());
+    let uncompressed = response.into_inner();
+
+    assert_eq!(compressed.object_ids.len(), 2_000);
+    assert_eq!(compressed.object_ids, uncompressed.object_ids);
+
+    // Account info is a bulk method, so the authority client asks for compression.
+    let client = NetworkAuthorityClient::new(
+        channel,
+        Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
+    );
+    let response = client.handle_account_info_request(request).await.unwrap();
+    assert_eq!(response.object_ids, uncompressed.object_ids);
+}
+
+#[tokio::test]
+async fn test_server_without_compression() {
+    let sender = dbg_addr(1);
+    let authority_state = init_state_with_ids([(sender, ObjectID::random())]).await;
+
+    let consensus_address = "/ip4/127.0.0.1/tcp/0/http".parse().unwrap();
+    let (tx_consensus_listener, _rx_consensus_listener) = tokio::sync::mpsc::channel(1);
+
+    let server = AuthorityServer::new(
+        "/ip4/127.0.0.1/tcp/0/http".parse().unwrap(),
+        Arc::new(authority_state),
+        consensus_address,
+        tx_consensus_listener,
+    )
+    .with_grpc_compression(false);
+    let server_handle = server.spawn().await.unwrap();
+    let channel = mysten_network::client::connect(server_handle.address())
+        .await
+        .unwrap();
+
+    // Clients accepting gzip get uncompressed responses.
+    let response = ValidatorClient::new(channel)
+        .accept_gzip()
+        .account_info(AccountInfoRequest::from(sender))
+        .await
+        .unwrap();
+    assert!(response.metadata().get("grpc-encoding").is_none());
+    assert_eq!(response.into_inner().object_ids.len(), 1);
+}
+
 #[tokio::test(flavor = "current_thread", start_paused = true)]
 async fn test_subscription() {
     let sender = dbg_addr(1);
@@ -52,7 +52,9 @@ impl SimpleFaucet {
 
         // Sync to have the latest status
         SuiClientCommands::SyncClientState {
-            address: Some(active_address),
+            address: Some(active_address.into()),
+            timeout: None,
+            from: None,
         }
         .execute(&mut wallet)
         .await
@@ -365,7 +367,9 @@ mod tests {
     async fn test_init_gas_queue() {
         let (_network, mut context, address) = setup_network_and_wallet().await.unwrap();
         let results = SuiClientCommands::Gas {
-            address: Some(address),
+            address: Some(address.into()),
+            min_balance: None,
+            refund_estimate: false,
         }
         .execute(&mut context)
         .await
@@ -390,7 +394,9 @@ mod tests {
     async fn test_transfer_state() {
         let (_network, mut context, address) = setup_network_and_wallet().await.unwrap();
         let results = SuiClientCommands::Gas {
-            address: Some(address),
+            address: Some(address.into()),
+            min_balance: None,
+            refund_estimate: false,
         }
         .execute(&mut context)
         .await
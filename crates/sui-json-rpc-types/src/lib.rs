@@ -34,14 +34,16 @@ use sui_types::base_types::{
     ObjectDigest, ObjectID, ObjectInfo, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
     TransactionEffectsDigest,
 };
-use sui_types::committee::EpochId;
+use sui_types::coin::Coin;
+use sui_types::committee::{Committee, EpochId, StakeUnit};
 use sui_types::crypto::{AuthorityStrongQuorumSignInfo, SignableBytes, Signature};
 use sui_types::error::SuiError;
 use sui_types::event::{Event, TransferType};
 use sui_types::event::{EventEnvelope, EventType};
 use sui_types::filter::{EventFilter, TransactionFilter};
-use sui_types::gas::GasCostSummary;
+use sui_types::gas::{GasCostSummary, MAX_GAS_BUDGET, MIN_GAS_BUDGET};
 use sui_types::gas_coin::GasCoin;
+use sui_types::id::UID;
 use sui_types::messages::{
     CallArg, CertifiedTransaction, CertifiedTransactionEffects, ExecuteTransactionResponse,
     ExecutionStatus, InputObjectKind, MoveModulePublish, ObjectArg, SingleTransactionKind,
@@ -49,8 +51,11 @@ use sui_types::messages::{
 };
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::move_package::{disassemble_modules, MovePackage};
-use sui_types::object::{Data, MoveObject, Object, ObjectFormatOptions, ObjectRead, Owner};
+use sui_types::object::{
+    Data, MoveObject, Object, ObjectFormatOptions, ObjectRead, ObjectTombstone, Owner,
+};
 use sui_types::sui_serde::{Base64, Encoding};
+use sui_types::sui_system_state::SuiSystemState;
 
 #[cfg(test)]
 #[path = "unit_tests/rpc_types_tests.rs"]
@@ -311,6 +316,14 @@ pub struct SuiTransactionResponse {
     pub parsed_data: Option<SuiParsedTransactionResponse>,
 }
 
+/// A transaction response along with the sequence number of the checkpoint which includes the
+/// transaction.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct SuiCheckpointedTransactionResponse {
+    pub response: SuiTransactionResponse,
+    pub checkpoint: CheckpointSequenceNumber,
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 pub enum SuiParsedTransactionResponse {
     Publish(SuiParsedPublishResponse),
@@ -526,6 +539,25 @@ impl From<ObjectRef> for SuiObjectRef {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename_all = "camelCase", rename = "ObjectTombstone")]
+pub struct SuiObjectTombstone {
+    /// Reference to the object as of its deletion
+    #[serde(flatten)]
+    pub reference: SuiObjectRef,
+    /// The transaction which deleted or wrapped the object
+    pub deleted_by: TransactionDigest,
+}
+
+impl From<ObjectTombstone> for SuiObjectTombstone {
+    fn from(tombstone: ObjectTombstone) -> Self {
+        Self {
+            reference: tombstone.object_ref.into(),
+            deleted_by: tombstone.deleted_by,
+        }
+    }
+}
+
 impl Display for SuiParsedObject {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let type_ = if self.data.type_().is_some() {
@@ -805,6 +837,9 @@ pub struct SuiRawMoveObject {
     #[serde_as(as = "Base64")]
     #[schemars(with = "Base64")]
     pub bcs_bytes: Vec<u8>,
+    /// The fields of the object decoded from `bcs_bytes`, only included when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<BTreeMap<String, Value>>,
 }
 
 impl From<MoveObject> for SuiRawMoveObject {
@@ -815,6 +850,7 @@ impl From<MoveObject> for SuiRawMoveObject {
             version: o.version(),
             child_count: o.child_count(),
             bcs_bytes: o.into_contents(),
+            fields: None,
         }
     }
 }
@@ -830,6 +866,7 @@ impl SuiMoveObject for SuiRawMoveObject {
             version: object.version(),
             child_count: object.child_count(),
             bcs_bytes: object.into_contents(),
+            fields: None,
         })
     }
 
@@ -842,6 +879,19 @@ impl SuiRawMoveObject {
     pub fn deserialize<'a, T: Deserialize<'a>>(&'a self) -> Result<T, anyhow::Error> {
         Ok(bcs::from_bytes(self.bcs_bytes.as_slice())?)
     }
+
+    /// Decode the fields of the object against its layout, or None if its contents don't match
+    /// the layout.
+    fn decode_fields(&self, layout: &MoveStructLayout) -> Option<BTreeMap<String, Value>> {
+        let move_struct = MoveStruct::simple_deserialize(&self.bcs_bytes, layout).ok()?;
+        match move_struct.into() {
+            SuiMoveStruct::WithTypes { fields, .. } | SuiMoveStruct::WithFields(fields) => fields
+                .into_iter()
+                .map(|(name, value)| Some((name, serde_json::to_value(value).ok()?)))
+                .collect(),
+            SuiMoveStruct::Runtime(_) => None,
+        }
+    }
 }
 
 #[serde_as]
@@ -885,20 +935,32 @@ impl TryFrom<&SuiParsedObject> for GasCoin {
 }
 
 impl TryFrom<&SuiMoveStruct> for GasCoin {
+    type Error = SuiError;
+    fn try_from(move_struct: &SuiMoveStruct) -> Result<Self, Self::Error> {
+        Coin::try_from(move_struct)
+            .map(GasCoin)
+            .map_err(|_| SuiError::TypeError {
+                error: format!("Struct is not a gas coin: {move_struct:?}"),
+            })
+    }
+}
+
+/// The fields of a coin of any currency, `Coin<T>`.
+impl TryFrom<&SuiMoveStruct> for Coin {
     type Error = SuiError;
     fn try_from(move_struct: &SuiMoveStruct) -> Result<Self, Self::Error> {
         match move_struct {
             SuiMoveStruct::WithFields(fields) | SuiMoveStruct::WithTypes { type_: _, fields } => {
                 if let Some(SuiMoveValue::Number(balance)) = fields.get("balance") {
                     if let Some(SuiMoveValue::UID { id }) = fields.get("id") {
-                        return Ok(GasCoin::new(*id, *balance));
+                        return Ok(Coin::new(UID::new(*id), *balance));
                     }
                 }
             }
             _ => {}
         }
         Err(SuiError::TypeError {
-            error: format!("Struct is not a gas coin: {move_struct:?}"),
+            error: format!("Struct is not a coin: {move_struct:?}"),
         })
     }
 }
@@ -912,6 +974,69 @@ pub struct SuiParsedPublishResponse {
     pub created_objects: Vec<SuiParsedObject>,
     /// The updated gas payment object after deducting payment
     pub updated_gas: SuiParsedObject,
+    /// Every object created by the publish transaction, tagged with what it is
+    pub classified_objects: Vec<SuiPublishedObject>,
+}
+
+impl SuiParsedPublishResponse {
+    pub fn new(
+        package: SuiObjectRef,
+        created_objects: Vec<SuiParsedObject>,
+        updated_gas: SuiParsedObject,
+    ) -> Self {
+        // The package is the only one a publish creates, created objects are Move objects.
+        let classified_objects = std::iter::once(SuiPublishedObject {
+            object_id: package.object_id,
+            kind: SuiPublishedObjectKind::Package,
+        })
+        .chain(
+            created_objects
+                .iter()
+                .filter_map(|object| match &object.data {
+                    SuiParsedData::MoveObject(o) => Some(SuiPublishedObject {
+                        object_id: object.id(),
+                        kind: SuiPublishedObjectKind::Other {
+                            object_type: o.type_.clone(),
+                        },
+                    }),
+                    SuiParsedData::Package(_) => None,
+                }),
+        )
+        .collect();
+        Self {
+            package,
+            created_objects,
+            updated_gas,
+            classified_objects,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiPublishedObject {
+    pub object_id: ObjectID,
+    #[serde(flatten)]
+    pub kind: SuiPublishedObjectKind,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, Eq, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum SuiPublishedObjectKind {
+    /// The published package itself
+    Package,
+    /// Any other object, created by the module initializers
+    #[serde(rename_all = "camelCase")]
+    Other { object_type: String },
+}
+
+impl Display for SuiPublishedObjectKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Package => write!(f, "Package"),
+            Self::Other { object_type } => write!(f, "{}", object_type),
+        }
+    }
 }
 
 impl Display for SuiParsedPublishResponse {
@@ -927,6 +1052,11 @@ impl Display for SuiParsedPublishResponse {
             )
             .bold()
         )?;
+        writeln!(writer, "Created objects:")?;
+        for object in &self.classified_objects {
+            writeln!(writer, "  {} : {}", object.object_id, object.kind)?;
+        }
+        writeln!(writer)?;
         if !self.created_objects.is_empty() {
             writeln!(
                 writer,
@@ -950,7 +1080,7 @@ pub type GetRawObjectDataResponse = SuiObjectRead<SuiRawData>;
 pub enum SuiObjectRead<T: SuiData> {
     Exists(SuiObject<T>),
     NotExists(ObjectID),
-    Deleted(SuiObjectRef),
+    Deleted(SuiObjectTombstone),
 }
 
 impl<T: SuiData> SuiObjectRead<T> {
@@ -958,8 +1088,8 @@ impl<T: SuiData> SuiObjectRead<T> {
     /// the object does not exist or is deleted.
     pub fn object(&self) -> Result<&SuiObject<T>, SuiError> {
         match &self {
-            Self::Deleted(oref) => Err(SuiError::ObjectDeleted {
-                object_ref: oref.to_object_ref(),
+            Self::Deleted(tombstone) => Err(SuiError::ObjectDeleted {
+                object_ref: tombstone.reference.to_object_ref(),
             }),
             Self::NotExists(id) => Err(SuiError::ObjectNotFound { object_id: *id }),
             Self::Exists(o) => Ok(o),
@@ -970,8 +1100,8 @@ impl<T: SuiData> SuiObjectRead<T> {
     /// the object does not exist or is deleted.
     pub fn into_object(self) -> Result<SuiObject<T>, SuiError> {
         match self {
-            Self::Deleted(oref) => Err(SuiError::ObjectDeleted {
-                object_ref: oref.to_object_ref(),
+            Self::Deleted(tombstone) => Err(SuiError::ObjectDeleted {
+                object_ref: tombstone.reference.to_object_ref(),
             }),
             Self::NotExists(id) => Err(SuiError::ObjectNotFound { object_id: id }),
             Self::Exists(o) => Ok(o),
@@ -988,8 +1118,31 @@ impl<T: SuiData> TryFrom<ObjectRead> for SuiObjectRead<T> {
             ObjectRead::Exists(_, o, layout) => {
                 Ok(SuiObjectRead::Exists(SuiObject::try_from(o, layout)?))
             }
-            ObjectRead::Deleted(oref) => Ok(SuiObjectRead::Deleted(oref.into())),
+            ObjectRead::Deleted(tombstone) => Ok(SuiObjectRead::Deleted(tombstone.into())),
+        }
+    }
+}
+
+impl SuiObjectRead<SuiRawData> {
+    /// Convert an object read, also decoding the fields of a Move object when `decode_fields` is
+    /// set. The fields are left out if the layout of the object could not be resolved.
+    pub fn try_from_read(read: ObjectRead, decode_fields: bool) -> Result<Self, anyhow::Error> {
+        let layout = match &read {
+            ObjectRead::Exists(_, _, layout) if decode_fields => layout.clone(),
+            _ => None,
+        };
+        let mut response = Self::try_from(read)?;
+        if let (
+            SuiObjectRead::Exists(SuiObject {
+                data: SuiRawData::MoveObject(object),
+                ..
+            }),
+            Some(layout),
+        ) = (&mut response, layout)
+        {
+            object.fields = object.decode_fields(&layout);
         }
+        Ok(response)
     }
 }
 
@@ -1273,6 +1426,7 @@ pub struct SuiTransactionData {
     pub transactions: Vec<SuiTransactionKind>,
     pub sender: SuiAddress,
     pub gas_payment: SuiObjectRef,
+    pub gas_price: u64,
     pub gas_budget: u64,
 }
 
@@ -1309,6 +1463,7 @@ impl TryFrom<TransactionData> for SuiTransactionData {
             transactions,
             sender: data.signer(),
             gas_payment: data.gas().into(),
+            gas_price: data.gas_price,
             gas_budget: data.gas_budget,
         })
     }
@@ -1696,6 +1851,13 @@ pub struct SuiGasCostSummary {
     pub storage_rebate: u64,
 }
 
+impl SuiGasCostSummary {
+    /// The gas charged before the storage rebate, which must fit in the gas budget.
+    pub fn gas_used(&self) -> u64 {
+        self.computation_cost + self.storage_cost
+    }
+}
+
 impl From<GasCostSummary> for SuiGasCostSummary {
     fn from(s: GasCostSummary) -> Self {
         Self {
@@ -2136,6 +2298,10 @@ pub struct MoveCallParams {
 #[serde(rename = "SuiTransactionFilter")]
 pub enum SuiTransactionFilter {
     Any,
+    /// Transactions that create, mutate, unwrap, wrap or delete the object
+    MutatedObject(ObjectID),
+    /// Transactions sent by the address, or leaving objects owned by it
+    AffectedAddress(SuiAddress),
 }
 
 impl From<SuiTransactionFilter> for TransactionFilter {
@@ -2143,6 +2309,8 @@ impl From<SuiTransactionFilter> for TransactionFilter {
         use SuiTransactionFilter::*;
         match filter {
             Any => TransactionFilter::Any,
+            MutatedObject(object_id) => TransactionFilter::MutatedObject(object_id),
+            AffectedAddress(address) => TransactionFilter::AffectedAddress(address),
         }
     }
 }
@@ -2230,3 +2398,111 @@ impl TransactionBytes {
         TransactionData::from_signable_bytes(&self.tx_bytes.to_vec()?)
     }
 }
+
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiCommitteeMember {
+    /// the authority's public key bytes, as base-64 encoded string
+    pub authority_name: Base64,
+    pub stake: StakeUnit,
+}
+
+/// How far an incremental sync of the objects owned by an address got. Objects are synced in
+/// object id order, so a sync can be resumed from `next_cursor`.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncProgress {
+    /// Number of owned objects synced so far, including those before the cursor synced from.
+    pub synced_objects: usize,
+    pub total_objects: usize,
+    /// Number of transactions processed to sync the objects of this step.
+    pub processed_transactions: usize,
+    /// Last object synced, if some owned objects remain to be synced.
+    pub next_cursor: Option<ObjectID>,
+}
+
+/// A page of the objects owned by an address, in object id order.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnedObjectsPage {
+    pub data: Vec<SuiObjectInfo>,
+    /// Last object of the page, if more objects may follow it. Requesting the objects after it
+    /// returns the next page.
+    pub next_cursor: Option<ObjectID>,
+}
+
+/// The effects of a transaction simulated with overrides of its input objects. The transaction
+/// is not signed, so it is executed under a digest derived from its data alone rather than from
+/// the signed transaction: the transaction digest of the effects, and the ids of the objects they
+/// create, are not those execution of the signed transaction assigns.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedTransactionEffects {
+    pub effects: SuiTransactionEffects,
+    /// Whether the transaction digest and the ids of the created objects are synthetic, which
+    /// they always are for a simulation.
+    pub synthetic_object_ids: bool,
+}
+
+/// Summary of the current epoch, committee and gas parameters of the network.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemState {
+    pub epoch: EpochId,
+    pub committee: Vec<SuiCommitteeMember>,
+    pub total_stake: StakeUnit,
+    pub reference_gas_price: u64,
+    pub storage_gas_price: u64,
+    pub min_gas_budget: u64,
+    pub max_gas_budget: u64,
+}
+
+impl SystemState {
+    pub fn new(committee: &Committee, system_state: &SuiSystemState) -> Self {
+        Self {
+            epoch: committee.epoch,
+            committee: committee
+                .members()
+                .map(|(name, stake)| SuiCommitteeMember {
+                    authority_name: Base64::from_bytes(name.as_ref()),
+                    stake: *stake,
+                })
+                .collect(),
+            total_stake: committee.total_votes,
+            reference_gas_price: system_state.reference_gas_price,
+            storage_gas_price: system_state.parameters.storage_gas_price,
+            min_gas_budget: *MIN_GAS_BUDGET,
+            max_gas_budget: *MAX_GAS_BUDGET,
+        }
+    }
+}
+
+impl Display for SystemState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = String::new();
+        writeln!(writer, "{}", "----- System State ----".bold())?;
+        writeln!(writer, "Epoch: {}", self.epoch)?;
+        writeln!(writer, "Reference Gas Price: {}", self.reference_gas_price)?;
+        writeln!(writer, "Storage Gas Price: {}", self.storage_gas_price)?;
+        writeln!(
+            writer,
+            "Gas Budget Range: [{}, {}]",
+            self.min_gas_budget, self.max_gas_budget
+        )?;
+        writeln!(
+            writer,
+            "Committee ({} authorities, total stake {}):",
+            self.committee.len(),
+            self.total_stake
+        )?;
+        for member in &self.committee {
+            writeln!(
+                writer,
+                "  {} : {}",
+                member.authority_name.encoded(),
+                member.stake
+            )?;
+        }
+        write!(f, "{}", writer)
+    }
+}
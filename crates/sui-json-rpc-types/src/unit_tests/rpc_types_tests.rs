@@ -4,13 +4,13 @@
 use anyhow::anyhow;
 use move_core_types::ident_str;
 use move_core_types::language_storage::StructTag;
-use move_core_types::value::{MoveStruct, MoveValue};
+use move_core_types::value::{MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue};
 
-use crate::{SuiMoveStruct, SuiMoveValue};
+use crate::{GetRawObjectDataResponse, SuiMoveStruct, SuiMoveValue, SuiObjectRead, SuiRawData};
 use sui_types::base_types::SequenceNumber;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::gas_coin::GasCoin;
-use sui_types::object::MoveObject;
+use sui_types::object::{MoveObject, ObjectRead};
 use sui_types::sui_serde::Base64;
 use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
 
@@ -45,6 +45,37 @@ fn test_move_value_to_sui_coin() {
     assert_eq!(coin.id(), gas_coin.id());
 }
 
+#[test]
+fn test_raw_object_decode_fields() {
+    let coin = GasCoin::new(ObjectID::random(), 10000);
+    let object = coin.to_object(SequenceNumber::from_u64(1));
+    let read =
+        |layout| ObjectRead::Exists(object.compute_object_reference(), object.clone(), layout);
+    let decoded_fields = |response: GetRawObjectDataResponse| match response {
+        SuiObjectRead::Exists(object) => match object.data {
+            SuiRawData::MoveObject(object) => object.fields,
+            SuiRawData::Package(_) => panic!("Expected a Move object"),
+        },
+        _ => panic!("Expected an existing object"),
+    };
+
+    let response =
+        GetRawObjectDataResponse::try_from_read(read(Some(GasCoin::layout())), true).unwrap();
+    let fields = decoded_fields(response).unwrap();
+    assert!(fields.contains_key("id"));
+    assert!(fields.contains_key("balance"));
+
+    // Fields are only decoded on request.
+    let response =
+        GetRawObjectDataResponse::try_from_read(read(Some(GasCoin::layout())), false).unwrap();
+    assert!(decoded_fields(response).is_none());
+
+    // A layout which doesn't match the contents of the object leaves the fields out.
+    let layout = MoveStructLayout::Runtime(vec![MoveTypeLayout::U64]);
+    let response = GetRawObjectDataResponse::try_from_read(read(Some(layout)), true).unwrap();
+    assert!(decoded_fields(response).is_none());
+}
+
 #[test]
 fn test_move_value_to_string() {
     let test_string = "Some test string";
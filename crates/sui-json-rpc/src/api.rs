@@ -8,15 +8,19 @@ use jsonrpsee_proc_macros::rpc;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
     GatewayTxSeqNumber, GetObjectDataResponse, GetRawObjectDataResponse, MoveFunctionArgType,
-    RPCTransactionRequestParams, SuiEventEnvelope, SuiEventFilter, SuiExecuteTransactionResponse,
-    SuiGasCostSummary, SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct,
-    SuiObjectInfo, SuiTransactionFilter, SuiTransactionResponse, SuiTypeTag, TransactionBytes,
+    OwnedObjectsPage, RPCTransactionRequestParams, SimulatedTransactionEffects,
+    SuiCheckpointedTransactionResponse, SuiEventEnvelope, SuiEventFilter,
+    SuiExecuteTransactionResponse, SuiGasCostSummary, SuiMoveNormalizedFunction,
+    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo, SuiTransactionEffects,
+    SuiTransactionFilter, SuiTransactionResponse, SuiTypeTag, SyncProgress, SystemState,
+    TransactionBytes,
 };
 use sui_open_rpc_macros::open_rpc;
-use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::crypto::SignatureScheme;
 use sui_types::messages::ExecuteTransactionRequestType;
 use sui_types::object::Owner;
+use sui_types::protocol_config::ProtocolConfig;
 use sui_types::sui_serde::Base64;
 
 /// Maximum number of events returned in an event query.
@@ -41,6 +45,54 @@ pub trait RpcGatewayApi {
         /// signer's public key, as base-64 encoded string
         pub_key: Base64,
     ) -> RpcResult<SuiTransactionResponse>;
+
+    /// Execute the transaction locally without signing or submitting it, and return its effects.
+    #[method(name = "dryRunTransaction")]
+    async fn dry_run_transaction(
+        &self,
+        /// transaction data bytes, as base-64 encoded string
+        tx_bytes: Base64,
+    ) -> RpcResult<SuiTransactionEffects>;
+
+    /// Execute the transaction locally as `dryRunTransaction` does, as if some of its input
+    /// objects had the given state, and return its effects. Nothing is signed, submitted or
+    /// stored. The ids of the objects the simulation creates are synthetic.
+    #[method(name = "simulateWithOverrides")]
+    async fn simulate_with_overrides(
+        &self,
+        /// transaction data bytes, as base-64 encoded string
+        tx_bytes: Base64,
+        /// BCS serialized objects replacing the inputs of the same ID and version, as base-64
+        /// encoded strings
+        overrides: Vec<Base64>,
+    ) -> RpcResult<SimulatedTransactionEffects>;
+
+    /// Execute the transaction, then wait for it to be included in a certified checkpoint, and
+    /// return the response along with the sequence number of that checkpoint.
+    #[method(name = "executeTransactionAndWaitForCheckpoint")]
+    async fn execute_transaction_and_wait_for_checkpoint(
+        &self,
+        /// transaction data bytes, as base-64 encoded string
+        tx_bytes: Base64,
+        /// Flag of the signature scheme that is used.
+        sig_scheme: SignatureScheme,
+        /// transaction signature, as base-64 encoded string
+        signature: Base64,
+        /// signer's public key, as base-64 encoded string
+        pub_key: Base64,
+        /// how long to wait for the checkpoint, in milliseconds
+        timeout_ms: u64,
+    ) -> RpcResult<SuiCheckpointedTransactionResponse>;
+
+    /// Wait for a transaction to be committed and return it, or fail once the timeout elapses.
+    #[method(name = "waitForTransaction")]
+    async fn wait_for_transaction(
+        &self,
+        /// the digest of the queried transaction
+        digest: TransactionDigest,
+        /// how long to wait for the transaction, in milliseconds
+        timeout_ms: u64,
+    ) -> RpcResult<SuiTransactionResponse>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Wallet Sync API")]
@@ -53,6 +105,19 @@ pub trait WalletSyncApi {
         /// the Sui address to be synchronized
         address: SuiAddress,
     ) -> RpcResult<()>;
+
+    /// Synchronize some of the objects owned by an address with validators, in object id order.
+    /// Synchronizing from the returned cursor until none is returned synchronizes them all.
+    #[method(name = "syncAccountStateIncremental")]
+    async fn sync_account_state_incremental(
+        &self,
+        /// the Sui address to be synchronized
+        address: SuiAddress,
+        /// the object to synchronize after, from the start if not specified
+        cursor: Option<ObjectID>,
+        /// the maximum number of objects to synchronize
+        limit: usize,
+    ) -> RpcResult<SyncProgress>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Read API")]
@@ -74,6 +139,29 @@ pub trait RpcReadApi {
         object_id: ObjectID,
     ) -> RpcResult<Vec<SuiObjectInfo>>;
 
+    /// Return a page of the objects owned by an address, in object id order, along with the
+    /// cursor to request the next page from.
+    #[method(name = "getObjectsOwnedByAddressPage")]
+    async fn get_objects_owned_by_address_page(
+        &self,
+        /// the owner's Sui address
+        address: SuiAddress,
+        /// the object to return the objects after, from the first one if not specified
+        cursor: Option<ObjectID>,
+        /// the maximum number of objects to return
+        limit: usize,
+        /// only return the objects of this type, e.g. `0x2::coin::Coin<0x2::sui::SUI>`, or of any instantiation of it if it has no type arguments
+        type_filter: Option<String>,
+    ) -> RpcResult<OwnedObjectsPage>;
+
+    /// Return the number of objects owned by an address, per object type.
+    #[method(name = "getOwnedObjectSummary")]
+    async fn get_owned_object_summary(
+        &self,
+        /// the owner's Sui address
+        address: SuiAddress,
+    ) -> RpcResult<BTreeMap<String, u64>>;
+
     /// Return the total number of transactions known to the server.
     #[method(name = "getTotalTransactionNumber")]
     async fn get_total_transaction_number(&self) -> RpcResult<u64>;
@@ -111,6 +199,32 @@ pub trait RpcReadApi {
         /// the ID of the queried object
         object_id: ObjectID,
     ) -> RpcResult<GetObjectDataResponse>;
+
+    /// Return the current epoch, committee and gas parameters of the network
+    #[method(name = "getSystemState")]
+    async fn get_system_state(&self) -> RpcResult<SystemState>;
+
+    /// Return the gas price transactions should use by default, i.e. the reference gas price
+    /// of the network, or the minimum gas price if no reference gas price is set
+    #[method(name = "getReferenceGasPrice")]
+    async fn get_reference_gas_price(&self) -> RpcResult<u64>;
+
+    /// Return the protocol parameters of the current epoch, such as the maximum transaction size
+    /// and the minimum gas price
+    #[method(name = "getProtocolConfig")]
+    async fn get_protocol_config(&self) -> RpcResult<ProtocolConfig>;
+
+    /// Return the owner of an object as of the given version, or null if the object did not
+    /// exist, or was deleted or wrapped, at that version. Versions newer than the latest one
+    /// are rejected
+    #[method(name = "getOwnerAtVersion")]
+    async fn get_owner_at_version(
+        &self,
+        /// the ID of the queried object
+        object_id: ObjectID,
+        /// the version of the object to query the owner at
+        version: SequenceNumber,
+    ) -> RpcResult<Option<Owner>>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Full Node API")]
@@ -202,6 +316,28 @@ pub trait RpcFullNodeReadApi {
         /// the recipient's Sui address
         addr: SuiAddress,
     ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>>;
+
+    /// Return list of transactions a Sui address sent or received objects from, in sequence
+    /// number order.
+    #[method(name = "getTransactionsTouchingAddress")]
+    async fn get_transactions_touching_addr(
+        &self,
+        /// the Sui address
+        addr: SuiAddress,
+        /// the matching transactions' sequence number will be greater than or equals to the starting sequence number
+        start: GatewayTxSeqNumber,
+        /// maximum size of the result
+        count: u64,
+    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>>;
+
+    /// Re-execute an already executed transaction against the objects it read, without
+    /// committing anything, and return the raw BCS serialized effects, base64 encoded.
+    #[method(name = "replayTransaction")]
+    async fn replay_transaction(
+        &self,
+        /// the digest of the transaction to replay
+        digest: TransactionDigest,
+    ) -> RpcResult<Base64>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Transaction Builder API")]
@@ -337,6 +473,24 @@ pub trait RpcTransactionBuilder {
         /// the gas budget, the transaction will fail if the gas cost exceed the budget
         gas_budget: u64,
     ) -> RpcResult<TransactionBytes>;
+
+    /// Create an unsigned batched transaction transferring several objects to the same address,
+    /// so that either all of them or none are transferred. Every object must be owned by the signer
+    /// and its type must allow public transfers
+    #[method(name = "batchTransferObjects")]
+    async fn batch_transfer_objects(
+        &self,
+        /// the transaction signer's Sui address
+        signer: SuiAddress,
+        /// the IDs of the objects to be transferred
+        object_ids: Vec<ObjectID>,
+        /// the recipient's Sui address
+        recipient: SuiAddress,
+        /// gas object to be used in this transaction, the gateway will pick one from the signer's possession if not provided
+        gas: Option<ObjectID>,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget
+        gas_budget: u64,
+    ) -> RpcResult<TransactionBytes>;
 }
 
 #[open_rpc(namespace = "sui", tag = "BCS API")]
@@ -348,7 +502,17 @@ pub trait RpcBcsApi {
         &self,
         /// the id of the object
         object_id: ObjectID,
+        /// whether to also return the fields of a Move object, decoded against its layout, false if not provided
+        decode_fields: Option<bool>,
     ) -> RpcResult<GetRawObjectDataResponse>;
+
+    /// Return the raw BCS serialized effects of a specified transaction, base64 encoded.
+    #[method(name = "getRawTransactionEffects")]
+    async fn get_raw_effects(
+        &self,
+        /// the digest of the queried transaction
+        digest: TransactionDigest,
+    ) -> RpcResult<Base64>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Transaction Subscription")]
@@ -13,7 +13,8 @@ use sui_core::authority::AuthorityState;
 use sui_core::gateway_state::GatewayClient;
 use sui_json_rpc_types::GetRawObjectDataResponse;
 use sui_open_rpc::Module;
-use sui_types::base_types::ObjectID;
+use sui_types::base_types::{ObjectID, TransactionDigest};
+use sui_types::sui_serde::Base64;
 
 pub struct BcsApiImpl {
     client: ClientStateAdaptor,
@@ -42,22 +43,48 @@ impl ClientStateAdaptor {
     async fn get_raw_object(
         &self,
         object_id: ObjectID,
+        decode_fields: bool,
     ) -> Result<GetRawObjectDataResponse, anyhow::Error> {
         match self {
-            ClientStateAdaptor::Gateway(client) => client.get_raw_object(object_id).await,
-            ClientStateAdaptor::FullNode(client) => client
-                .get_object_read(&object_id)
-                .await
-                .map_err(|e| anyhow!("{e}"))?
-                .try_into(),
+            ClientStateAdaptor::Gateway(client) => {
+                client.get_raw_object(object_id, decode_fields).await
+            }
+            ClientStateAdaptor::FullNode(client) => GetRawObjectDataResponse::try_from_read(
+                client
+                    .get_object_read(&object_id)
+                    .await
+                    .map_err(|e| anyhow!("{e}"))?,
+                decode_fields,
+            ),
+        }
+    }
+
+    async fn get_raw_effects(&self, digest: TransactionDigest) -> Result<Base64, anyhow::Error> {
+        match self {
+            ClientStateAdaptor::Gateway(client) => client.get_raw_effects(digest).await,
+            ClientStateAdaptor::FullNode(client) => {
+                let (_, effects) = client.get_transaction(digest).await?;
+                Ok(Base64::from_bytes(&bcs::to_bytes(&effects)?))
+            }
         }
     }
 }
 
 #[async_trait]
 impl RpcBcsApiServer for BcsApiImpl {
-    async fn get_raw_object(&self, object_id: ObjectID) -> RpcResult<GetRawObjectDataResponse> {
-        Ok(self.client.get_raw_object(object_id).await?)
+    async fn get_raw_object(
+        &self,
+        object_id: ObjectID,
+        decode_fields: Option<bool>,
+    ) -> RpcResult<GetRawObjectDataResponse> {
+        Ok(self
+            .client
+            .get_raw_object(object_id, decode_fields.unwrap_or(false))
+            .await?)
+    }
+
+    async fn get_raw_effects(&self, digest: TransactionDigest) -> RpcResult<Base64> {
+        Ok(self.client.get_raw_effects(digest).await?)
     }
 }
 
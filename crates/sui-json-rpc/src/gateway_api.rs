@@ -1,6 +1,9 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
+use std::time::Duration;
+
 use anyhow::anyhow;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
@@ -15,17 +18,22 @@ use crate::SuiRpcModule;
 use sui_core::gateway_state::{GatewayClient, GatewayTxSeqNumber};
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, RPCTransactionRequestParams, SuiObjectInfo, SuiTransactionResponse,
-    SuiTypeTag, TransactionBytes,
+    GetObjectDataResponse, OwnedObjectsPage, RPCTransactionRequestParams,
+    SimulatedTransactionEffects, SuiCheckpointedTransactionResponse, SuiObjectInfo,
+    SuiTransactionEffects, SuiTransactionResponse, SuiTypeTag, SyncProgress, SystemState,
+    TransactionBytes,
 };
 use sui_open_rpc::Module;
 use sui_types::crypto::SignatureScheme;
 use sui_types::sui_serde::Base64;
 use sui_types::{
-    base_types::{ObjectID, SuiAddress, TransactionDigest},
+    base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest},
     crypto,
     crypto::SignableBytes,
     messages::{Transaction, TransactionData},
+    object::{Object, Owner},
+    parse_sui_struct_tag,
+    protocol_config::ProtocolConfig,
 };
 
 pub struct RpcGatewayImpl {
@@ -76,18 +84,70 @@ impl RpcGatewayApiServer for RpcGatewayImpl {
         signature: Base64,
         pub_key: Base64,
     ) -> RpcResult<SuiTransactionResponse> {
-        let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
-        let flag = vec![sig_scheme.flag()];
-        let signature = crypto::Signature::from_bytes(
-            &[&*flag, &*signature.to_vec()?, &pub_key.to_vec()?].concat(),
-        )
-        .map_err(|e| anyhow!(e))?;
-        let result = self
-            .client
-            .execute_transaction(Transaction::new(data, signature))
-            .await;
+        let tx = to_transaction(tx_bytes, sig_scheme, signature, pub_key)?;
+        let result = self.client.execute_transaction(tx).await;
         Ok(result?)
     }
+
+    async fn dry_run_transaction(&self, tx_bytes: Base64) -> RpcResult<SuiTransactionEffects> {
+        Ok(self.client.dry_run_transaction_bytes(tx_bytes).await?)
+    }
+
+    async fn simulate_with_overrides(
+        &self,
+        tx_bytes: Base64,
+        overrides: Vec<Base64>,
+    ) -> RpcResult<SimulatedTransactionEffects> {
+        let overrides = overrides
+            .iter()
+            .map(|object| Ok(bcs::from_bytes::<Object>(&object.to_vec()?)?))
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+        Ok(self
+            .client
+            .simulate_with_overrides(tx_bytes, overrides)
+            .await?)
+    }
+
+    async fn execute_transaction_and_wait_for_checkpoint(
+        &self,
+        tx_bytes: Base64,
+        sig_scheme: SignatureScheme,
+        signature: Base64,
+        pub_key: Base64,
+        timeout_ms: u64,
+    ) -> RpcResult<SuiCheckpointedTransactionResponse> {
+        let tx = to_transaction(tx_bytes, sig_scheme, signature, pub_key)?;
+        Ok(self
+            .client
+            .execute_transaction_and_wait_for_checkpoint(tx, Duration::from_millis(timeout_ms))
+            .await?)
+    }
+
+    async fn wait_for_transaction(
+        &self,
+        digest: TransactionDigest,
+        timeout_ms: u64,
+    ) -> RpcResult<SuiTransactionResponse> {
+        Ok(self
+            .client
+            .wait_for_transaction(digest, Duration::from_millis(timeout_ms))
+            .await?)
+    }
+}
+
+fn to_transaction(
+    tx_bytes: Base64,
+    sig_scheme: SignatureScheme,
+    signature: Base64,
+    pub_key: Base64,
+) -> Result<Transaction, anyhow::Error> {
+    let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
+    let flag = vec![sig_scheme.flag()];
+    let signature = crypto::Signature::from_bytes(
+        &[&*flag, &*signature.to_vec()?, &pub_key.to_vec()?].concat(),
+    )
+    .map_err(|e| anyhow!(e))?;
+    Ok(Transaction::new(data, signature))
 }
 
 impl SuiRpcModule for RpcGatewayImpl {
@@ -107,6 +167,22 @@ impl WalletSyncApiServer for GatewayWalletSyncApiImpl {
         self.client.sync_account_state(address).await?;
         Ok(())
     }
+
+    async fn sync_account_state_incremental(
+        &self,
+        address: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> RpcResult<SyncProgress> {
+        debug!(
+            "sync_account_state_incremental : {} from {:?}",
+            address, cursor
+        );
+        Ok(self
+            .client
+            .sync_account_state_incremental(address, cursor, limit)
+            .await?)
+    }
 }
 
 impl SuiRpcModule for GatewayWalletSyncApiImpl {
@@ -137,6 +213,33 @@ impl RpcReadApiServer for GatewayReadApiImpl {
         Ok(self.client.get_objects_owned_by_object(object_id).await?)
     }
 
+    async fn get_objects_owned_by_address_page(
+        &self,
+        address: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: usize,
+        type_filter: Option<String>,
+    ) -> RpcResult<OwnedObjectsPage> {
+        debug!(
+            "get_objects_owned_by_address_page : {} from {:?}",
+            address, cursor
+        );
+        let type_filter = type_filter
+            .map(|type_filter| parse_sui_struct_tag(&type_filter))
+            .transpose()?;
+        Ok(self
+            .client
+            .get_objects_owned_by_address_page(address, cursor, limit, type_filter)
+            .await?)
+    }
+
+    async fn get_owned_object_summary(
+        &self,
+        address: SuiAddress,
+    ) -> RpcResult<BTreeMap<String, u64>> {
+        Ok(self.client.get_owned_object_summary(address).await?)
+    }
+
     async fn get_object(&self, object_id: ObjectID) -> RpcResult<GetObjectDataResponse> {
         Ok(self.client.get_object(object_id).await?)
     }
@@ -166,6 +269,26 @@ impl RpcReadApiServer for GatewayReadApiImpl {
     ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
         Ok(self.client.get_transactions_in_range(start, end)?)
     }
+
+    async fn get_system_state(&self) -> RpcResult<SystemState> {
+        Ok(self.client.get_system_state().await?)
+    }
+
+    async fn get_reference_gas_price(&self) -> RpcResult<u64> {
+        Ok(self.client.get_reference_gas_price().await?)
+    }
+
+    async fn get_protocol_config(&self) -> RpcResult<ProtocolConfig> {
+        Ok(self.client.get_protocol_config().await?)
+    }
+
+    async fn get_owner_at_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> RpcResult<Option<Owner>> {
+        Ok(self.client.get_owner_at_version(object_id, version).await?)
+    }
 }
 
 impl SuiRpcModule for GatewayReadApiImpl {
@@ -318,6 +441,21 @@ impl RpcTransactionBuilderServer for TransactionBuilderImpl {
         .await?;
         Ok(TransactionBytes::from_data(data)?)
     }
+
+    async fn batch_transfer_objects(
+        &self,
+        signer: SuiAddress,
+        object_ids: Vec<ObjectID>,
+        recipient: SuiAddress,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> RpcResult<TransactionBytes> {
+        let data = self
+            .client
+            .batch_transfer_objects(signer, object_ids, recipient, gas, gas_budget)
+            .await?;
+        Ok(TransactionBytes::from_data(data)?)
+    }
 }
 
 impl SuiRpcModule for TransactionBuilderImpl {
@@ -4,7 +4,11 @@
 use jsonrpsee_core::server::access_control::AccessControlBuilder;
 use jsonrpsee_core::server::rpc_module::RpcModule;
 
+use crate::api::EventStreamingApiOpenRpc;
+use crate::bcs_api::BcsApiImpl;
+use crate::gateway_api::{GatewayWalletSyncApiImpl, RpcGatewayImpl, TransactionBuilderImpl};
 use crate::http_server::{HttpServerBuilder, HttpServerHandle};
+use crate::read_api::{FullNodeApi, ReadApi};
 use crate::ws_server::{WsServerBuilder, WsServerHandle};
 
 use jsonrpsee::types::Params;
@@ -81,6 +85,22 @@ pub fn sui_rpc_doc() -> Project {
     )
 }
 
+/// The OpenRPC document of every method served by the gateway and the full node, along with the
+/// JSON schema of their params and results.
+pub fn sui_rpc_spec() -> Project {
+    let mut open_rpc = sui_rpc_doc();
+    open_rpc.add_module(TransactionBuilderImpl::rpc_doc_module());
+    open_rpc.add_module(RpcGatewayImpl::rpc_doc_module());
+    open_rpc.add_module(ReadApi::rpc_doc_module());
+    open_rpc.add_module(FullNodeApi::rpc_doc_module());
+    open_rpc.add_module(BcsApiImpl::rpc_doc_module());
+    open_rpc.add_module(EventStreamingApiOpenRpc::module_doc());
+    // TODO: Re-enable this when event read API is ready
+    //open_rpc.add_module(EventReadApiOpenRpc::module_doc());
+    open_rpc.add_module(GatewayWalletSyncApiImpl::rpc_doc_module());
+    open_rpc
+}
+
 impl JsonRpcServerBuilder {
     pub fn new(
         use_websocket: bool,
@@ -12,17 +12,20 @@ use move_binary_format::normalized::{Module as NormalizedModule, Type};
 use move_core_types::identifier::Identifier;
 use std::collections::BTreeMap;
 use std::sync::Arc;
-use sui_core::authority::AuthorityState;
+use sui_core::authority::{AuthorityState, MAX_ITEMS_LIMIT};
 use sui_core::gateway_state::GatewayTxSeqNumber;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, MoveFunctionArgType, ObjectValueKind, SuiMoveNormalizedFunction,
-    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo, SuiTransactionEffects,
-    SuiTransactionResponse,
+    GetObjectDataResponse, MoveFunctionArgType, ObjectValueKind, OwnedObjectsPage,
+    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo,
+    SuiTransactionEffects, SuiTransactionResponse, SystemState,
 };
 use sui_open_rpc::Module;
-use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::move_package::normalize_modules;
 use sui_types::object::{Data, ObjectRead, Owner};
+use sui_types::parse_sui_struct_tag;
+use sui_types::protocol_config::ProtocolConfig;
+use sui_types::sui_serde::Base64;
 
 // An implementation of the read portion of the Gateway JSON-RPC interface intended for use in
 // Fullnodes.
@@ -74,6 +77,47 @@ impl RpcReadApiServer for ReadApi {
             .collect())
     }
 
+    async fn get_objects_owned_by_address_page(
+        &self,
+        address: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: usize,
+        type_filter: Option<String>,
+    ) -> RpcResult<OwnedObjectsPage> {
+        // Same bound as the validators serving account info pages.
+        let limit = limit.min(MAX_ITEMS_LIMIT as usize);
+        if limit == 0 {
+            return Err(anyhow!("Cannot get zero objects at a time").into());
+        }
+        let type_filter = type_filter
+            .map(|type_filter| parse_sui_struct_tag(&type_filter))
+            .transpose()?;
+        let (objects, next_cursor) = self
+            .state
+            .get_owner_objects_page(Owner::AddressOwner(address), cursor, limit, type_filter)
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        Ok(OwnedObjectsPage {
+            data: objects.into_iter().map(SuiObjectInfo::from).collect(),
+            next_cursor,
+        })
+    }
+
+    async fn get_owned_object_summary(
+        &self,
+        address: SuiAddress,
+    ) -> RpcResult<BTreeMap<String, u64>> {
+        let mut summary = BTreeMap::new();
+        for info in self
+            .state
+            .get_owner_objects(Owner::AddressOwner(address))
+            .map_err(|e| anyhow!("{e}"))?
+        {
+            *summary.entry(info.type_).or_default() += 1;
+        }
+        Ok(summary)
+    }
+
     async fn get_object(&self, object_id: ObjectID) -> RpcResult<GetObjectDataResponse> {
         Ok(self
             .state
@@ -114,6 +158,38 @@ impl RpcReadApiServer for ReadApi {
             parsed_data: None,
         })
     }
+
+    async fn get_system_state(&self) -> RpcResult<SystemState> {
+        Ok(self
+            .state
+            .get_system_state()
+            .await
+            .map_err(|e| anyhow!("{e}"))?)
+    }
+
+    async fn get_reference_gas_price(&self) -> RpcResult<u64> {
+        Ok(self
+            .state
+            .get_reference_gas_price()
+            .await
+            .map_err(|e| anyhow!("{e}"))?)
+    }
+
+    async fn get_protocol_config(&self) -> RpcResult<ProtocolConfig> {
+        Ok((*self.state.get_protocol_config()).clone())
+    }
+
+    async fn get_owner_at_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> RpcResult<Option<Owner>> {
+        Ok(self
+            .state
+            .get_owner_at_version(object_id, version)
+            .await
+            .map_err(|e| anyhow!("{e}"))?)
+    }
 }
 
 impl SuiRpcModule for ReadApi {
@@ -277,6 +353,30 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
     ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
         Ok(self.state.get_transactions_to_addr(addr).await?)
     }
+
+    async fn get_transactions_touching_addr(
+        &self,
+        addr: SuiAddress,
+        start: GatewayTxSeqNumber,
+        count: u64,
+    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
+        Ok(self
+            .state
+            .get_transactions_touching_addr(addr, start, count)
+            .await?)
+    }
+
+    async fn replay_transaction(&self, digest: TransactionDigest) -> RpcResult<Base64> {
+        let (cert, effects) = self.state.get_transaction(digest).await?;
+        let replayed_effects = self
+            .state
+            .replay_certificate(&cert, &effects)
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        Ok(Base64::from_bytes(
+            &bcs::to_bytes(&replayed_effects).map_err(|e| anyhow!("{e}"))?,
+        ))
+    }
 }
 
 impl SuiRpcModule for FullNodeApi {
@@ -3,7 +3,9 @@
 
 use anyhow::{anyhow, bail};
 use move_binary_format::{
-    access::ModuleAccess, binary_views::BinaryIndexedView, file_format::SignatureToken,
+    access::ModuleAccess,
+    binary_views::BinaryIndexedView,
+    file_format::{SignatureToken, StructHandleIndex},
 };
 use move_core_types::account_address::AccountAddress;
 use move_core_types::{
@@ -17,7 +19,8 @@ use std::collections::VecDeque;
 use std::fmt::{Debug, Formatter};
 use sui_types::base_types::{decode_bytes_hex, ObjectID, SuiAddress};
 use sui_types::move_package::MovePackage;
-use sui_verifier::entry_points_verifier::is_tx_context;
+use sui_verifier::entry_points_verifier::{is_tx_context, RESOLVED_SUI_ID};
+use sui_verifier::{format_signature_token, resolve_struct};
 
 const HEX_PREFIX: &str = "0x";
 
@@ -246,10 +249,11 @@ fn is_homogeneous_rec(curr_q: &mut VecDeque<&JsonValue>) -> bool {
 }
 
 fn resolve_primtive_arg(
+    view: &BinaryIndexedView,
     arg: &SuiJsonValue,
     param: &SignatureToken,
 ) -> Result<Vec<u8>, anyhow::Error> {
-    let move_type_layout = make_prim_move_type_layout(param)?;
+    let move_type_layout = make_prim_move_type_layout(view, param)?;
     // Check that the args are what we expect or can be converted
     // Then return the serialized bcs value
     arg.to_bcs_bytes(&move_type_layout).map_err(|e| {
@@ -261,7 +265,10 @@ fn resolve_primtive_arg(
     })
 }
 
-fn make_prim_move_type_layout(param: &SignatureToken) -> Result<MoveTypeLayout, anyhow::Error> {
+fn make_prim_move_type_layout(
+    view: &BinaryIndexedView,
+    param: &SignatureToken,
+) -> Result<MoveTypeLayout, anyhow::Error> {
     Ok(match param {
         SignatureToken::Bool => MoveTypeLayout::Bool,
         SignatureToken::U8 => MoveTypeLayout::U8,
@@ -270,18 +277,21 @@ fn make_prim_move_type_layout(param: &SignatureToken) -> Result<MoveTypeLayout,
         SignatureToken::Address => MoveTypeLayout::Address,
         SignatureToken::Signer => MoveTypeLayout::Signer,
         SignatureToken::Vector(inner) => {
-            MoveTypeLayout::Vector(Box::new(make_prim_move_type_layout(inner)?))
+            MoveTypeLayout::Vector(Box::new(make_prim_move_type_layout(view, inner)?))
         }
+        // An ID only wraps an address, so both have the same BCS encoding
+        SignatureToken::Struct(idx) if is_id(view, *idx) => MoveTypeLayout::Address,
+        // Vectors of objects pass the entry function verifier, but cannot be given as call args
         SignatureToken::Struct(_)
         | SignatureToken::StructInstantiation(_, _)
         | SignatureToken::Reference(_)
         | SignatureToken::MutableReference(_)
         | SignatureToken::TypeParameter(_) => {
-            debug_assert!(
-                false,
-                "Should be unreachable. Args should be primitive types only"
-            );
-            bail!("Could not serialize argument of type {:?}", param)
+            bail!(
+                "Could not serialize argument of type {}. Only primitive values, IDs and vectors \
+                of them can be passed by value",
+                format_signature_token(view, param)
+            )
         }
     })
 }
@@ -306,18 +316,38 @@ fn resolve_object_arg(idx: usize, arg: &SuiJsonValue) -> Result<ObjectID, anyhow
     }
 }
 
+fn is_id(view: &BinaryIndexedView, idx: StructHandleIndex) -> bool {
+    resolve_struct(view, idx) == RESOLVED_SUI_ID
+}
+
 fn resolve_call_arg(
+    view: &BinaryIndexedView,
     idx: usize,
     arg: &SuiJsonValue,
     param: &SignatureToken,
 ) -> Result<SuiJsonCallArg, anyhow::Error> {
+    // Arrays are only converted to vectors, so reject them early with a clearer error than the
+    // conversion would give
+    if matches!(arg.0, JsonValue::Array(_)) && !matches!(param, SignatureToken::Vector(_)) {
+        bail!(
+            "Unexpected array {} at pos {}. Expected a single value of type {}",
+            arg.0,
+            idx,
+            format_signature_token(view, param)
+        )
+    }
     Ok(match param {
         SignatureToken::Bool
         | SignatureToken::U8
         | SignatureToken::U64
         | SignatureToken::U128
         | SignatureToken::Address
-        | SignatureToken::Vector(_) => SuiJsonCallArg::Pure(resolve_primtive_arg(arg, param)?),
+        | SignatureToken::Vector(_) => {
+            SuiJsonCallArg::Pure(resolve_primtive_arg(view, arg, param)?)
+        }
+        SignatureToken::Struct(sidx) if is_id(view, *sidx) => {
+            SuiJsonCallArg::Pure(resolve_primtive_arg(view, arg, param)?)
+        }
 
         SignatureToken::Struct(_)
         | SignatureToken::StructInstantiation(_, _)
@@ -332,6 +362,7 @@ fn resolve_call_arg(
 }
 
 fn resolve_call_args(
+    view: &BinaryIndexedView,
     json_args: &[SuiJsonValue],
     parameter_types: &[SignatureToken],
 ) -> Result<Vec<SuiJsonCallArg>, anyhow::Error> {
@@ -339,7 +370,7 @@ fn resolve_call_args(
         .iter()
         .zip(parameter_types)
         .enumerate()
-        .map(|(idx, (arg, param))| resolve_call_arg(idx, arg, param))
+        .map(|(idx, (arg, param))| resolve_call_arg(view, idx, arg, param))
         .collect()
 }
 
@@ -394,7 +425,11 @@ pub fn resolve_move_function_args(
     }
 
     // Check that the args are valid and convert to the correct format
-    resolve_call_args(&combined_args_json, parameters)
+    resolve_call_args(
+        &BinaryIndexedView::Module(&module),
+        &combined_args_json,
+        parameters,
+    )
 }
 
 fn convert_string_to_u128(s: &str) -> Result<u128, anyhow::Error> {
@@ -477,6 +477,90 @@ fn test_basic_args_linter_top_level() {
     );
 }
 
+#[test]
+fn test_basic_args_linter_vectors() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../sui/src/unit_tests/data/move_call_args_linter");
+    let compiled_modules =
+        sui_framework::build_and_verify_package(&path, move_package::BuildConfig::default())
+            .unwrap();
+    let example_package = Object::new_package(compiled_modules, TransactionDigest::genesis());
+    let example_package = example_package.data.try_as_package().unwrap();
+
+    let module = Identifier::new("object_basics").unwrap();
+    let object_id = ObjectID::random();
+
+    /*
+    Function signature:
+            public entry fun set_value_from_bytes(o: &mut Object, bytes: vector<u8>)
+    */
+    let function = Identifier::new("set_value_from_bytes").unwrap();
+    let args = vec![json!(object_id.to_hex_literal()), json!([1, 2, 3])]
+        .into_iter()
+        .map(|q| SuiJsonValue::new(q).unwrap())
+        .collect();
+
+    let args = resolve_move_function_args(example_package, module.clone(), function, args).unwrap();
+
+    assert_eq!(args[0], SuiJsonCallArg::Object(object_id));
+    assert_eq!(
+        args[1],
+        SuiJsonCallArg::Pure(bcs::to_bytes(&vec![1u8, 2, 3]).unwrap())
+    );
+
+    /*
+    Function signature:
+            public entry fun set_value_from_ids(o: &mut Object, ids: vector<ID>)
+    */
+    let function = Identifier::new("set_value_from_ids").unwrap();
+    let ids = vec![ObjectID::random(), ObjectID::random()];
+    let args = vec![
+        json!(object_id.to_hex_literal()),
+        json!([ids[0].to_hex_literal(), ids[1].to_hex_literal()]),
+    ]
+    .into_iter()
+    .map(|q| SuiJsonValue::new(q).unwrap())
+    .collect();
+
+    let args = resolve_move_function_args(example_package, module.clone(), function, args).unwrap();
+
+    // An ID is encoded like the address it wraps
+    let addresses: Vec<AccountAddress> = ids.into_iter().map(AccountAddress::from).collect();
+    assert_eq!(args[0], SuiJsonCallArg::Object(object_id));
+    assert_eq!(
+        args[1],
+        SuiJsonCallArg::Pure(bcs::to_bytes(&addresses).unwrap())
+    );
+
+    /*
+    Function signature:
+            public entry fun set_value(o: &mut Object, value: u64)
+    */
+    let function = Identifier::new("set_value").unwrap();
+    let args = vec![json!(object_id.to_hex_literal()), json!([1, 2])]
+        .into_iter()
+        .map(|q| SuiJsonValue::new(q).unwrap())
+        .collect();
+
+    let err = resolve_move_function_args(example_package, module.clone(), function, args)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Unexpected array [1,2] at pos 1. Expected a single value of type u64"));
+
+    // Arrays are not object IDs either
+    let function = Identifier::new("set_value").unwrap();
+    let args = vec![json!([object_id.to_hex_literal()]), json!(1)]
+        .into_iter()
+        .map(|q| SuiJsonValue::new(q).unwrap())
+        .collect();
+
+    let err = resolve_move_function_args(example_package, module, function, args)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Unexpected array"), "{err}");
+    assert!(err.contains("at pos 0"), "{err}");
+}
+
 #[test]
 fn test_convert_address_from_bcs() {
     let bcs_bytes = [
@@ -46,6 +46,15 @@ fn main() -> Result<()> {
                 .codec_path(codec_path)
                 .build(),
         )
+        .method(
+            Method::builder()
+                .name("account_info_page")
+                .route_name("AccountInfoPage")
+                .input_type("sui_types::messages::AccountInfoPageRequest")
+                .output_type("sui_types::messages::AccountInfoPageResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
         .method(
             Method::builder()
                 .name("object_info")
@@ -55,6 +64,17 @@ fn main() -> Result<()> {
                 .codec_path(codec_path)
                 .build(),
         )
+        .method(
+            Method::builder()
+                .name("multi_object_info")
+                .route_name("MultiObjectInfo")
+                .input_type("sui_types::messages::MultiObjectInfoRequest")
+                .output_type(
+                    "Vec<sui_types::error::SuiResult<sui_types::messages::ObjectInfoResponse>>",
+                )
+                .codec_path(codec_path)
+                .build(),
+        )
         .method(
             Method::builder()
                 .name("transaction_info")
@@ -64,6 +84,34 @@ fn main() -> Result<()> {
                 .codec_path(codec_path)
                 .build(),
         )
+        .method(
+            Method::builder()
+                .name("transaction_info_batch")
+                .route_name("TransactionInfoBatch")
+                .input_type("sui_types::messages::TransactionInfoBatchRequest")
+                .output_type("Vec<sui_types::messages::TransactionInfoResponse>")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            Method::builder()
+                .name("events_by_transaction")
+                .route_name("EventsByTransaction")
+                .input_type("sui_types::messages::EventsByTransactionRequest")
+                .output_type("Vec<sui_types::event::Event>")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            Method::builder()
+                .name("subscribe_events")
+                .route_name("SubscribeEvents")
+                .input_type("sui_types::messages::EventSubscriptionRequest")
+                .output_type("sui_types::messages::EventResponseItem")
+                .server_streaming()
+                .codec_path(codec_path)
+                .build(),
+        )
         .method(
             Method::builder()
                 .name("checkpoint")
@@ -83,6 +131,15 @@ fn main() -> Result<()> {
                 .codec_path(codec_path)
                 .build(),
         )
+        .method(
+            Method::builder()
+                .name("reverse_batch_info")
+                .route_name("ReverseBatchInfo")
+                .input_type("sui_types::messages::ReverseBatchInfoRequest")
+                .output_type("sui_types::messages::ReverseBatchInfoResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
         .method(
             Method::builder()
                 .name("epoch_info")
@@ -92,6 +149,24 @@ fn main() -> Result<()> {
                 .codec_path(codec_path)
                 .build(),
         )
+        .method(
+            Method::builder()
+                .name("node_stats")
+                .route_name("NodeStats")
+                .input_type("sui_types::messages::NodeStatsRequest")
+                .output_type("sui_types::messages::NodeStatsResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            Method::builder()
+                .name("coin_supply")
+                .route_name("CoinSupply")
+                .input_type("sui_types::messages::CoinSupplyRequest")
+                .output_type("sui_types::messages::CoinSupplyResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
         .build();
 
     Builder::new()
@@ -5,21 +5,33 @@ use axum::{
     extract::Extension,
     http::StatusCode,
     routing::{get, post},
-    Router,
+    Json, Router,
 };
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use sui_core::authority::AuthorityState;
 use telemetry_subscribers::FilterHandle;
+use tokio::sync::OnceCell;
 use tracing::info;
 
 const LOGGING_ROUTE: &str = "/logging";
+const NATIVE_FUNCTIONS_ROUTE: &str = "/native-functions";
 
-pub fn start_admin_server(port: u16, filter_handle: FilterHandle) {
+/// Start the admin server. It is started before the node, so that logging can be adjusted while
+/// the node starts, and serves the routes needing the node state once `state` is set.
+pub fn start_admin_server(
+    port: u16,
+    filter_handle: FilterHandle,
+    state: Arc<OnceCell<Arc<AuthorityState>>>,
+) {
     let filter = filter_handle.get().unwrap();
 
     let app = Router::new()
         .route(LOGGING_ROUTE, get(get_filter))
         .route(LOGGING_ROUTE, post(set_filter))
-        .layer(Extension(filter_handle));
+        .route(NATIVE_FUNCTIONS_ROUTE, get(get_native_functions))
+        .layer(Extension(filter_handle))
+        .layer(Extension(state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
     info!(
@@ -55,3 +67,26 @@ async fn set_filter(
         Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
     }
 }
+
+/// The native functions supported by the node, as `[address, module, function]` triples.
+async fn get_native_functions(
+    Extension(state): Extension<Arc<OnceCell<Arc<AuthorityState>>>>,
+) -> Result<Json<Vec<(String, String, String)>>, (StatusCode, String)> {
+    let state = state.get().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "The node is starting".to_string(),
+    ))?;
+    Ok(Json(
+        state
+            .native_functions()
+            .into_iter()
+            .map(|(address, module, function)| {
+                (
+                    address.to_hex_literal(),
+                    module.into_string(),
+                    function.into_string(),
+                )
+            })
+            .collect(),
+    ))
+}
@@ -17,7 +17,7 @@ use sui_core::authority_server::ValidatorService;
 use sui_core::safe_client::SafeClientMetrics;
 use sui_core::transaction_streamer::TransactionStreamer;
 use sui_core::{
-    authority::{AuthorityState, AuthorityStore},
+    authority::{AuthorityState, AuthorityStore, CoinSupplyCache},
     authority_active::{gossip::GossipMetrics, ActiveAuthority},
     authority_client::{
         make_network_authority_client_sets_from_genesis,
@@ -61,6 +61,7 @@ pub struct SuiNode {
     _ws_subscription_service: Option<WsServerHandle>,
     _batch_subsystem_handle: tokio::task::JoinHandle<Result<()>>,
     _post_processing_subsystem_handle: Option<tokio::task::JoinHandle<Result<()>>>,
+    _store_pruning_handle: Option<tokio::task::JoinHandle<()>>,
     _gossip_handle: Option<tokio::task::JoinHandle<()>>,
     _execute_driver_handle: tokio::task::JoinHandle<()>,
     _checkpoint_process_handle: Option<tokio::task::JoinHandle<()>>,
@@ -146,9 +147,12 @@ impl SuiNode {
                 genesis,
                 &prometheus_registry,
                 tx_reconfigure_consensus,
+                config.authority_batch_config.clone(),
+                config.enable_coin_supply.then(CoinSupplyCache::default),
             )
             .await,
         );
+        state.report_inconsistent_effects(config.num_effects_checked_at_startup)?;
 
         let mut net_config = mysten_network::config::Config::new();
         net_config.connect_timeout = Some(Duration::from_secs(5));
@@ -163,12 +167,14 @@ impl SuiNode {
             make_network_authority_client_sets_from_system_state(
                 &sui_system_state,
                 &net_config,
+                config.grpc_compression,
                 network_metrics.clone(),
             )
         } else {
             make_network_authority_client_sets_from_genesis(
                 genesis,
                 &net_config,
+                config.grpc_compression,
                 network_metrics.clone(),
             )
         }?;
@@ -219,9 +225,12 @@ impl SuiNode {
             active_authority.clone().spawn_node_sync_process().await;
             None
         } else if config.enable_gossip {
-            // TODO: get degree from config file.
-            let degree = 4;
-            Some(active_authority.clone().spawn_gossip_process(degree).await)
+            Some(
+                active_authority
+                    .clone()
+                    .spawn_gossip_process(config.gossip_degree)
+                    .await,
+            )
         } else {
             None
         };
@@ -243,9 +252,14 @@ impl SuiNode {
         let batch_subsystem_handle = {
             // Start batch system so that this node can be followed
             let batch_state = state.clone();
+            let batch_config = config.authority_batch_config.clone();
             tokio::task::spawn(async move {
                 batch_state
-                    .run_batch_service(1000, Duration::from_secs(1))
+                    .run_batch_service_with_size_limit(
+                        batch_config.min_batch_size,
+                        batch_config.max_batch_size_bytes,
+                        batch_config.max_delay(),
+                    )
                     .await
                     .map_err(Into::into)
             })
@@ -264,6 +278,16 @@ impl SuiNode {
                 None
             };
 
+        let store_pruning_handle = if config.authority_store_pruning_config.is_enabled() {
+            let pruning_state = state.clone();
+            let pruning_config = config.authority_store_pruning_config.clone();
+            Some(tokio::task::spawn(async move {
+                pruning_state.run_store_pruning(pruning_config).await
+            }))
+        } else {
+            None
+        };
+
         let registry = prometheus_registry.clone();
         let validator_service = if config.consensus_config().is_some() {
             Some(
@@ -281,8 +305,11 @@ impl SuiNode {
             let mut server_builder = server_conf.server_builder();
 
             if let Some(validator_service) = validator_service {
-                server_builder =
-                    server_builder.add_service(ValidatorServer::new(validator_service));
+                let mut validator_server = ValidatorServer::new(validator_service);
+                if config.grpc_compression {
+                    validator_server = validator_server.send_gzip().accept_gzip();
+                }
+                server_builder = server_builder.add_service(validator_server);
             }
 
             let server = server_builder
@@ -311,6 +338,7 @@ impl SuiNode {
             _checkpoint_process_handle: checkpoint_process_handle,
             _batch_subsystem_handle: batch_subsystem_handle,
             _post_processing_subsystem_handle: post_processing_subsystem_handle,
+            _store_pruning_handle: store_pruning_handle,
             state,
             active: active_authority,
             quorum_driver_handler,
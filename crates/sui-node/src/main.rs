@@ -5,9 +5,11 @@ use anyhow::Result;
 use clap::Parser;
 use multiaddr::Multiaddr;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 use sui_config::{Config, NodeConfig};
 use sui_telemetry::send_telemetry_event;
+use tokio::sync::OnceCell;
 use tokio::task;
 use tokio::time::sleep;
 use tracing::warn;
@@ -122,9 +124,16 @@ async fn main() -> Result<()> {
         }
     });
 
-    sui_node::admin::start_admin_server(config.admin_interface_port, filter_handle);
+    let node_state = Arc::new(OnceCell::new());
+    sui_node::admin::start_admin_server(
+        config.admin_interface_port,
+        filter_handle,
+        node_state.clone(),
+    );
 
     let node = sui_node::SuiNode::start(&config).await?;
+    // Only set here, so it cannot already be set.
+    let _ = node_state.set(node.state());
     node.wait().await?;
 
     Ok(())
@@ -270,6 +270,7 @@ impl RpcExampleProvider {
                 version: object.version(),
                 child_count: None,
                 bcs_bytes: object.into_contents(),
+                fields: None,
             }),
             owner: Owner::AddressOwner(SuiAddress::from(ObjectID::new(self.rng.gen()))),
             previous_transaction: TransactionDigest::new(self.rng.gen()),
@@ -21,15 +21,10 @@ use sui::client_commands::{EXAMPLE_NFT_DESCRIPTION, EXAMPLE_NFT_NAME, EXAMPLE_NF
 use sui_config::genesis_config::GenesisConfig;
 use sui_config::SUI_CLIENT_CONFIG;
 use sui_json::SuiJsonValue;
-use sui_json_rpc::api::EventStreamingApiOpenRpc;
 use sui_json_rpc::api::RpcReadApiClient;
 use sui_json_rpc::api::RpcTransactionBuilderClient;
 use sui_json_rpc::api::WalletSyncApiClient;
-use sui_json_rpc::bcs_api::BcsApiImpl;
-use sui_json_rpc::gateway_api::{GatewayWalletSyncApiImpl, RpcGatewayImpl, TransactionBuilderImpl};
-use sui_json_rpc::read_api::{FullNodeApi, ReadApi};
-use sui_json_rpc::sui_rpc_doc;
-use sui_json_rpc::SuiRpcModule;
+use sui_json_rpc::sui_rpc_spec;
 use sui_json_rpc_types::{
     GetObjectDataResponse, MoveFunctionArgType, ObjectValueKind, SuiData, SuiObjectInfo,
     SuiTransactionResponse, TransactionBytes,
@@ -73,17 +68,7 @@ const OWNED_OBJECT_SAMPLE_FILE_PATH: &str =
 async fn main() {
     let options = Options::parse();
 
-    let mut open_rpc = sui_rpc_doc();
-    open_rpc.add_module(TransactionBuilderImpl::rpc_doc_module());
-    open_rpc.add_module(RpcGatewayImpl::rpc_doc_module());
-    open_rpc.add_module(ReadApi::rpc_doc_module());
-    open_rpc.add_module(FullNodeApi::rpc_doc_module());
-    open_rpc.add_module(BcsApiImpl::rpc_doc_module());
-    open_rpc.add_module(EventStreamingApiOpenRpc::module_doc());
-    // TODO: Re-enable this when event read API is ready
-    //open_rpc.add_module(EventReadApiOpenRpc::module_doc());
-    open_rpc.add_module(GatewayWalletSyncApiImpl::rpc_doc_module());
-
+    let mut open_rpc = sui_rpc_spec();
     open_rpc.add_examples(RpcExampleProvider::new().examples());
 
     match options.action {
@@ -221,6 +206,7 @@ async fn create_package_object_response(
         build_config,
         gas: None,
         gas_budget: 10000,
+        gas_price: None,
     }
     .execute(context)
     .await?;
@@ -250,10 +236,13 @@ async fn create_transfer_response(
     coins: &[SuiObjectInfo],
 ) -> Result<SuiTransactionResponse, anyhow::Error> {
     let response = SuiClientCommands::Transfer {
-        to: address,
+        to: address.into(),
         object_id: coins.first().unwrap().object_id,
         gas: None,
-        gas_budget: 1000,
+        gas_budget: Some(1000),
+        gas_price: None,
+        yes: true,
+        authority: None,
     }
     .execute(context)
     .await?;
@@ -275,14 +264,17 @@ async fn create_transfer_sui_response(
     coins: &[SuiObjectInfo],
 ) -> Result<SuiTransactionResponse, anyhow::Error> {
     let response = SuiClientCommands::TransferSui {
-        to: address,
-        sui_coin_object_id: coins.first().unwrap().object_id,
+        to: address.into(),
+        sui_coin_object_id: Some(coins.first().unwrap().object_id),
+        input_coins: None,
         gas_budget: 1000,
+        gas_price: None,
         amount: Some(10),
+        yes: true,
     }
     .execute(context)
     .await?;
-    if let SuiClientCommandResult::TransferSui(certificate, effects) = response {
+    if let SuiClientCommandResult::TransferSui(_, certificate, effects) = response {
         Ok(SuiTransactionResponse {
             certificate,
             effects,
@@ -308,6 +300,7 @@ async fn create_hero_response(
         gas: None,
         build_config,
         gas_budget: 10000,
+        gas_price: None,
     }
     .execute(context)
     .await?;
@@ -329,7 +322,8 @@ async fn create_hero_response(
             type_args: vec![],
             args: vec![game_info, coin],
             gas: None,
-            gas_budget: 10000,
+            gas_budget: Some(10000),
+            gas_price: None,
         }
         .execute(context)
         .await?;
@@ -407,9 +401,10 @@ async fn create_coin_split_response(
     let result = SuiClientCommands::SplitCoin {
         coin_id: coins.first().unwrap().object_id,
         amounts: Some(vec![20, 20, 20, 20, 20]),
-        count: 0,
+        count: None,
         gas: None,
-        gas_budget: 1000,
+        gas_budget: Some(1000),
+        gas_price: None,
     }
     .execute(context)
     .await?;
@@ -441,7 +436,8 @@ async fn get_nft_response(
         type_args: vec![],
         args,
         gas: None,
-        gas_budget: 10000,
+        gas_budget: Some(10000),
+        gas_price: None,
     }
     .execute(context)
     .await?;
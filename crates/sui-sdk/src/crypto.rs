@@ -22,8 +22,8 @@ use rand::rngs::adapter::ReadRng;
 
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{
-    get_key_pair_from_rng, random_key_pair_by_type_from_rng, EncodeDecodeBase64, PublicKey,
-    Signature, SignatureScheme, SuiKeyPair,
+    get_key_pair_from_rng, random_key_pair_by_type_from_rng, AccountKeyPair, EncodeDecodeBase64,
+    PublicKey, Signature, SignatureScheme, SuiKeyPair,
 };
 
 #[derive(Serialize, Deserialize)]
@@ -38,6 +38,7 @@ pub trait AccountKeystore: Send + Sync {
     fn sign(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error>;
     fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error>;
     fn keys(&self) -> Vec<PublicKey>;
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error>;
 }
 
 impl KeystoreType {
@@ -94,6 +95,12 @@ impl AccountKeystore for FileBasedKeystore {
     fn keys(&self) -> Vec<PublicKey> {
         self.keys.values().map(|key| key.public()).collect()
     }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        self.keys
+            .get(address)
+            .ok_or_else(|| anyhow!("Cannot find key for address: [{address}]"))
+    }
 }
 
 impl FileBasedKeystore {
@@ -104,7 +111,13 @@ impl FileBasedKeystore {
             kp_strings
                 .iter()
                 .map(|kpstr| {
-                    let key = SuiKeyPair::decode_base64(kpstr);
+                    let key = SuiKeyPair::decode_base64(kpstr).or_else(|e| {
+                        // Keystores written before keys were tagged with their scheme flag only
+                        // hold ed25519 keys.
+                        AccountKeyPair::decode_base64(kpstr)
+                            .map(SuiKeyPair::Ed25519SuiKeyPair)
+                            .map_err(|_| e)
+                    });
                     key.map(|k| (Into::<SuiAddress>::into(&k.public()), k))
                 })
                 .collect::<Result<BTreeMap<_, _>, _>>()
@@ -179,6 +192,10 @@ impl SuiKeystore {
         self.keys().iter().map(|k| k.into()).collect()
     }
 
+    pub fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        self.0.get_key(address)
+    }
+
     pub fn signer(&self, signer: SuiAddress) -> impl Signer<Signature> + '_ {
         KeystoreSigner::new(&*self.0, signer)
     }
@@ -270,6 +287,12 @@ impl AccountKeystore for InMemKeystore {
     fn keys(&self) -> Vec<PublicKey> {
         self.keys.values().map(|key| key.public()).collect()
     }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        self.keys
+            .get(address)
+            .ok_or_else(|| anyhow!("Cannot find key for address: [{address}]"))
+    }
 }
 
 impl InMemKeystore {
@@ -296,4 +319,8 @@ impl AccountKeystore for Box<dyn AccountKeystore> {
     fn keys(&self) -> Vec<PublicKey> {
         (**self).keys()
     }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        (**self).get_key(address)
+    }
 }
@@ -1,9 +1,13 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
 use std::fmt::Write;
 use std::fmt::{Display, Formatter};
 use std::sync::Arc;
+use std::time::Duration;
+
+use move_core_types::language_storage::StructTag;
 
 use anyhow::anyhow;
 use futures::StreamExt;
@@ -25,15 +29,23 @@ use sui_json_rpc::api::RpcBcsApiClient;
 use sui_json_rpc::api::RpcFullNodeReadApiClient;
 use sui_json_rpc::api::RpcGatewayApiClient;
 use sui_json_rpc::api::RpcReadApiClient;
+use sui_json_rpc::api::TransactionStreamingApiClient;
 use sui_json_rpc::api::WalletSyncApiClient;
 pub use sui_json_rpc_types as rpc_types;
 use sui_json_rpc_types::{
-    GatewayTxSeqNumber, GetObjectDataResponse, GetRawObjectDataResponse, SuiEventEnvelope,
-    SuiEventFilter, SuiObjectInfo, SuiTransactionResponse,
+    GatewayTxSeqNumber, GetObjectDataResponse, GetRawObjectDataResponse, OwnedObjectsPage,
+    SimulatedTransactionEffects, SuiCheckpointedTransactionResponse, SuiEventEnvelope,
+    SuiEventFilter, SuiObjectInfo, SuiTransactionEffects, SuiTransactionFilter,
+    SuiTransactionResponse, SyncProgress, SystemState,
 };
 pub use sui_types as types;
-use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
-use sui_types::messages::Transaction;
+use sui_types::base_types::{
+    AuthorityName, ObjectID, SequenceNumber, SuiAddress, TransactionDigest,
+};
+use sui_types::messages::{Transaction, TransactionEffects, TransactionInfoResponse};
+use sui_types::object::{Object, Owner};
+use sui_types::protocol_config::ProtocolConfig;
+use sui_types::sui_serde::Base64;
 use types::messages::ExecuteTransactionRequestType;
 
 use crate::transaction_builder::TransactionBuilder;
@@ -122,6 +134,43 @@ impl ReadApi {
         })
     }
 
+    /// Return a page of at most `limit` objects owned by an address after `cursor`, in object id
+    /// order, only those whose type matches `type_filter` if given.
+    pub async fn get_objects_owned_by_address_page(
+        &self,
+        address: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: usize,
+        type_filter: Option<StructTag>,
+    ) -> anyhow::Result<OwnedObjectsPage> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c, _) => {
+                c.get_objects_owned_by_address_page(
+                    address,
+                    cursor,
+                    limit,
+                    type_filter.map(|tag| tag.to_string()),
+                )
+                .await?
+            }
+            SuiClientApi::Embedded(c) => {
+                c.get_objects_owned_by_address_page(address, cursor, limit, type_filter)
+                    .await?
+            }
+        })
+    }
+
+    /// Return the number of objects owned by an address, per object type.
+    pub async fn get_owned_object_summary(
+        &self,
+        address: SuiAddress,
+    ) -> anyhow::Result<BTreeMap<String, u64>> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c, _) => c.get_owned_object_summary(address).await?,
+            SuiClientApi::Embedded(c) => c.get_owned_object_summary(address).await?,
+        })
+    }
+
     pub async fn get_parsed_object(
         &self,
         object_id: ObjectID,
@@ -137,8 +186,15 @@ impl ReadApi {
         object_id: ObjectID,
     ) -> anyhow::Result<GetRawObjectDataResponse> {
         Ok(match &*self.api {
-            SuiClientApi::Rpc(c, _) => c.get_raw_object(object_id).await?,
-            SuiClientApi::Embedded(c) => c.get_raw_object(object_id).await?,
+            SuiClientApi::Rpc(c, _) => c.get_raw_object(object_id, None).await?,
+            SuiClientApi::Embedded(c) => c.get_raw_object(object_id, false).await?,
+        })
+    }
+
+    pub async fn get_raw_effects(&self, digest: TransactionDigest) -> anyhow::Result<Base64> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c, _) => c.get_raw_effects(digest).await?,
+            SuiClientApi::Embedded(c) => c.get_raw_effects(digest).await?,
         })
     }
 
@@ -179,6 +235,38 @@ impl ReadApi {
             SuiClientApi::Embedded(c) => c.get_transaction(digest).await?,
         })
     }
+
+    pub async fn get_system_state(&self) -> anyhow::Result<SystemState> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c, _) => c.get_system_state().await?,
+            SuiClientApi::Embedded(c) => c.get_system_state().await?,
+        })
+    }
+
+    pub async fn get_reference_gas_price(&self) -> anyhow::Result<u64> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c, _) => c.get_reference_gas_price().await?,
+            SuiClientApi::Embedded(c) => c.get_reference_gas_price().await?,
+        })
+    }
+
+    pub async fn get_protocol_config(&self) -> anyhow::Result<ProtocolConfig> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c, _) => c.get_protocol_config().await?,
+            SuiClientApi::Embedded(c) => c.get_protocol_config().await?,
+        })
+    }
+
+    pub async fn get_owner_at_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> anyhow::Result<Option<Owner>> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c, _) => c.get_owner_at_version(object_id, version).await?,
+            SuiClientApi::Embedded(c) => c.get_owner_at_version(object_id, version).await?,
+        })
+    }
 }
 
 pub struct FullNodeApi(Arc<SuiClientApi>);
@@ -251,6 +339,35 @@ impl FullNodeApi {
         }
         .await?)
     }
+
+    pub async fn get_transactions_touching_addr(
+        &self,
+        addr: SuiAddress,
+        start: GatewayTxSeqNumber,
+        count: u64,
+    ) -> anyhow::Result<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c, _) => c.get_transactions_touching_addr(addr, start, count),
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        }
+        .await?)
+    }
+
+    /// Re-execute a transaction on the full node and return the effects it produced.
+    pub async fn replay_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<TransactionEffects> {
+        let raw_effects = match &*self.0 {
+            SuiClientApi::Rpc(c, _) => c.replay_transaction(digest).await?,
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        };
+        Ok(bcs::from_bytes(&raw_effects.to_vec()?)?)
+    }
 }
 pub struct EventApi(Arc<SuiClientApi>);
 
@@ -268,6 +385,20 @@ impl EventApi {
             _ => Err(anyhow!("Subscription only supported by WebSocket client.")),
         }
     }
+
+    pub async fn subscribe_transaction(
+        &self,
+        filter: SuiTransactionFilter,
+    ) -> anyhow::Result<impl Stream<Item = Result<SuiTransactionResponse, anyhow::Error>>> {
+        match &*self.0 {
+            SuiClientApi::Rpc(_, Some(c)) => {
+                let subscription: Subscription<SuiTransactionResponse> =
+                    c.subscribe_transaction(filter).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(anyhow!("Subscription only supported by WebSocket client.")),
+        }
+    }
 }
 pub struct QuorumDriver {
     api: Arc<SuiClientApi>,
@@ -288,6 +419,80 @@ impl QuorumDriver {
         })
     }
 
+    /// Execute the transaction bytes locally on the gateway without submitting them, and return
+    /// the resulting effects.
+    pub async fn dry_run_transaction(
+        &self,
+        tx_bytes: Base64,
+    ) -> anyhow::Result<SuiTransactionEffects> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c, _) => c.dry_run_transaction(tx_bytes).await?,
+            SuiClientApi::Embedded(c) => c.dry_run_transaction_bytes(tx_bytes).await?,
+        })
+    }
+
+    /// Dry run the transaction bytes on the gateway as if the given input objects had the given
+    /// state, without submitting them or storing the overrides. The ids of the objects the
+    /// simulation creates are synthetic.
+    pub async fn simulate_with_overrides(
+        &self,
+        tx_bytes: Base64,
+        overrides: Vec<Object>,
+    ) -> anyhow::Result<SimulatedTransactionEffects> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c, _) => {
+                let overrides = overrides
+                    .iter()
+                    .map(|object| Ok(Base64::from_bytes(&bcs::to_bytes(object)?)))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                c.simulate_with_overrides(tx_bytes, overrides).await?
+            }
+            SuiClientApi::Embedded(c) => c.simulate_with_overrides(tx_bytes, overrides).await?,
+        })
+    }
+
+    /// Execute the transaction, then wait up to `timeout` for it to be included in a certified
+    /// checkpoint.
+    pub async fn execute_transaction_and_wait_for_checkpoint(
+        &self,
+        tx: Transaction,
+        timeout: Duration,
+    ) -> anyhow::Result<SuiCheckpointedTransactionResponse> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c, _) => {
+                let (tx_bytes, flag, signature, pub_key) = tx.to_network_data_for_execution();
+                c.execute_transaction_and_wait_for_checkpoint(
+                    tx_bytes,
+                    flag,
+                    signature,
+                    pub_key,
+                    timeout.as_millis() as u64,
+                )
+                .await?
+            }
+            SuiClientApi::Embedded(c) => {
+                c.execute_transaction_and_wait_for_checkpoint(tx, timeout)
+                    .await?
+            }
+        })
+    }
+
+    /// Wait up to `timeout` for a transaction to be committed, typically one submitted by another
+    /// client, and return it.
+    pub async fn wait_for_transaction(
+        &self,
+        digest: TransactionDigest,
+        timeout: Duration,
+    ) -> anyhow::Result<SuiTransactionResponse> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c, _) => {
+                c.wait_for_transaction(digest, timeout.as_millis() as u64)
+                    .await?
+            }
+            SuiClientApi::Embedded(c) => c.wait_for_transaction(digest, timeout).await?,
+        })
+    }
+
     pub async fn execute_transaction_by_fullnode(
         &self,
         tx: Transaction,
@@ -310,6 +515,21 @@ impl QuorumDriver {
             SuiClientApi::Embedded(_c) => unimplemented!(),
         })
     }
+
+    /// Debug only: submit the transaction to a single authority, bypassing quorum aggregation,
+    /// and return the authority's raw response.
+    pub async fn execute_transaction_on_authority(
+        &self,
+        tx: Transaction,
+        authority: AuthorityName,
+    ) -> anyhow::Result<TransactionInfoResponse> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(_, _) => {
+                return Err(anyhow!("Method not supported by RPC gateway client."))
+            }
+            SuiClientApi::Embedded(c) => c.execute_transaction_on_authority(tx, authority).await?,
+        })
+    }
 }
 
 pub struct WalletSyncApi(Arc<SuiClientApi>);
@@ -322,6 +542,24 @@ impl WalletSyncApi {
         }
         Ok(())
     }
+
+    pub async fn sync_account_state_incremental(
+        &self,
+        address: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> anyhow::Result<SyncProgress> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c, _) => {
+                c.sync_account_state_incremental(address, cursor, limit)
+                    .await?
+            }
+            SuiClientApi::Embedded(c) => {
+                c.sync_account_state_incremental(address, cursor, limit)
+                    .await?
+            }
+        })
+    }
 }
 
 impl SuiClient {
@@ -343,6 +581,13 @@ impl SuiClient {
     pub fn wallet_sync_api(&self) -> &WalletSyncApi {
         &self.wallet_sync_api
     }
+    /// The gateway backing this client, if it is an embedded gateway.
+    pub fn embedded_gateway(&self) -> Option<GatewayClient> {
+        match &*self.read_api.api {
+            SuiClientApi::Embedded(c) => Some(c.clone()),
+            SuiClientApi::Rpc(..) => None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize)]
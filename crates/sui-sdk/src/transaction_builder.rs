@@ -20,7 +20,7 @@ use sui_types::messages::{
     TransactionKind, TransferObject,
 };
 use sui_types::move_package::MovePackage;
-use sui_types::object::Object;
+use sui_types::object::{Object, Owner};
 use sui_types::{coin, fp_ensure, SUI_FRAMEWORK_OBJECT_ID};
 
 use crate::ReadApi;
@@ -390,6 +390,63 @@ impl TransactionBuilder {
         ))
     }
 
+    pub async fn batch_transfer_objects(
+        &self,
+        signer: SuiAddress,
+        object_ids: Vec<ObjectID>,
+        recipient: SuiAddress,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        fp_ensure!(
+            !object_ids.is_empty(),
+            SuiError::InvalidBatchTransaction {
+                error: "Batch transfer must transfer at least one object".to_owned(),
+            }
+            .into()
+        );
+        let mut tx_kinds = Vec::new();
+        for (i, object_id) in object_ids.iter().enumerate() {
+            fp_ensure!(
+                !object_ids[..i].contains(object_id),
+                SuiError::DuplicateObjectRefInput.into()
+            );
+            let response = self.0.get_object(*object_id).await?;
+            let object = response.object()?;
+            match object.owner {
+                Owner::AddressOwner(owner) if owner == signer => {}
+                Owner::Shared => {
+                    return Err(anyhow!(
+                        "Object {object_id} is shared and cannot be transferred"
+                    ))
+                }
+                Owner::Immutable => {
+                    return Err(anyhow!(
+                        "Object {object_id} is immutable and cannot be transferred"
+                    ))
+                }
+                owner => {
+                    return Err(anyhow!(
+                        "Object {object_id} is owned by {owner}, not by the signer {signer}"
+                    ))
+                }
+            }
+            tx_kinds.push(SingleTransactionKind::TransferObject(TransferObject {
+                recipient,
+                object_ref: object.reference.to_object_ref(),
+            }));
+        }
+
+        let gas = self.select_gas(signer, gas, gas_budget, object_ids).await?;
+
+        Ok(TransactionData::new(
+            TransactionKind::Batch(tx_kinds),
+            signer,
+            gas,
+            gas_budget,
+        ))
+    }
+
     async fn get_object_ref(&self, object_id: ObjectID) -> anyhow::Result<ObjectRef> {
         Ok(self
             .0
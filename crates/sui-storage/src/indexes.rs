@@ -4,13 +4,15 @@
 //! IndexStore supports creation of various ancillary indexes of state in SuiDataStore.
 //! The main user of this data is the explorer.
 
+use std::collections::BTreeMap;
+
 use rocksdb::Options;
 use serde::{de::DeserializeOwned, Serialize};
 use typed_store_macros::DBMapUtils;
 
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::batch::TxSequenceNumber;
-use sui_types::error::SuiResult;
+use sui_types::error::{SuiError, SuiResult};
 
 use sui_types::base_types::ObjectRef;
 use sui_types::object::Owner;
@@ -205,4 +207,32 @@ impl IndexStore {
     ) -> SuiResult<Vec<(TxSequenceNumber, TransactionDigest)>> {
         Self::get_transactions_by_object(&self.transactions_to_addr, addr)
     }
+
+    /// The transactions `addr` sent, or which left it owning an object they created or mutated,
+    /// with sequence number starting from `start`, at most `limit`, in sequence order.
+    pub fn get_transactions_touching_addr(
+        &self,
+        addr: SuiAddress,
+        start: TxSequenceNumber,
+        limit: usize,
+    ) -> SuiResult<Vec<(TxSequenceNumber, TransactionDigest)>> {
+        let page = |index: &DBMap<(SuiAddress, TxSequenceNumber), TransactionDigest>| {
+            Ok::<_, SuiError>(
+                index
+                    .iter()
+                    .skip_to(&(addr, start))?
+                    .take_while(|((a, _), _)| *a == addr)
+                    .take(limit)
+                    .map(|((_, seq), digest)| (seq, digest))
+                    .collect::<Vec<_>>(),
+            )
+        };
+        // Both indexes are in sequence order, so the first `limit` transactions touching the
+        // address are among the first `limit` of each.
+        let touching: BTreeMap<_, _> = page(&self.transactions_from_addr)?
+            .into_iter()
+            .chain(page(&self.transactions_to_addr)?)
+            .collect();
+        Ok(touching.into_iter().take(limit).collect())
+    }
 }
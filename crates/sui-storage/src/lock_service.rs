@@ -7,6 +7,12 @@
 //! 2. None (object has an empty lock, but exists. The state when a new object is created)
 //! 3. Locked (object has a Transaction digest in the lock, so it's only usable by that transaction)
 //!
+//! Locks are stamped with the epoch in which they were acquired. A lock acquired in a previous
+//! epoch no longer binds the object: the transaction it points to can never be certified by the
+//! new committee, so it is overwritten by the first transaction locking the object in the new epoch.
+//! Locks written before they were stamped are stamped with the current epoch by
+//! `LockService::migrate_legacy_locks`, which runs at startup.
+//!
 //! The cycle goes from None (object creation) -> Locked -> deleted/doesn't exist after a Transaction.
 //!
 //! Lock state is persisted in RocksDB and should be consistent.
@@ -17,6 +23,7 @@
 
 use futures::channel::oneshot;
 use rocksdb::Options;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::thread::JoinHandle;
@@ -29,6 +36,7 @@ use typed_store_macros::DBMapUtils;
 
 use sui_types::base_types::{ObjectRef, TransactionDigest};
 use sui_types::batch::TxSequenceNumber;
+use sui_types::committee::EpochId;
 use sui_types::error::{SuiError, SuiResult};
 
 use crate::default_db_options;
@@ -39,6 +47,7 @@ use crate::default_db_options;
 enum LockServiceCommands {
     Acquire {
         refs: Vec<ObjectRef>,
+        epoch: EpochId,
         tx_digest: TransactionDigest,
         resp: oneshot::Sender<SuiResult>,
     },
@@ -58,9 +67,20 @@ enum LockServiceCommands {
         objects: Vec<ObjectRef>,
         resp: oneshot::Sender<SuiResult>,
     },
+    MigrateLegacyLocks {
+        epoch: EpochId,
+        resp: oneshot::Sender<SuiResult<usize>>,
+    },
+}
+
+/// The transaction an object is locked to, and the epoch in which the lock was acquired.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockDetails {
+    pub epoch: EpochId,
+    pub tx_digest: TransactionDigest,
 }
 
-type SuiLockResult = Result<Option<Option<TransactionDigest>>, SuiError>;
+type SuiLockResult = Result<Option<Option<LockDetails>>, SuiError>;
 
 /// Queries to the LockService state
 #[derive(Debug)]
@@ -86,9 +106,14 @@ pub struct LockServiceImpl {
     /// the lock once it is set. After a certificate for this object is processed it can be
     /// forgotten.
     #[default_options_override_fn = "transaction_lock_table_default_config"]
+    epoch_transaction_lock: DBMap<ObjectRef, Option<LockDetails>>,
+
+    /// The locks written before they were stamped with an epoch. They are moved to
+    /// `epoch_transaction_lock` by `migrate_legacy_locks`, after which this table is empty.
+    #[default_options_override_fn = "transaction_lock_table_default_config"]
     transaction_lock: DBMap<ObjectRef, Option<TransactionDigest>>,
 
-    /// The semantics of transaction_lock ensure that certificates are always processed
+    /// The semantics of epoch_transaction_lock ensure that certificates are always processed
     /// in causal order - that is, certificates naturally form a partial order. tx_sequence
     /// records a total ordering among all processed certificates (which is naturally local
     /// to this authority).
@@ -114,9 +139,9 @@ impl LockServiceImpl {
     /// Returns the state of a single lock.
     /// * None - lock does not exist and is not initialized
     /// * Some(None) - lock exists and is initialized, but not locked to a particular transaction
-    /// * Some(Some(tx_digest)) - lock exists and set to transaction
-    fn get_lock(&self, object: ObjectRef) -> Result<Option<Option<TransactionDigest>>, SuiError> {
-        self.transaction_lock
+    /// * Some(Some(lock_details)) - lock exists and set to transaction
+    fn get_lock(&self, object: ObjectRef) -> SuiLockResult {
+        self.epoch_transaction_lock
             .get(&object)
             .map_err(SuiError::StorageError)
     }
@@ -124,7 +149,7 @@ impl LockServiceImpl {
     /// Checks multiple object locks exist.
     /// Returns Err(TransactionLockDoesNotExist) if at least one object lock is not initialized.
     fn locks_exist(&self, objects: &[ObjectRef]) -> SuiResult {
-        let locks = self.transaction_lock.multi_get(objects)?;
+        let locks = self.epoch_transaction_lock.multi_get(objects)?;
         locks
             .iter()
             .all(Option::is_some)
@@ -138,7 +163,7 @@ impl LockServiceImpl {
     }
 
     fn create_locks_for_genesis_objects(&self, objects: &[ObjectRef]) -> SuiResult {
-        let write_batch = self.transaction_lock.batch();
+        let write_batch = self.epoch_transaction_lock.batch();
         let write_batch = self.initialize_locks_impl(write_batch, objects, false)?;
         write_batch.write()?;
         Ok(())
@@ -227,45 +252,66 @@ impl LockServiceImpl {
     }
 
     /// Acquires a lock for a transaction on the given objects if they have all been initialized previously
-    /// to None state.  It is also OK if they have been set to the same transaction.
-    /// The locks are all set to the given transacton digest.
-    /// Otherwise, SuiError(TransactionLockDoesNotExist, ConflictingTransaction) is returned.
+    /// to None state.  It is also OK if they have been set to the same transaction in the same epoch,
+    /// or to any transaction in a previous epoch.
+    /// The locks are all set to the given transacton digest and epoch.
+    /// Otherwise, SuiError(TransactionLockDoesNotExist, ConflictingTransaction,
+    /// ObjectLockedAtFutureEpoch) is returned.
     fn acquire_locks(
         &self,
         owned_input_objects: &[ObjectRef],
+        epoch: EpochId,
         tx_digest: TransactionDigest,
     ) -> SuiResult {
-        debug!(?tx_digest, ?owned_input_objects, "acquire_locks");
+        debug!(?tx_digest, ?epoch, ?owned_input_objects, "acquire_locks");
         let mut locks_to_write = Vec::new();
-        let locks = self.transaction_lock.multi_get(owned_input_objects)?;
+        let locks = self.epoch_transaction_lock.multi_get(owned_input_objects)?;
 
         for (i, lock) in locks.iter().enumerate() {
             // The object / version must exist, and therefore lock initialized.
             let lock = lock.ok_or(SuiError::TransactionLockDoesNotExist)?;
-
-            if let Some(previous_tx_digest) = lock {
-                // Lock already set to different transaction
-                if previous_tx_digest != tx_digest {
-                    // TODO: add metrics here
-                    debug!(prev_tx_digest =? previous_tx_digest,
-                          cur_tx_digest =? tx_digest,
-                          "Conflicting transaction!  Lock state changed in unexpected way");
-                    return Err(SuiError::ConflictingTransaction {
-                        pending_transaction: previous_tx_digest,
+            let obj_ref = owned_input_objects[i];
+
+            if let Some(LockDetails {
+                epoch: previous_epoch,
+                tx_digest: previous_tx_digest,
+            }) = lock
+            {
+                if previous_epoch > epoch {
+                    return Err(SuiError::ObjectLockedAtFutureEpoch {
+                        obj_ref,
+                        locked_epoch: previous_epoch,
+                        current_epoch: epoch,
+                        locked_by_tx: previous_tx_digest,
                     });
                 }
-            } else {
-                // Only write the locks that need to be written (are uninitialized)
-                let obj_ref = owned_input_objects[i];
-                locks_to_write.push((obj_ref, Some(tx_digest)));
+                if previous_epoch == epoch {
+                    // Lock already set to different transaction
+                    if previous_tx_digest != tx_digest {
+                        // TODO: add metrics here
+                        debug!(prev_tx_digest =? previous_tx_digest,
+                              cur_tx_digest =? tx_digest,
+                              "Conflicting transaction!  Lock state changed in unexpected way");
+                        return Err(SuiError::ConflictingTransaction {
+                            pending_transaction: previous_tx_digest,
+                        });
+                    }
+                    continue;
+                }
+                // The lock was set in a previous epoch, the transaction holding it can no
+                // longer be certified so the lock is overwritten.
+                debug!(?obj_ref, ?previous_epoch, prev_tx_digest =? previous_tx_digest,
+                       "Overwriting lock set in a previous epoch");
             }
+            // Only write the locks that need to be written (are uninitialized or stale)
+            locks_to_write.push((obj_ref, Some(LockDetails { epoch, tx_digest })));
         }
 
         if !locks_to_write.is_empty() {
             trace!(?locks_to_write, "Writing locks");
-            self.transaction_lock
+            self.epoch_transaction_lock
                 .batch()
-                .insert_batch(&self.transaction_lock, locks_to_write)?
+                .insert_batch(&self.epoch_transaction_lock, locks_to_write)?
                 .write()?;
         }
 
@@ -282,14 +328,14 @@ impl LockServiceImpl {
     ) -> SuiResult<DBBatch> {
         debug!(?objects, "initialize_locks");
         // Use a multiget for efficiency
-        let locks = self.transaction_lock.multi_get(objects)?;
+        let locks = self.epoch_transaction_lock.multi_get(objects)?;
 
         if !is_force_reset {
             // If any locks exist and are not None, return errors for them
             let existing_locks: Vec<ObjectRef> = locks
                 .iter()
                 .zip(objects)
-                .filter_map(|(lock_opt, objref)| lock_opt.flatten().map(|_tx_digest| *objref))
+                .filter_map(|(lock_opt, objref)| lock_opt.flatten().map(|_lock| *objref))
                 .collect();
             if !existing_locks.is_empty() {
                 info!(
@@ -303,7 +349,7 @@ impl LockServiceImpl {
         }
 
         let write_batch = write_batch.insert_batch(
-            &self.transaction_lock,
+            &self.epoch_transaction_lock,
             objects.iter().map(|obj_ref| (obj_ref, None)),
         )?;
 
@@ -311,7 +357,7 @@ impl LockServiceImpl {
     }
 
     fn initialize_locks(&self, objects: &[ObjectRef], is_force_reset: bool) -> SuiResult {
-        let write_batch = self.transaction_lock.batch();
+        let write_batch = self.epoch_transaction_lock.batch();
         let write_batch = self.initialize_locks_impl(write_batch, objects, is_force_reset)?;
         write_batch.write()?;
         Ok(())
@@ -320,10 +366,51 @@ impl LockServiceImpl {
     /// Removes locks for a given list of ObjectRefs.
     fn delete_locks(&self, objects: &[ObjectRef]) -> SuiResult {
         debug!(?objects, "delete_locks");
-        self.transaction_lock.multi_remove(objects)?;
+        self.epoch_transaction_lock.multi_remove(objects)?;
         Ok(())
     }
 
+    /// Move the locks written before they were stamped with an epoch to `epoch_transaction_lock`,
+    /// stamped with `epoch`. The epoch they were acquired in is unknown, and stamping them with
+    /// an earlier one would let a conflicting transaction take over a lock held in this epoch,
+    /// so a legacy lock keeps binding its object until the next epoch. Moves at most
+    /// `LEGACY_LOCKS_MIGRATED_PER_BATCH` locks per write batch, so an interrupted migration
+    /// resumes where it stopped. Returns the number of locks moved.
+    fn migrate_legacy_locks(&self, epoch: EpochId) -> SuiResult<usize> {
+        let mut migrated = 0;
+        loop {
+            let legacy: Vec<_> = self
+                .transaction_lock
+                .iter()
+                .take(LEGACY_LOCKS_MIGRATED_PER_BATCH)
+                .collect();
+            if legacy.is_empty() {
+                break;
+            }
+            migrated += legacy.len();
+            let locks = legacy.iter().map(|(obj_ref, lock)| {
+                let lock = lock.map(|tx_digest| LockDetails { epoch, tx_digest });
+                (*obj_ref, lock)
+            });
+            self.epoch_transaction_lock
+                .batch()
+                .insert_batch(&self.epoch_transaction_lock, locks)?
+                .delete_batch(
+                    &self.transaction_lock,
+                    legacy.iter().map(|(obj_ref, _)| *obj_ref),
+                )?
+                .write()?;
+        }
+        if migrated > 0 {
+            info!(
+                migrated,
+                ?epoch,
+                "Stamped the legacy transaction locks with an epoch"
+            );
+        }
+        Ok(migrated)
+    }
+
     /// Loop to continuously process mutating commands in a single thread from async senders.
     /// It terminates when the sender drops, which usually is when the containing data store is dropped.
     fn run_command_loop(&self, mut receiver: Receiver<LockServiceCommands>) {
@@ -333,10 +420,11 @@ impl LockServiceImpl {
             match msg {
                 LockServiceCommands::Acquire {
                     refs,
+                    epoch,
                     tx_digest,
                     resp,
                 } => {
-                    let res = self.acquire_locks(&refs, tx_digest);
+                    let res = self.acquire_locks(&refs, epoch, tx_digest);
                     if let Err(_e) = resp.send(res) {
                         warn!("Could not respond to sender, sender dropped!");
                     }
@@ -368,6 +456,11 @@ impl LockServiceImpl {
                         warn!("Could not respond to sender!");
                     }
                 }
+                LockServiceCommands::MigrateLegacyLocks { epoch, resp } => {
+                    if let Err(_e) = resp.send(self.migrate_legacy_locks(epoch)) {
+                        warn!("Could not respond to sender!");
+                    }
+                }
             }
         }
         info!("LockService command loop stopped, the sender on other end hung up/dropped");
@@ -395,6 +488,7 @@ impl LockServiceImpl {
 }
 
 const LOCKSERVICE_QUEUE_LEN: usize = 500;
+const LEGACY_LOCKS_MIGRATED_PER_BATCH: usize = 10_000;
 
 /// Atomic Sui Object locking service.
 /// Primary abstraction is an atomic op to acquire a lock on a given set of objects.
@@ -479,13 +573,16 @@ impl LockService {
     }
 
     /// Acquires a lock for a transaction on the given objects if they have all been initialized previously
-    /// to None state.  It is also OK if they have been set to the same transaction.
-    /// The locks are all set to the given transacton digest.
-    /// Otherwise, SuiError(TransactionLockDoesNotExist, ConflictingTransaction) is returned.
+    /// to None state.  It is also OK if they have been set to the same transaction in the same epoch,
+    /// or to any transaction in a previous epoch.
+    /// The locks are all set to the given transacton digest and epoch.
+    /// Otherwise, SuiError(TransactionLockDoesNotExist, ConflictingTransaction,
+    /// ObjectLockedAtFutureEpoch) is returned.
     /// Note that this method sends a message to inner LockService implementation and waits for a response
     pub async fn acquire_locks(
         &self,
         refs: Vec<ObjectRef>,
+        epoch: EpochId,
         tx_digest: TransactionDigest,
     ) -> SuiResult {
         let (os_sender, os_receiver) = oneshot::channel::<SuiResult>();
@@ -494,6 +591,7 @@ impl LockService {
             .sender()
             .send(LockServiceCommands::Acquire {
                 refs,
+                epoch,
                 tx_digest,
                 resp: os_sender,
             })
@@ -527,7 +625,7 @@ impl LockService {
     /// Returns the state of a single lock.
     /// * None - lock does not exist and is not initialized
     /// * Some(None) - lock exists and is initialized, but not locked to a particular transaction
-    /// * Some(Some(tx_digest)) - lock exists and set to transaction
+    /// * Some(Some(lock_details)) - lock exists and set to transaction
     pub async fn get_lock(&self, object: ObjectRef) -> SuiLockResult {
         let (os_sender, os_receiver) = oneshot::channel::<SuiLockResult>();
         self.inner
@@ -593,6 +691,23 @@ impl LockService {
             .expect("Response from lockservice was cancelled, should not happen!")
     }
 
+    /// Stamp the locks written before locks were stamped with an epoch with the given epoch, the
+    /// current one. Must run before any lock is used. Returns the number of locks stamped.
+    pub async fn migrate_legacy_locks(&self, epoch: EpochId) -> SuiResult<usize> {
+        let (os_sender, os_receiver) = oneshot::channel::<SuiResult<usize>>();
+        self.inner
+            .sender()
+            .send(LockServiceCommands::MigrateLegacyLocks {
+                epoch,
+                resp: os_sender,
+            })
+            .await
+            .expect("Could not send message to inner LockService");
+        os_receiver
+            .await
+            .expect("Response from lockservice was cancelled, should not happen!")
+    }
+
     /// Checks multiple object locks exist.
     /// Returns Err(TransactionLockDoesNotExist) if at least one object lock is not initialized.
     pub async fn locks_exist(&self, objects: Vec<ObjectRef>) -> SuiResult {
@@ -649,7 +764,7 @@ mod tests {
 
         // Should not be able to acquire lock for uninitialized locks
         assert_eq!(
-            ls.acquire_locks(&[ref1, ref2], tx1),
+            ls.acquire_locks(&[ref1, ref2], 0, tx1),
             Err(SuiError::TransactionLockDoesNotExist)
         );
         assert_eq!(ls.get_lock(ref1), Ok(None));
@@ -662,13 +777,19 @@ mod tests {
 
         // Should not be able to acquire lock if not all objects initialized
         assert_eq!(
-            ls.acquire_locks(&[ref1, ref2, ref3], tx1),
+            ls.acquire_locks(&[ref1, ref2, ref3], 0, tx1),
             Err(SuiError::TransactionLockDoesNotExist)
         );
 
         // Should be able to acquire lock if all objects initialized
-        ls.acquire_locks(&[ref1, ref2], tx1).unwrap();
-        assert_eq!(ls.get_lock(ref2), Ok(Some(Some(tx1))));
+        ls.acquire_locks(&[ref1, ref2], 0, tx1).unwrap();
+        assert_eq!(
+            ls.get_lock(ref2),
+            Ok(Some(Some(LockDetails {
+                epoch: 0,
+                tx_digest: tx1
+            })))
+        );
 
         // Should be able to check locks exist for ref1 and ref2, but not others
         assert_eq!(ls.locks_exist(&[ref1, ref2]), Ok(()));
@@ -687,11 +808,104 @@ mod tests {
         ls.initialize_locks(&[ref3], false /* is_force_reset */)
             .unwrap();
         assert!(matches!(
-            ls.acquire_locks(&[ref2, ref3], tx2),
+            ls.acquire_locks(&[ref2, ref3], 0, tx2),
             Err(SuiError::ConflictingTransaction { .. })
         ));
     }
 
+    #[test]
+    fn test_lockdb_acquire_across_epochs() {
+        let ls = init_lockservice_db();
+
+        let ref1: ObjectRef = (ObjectID::random(), 1.into(), ObjectDigest::random());
+        let ref2: ObjectRef = (ObjectID::random(), 1.into(), ObjectDigest::random());
+
+        let tx1 = TransactionDigest::random();
+        let tx2 = TransactionDigest::random();
+        let tx3 = TransactionDigest::random();
+
+        ls.initialize_locks(&[ref1, ref2], false /* is_force_reset */)
+            .unwrap();
+
+        // Lock ref1 in epoch 0
+        ls.acquire_locks(&[ref1], 0, tx1).unwrap();
+        assert!(matches!(
+            ls.acquire_locks(&[ref1, ref2], 0, tx2),
+            Err(SuiError::ConflictingTransaction { .. })
+        ));
+
+        // After the epoch change the stale lock no longer binds ref1
+        ls.acquire_locks(&[ref1, ref2], 1, tx2).unwrap();
+        assert_eq!(
+            ls.get_lock(ref1),
+            Ok(Some(Some(LockDetails {
+                epoch: 1,
+                tx_digest: tx2
+            })))
+        );
+        assert_eq!(
+            ls.get_lock(ref2),
+            Ok(Some(Some(LockDetails {
+                epoch: 1,
+                tx_digest: tx2
+            })))
+        );
+
+        // Re-acquiring for the same transaction in the same epoch is fine
+        ls.acquire_locks(&[ref1, ref2], 1, tx2).unwrap();
+
+        // But a lock from the new epoch still conflicts within that epoch
+        assert!(matches!(
+            ls.acquire_locks(&[ref1], 1, tx3),
+            Err(SuiError::ConflictingTransaction { .. })
+        ));
+
+        // And cannot be taken over by a transaction from the old epoch
+        assert_eq!(
+            ls.acquire_locks(&[ref1], 0, tx1),
+            Err(SuiError::ObjectLockedAtFutureEpoch {
+                obj_ref: ref1,
+                locked_epoch: 1,
+                current_epoch: 0,
+                locked_by_tx: tx2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_lockdb_migrate_legacy_locks() {
+        let ls = init_lockservice_db();
+
+        let ref1: ObjectRef = (ObjectID::random(), 1.into(), ObjectDigest::random());
+        let ref2: ObjectRef = (ObjectID::random(), 1.into(), ObjectDigest::random());
+        let tx1 = TransactionDigest::random();
+        let tx2 = TransactionDigest::random();
+
+        // Locks as written before they were stamped with an epoch
+        ls.transaction_lock.insert(&ref1, &None).unwrap();
+        ls.transaction_lock.insert(&ref2, &Some(tx1)).unwrap();
+        assert_eq!(ls.get_lock(ref2), Ok(None));
+
+        assert_eq!(ls.migrate_legacy_locks(3), Ok(2));
+        assert_eq!(ls.get_lock(ref1), Ok(Some(None)));
+        assert_eq!(
+            ls.get_lock(ref2),
+            Ok(Some(Some(LockDetails {
+                epoch: 3,
+                tx_digest: tx1
+            })))
+        );
+        assert!(ls.transaction_lock.iter().next().is_none());
+        assert_eq!(ls.migrate_legacy_locks(3), Ok(0));
+
+        // A legacy lock binds its object for the rest of the epoch it was stamped with
+        assert!(matches!(
+            ls.acquire_locks(&[ref2], 3, tx2),
+            Err(SuiError::ConflictingTransaction { .. })
+        ));
+        ls.acquire_locks(&[ref2], 4, tx2).unwrap();
+    }
+
     #[test]
     fn test_lockdb_remove_multiple() {
         let ls = init_lockservice_db();
@@ -706,8 +920,14 @@ mod tests {
             .unwrap();
 
         // Should be able to acquire lock if all objects initialized
-        ls.acquire_locks(&[ref1, ref2], tx1).unwrap();
-        assert_eq!(ls.get_lock(ref2), Ok(Some(Some(tx1))));
+        ls.acquire_locks(&[ref1, ref2], 0, tx1).unwrap();
+        assert_eq!(
+            ls.get_lock(ref2),
+            Ok(Some(Some(LockDetails {
+                epoch: 0,
+                tx_digest: tx1
+            })))
+        );
 
         // Cannot initialize them again since they are locked already
         assert!(matches!(
@@ -753,7 +973,7 @@ mod tests {
         let futures = txdigests.iter().map(|tx| {
             let ls = ls.clone();
             let tx = *tx;
-            tokio::spawn(async move { ls.acquire_locks(vec![ref1, ref2], tx).await })
+            tokio::spawn(async move { ls.acquire_locks(vec![ref1, ref2], 0, tx).await })
         });
         let results = join_all(futures).await;
         let inner_res: Vec<_> = results.into_iter().map(|r| r.unwrap()).collect();
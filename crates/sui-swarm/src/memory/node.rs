@@ -8,6 +8,7 @@ use std::thread;
 use sui_config::NodeConfig;
 use sui_node::SuiNode;
 use sui_types::base_types::SuiAddress;
+use sui_types::crypto::AuthorityPublicKeyBytes;
 use tap::TapFallible;
 use tracing::{error, trace};
 
@@ -44,6 +45,11 @@ impl Node {
         self.config.sui_address()
     }
 
+    /// Return the protocol public key of this Node, which names it in the committee
+    pub fn protocol_public_key(&self) -> AuthorityPublicKeyBytes {
+        self.config.protocol_public_key()
+    }
+
     pub fn json_rpc_address(&self) -> std::net::SocketAddr {
         self.config.json_rpc_address
     }
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::Node;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use futures::future::try_join_all;
 use rand::rngs::OsRng;
 use std::collections::HashMap;
@@ -16,6 +16,7 @@ use sui_config::builder::ConfigBuilder;
 use sui_config::genesis_config::GenesisConfig;
 use sui_config::NetworkConfig;
 use sui_types::base_types::SuiAddress;
+use sui_types::crypto::AuthorityPublicKeyBytes;
 use tempfile::TempDir;
 
 pub struct SwarmBuilder<R = OsRng> {
@@ -190,6 +191,25 @@ impl Swarm {
         Ok(())
     }
 
+    /// Only keep the Validators with the provided protocol public keys, so that launching this
+    /// Swarm starts a subset of the Validators of its `NetworkConfig`. Fails if any of the keys
+    /// is not the key of a Validator.
+    pub fn retain_validators(&mut self, keys: &[AuthorityPublicKeyBytes]) -> Result<()> {
+        if let Some(key) = keys.iter().find(|key| {
+            !self
+                .validators
+                .values()
+                .any(|node| node.protocol_public_key() == **key)
+        }) {
+            return Err(anyhow!(
+                "No validator with public key {key} in the network config"
+            ));
+        }
+        self.validators
+            .retain(|_, node| keys.contains(&node.protocol_public_key()));
+        Ok(())
+    }
+
     /// Return the path to the directory where this Swarm's on-disk data is kept.
     pub fn dir(&self) -> &Path {
         self.dir.as_ref()
@@ -27,11 +27,13 @@ use sha3::Sha3_256;
 
 use crate::committee::EpochId;
 use crate::crypto::{
-    AuthorityPublicKey, AuthorityPublicKeyBytes, KeypairTraits, PublicKey, SuiPublicKey,
+    AuthorityPublicKey, AuthorityPublicKeyBytes, KeypairTraits, PublicKey, SignatureScheme,
+    SuiPublicKey,
 };
 use crate::error::ExecutionError;
 use crate::error::ExecutionErrorKind;
 use crate::error::SuiError;
+use crate::multisig::MultiSigPublicKey;
 use crate::object::{Object, Owner};
 use crate::sui_serde::Base64;
 use crate::sui_serde::Hex;
@@ -223,6 +225,26 @@ impl From<&PublicKey> for SuiAddress {
     }
 }
 
+/// The address of a multisig is derived from its threshold and all of its public keys, in the
+/// order they are kept in, under the multisig scheme flag so it cannot collide with the address
+/// of a single key.
+impl From<&MultiSigPublicKey> for SuiAddress {
+    fn from(multisig_pk: &MultiSigPublicKey) -> Self {
+        let mut hasher = Sha3_256::default();
+        hasher.update(&[SignatureScheme::MultiSig.flag()]);
+        hasher.update(multisig_pk.threshold().to_le_bytes());
+        for pk in multisig_pk.public_keys() {
+            hasher.update(&[pk.flag()]);
+            hasher.update(pk);
+        }
+        let g_arr = hasher.finalize();
+
+        let mut res = [0u8; SUI_ADDRESS_LENGTH];
+        res.copy_from_slice(&AsRef::<[u8]>::as_ref(&g_arr)[..SUI_ADDRESS_LENGTH]);
+        SuiAddress(res)
+    }
+}
+
 impl TryFrom<&[u8]> for SuiAddress {
     type Error = SuiError;
 
@@ -22,6 +22,7 @@ pub const COIN_STRUCT_NAME: &IdentStr = ident_str!("Coin");
 pub const COIN_JOIN_FUNC_NAME: &IdentStr = ident_str!("join");
 pub const COIN_SPLIT_N_FUNC_NAME: &IdentStr = ident_str!("split_n");
 pub const COIN_SPLIT_VEC_FUNC_NAME: &IdentStr = ident_str!("split_vec");
+pub const COIN_SPLIT_AND_TRANSFER_FUNC_NAME: &IdentStr = ident_str!("split_and_transfer");
 
 // Rust version of the Move sui::coin::Coin type
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
@@ -32,6 +32,7 @@ use signature::Signer;
 use crate::base_types::{AuthorityName, SuiAddress};
 use crate::committee::{Committee, EpochId};
 use crate::error::{SuiError, SuiResult};
+use crate::multisig::MultiSigSuiSignature;
 use crate::sui_serde::{Base64, Readable, SuiBitmap};
 pub use enum_dispatch::enum_dispatch;
 
@@ -495,6 +496,9 @@ where
             let (addr, key_pair): (_, Ed25519KeyPair) = get_key_pair_from_rng(csprng);
             Ok((addr, SuiKeyPair::Ed25519SuiKeyPair(key_pair)))
         }
+        SignatureScheme::MultiSig => Err(anyhow!(
+            "Multisig addresses are derived from existing public keys, not generated"
+        )),
     }
 }
 
@@ -523,6 +527,7 @@ where
 pub enum Signature {
     Ed25519SuiSignature,
     Secp256k1SuiSignature,
+    MultiSigSuiSignature,
 }
 
 impl Serialize for Signature {
@@ -577,6 +582,7 @@ impl AsRef<[u8]> for Signature {
         match self {
             Signature::Ed25519SuiSignature(sig) => sig.as_ref(),
             Signature::Secp256k1SuiSignature(sig) => sig.as_ref(),
+            Signature::MultiSigSuiSignature(sig) => sig.as_ref(),
         }
     }
 }
@@ -593,6 +599,10 @@ impl signature::Signature for Signature {
                     Ok(<Secp256k1SuiSignature as ToFromBytes>::from_bytes(bytes)
                         .map_err(|_| signature::Error::new())?
                         .into())
+                } else if x == &SignatureScheme::MultiSig.flag() {
+                    Ok(<MultiSigSuiSignature as ToFromBytes>::from_bytes(bytes)
+                        .map_err(|_| signature::Error::new())?
+                        .into())
                 } else {
                     Err(signature::Error::new())
                 }
@@ -1151,7 +1161,9 @@ mod bcs_signable {
     impl BcsSignable for crate::messages::TransactionEffects {}
     impl BcsSignable for crate::messages::TransactionData {}
     impl BcsSignable for crate::messages::SenderSignedData {}
-    impl BcsSignable for crate::messages::EpochInfo {}
+    impl BcsSignable for crate::messages::EpochInfoV1 {}
+    impl BcsSignable for crate::messages::EpochInfoV2 {}
+    impl BcsSignable for crate::messages::ObjectOwnershipStatement {}
     impl BcsSignable for crate::object::Object {}
 
     impl BcsSignable for super::bcs_signable_test::Foo {}
@@ -1179,8 +1191,11 @@ where
     fn from_signable_bytes(bytes: &[u8]) -> Result<Self, Error> {
         // Remove name tag before deserialization using BCS
         let name = serde_name::trace_name::<Self>().expect("Self should be a struct or an enum");
-        let name_byte_len = format!("{}::", name).bytes().len();
-        Ok(bcs::from_bytes(&bytes[name_byte_len..])?)
+        let prefix = format!("{}::", name);
+        let bytes = bytes
+            .strip_prefix(prefix.as_bytes())
+            .ok_or_else(|| anyhow!("Expected bytes to start with the {prefix:?} name tag"))?;
+        Ok(bcs::from_bytes(bytes)?)
     }
 }
 
@@ -1328,6 +1343,7 @@ pub mod bcs_signable_test {
 pub enum SignatureScheme {
     ED25519,
     Secp256k1,
+    MultiSig,
 }
 
 impl SignatureScheme {
@@ -1335,6 +1351,7 @@ impl SignatureScheme {
         match self {
             SignatureScheme::ED25519 => 0x00,
             SignatureScheme::Secp256k1 => 0x01,
+            SignatureScheme::MultiSig => 0x03,
         }
     }
 
@@ -1345,6 +1362,7 @@ impl SignatureScheme {
         match byte_int {
             0x00 => Ok(SignatureScheme::ED25519),
             0x01 => Ok(SignatureScheme::Secp256k1),
+            0x03 => Ok(SignatureScheme::MultiSig),
             _ => Err(SuiError::KeyConversionError(
                 "Invalid key scheme".to_string(),
             )),
@@ -1358,6 +1376,7 @@ impl FromStr for SignatureScheme {
         match s {
             "ed25519" => Ok(SignatureScheme::ED25519),
             "secp256k1" => Ok(SignatureScheme::Secp256k1),
+            "multisig" => Ok(SignatureScheme::MultiSig),
             _ => Err(SuiError::KeyConversionError(
                 "Invalid key scheme".to_string(),
             )),
@@ -1370,6 +1389,7 @@ impl ToString for SignatureScheme {
         match self {
             SignatureScheme::ED25519 => "ed25519".to_string(),
             SignatureScheme::Secp256k1 => "secp256k1".to_string(),
+            SignatureScheme::MultiSig => "multisig".to_string(),
         }
     }
 }
@@ -8,9 +8,14 @@ use move_core_types::vm_status::{StatusCode, StatusType};
 use narwhal_executor::SubscriberError;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
+use strum::VariantNames;
 use thiserror::Error;
 use typed_store::rocks::TypedStoreError;
 
+#[cfg(test)]
+#[path = "unit_tests/error_tests.rs"]
+mod error_tests;
+
 #[macro_export]
 macro_rules! fp_bail {
     ($e:expr) => {
@@ -41,8 +46,22 @@ macro_rules! exit_main {
     };
 }
 
+/// The gRPC metadata key under which authorities send the code of an error, see `SuiError::code`.
+pub const SUI_ERROR_CODE_METADATA_KEY: &str = "sui-error-code";
+
 /// Custom error type for Sui.
-#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, Error, Hash)]
+#[derive(
+    Eq,
+    PartialEq,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    Error,
+    Hash,
+    strum_macros::IntoStaticStr,
+    strum_macros::EnumVariantNames,
+)]
 #[allow(clippy::large_enum_variant)]
 pub enum SuiError {
     // Object misuse issues
@@ -70,6 +89,16 @@ pub enum SuiError {
     SharedObjectLockNotSetObject,
     #[error("Invalid Batch Transaction: {}", error)]
     InvalidBatchTransaction { error: String },
+    #[error("Transaction is {size} bytes, larger than the maximum of {limit} bytes")]
+    TransactionTooLarge { size: u64, limit: u64 },
+    #[error("Transaction has {count} input objects, more than the maximum of {limit}")]
+    TooManyInputObjects { count: u64, limit: u64 },
+    #[error("Type argument has a nesting depth of {depth}, more than the maximum of {limit}")]
+    TypeArgumentDepthExceeded { depth: u64, limit: u64 },
+    #[error("Request has {count} items, more than the maximum of {limit}")]
+    TooManyItemsError { count: u64, limit: u64 },
+    #[error("A page of items must hold at least one item")]
+    ZeroPageLimit,
     #[error("Object {child_id:?} is owned by object {parent_id:?}, which is not in the input")]
     MissingObjectOwner {
         child_id: ObjectID,
@@ -85,6 +114,10 @@ pub enum SuiError {
     IncorrectSigner { error: String },
     #[error("Value was not signed by a known authority")]
     UnknownSigner,
+    #[error("Invalid multisig public key: {}", error)]
+    InvalidMultiSigPublicKey { error: String },
+    #[error("Multisig requires signatures from {threshold} keys, got {signatures}")]
+    MultiSigThresholdNotMet { signatures: usize, threshold: u16 },
     // Certificate verification
     #[error("Signature or certificate from wrong epoch, expected {expected_epoch}")]
     WrongEpoch { expected_epoch: EpochId },
@@ -104,6 +137,16 @@ pub enum SuiError {
     ConflictingTransaction {
         pending_transaction: TransactionDigest,
     },
+    #[error(
+        "Object {obj_ref:?} is locked by transaction {locked_by_tx:?} at epoch {locked_epoch}, \
+        later than the current epoch {current_epoch}"
+    )]
+    ObjectLockedAtFutureEpoch {
+        obj_ref: ObjectRef,
+        locked_epoch: EpochId,
+        current_epoch: EpochId,
+        locked_by_tx: TransactionDigest,
+    },
     #[error("Transaction processing failed: {err}")]
     ErrorWhileProcessingTransactionTransaction { err: String },
     #[error("Confirmation transaction processing failed: {err}")]
@@ -260,6 +303,8 @@ pub enum SuiError {
     GasBudgetTooHigh { error: String },
     #[error("Insufficient gas: {error:?}.")]
     InsufficientGas { error: String },
+    #[error("Gas price {gas_price} is lower than the minimum of {min_gas_price}.")]
+    GasPriceTooLow { gas_price: u64, min_gas_price: u64 },
 
     // Internal state errors
     #[error("Attempt to update state of TxContext from a different instance than original.")]
@@ -276,6 +321,19 @@ pub enum SuiError {
     ObjectNotFound { object_id: ObjectID },
     #[error("Object deleted at reference {:?}.", object_ref)]
     ObjectDeleted { object_ref: ObjectRef },
+    #[error("Object {object_id:?} at version {version:?} has been pruned from the store.")]
+    ObjectVersionPruned {
+        object_id: ObjectID,
+        version: SequenceNumber,
+    },
+    #[error(
+        "Object {object_id:?} at version {version:?} is newer than its latest known version {latest_version:?}."
+    )]
+    ObjectVersionNotYetKnown {
+        object_id: ObjectID,
+        version: SequenceNumber,
+        latest_version: SequenceNumber,
+    },
     #[error("Object ID did not have the expected type")]
     BadObjectType { error: String },
     #[error("Move Execution failed")]
@@ -311,6 +369,13 @@ pub enum SuiError {
     StorageMissingFieldError(String),
     #[error("Corrupted fields/data in storage error: {0}")]
     StorageCorruptedFieldError(String),
+    #[error(
+        "Effects of transaction {digest:?} are inconsistent with the objects it wrote: {error}"
+    )]
+    InconsistentEffects {
+        digest: TransactionDigest,
+        error: String,
+    },
 
     #[error("Batch error: cannot send transaction to batch.")]
     BatchErrorSender,
@@ -388,11 +453,18 @@ pub enum SuiError {
     ValidatorHaltedAtEpochEnd,
     #[error("Inconsistent state detected during epoch change: {:?}", error)]
     InconsistentEpochState { error: String },
+    #[error(
+        "Transaction expired at the end of epoch {expiration_epoch}, the current epoch is {current_epoch}"
+    )]
+    TransactionExpired {
+        expiration_epoch: EpochId,
+        current_epoch: EpochId,
+    },
 
     // These are errors that occur when an RPC fails and is simply the utf8 message sent in a
-    // Tonic::Status
+    // Tonic::Status, with the code of the error if the authority sent one.
     #[error("{1} - {0}")]
-    RpcError(String, &'static str),
+    RpcError(String, &'static str, Option<String>),
 
     #[error("Use of disabled feature: {:?}", error)]
     UnsupportedFeatureError { error: String },
@@ -408,6 +480,8 @@ pub enum SuiError {
 
     #[error("Invalid committee composition")]
     InvalidCommittee(String),
+    #[error("Protocol version {version} is not supported, the latest supported version is {max_supported}")]
+    UnsupportedProtocolVersion { version: u64, max_supported: u64 },
 
     #[error("Invalid authenticated epoch: {0}")]
     InvalidAuthenticatedEpoch(String),
@@ -447,9 +521,150 @@ impl std::convert::From<SubscriberError> for SuiError {
     }
 }
 
+impl SuiError {
+    /// Whether the error comes from the network rather than from the authority, e.g. because the
+    /// authority could not be reached, so that the same request may succeed if retried.
+    pub fn is_transport_error(&self) -> bool {
+        matches!(
+            self,
+            SuiError::RpcError(_, code, _)
+                if *code == tonic::Code::Unavailable.description()
+                    || *code == tonic::Code::DeadlineExceeded.description()
+        )
+    }
+
+    /// A stable code for the kind of the error, for clients to branch on instead of messages.
+    /// The code is the name of the variant, e.g. `ObjectNotFound`, and for an error received
+    /// from an authority, the code of the error the authority sent, if it is a known one.
+    pub fn code(&self) -> &str {
+        match self {
+            SuiError::RpcError(_, _, Some(code)) => code,
+            _ => self.into(),
+        }
+    }
+
+    /// The gRPC status code authorities answer the error with.
+    pub fn grpc_code(&self) -> tonic::Code {
+        use tonic::Code;
+        match self {
+            SuiError::ObjectErrors { .. }
+            | SuiError::ConflictingTransaction { .. }
+            | SuiError::ObjectLockedAtFutureEpoch { .. }
+            | SuiError::TransactionLockExists { .. }
+            | SuiError::TransactionLockDoesNotExist
+            | SuiError::TransactionLockReset
+            | SuiError::UnexpectedSequenceNumber { .. }
+            | SuiError::MissingEarlierConfirmations { .. }
+            | SuiError::ObjectDeleted { .. }
+            | SuiError::WrongEpoch { .. }
+            | SuiError::ValidatorHaltedAtEpochEnd
+            | SuiError::TransactionExpired { .. } => Code::FailedPrecondition,
+
+            SuiError::TooManyItemsError { .. }
+            | SuiError::TransactionTooLarge { .. }
+            | SuiError::TooManyInputObjects { .. }
+            | SuiError::ListenerCapacityExceeded => Code::ResourceExhausted,
+
+            SuiError::TransferUnownedError
+            | SuiError::TransferObjectWithoutPublicTransferError
+            | SuiError::MoveObjectAsPackage { .. }
+            | SuiError::TransferInsufficientBalance { .. }
+            | SuiError::MovePackageAsObject { .. }
+            | SuiError::UnexpectedOwnerType
+            | SuiError::UnsupportedSharedObjectError
+            | SuiError::NotSharedObjectError
+            | SuiError::DeleteObjectOwnedObject
+            | SuiError::InvalidBatchTransaction { .. }
+            | SuiError::TypeArgumentDepthExceeded { .. }
+            | SuiError::ZeroPageLimit
+            | SuiError::InvalidSignature { .. }
+            | SuiError::SenderSigUnbatchable
+            | SuiError::IncorrectSigner { .. }
+            | SuiError::UnknownSigner
+            | SuiError::InvalidMultiSigPublicKey { .. }
+            | SuiError::MultiSigThresholdNotMet
+            | SuiError::CertificateRequiresQuorum
+            | SuiError::InvalidAuthorityBitmap { .. }
+            | SuiError::InvalidAuthenticator
+            | SuiError::InvalidAddress
+            | SuiError::InvalidTransactionDigest
+            | SuiError::InvalidObjectDigest { .. }
+            | SuiError::InvalidDecoding
+            | SuiError::DuplicateObjectRefInput
+            | SuiError::TransferImmutableError
+            | SuiError::InvalidSequenceRangeError
+            | SuiError::InvalidFunctionSignature { .. }
+            | SuiError::InvalidNonEntryFunction
+            | SuiError::GasBudgetTooHigh { .. }
+            | SuiError::InsufficientGas { .. }
+            | SuiError::GasPriceTooLow { .. }
+            | SuiError::BadObjectType { .. }
+            | SuiError::UnsupportedProtocolVersion { .. } => Code::InvalidArgument,
+
+            SuiError::ObjectNotFound { .. }
+            | SuiError::ObjectVersionPruned { .. }
+            | SuiError::ObjectVersionNotYetKnown { .. }
+            | SuiError::TransactionNotFound { .. }
+            | SuiError::CertificateNotfound { .. }
+            | SuiError::ParentNotfound { .. }
+            | SuiError::DependentPackageNotFound { .. }
+            | SuiError::FunctionNotFound { .. }
+            | SuiError::ModuleNotFound { .. }
+            | SuiError::NoBatchesFoundError => Code::NotFound,
+
+            SuiError::UnsupportedFeatureError { .. } | SuiError::NoEventStore => {
+                Code::Unimplemented
+            }
+
+            _ => Code::Internal,
+        }
+    }
+
+    /// A gRPC status with the given code and the message of the error, carrying the code of the
+    /// error under `SUI_ERROR_CODE_METADATA_KEY`.
+    pub fn to_status(&self, code: tonic::Code) -> tonic::Status {
+        let mut status = tonic::Status::new(code, self.to_string());
+        // Codes are variant names, which are valid metadata values.
+        let code: &'static str = match self {
+            SuiError::RpcError(_, _, Some(code)) => match Self::known_code(code) {
+                Some(code) => code,
+                None => return status,
+            },
+            _ => self.into(),
+        };
+        status.metadata_mut().insert(
+            SUI_ERROR_CODE_METADATA_KEY,
+            tonic::metadata::MetadataValue::from_static(code),
+        );
+        status
+    }
+
+    fn known_code(code: &str) -> Option<&'static str> {
+        Self::VARIANTS
+            .iter()
+            .find(|variant| **variant == code)
+            .copied()
+    }
+}
+
+impl From<SuiError> for tonic::Status {
+    fn from(error: SuiError) -> Self {
+        error.to_status(error.grpc_code())
+    }
+}
+
 impl From<tonic::Status> for SuiError {
     fn from(status: tonic::Status) -> Self {
-        Self::RpcError(status.message().to_owned(), status.code().description())
+        let code = status
+            .metadata()
+            .get(SUI_ERROR_CODE_METADATA_KEY)
+            .and_then(|code| code.to_str().ok())
+            .and_then(Self::known_code);
+        Self::RpcError(
+            status.message().to_owned(),
+            status.code().description(),
+            code.map(str::to_owned),
+        )
     }
 }
 
@@ -79,12 +79,32 @@ impl Filter<EventEnvelope> for EventFilter {
 pub enum TransactionFilter {
     // subscribe to all transactions
     Any,
+    // subscribe to transactions that create, mutate, unwrap, wrap or delete the object
+    MutatedObject(ObjectID),
+    // subscribe to transactions sent by the address, or leaving objects owned by it
+    AffectedAddress(SuiAddress),
 }
 
 impl Filter<TxCertAndSignedEffects> for TransactionFilter {
-    fn matches(&self, _item: &TxCertAndSignedEffects) -> bool {
+    fn matches(&self, (cert, signed_effects): &TxCertAndSignedEffects) -> bool {
         match self {
             TransactionFilter::Any => true,
+            TransactionFilter::MutatedObject(object_id) => {
+                let effects = &signed_effects.effects;
+                effects
+                    .all_mutated()
+                    .map(|(object_ref, _)| object_ref)
+                    .chain(effects.deleted.iter())
+                    .chain(effects.wrapped.iter())
+                    .any(|(id, _, _)| id == object_id)
+            }
+            TransactionFilter::AffectedAddress(address) => {
+                cert.sender_address() == *address
+                    || signed_effects
+                        .effects
+                        .all_mutated()
+                        .any(|(_, owner)| *owner == Owner::AddressOwner(*address))
+            }
         }
     }
 }
@@ -30,7 +30,9 @@ pub mod message_envelope;
 pub mod messages;
 pub mod messages_checkpoint;
 pub mod move_package;
+pub mod multisig;
 pub mod object;
+pub mod protocol_config;
 pub mod signature_seed;
 pub mod storage;
 pub mod sui_serde;
@@ -68,6 +70,15 @@ pub fn parse_sui_struct_tag(s: &str) -> anyhow::Result<StructTag> {
     ParsedStructType::parse(s)?.into_struct_tag(&resolve_address)
 }
 
+/// Whether `tag` is of the type `filter`. A filter without type arguments matches every
+/// instantiation of its struct, e.g. `0x2::coin::Coin` matches the coins of every currency.
+pub fn struct_tag_matches(filter: &StructTag, tag: &StructTag) -> bool {
+    filter.address == tag.address
+        && filter.module == tag.module
+        && filter.name == tag.name
+        && (filter.type_params.is_empty() || filter.type_params == tag.type_params)
+}
+
 pub fn parse_sui_type_tag(s: &str) -> anyhow::Result<TypeTag> {
     use move_command_line_common::types::ParsedType;
     ParsedType::parse(s)?.into_type_tag(&resolve_address)
@@ -12,6 +12,7 @@ use crate::crypto::{
 use crate::gas::GasCostSummary;
 use crate::messages_checkpoint::{CheckpointFragment, CheckpointSequenceNumber};
 use crate::object::{Object, ObjectFormatOptions, Owner, OBJECT_START_VERSION};
+use crate::protocol_config::{ProtocolVersion, GENESIS_PROTOCOL_VERSION};
 use crate::storage::DeleteKind;
 use crate::sui_serde::Base64;
 use crate::SUI_SYSTEM_STATE_OBJECT_ID;
@@ -23,7 +24,9 @@ use move_binary_format::file_format::LocalIndex;
 use move_binary_format::CompiledModule;
 use move_core_types::language_storage::ModuleId;
 use move_core_types::{
-    account_address::AccountAddress, identifier::Identifier, language_storage::TypeTag,
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
     value::MoveStructLayout,
 };
 use name_variant::NamedVariant;
@@ -268,7 +271,7 @@ pub enum TransactionKind {
     Single(SingleTransactionKind),
     /// A batch of single transactions.
     Batch(Vec<SingleTransactionKind>),
-    // .. more transaction types go here
+    // .. more transaction types go here, each with a matching variant in `TransactionDataRepr`
 }
 
 impl TransactionKind {
@@ -349,13 +352,146 @@ impl Display for TransactionKind {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize)]
+#[serde(from = "TransactionDataRepr")]
 pub struct TransactionData {
     pub kind: TransactionKind,
     sender: SuiAddress,
     gas_payment: ObjectRef,
     pub gas_price: u64,
     pub gas_budget: u64,
+    /// The last epoch in which the transaction may be signed, authorities reject it afterwards.
+    /// It never expires if None.
+    pub expiration_epoch: Option<EpochId>,
+}
+
+/// The serialized form of `TransactionData`, versioned so that transactions without an
+/// expiration keep the bytes, and so the digest and signatures, they had before it existed.
+/// `Single` and `Batch` take the place of the variants of the `kind` the legacy layout started
+/// with, which is why any new `TransactionKind` variant has to be matched by a variant here,
+/// before `V2`.
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "TransactionData")]
+enum TransactionDataRepr {
+    Single(SingleTransactionKind, SuiAddress, ObjectRef, u64, u64),
+    Batch(Vec<SingleTransactionKind>, SuiAddress, ObjectRef, u64, u64),
+    V2 {
+        kind: TransactionKind,
+        sender: SuiAddress,
+        gas_payment: ObjectRef,
+        gas_price: u64,
+        gas_budget: u64,
+        expiration_epoch: EpochId,
+    },
+}
+
+/// `TransactionDataRepr` borrowing from the transaction, as transactions are serialized to be
+/// hashed and signed far more often than they are deserialized.
+#[derive(Serialize)]
+#[serde(rename = "TransactionData")]
+enum TransactionDataReprRef<'a> {
+    Single(
+        &'a SingleTransactionKind,
+        &'a SuiAddress,
+        &'a ObjectRef,
+        u64,
+        u64,
+    ),
+    Batch(
+        &'a Vec<SingleTransactionKind>,
+        &'a SuiAddress,
+        &'a ObjectRef,
+        u64,
+        u64,
+    ),
+    V2 {
+        kind: &'a TransactionKind,
+        sender: &'a SuiAddress,
+        gas_payment: &'a ObjectRef,
+        gas_price: u64,
+        gas_budget: u64,
+        expiration_epoch: EpochId,
+    },
+}
+
+impl Serialize for TransactionData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let repr = match (&self.kind, self.expiration_epoch) {
+            (_, Some(expiration_epoch)) => TransactionDataReprRef::V2 {
+                kind: &self.kind,
+                sender: &self.sender,
+                gas_payment: &self.gas_payment,
+                gas_price: self.gas_price,
+                gas_budget: self.gas_budget,
+                expiration_epoch,
+            },
+            (TransactionKind::Single(kind), None) => TransactionDataReprRef::Single(
+                kind,
+                &self.sender,
+                &self.gas_payment,
+                self.gas_price,
+                self.gas_budget,
+            ),
+            (TransactionKind::Batch(kinds), None) => TransactionDataReprRef::Batch(
+                kinds,
+                &self.sender,
+                &self.gas_payment,
+                self.gas_price,
+                self.gas_budget,
+            ),
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl From<TransactionDataRepr> for TransactionData {
+    fn from(repr: TransactionDataRepr) -> Self {
+        let (kind, sender, gas_payment, gas_price, gas_budget, expiration_epoch) = match repr {
+            TransactionDataRepr::Single(kind, sender, gas_payment, gas_price, gas_budget) => (
+                TransactionKind::Single(kind),
+                sender,
+                gas_payment,
+                gas_price,
+                gas_budget,
+                None,
+            ),
+            TransactionDataRepr::Batch(kinds, sender, gas_payment, gas_price, gas_budget) => (
+                TransactionKind::Batch(kinds),
+                sender,
+                gas_payment,
+                gas_price,
+                gas_budget,
+                None,
+            ),
+            TransactionDataRepr::V2 {
+                kind,
+                sender,
+                gas_payment,
+                gas_price,
+                gas_budget,
+                expiration_epoch,
+            } => (
+                kind,
+                sender,
+                gas_payment,
+                gas_price,
+                gas_budget,
+                Some(expiration_epoch),
+            ),
+        };
+        Self {
+            kind,
+            sender,
+            gas_payment,
+            gas_price,
+            gas_budget,
+            expiration_epoch,
+        }
+    }
 }
 
 impl TransactionData {
@@ -372,6 +508,7 @@ impl TransactionData {
             gas_price: 1,
             gas_payment,
             gas_budget,
+            expiration_epoch: None,
         }
     }
 
@@ -388,6 +525,7 @@ impl TransactionData {
             gas_price,
             gas_payment,
             gas_budget,
+            expiration_epoch: None,
         }
     }
 
@@ -460,6 +598,13 @@ impl TransactionData {
         self.gas_payment
     }
 
+    /// How urgently the transaction should be processed when an authority is saturated, higher
+    /// first. It is derived from the gas price, and only a hint: nothing guarantees a higher
+    /// priority transaction is processed before a lower priority one.
+    pub fn priority(&self) -> u64 {
+        self.gas_price
+    }
+
     pub fn signer(&self) -> SuiAddress {
         self.sender
     }
@@ -828,12 +973,43 @@ pub struct BatchInfoRequest {
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BatchInfoResponseItem(pub UpdateItem);
 
+/// A request for the batches and transactions before a sequence number, newest first, answered
+/// with a `ReverseBatchInfoResponse`. There is nothing newer to follow, so unlike a
+/// `BatchInfoRequest` it is not streamed.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct ReverseBatchInfoRequest {
+    // The sequence number at which the items end, or None for the latest batch.
+    pub end: Option<TxSequenceNumber>,
+    // The total number of items to receive. Could receive a bit more or a bit less.
+    pub length: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct ReverseBatchInfoResponse {
+    // Each batch comes before its transactions, newest first.
+    pub items: Vec<UpdateItem>,
+}
+
 impl From<SuiAddress> for AccountInfoRequest {
     fn from(account: SuiAddress) -> Self {
         AccountInfoRequest { account }
     }
 }
 
+/// Request a page of the objects owned by an account, in object id order, answered with an
+/// `AccountInfoPageResponse`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct AccountInfoPageRequest {
+    pub account: SuiAddress,
+    /// Return the objects after this one, or from the first one if None.
+    pub cursor: Option<ObjectID>,
+    /// The maximum number of objects to return, at most `MAX_ITEMS_LIMIT`.
+    pub limit: u64,
+    /// Only return the objects of this type, or of any instantiation of it if it has no type
+    /// arguments.
+    pub type_filter: Option<StructTag>,
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub enum ObjectInfoRequestKind {
     /// Request the latest object state, if a format option is provided,
@@ -846,6 +1022,9 @@ pub enum ObjectInfoRequestKind {
     /// we stop storing all historic versions of every object.
     /// No production code should depend on this kind.
     PastObjectInfoDebug(SequenceNumber, Option<ObjectFormatOptions>),
+    /// Request the certificate which created or last mutated the object, along with the
+    /// reference of the latest version, without the object itself.
+    ObjectProvenance,
 }
 
 /// A request for information about an object and optionally its
@@ -875,6 +1054,27 @@ impl ObjectInfoRequest {
             request_kind: ObjectInfoRequestKind::LatestObjectInfo(layout),
         }
     }
+
+    pub fn object_provenance_request(object_id: ObjectID) -> Self {
+        ObjectInfoRequest {
+            object_id,
+            request_kind: ObjectInfoRequestKind::ObjectProvenance,
+        }
+    }
+}
+
+/// The most objects a `MultiObjectInfoRequest` may hold.
+pub const MAX_MULTI_OBJECT_INFO_SIZE: usize = 1000;
+
+/// Request the info of several objects at once, answered with the result of an
+/// `ObjectInfoRequest` per object, in the same order, so that one failed object does not fail
+/// the others. The answer covers the first objects whose responses fit in
+/// `MAX_BATCH_RESPONSE_SIZE_BYTES`, and at least one, the others must be requested again.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct MultiObjectInfoRequest {
+    pub object_ids: Vec<ObjectID>,
+    /// The type of request, applied to every object.
+    pub request_kind: ObjectInfoRequestKind,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
@@ -883,6 +1083,15 @@ pub struct AccountInfoResponse {
     pub owner: SuiAddress,
 }
 
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct AccountInfoPageResponse {
+    pub object_ids: Vec<ObjectRef>,
+    pub owner: SuiAddress,
+    /// The last object returned, if more objects owned by the account follow it. Requesting the
+    /// objects after this cursor returns the next page.
+    pub next_cursor: Option<ObjectID>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ObjectResponse {
     /// Value of the requested object in this authority
@@ -933,6 +1142,43 @@ impl From<TransactionDigest> for TransactionInfoRequest {
     }
 }
 
+/// The most digests a `TransactionInfoBatchRequest` may hold.
+pub const MAX_TRANSACTION_INFO_BATCH_SIZE: usize = 1000;
+
+/// The most bytes the BCS encoded responses to a `MultiObjectInfoRequest` or a
+/// `TransactionInfoBatchRequest` may take, to stay under the 4 MiB gRPC message limit.
+pub const MAX_BATCH_RESPONSE_SIZE_BYTES: u64 = 3 * 1024 * 1024;
+
+/// Request the info of several transactions at once, answered with a `TransactionInfoResponse`
+/// per digest, in the same order. The answer covers the first digests whose responses fit in
+/// `MAX_BATCH_RESPONSE_SIZE_BYTES`, and at least one, the others must be requested again.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct TransactionInfoBatchRequest {
+    pub digests: Vec<TransactionDigest>,
+}
+
+/// Request the events emitted by an executed transaction, answered in the order they were
+/// emitted.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct EventsByTransactionRequest {
+    pub transaction_digest: TransactionDigest,
+}
+
+/// Subscribe to the events emitted by the transactions an authority executes, as they are
+/// executed. If `start` is set, the events of that transaction and of every transaction executed
+/// after it are replayed first, in execution order.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct EventSubscriptionRequest {
+    pub start: Option<TransactionDigest>,
+}
+
+/// An event streamed to an event subscriber, along with the transaction that emitted it.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct EventResponseItem {
+    pub transaction_digest: TransactionDigest,
+    pub event: Event,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TransactionInfoResponse {
     // The signed transaction response to handle_transaction
@@ -1391,6 +1637,41 @@ pub struct TransactionEffects {
 }
 
 impl TransactionEffects {
+    /// Compare these effects against `other`, returning a description of every field that
+    /// differs. An empty result means both effects are identical.
+    pub fn diff(&self, other: &TransactionEffects) -> Vec<String> {
+        let mut diffs = Vec::new();
+        macro_rules! diff_fields {
+            ($($field:ident),*) => {
+                $(
+                    if self.$field != other.$field {
+                        diffs.push(format!(
+                            "{}: {:?} != {:?}",
+                            stringify!($field),
+                            self.$field,
+                            other.$field
+                        ));
+                    }
+                )*
+            };
+        }
+        diff_fields!(
+            status,
+            gas_used,
+            shared_objects,
+            transaction_digest,
+            created,
+            mutated,
+            unwrapped,
+            deleted,
+            wrapped,
+            gas_object,
+            events,
+            dependencies
+        );
+        diffs
+    }
+
     /// Return an iterator that iterates through all mutated objects, including mutated,
     /// created and unwrapped objects. In other words, all objects that still exist
     /// in the object state after this transaction.
@@ -1671,6 +1952,13 @@ impl InputObjects {
             .collect()
     }
 
+    /// Whether one of the inputs is the object with that ID and version.
+    pub fn contains_object(&self, id: ObjectID, version: SequenceNumber) -> bool {
+        self.objects
+            .iter()
+            .any(|(_, input)| input.id() == id && input.version() == version)
+    }
+
     pub fn into_object_map(self) -> BTreeMap<ObjectID, Object> {
         self.objects
             .into_iter()
@@ -1899,9 +2187,53 @@ pub enum ExecuteTransactionResponse {
     EffectsCert(Box<(CertifiedTransaction, CertifiedTransactionEffects)>),
 }
 
+/// A claim by `owner` that it owns the object at `object_ref`. Signed by the owner, it proves
+/// ownership of the object at that version to off-chain services, e.g. for token-gated access.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectOwnershipStatement {
+    pub owner: SuiAddress,
+    pub object_ref: ObjectRef,
+    /// Chosen by the service the proof is made for, e.g. a random nonce, so that the proof cannot
+    /// be replayed to it later or to another service.
+    pub challenge: String,
+}
+
+impl ObjectOwnershipStatement {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Vec::new();
+        self.write(&mut writer);
+        writer
+    }
+}
+
+/// An `ObjectOwnershipStatement` along with the signature of its owner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectOwnershipProof {
+    pub statement: ObjectOwnershipStatement,
+    pub signature: Signature,
+}
+
+impl ObjectOwnershipProof {
+    /// Check that the statement answers `challenge` and was signed by the owner it names. This
+    /// does not check that the owner still owns the object.
+    pub fn verify(&self, challenge: &str) -> SuiResult {
+        fp_ensure!(
+            self.statement.challenge == challenge,
+            SuiError::InvalidSignature {
+                error: format!(
+                    "The proof answers the challenge {:?}, not {challenge:?}",
+                    self.statement.challenge
+                ),
+            }
+        );
+        self.signature.verify(&self.statement, self.statement.owner)
+    }
+}
+
 // Epoch related data structures.
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "EpochInfoRepr", into = "EpochInfoRepr")]
 pub struct EpochInfo {
     /// The committee of this epoch.
     committee: Committee,
@@ -1909,6 +2241,8 @@ pub struct EpochInfo {
     first_checkpoint: CheckpointSequenceNumber,
     /// Digest of the epoch info from the previous epoch.
     prev_digest: EpochInfoDigest,
+    /// The protocol version the committee of this epoch runs.
+    protocol_version: ProtocolVersion,
 }
 
 impl EpochInfo {
@@ -1916,11 +2250,13 @@ impl EpochInfo {
         committee: Committee,
         first_checkpoint: CheckpointSequenceNumber,
         prev_digest: EpochInfoDigest,
+        protocol_version: ProtocolVersion,
     ) -> Self {
         Self {
             committee,
             first_checkpoint,
             prev_digest,
+            protocol_version,
         }
     }
 
@@ -1944,11 +2280,95 @@ impl EpochInfo {
         &self.prev_digest
     }
 
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
     pub fn digest(&self) -> EpochInfoDigest {
         sha3_hash(self)
     }
 }
 
+/// `EpochInfo` as it was before it recorded the protocol version. Epochs running the genesis
+/// protocol version are signed and hashed in this layout, so that the signatures and digests of
+/// the epochs from before then stay valid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename = "EpochInfo")]
+pub struct EpochInfoV1 {
+    pub committee: Committee,
+    pub first_checkpoint: CheckpointSequenceNumber,
+    pub prev_digest: EpochInfoDigest,
+}
+
+/// `EpochInfo` of the epochs running a later protocol version than the genesis one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpochInfoV2 {
+    pub committee: Committee,
+    pub first_checkpoint: CheckpointSequenceNumber,
+    pub prev_digest: EpochInfoDigest,
+    pub protocol_version: ProtocolVersion,
+}
+
+/// The serialized form of `EpochInfo`, versioned so that its layout can change.
+#[derive(Serialize, Deserialize)]
+enum EpochInfoRepr {
+    V1(EpochInfoV1),
+    V2(EpochInfoV2),
+}
+
+impl From<EpochInfoV1> for EpochInfo {
+    fn from(info: EpochInfoV1) -> Self {
+        Self::new(
+            info.committee,
+            info.first_checkpoint,
+            info.prev_digest,
+            GENESIS_PROTOCOL_VERSION,
+        )
+    }
+}
+
+impl From<EpochInfoRepr> for EpochInfo {
+    fn from(repr: EpochInfoRepr) -> Self {
+        match repr {
+            EpochInfoRepr::V1(info) => info.into(),
+            EpochInfoRepr::V2(info) => Self::new(
+                info.committee,
+                info.first_checkpoint,
+                info.prev_digest,
+                info.protocol_version,
+            ),
+        }
+    }
+}
+
+impl From<EpochInfo> for EpochInfoRepr {
+    fn from(info: EpochInfo) -> Self {
+        if info.protocol_version == GENESIS_PROTOCOL_VERSION {
+            Self::V1(EpochInfoV1 {
+                committee: info.committee,
+                first_checkpoint: info.first_checkpoint,
+                prev_digest: info.prev_digest,
+            })
+        } else {
+            Self::V2(EpochInfoV2 {
+                committee: info.committee,
+                first_checkpoint: info.first_checkpoint,
+                prev_digest: info.prev_digest,
+                protocol_version: info.protocol_version,
+            })
+        }
+    }
+}
+
+impl<W: std::io::Write> Signable<W> for EpochInfo {
+    fn write(&self, writer: &mut W) {
+        match EpochInfoRepr::from(self.clone()) {
+            EpochInfoRepr::V1(info) => info.write(writer),
+            EpochInfoRepr::V2(info) => info.write(writer),
+        }
+    }
+}
+
 pub type EpochInfoDigest = [u8; 32];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1964,7 +2384,12 @@ pub type CertifiedEpoch = EpochEnvelop<AuthorityStrongQuorumSignInfo>;
 impl GenesisEpoch {
     pub fn new(committee: Committee) -> Self {
         Self {
-            epoch_info: EpochInfo::new(committee, 0, EpochInfoDigest::default()),
+            epoch_info: EpochInfo::new(
+                committee,
+                0,
+                EpochInfoDigest::default(),
+                GENESIS_PROTOCOL_VERSION,
+            ),
             auth_sign_info: EmptySignInfo {},
         }
     }
@@ -1984,11 +2409,19 @@ impl GenesisEpoch {
             &self.epoch_info.committee == genesis_committee,
             SuiError::InvalidAuthenticatedEpoch("Genesis epoch committee mismatch".to_string())
         );
+        fp_ensure!(
+            self.epoch_info.protocol_version == GENESIS_PROTOCOL_VERSION,
+            SuiError::InvalidAuthenticatedEpoch(
+                "Genesis epoch must run the genesis protocol version".to_string()
+            )
+        );
         Ok(())
     }
 }
 
 impl SignedEpoch {
+    /// Sign the epoch following `prev_epoch_info`. The new epoch runs the same protocol version
+    /// as the previous one.
     pub fn new(
         committee: Committee,
         authority: AuthorityName,
@@ -1997,7 +2430,12 @@ impl SignedEpoch {
         prev_epoch_info: &EpochInfo,
     ) -> Self {
         let epoch = committee.epoch;
-        let epoch_info = EpochInfo::new(committee, first_checkpoint, prev_epoch_info.digest());
+        let epoch_info = EpochInfo::new(
+            committee,
+            first_checkpoint,
+            prev_epoch_info.digest(),
+            prev_epoch_info.protocol_version(),
+        );
         let signature = AuthoritySignature::new(&epoch_info, secret);
         Self {
             epoch_info,
@@ -2109,3 +2547,35 @@ pub struct EpochRequest {
 pub struct EpochResponse {
     pub epoch_info: Option<AuthenticatedEpoch>,
 }
+
+/// Request the recent activity of an authority, for operators.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeStatsRequest {}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeStatsResponse {
+    /// Certificates executed per second, over the last minute.
+    pub recent_tps: f64,
+    /// Certificates executed since the authority started from genesis.
+    pub total_certificates: u64,
+    /// The sequence number following the last batch, or None if no batch was made yet.
+    pub batch_watermark: Option<TxSequenceNumber>,
+}
+
+/// Request the total balance of the coins of a currency stored by an authority. This scans every
+/// object, so authorities only serve it if enabled in their config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoinSupplyRequest {
+    /// The currency type T of the `Coin<T>` objects, e.g. `0x2::sui::SUI`.
+    pub coin_type: StructTag,
+}
+
+/// The coins of a currency, as of the transactions committed by the authority a short while
+/// before the request. Coins wrapped in other objects are not counted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoinSupplyResponse {
+    /// The sum of the balances of the coins, which may not fit in a `u64`.
+    pub total_supply: u128,
+    /// The number of coin objects.
+    pub coin_count: u64,
+}
@@ -379,6 +379,31 @@ impl CertifiedCheckpointSummary {
     }
 }
 
+/// Check that `checkpoint` is the certified checkpoint at `sequence_number` of the epoch of
+/// `committee`, and that it is signed by enough of the committee that at least one honest
+/// authority vouches for it, as `CertifiedTransaction::verify` does for certificates. If
+/// `contents` are given, also check that they are the contents the checkpoint commits to.
+///
+/// This lets a client trust a checkpoint, and the transactions it contains, through the
+/// committee signatures alone, whichever authority or gateway it was fetched from.
+pub fn verify_certified_checkpoint(
+    checkpoint: &CertifiedCheckpointSummary,
+    sequence_number: CheckpointSequenceNumber,
+    committee: &Committee,
+    contents: Option<&CheckpointContents>,
+) -> SuiResult {
+    fp_ensure!(
+        checkpoint.summary.sequence_number == sequence_number,
+        SuiError::GenericAuthorityError {
+            error: format!(
+                "Expected checkpoint {sequence_number}, received checkpoint {}",
+                checkpoint.summary.sequence_number
+            )
+        }
+    );
+    checkpoint.verify(committee, contents)
+}
+
 /// CheckpointProposalContents represents the contents of a proposal.
 /// Contents in a proposal are not yet causally ordered, and hence we don't care about
 /// the order of transactions in the content. It's only important that two proposal
@@ -0,0 +1,300 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_with::{serde_as, Bytes, DeserializeAs};
+
+use crate::base_types::SuiAddress;
+use crate::crypto::{
+    PublicKey, Signable, Signature, SignatureScheme, SuiSignature, VerificationObligation,
+};
+use crate::error::{SuiError, SuiResult};
+use crate::sui_serde::{Base64, Readable};
+
+#[cfg(test)]
+#[path = "unit_tests/multisig_tests.rs"]
+mod multisig_tests;
+
+/// The maximum number of public keys a multisig address can be made of.
+pub const MAX_MULTISIG_PUBLIC_KEYS: usize = 10;
+
+/// The public keys that control a multisig address, and how many of them must sign for it.
+/// The keys are kept sorted so that the address only depends on the set of keys and the threshold.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MultiSigPublicKey {
+    pks: Vec<PublicKey>,
+    threshold: u16,
+}
+
+impl MultiSigPublicKey {
+    pub fn new(mut pks: Vec<PublicKey>, threshold: u16) -> SuiResult<Self> {
+        pks.sort_by(|a, b| (a.flag(), a.as_ref()).cmp(&(b.flag(), b.as_ref())));
+        let multisig_pk = Self { pks, threshold };
+        multisig_pk.validate()?;
+        Ok(multisig_pk)
+    }
+
+    pub fn public_keys(&self) -> &[PublicKey] {
+        &self.pks
+    }
+
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    fn validate(&self) -> SuiResult {
+        let error = |error: String| Err(SuiError::InvalidMultiSigPublicKey { error });
+        if self.pks.is_empty() || self.pks.len() > MAX_MULTISIG_PUBLIC_KEYS {
+            return error(format!(
+                "Expected between 1 and {MAX_MULTISIG_PUBLIC_KEYS} public keys, got {}",
+                self.pks.len()
+            ));
+        }
+        if self.threshold == 0 || self.threshold as usize > self.pks.len() {
+            return error(format!(
+                "Threshold must be between 1 and the number of public keys ({}), got {}",
+                self.pks.len(),
+                self.threshold
+            ));
+        }
+        for pair in self.pks.windows(2) {
+            match (pair[0].flag(), pair[0].as_ref()).cmp(&(pair[1].flag(), pair[1].as_ref())) {
+                std::cmp::Ordering::Less => (),
+                std::cmp::Ordering::Equal => {
+                    return error("Public keys must be distinct".to_string())
+                }
+                std::cmp::Ordering::Greater => {
+                    return error("Public keys must be sorted".to_string())
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn position(&self, signature: &Signature) -> Option<usize> {
+        self.pks.iter().position(|pk| {
+            pk.flag() == signature.scheme().flag() && pk.as_ref() == signature.public_key_bytes()
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for MultiSigPublicKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(Deserialize)]
+        #[serde(rename = "MultiSigPublicKey")]
+        struct UncheckedMultiSigPublicKey {
+            pks: Vec<PublicKey>,
+            threshold: u16,
+        }
+
+        let unchecked = UncheckedMultiSigPublicKey::deserialize(deserializer)?;
+        let multisig_pk = Self {
+            pks: unchecked.pks,
+            threshold: unchecked.threshold,
+        };
+        multisig_pk
+            .validate()
+            .map_err(|e| Error::custom(e.to_string()))?;
+        Ok(multisig_pk)
+    }
+}
+
+/// A signature on behalf of a multisig address: the signatures of some of its keys, along with
+/// the multisig public key they are checked against.
+///
+/// Serialized as `flag || bcs(signatures) || bcs(multisig public key)`, so that the signature
+/// and public key parts can be sent separately to the execution APIs like single key signatures.
+#[serde_as]
+#[derive(Clone, Debug, Serialize, JsonSchema, PartialEq, Eq, Hash)]
+pub struct MultiSigSuiSignature(
+    #[schemars(with = "Base64")]
+    #[serde_as(as = "Readable<Base64, Bytes>")]
+    Vec<u8>,
+);
+
+impl MultiSigSuiSignature {
+    /// Combine the signatures of keys of `multisig_pk`. Nothing is verified here, an address
+    /// accepts the signature once it carries valid signatures from `threshold` of its keys.
+    pub fn new(signatures: Vec<Signature>, multisig_pk: &MultiSigPublicKey) -> SuiResult<Self> {
+        let signatures = signatures
+            .iter()
+            .map(|signature| {
+                if matches!(signature.scheme(), SignatureScheme::MultiSig) {
+                    Err(SuiError::InvalidSignature {
+                        error: "Multisig signatures cannot be nested".to_string(),
+                    })
+                } else {
+                    Ok(signature.as_ref().to_vec())
+                }
+            })
+            .collect::<SuiResult<Vec<_>>>()?;
+        let mut bytes = vec![SignatureScheme::MultiSig.flag()];
+        bytes.extend(
+            bcs::to_bytes(&signatures).map_err(|e| SuiError::InvalidSignature {
+                error: e.to_string(),
+            })?,
+        );
+        bytes.extend(
+            bcs::to_bytes(multisig_pk).map_err(|e| SuiError::InvalidSignature {
+                error: e.to_string(),
+            })?,
+        );
+        Ok(Self(bytes))
+    }
+
+    /// Decode the signatures and the multisig public key. Signatures are decoded as raw bytes
+    /// first so that nested multisig signatures are rejected without recursing into them.
+    pub fn decode(&self) -> SuiResult<(Vec<Signature>, MultiSigPublicKey)> {
+        let invalid = |error: String| SuiError::InvalidSignature { error };
+        let (signatures, multisig_pk) = Self::decode_parts(&self.0)?;
+        let signatures = signatures
+            .iter()
+            .map(|bytes| {
+                if bytes.first() == Some(&SignatureScheme::MultiSig.flag()) {
+                    return Err(invalid("Multisig signatures cannot be nested".to_string()));
+                }
+                <Signature as signature::Signature>::from_bytes(bytes)
+                    .map_err(|e| invalid(e.to_string()))
+            })
+            .collect::<SuiResult<_>>()?;
+        Ok((signatures, multisig_pk))
+    }
+
+    /// Split the bytes into the raw signatures and the multisig public key, which is validated.
+    fn decode_parts(bytes: &[u8]) -> SuiResult<(Vec<Vec<u8>>, MultiSigPublicKey)> {
+        let invalid = |error: String| SuiError::InvalidSignature { error };
+        match bytes.split_first() {
+            Some((flag, parts)) if *flag == SignatureScheme::MultiSig.flag() => {
+                bcs::from_bytes(parts).map_err(|e| invalid(e.to_string()))
+            }
+            _ => Err(invalid("Expected a multisig signature flag".to_string())),
+        }
+    }
+
+    /// Returns the signatures and their signers, after checking that they are signed by distinct
+    /// keys of the multisig address `author`, and enough of them to meet its threshold.
+    fn get_verification_inputs(
+        &self,
+        author: SuiAddress,
+    ) -> SuiResult<Vec<(Signature, SuiAddress)>> {
+        let (signatures, multisig_pk) = self.decode()?;
+        let received_addr = SuiAddress::from(&multisig_pk);
+        if received_addr != author {
+            return Err(SuiError::IncorrectSigner {
+                error: format!(
+                    "Multisig signature author is {author}, received address is {received_addr}"
+                ),
+            });
+        }
+
+        let mut signers = BTreeSet::new();
+        let mut inputs = Vec::with_capacity(signatures.len());
+        for signature in signatures {
+            let index =
+                multisig_pk
+                    .position(&signature)
+                    .ok_or_else(|| SuiError::InvalidSignature {
+                        error: "Signature by a key which is not part of the multisig".to_string(),
+                    })?;
+            if !signers.insert(index) {
+                return Err(SuiError::InvalidSignature {
+                    error: "Multiple signatures by the same multisig key".to_string(),
+                });
+            }
+            let signer = SuiAddress::from(&multisig_pk.pks[index]);
+            inputs.push((signature, signer));
+        }
+
+        if signers.len() < multisig_pk.threshold as usize {
+            return Err(SuiError::MultiSigThresholdNotMet {
+                signatures: signers.len(),
+                threshold: multisig_pk.threshold,
+            });
+        }
+        Ok(inputs)
+    }
+
+    /// The index of the first byte of the multisig public key. Multisig signatures are decoded
+    /// whenever they are constructed or deserialized, so the bytes always split. Were they not to,
+    /// the whole signature is treated as signature bytes, which fails verification.
+    fn public_key_offset(&self) -> usize {
+        Self::decode_parts(&self.0)
+            .ok()
+            .and_then(|(signatures, _)| bcs::serialized_size(&signatures).ok())
+            .map_or(self.0.len(), |size| 1 + size)
+    }
+}
+
+impl AsRef<[u8]> for MultiSigSuiSignature {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl signature::Signature for MultiSigSuiSignature {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+        let signature = Self(bytes.to_vec());
+        signature.decode().map_err(|_| signature::Error::new())?;
+        Ok(signature)
+    }
+}
+
+impl<'de> Deserialize<'de> for MultiSigSuiSignature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let bytes: Vec<u8> = Readable::<Base64, Bytes>::deserialize_as(deserializer)?;
+        let signature = Self(bytes);
+        signature
+            .decode()
+            .map_err(|e| Error::custom(e.to_string()))?;
+        Ok(signature)
+    }
+}
+
+impl SuiSignature for MultiSigSuiSignature {
+    fn signature_bytes(&self) -> &[u8] {
+        self.0.get(1..self.public_key_offset()).unwrap_or_default()
+    }
+
+    fn public_key_bytes(&self) -> &[u8] {
+        self.0.get(self.public_key_offset()..).unwrap_or_default()
+    }
+
+    fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::MultiSig
+    }
+
+    fn verify<T>(&self, value: &T, author: SuiAddress) -> SuiResult<()>
+    where
+        T: Signable<Vec<u8>>,
+    {
+        self.get_verification_inputs(author)?
+            .iter()
+            .try_for_each(|(signature, signer)| signature.verify(value, *signer))
+    }
+
+    fn add_to_verification_obligation_or_verify(
+        &self,
+        author: SuiAddress,
+        obligation: &mut VerificationObligation,
+        idx: usize,
+    ) -> SuiResult<()> {
+        self.get_verification_inputs(author)?
+            .iter()
+            .try_for_each(|(signature, signer)| {
+                signature.add_to_verification_obligation_or_verify(*signer, obligation, idx)
+            })
+    }
+}
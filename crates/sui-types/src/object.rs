@@ -633,13 +633,21 @@ impl Object {
     }
 }
 
+/// What is left of an object once it has been deleted or wrapped: the reference it was given
+/// by its deletion, and the transaction that deleted it.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize, Hash)]
+pub struct ObjectTombstone {
+    pub object_ref: ObjectRef,
+    pub deleted_by: TransactionDigest,
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "status", content = "details")]
 pub enum ObjectRead {
     NotExists(ObjectID),
     Exists(ObjectRef, Object, Option<MoveStructLayout>),
-    Deleted(ObjectRef),
+    Deleted(ObjectTombstone),
 }
 
 impl ObjectRead {
@@ -647,7 +655,9 @@ impl ObjectRead {
     /// the object does not exist or is deleted.
     pub fn into_object(self) -> Result<Object, SuiError> {
         match self {
-            Self::Deleted(oref) => Err(SuiError::ObjectDeleted { object_ref: oref }),
+            Self::Deleted(tombstone) => Err(SuiError::ObjectDeleted {
+                object_ref: tombstone.object_ref,
+            }),
             Self::NotExists(id) => Err(SuiError::ObjectNotFound { object_id: id }),
             Self::Exists(_, o, _) => Ok(o),
         }
@@ -665,8 +675,12 @@ impl Default for ObjectFormatOptions {
 impl Display for ObjectRead {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Deleted(oref) => {
-                write!(f, "ObjectRead::Deleted ({:?})", oref)
+            Self::Deleted(tombstone) => {
+                write!(
+                    f,
+                    "ObjectRead::Deleted ({:?}, deleted by {:?})",
+                    tombstone.object_ref, tombstone.deleted_by
+                )
             }
             Self::NotExists(id) => {
                 write!(f, "ObjectRead::NotExists ({:?})", id)
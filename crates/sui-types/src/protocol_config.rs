@@ -0,0 +1,67 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{SuiError, SuiResult};
+
+/// The version of the protocol run by the committee of an epoch. It is recorded in the
+/// [`EpochInfo`](crate::messages::EpochInfo) of each epoch, and selects the [`ProtocolConfig`]
+/// in effect for that epoch. Clients read it from the epoch certified by a quorum.
+pub type ProtocolVersion = u64;
+
+/// The protocol version the network starts with.
+pub const GENESIS_PROTOCOL_VERSION: ProtocolVersion = 1;
+
+/// The latest protocol version this binary knows the parameters of.
+pub const MAX_PROTOCOL_VERSION: ProtocolVersion = 2;
+
+/// Limits and parameters that are part of the protocol, and so can only change with a new
+/// protocol version. Parameters of existing versions must never be changed, as every node has
+/// to agree on them for any epoch it processes.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolConfig {
+    pub version: ProtocolVersion,
+    /// Maximum size of the BCS encoded transaction data.
+    pub max_tx_size_bytes: u64,
+    /// Maximum number of objects, including packages, a transaction may take as input.
+    pub max_input_objects: u64,
+    /// Maximum number of transactions in a batch transaction.
+    pub max_transactions_in_batch: u64,
+    /// Maximum nesting depth of the type arguments of a Move call, `u64` having depth 1 and
+    /// `vector<u64>` depth 2. Not limited before version 2.
+    pub max_type_argument_depth: Option<u64>,
+    /// Minimum computation gas price a transaction may set.
+    pub min_gas_price: u64,
+}
+
+impl ProtocolConfig {
+    pub fn get_for_version(version: ProtocolVersion) -> SuiResult<Self> {
+        match version {
+            1 => Ok(Self {
+                version,
+                max_tx_size_bytes: 512 * 1024,
+                max_input_objects: 2048,
+                max_transactions_in_batch: 1024,
+                max_type_argument_depth: None,
+                min_gas_price: 1,
+            }),
+            2 => Ok(Self {
+                version,
+                max_type_argument_depth: Some(16),
+                ..Self::get_for_version(1)?
+            }),
+            _ => Err(SuiError::UnsupportedProtocolVersion {
+                version,
+                max_supported: MAX_PROTOCOL_VERSION,
+            }),
+        }
+    }
+
+    pub fn get_for_max_version() -> Self {
+        Self::get_for_version(MAX_PROTOCOL_VERSION)
+            .expect("Parameters of the max protocol version must be defined")
+    }
+}
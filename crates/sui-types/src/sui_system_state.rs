@@ -95,4 +95,14 @@ impl SuiSystemState {
             type_params: vec![],
         }
     }
+
+    /// The gas price transactions should use by default: the reference gas price, or the
+    /// minimum gas price of the protocol, `min_gas_price`, when no reference gas price is set yet.
+    pub fn effective_reference_gas_price(&self, min_gas_price: u64) -> u64 {
+        if self.reference_gas_price == 0 {
+            min_gas_price
+        } else {
+            self.reference_gas_price
+        }
+    }
 }
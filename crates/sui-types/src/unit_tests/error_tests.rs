@@ -0,0 +1,51 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn test_error_codes() {
+    let error = SuiError::ObjectNotFound {
+        object_id: ObjectID::random(),
+    };
+    assert_eq!(error.code(), "ObjectNotFound");
+    assert_eq!(error.grpc_code(), tonic::Code::NotFound);
+    assert_eq!(
+        SuiError::TooManyItemsError {
+            count: 10,
+            limit: 5
+        }
+        .grpc_code(),
+        tonic::Code::ResourceExhausted
+    );
+    assert_eq!(
+        SuiError::ObjectErrors { errors: vec![] }.grpc_code(),
+        tonic::Code::FailedPrecondition
+    );
+    assert_eq!(SuiError::TimeoutError.grpc_code(), tonic::Code::Internal);
+
+    // The code survives the network.
+    let status = tonic::Status::from(error.clone());
+    assert_eq!(status.code(), tonic::Code::NotFound);
+    assert_eq!(status.message(), error.to_string());
+    let received = SuiError::from(status);
+    assert!(matches!(received, SuiError::RpcError(..)));
+    assert_eq!(received.code(), "ObjectNotFound");
+    assert!(!received.is_transport_error());
+
+    // And is forwarded by whoever passes the error on.
+    let status = received.to_status(tonic::Code::Internal);
+    assert_eq!(SuiError::from(status).code(), "ObjectNotFound");
+
+    // Errors without a known code are plain RPC errors.
+    let status = tonic::Status::unavailable("Connection reset");
+    assert_eq!(SuiError::from(status).code(), "RpcError");
+    let mut status = tonic::Status::unavailable("Connection reset");
+    status.metadata_mut().insert(
+        SUI_ERROR_CODE_METADATA_KEY,
+        tonic::metadata::MetadataValue::from_static("NoSuchError"),
+    );
+    let error = SuiError::from(status);
+    assert_eq!(error.code(), "RpcError");
+    assert!(error.is_transport_error());
+}
@@ -11,6 +11,7 @@ use roaring::RoaringBitmap;
 
 use crate::crypto::bcs_signable_test::{get_obligation_input, Foo};
 use crate::crypto::Secp256k1SuiSignature;
+use crate::crypto::SignableBytes;
 use crate::crypto::SuiKeyPair;
 use crate::crypto::{get_key_pair, AccountKeyPair, AuthorityKeyPair, AuthorityPublicKeyBytes};
 use crate::messages_checkpoint::CheckpointContents;
@@ -696,3 +697,102 @@ fn verify_sender_signature_correctly_with_flag() {
         .verify(&transaction.signed_data, &committee)
         .is_err());
 }
+
+#[test]
+fn test_epoch_info_keeps_legacy_layout() {
+    let (_, sec): (_, AuthorityKeyPair) = get_key_pair();
+    let authorities = BTreeMap::from([(AuthorityPublicKeyBytes::from(sec.public()), 1)]);
+    let committee = Committee::new(1, authorities).unwrap();
+    let legacy = EpochInfoV1 {
+        committee: committee.clone(),
+        first_checkpoint: 5,
+        prev_digest: [7; 32],
+    };
+
+    // Epochs from before the protocol version was recorded run the genesis version, and keep
+    // their digest and signatures.
+    let epoch_info = EpochInfo::from(legacy.clone());
+    assert_eq!(epoch_info.protocol_version(), GENESIS_PROTOCOL_VERSION);
+    assert_eq!(epoch_info.digest(), sha3_hash(&legacy));
+    let signature = AuthoritySignature::new(&legacy, &sec);
+    assert!(signature
+        .verify(&epoch_info, AuthorityPublicKeyBytes::from(sec.public()))
+        .is_ok());
+
+    // Later protocol versions are recorded, and signed.
+    let upgraded = EpochInfo::new(committee, 5, [7; 32], GENESIS_PROTOCOL_VERSION + 1);
+    assert_ne!(upgraded.digest(), epoch_info.digest());
+    for epoch_info in [epoch_info, upgraded] {
+        let bytes = bcs::to_bytes(&epoch_info).unwrap();
+        let decoded: EpochInfo = bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.protocol_version(), epoch_info.protocol_version());
+        assert_eq!(decoded.digest(), epoch_info.digest());
+    }
+}
+
+#[test]
+fn test_transaction_data_keeps_legacy_layout() {
+    // `TransactionData` as it was before it had an expiration epoch.
+    #[derive(Serialize)]
+    struct LegacyTransactionData {
+        kind: TransactionKind,
+        sender: SuiAddress,
+        gas_payment: ObjectRef,
+        gas_price: u64,
+        gas_budget: u64,
+    }
+
+    let sender = SuiAddress::random_for_testing_only();
+    let gas_payment = random_object_ref();
+    let transfer = SingleTransactionKind::TransferSui(TransferSui {
+        recipient: SuiAddress::random_for_testing_only(),
+        amount: Some(10),
+    });
+    for kind in [
+        TransactionKind::Single(transfer.clone()),
+        TransactionKind::Batch(vec![transfer.clone(), transfer.clone()]),
+    ] {
+        let data = TransactionData::new(kind.clone(), sender, gas_payment, 10000);
+        let legacy = LegacyTransactionData {
+            kind,
+            sender,
+            gas_payment,
+            gas_price: data.gas_price,
+            gas_budget: data.gas_budget,
+        };
+
+        // Transactions without an expiration keep their bytes, and so their digest.
+        let legacy_bytes = bcs::to_bytes(&legacy).unwrap();
+        assert_eq!(bcs::to_bytes(&data).unwrap(), legacy_bytes);
+        assert_eq!(
+            data.to_bytes(),
+            [b"TransactionData::".as_slice(), &legacy_bytes].concat()
+        );
+        assert_eq!(
+            bcs::from_bytes::<TransactionData>(&legacy_bytes).unwrap(),
+            data
+        );
+        // Stored transactions are encoded with bincode.
+        assert_eq!(
+            bincode::serialize(&data).unwrap(),
+            bincode::serialize(&legacy).unwrap()
+        );
+
+        // An expiration changes the layout, and round trips.
+        let expiring = TransactionData {
+            expiration_epoch: Some(3),
+            ..data.clone()
+        };
+        let bytes = expiring.to_bytes();
+        assert_ne!(bytes, data.to_bytes());
+        assert_eq!(
+            TransactionData::from_signable_bytes(&bytes).unwrap(),
+            expiring
+        );
+        let bytes = bincode::serialize(&expiring).unwrap();
+        assert_eq!(
+            bincode::deserialize::<TransactionData>(&bytes).unwrap(),
+            expiring
+        );
+    }
+}
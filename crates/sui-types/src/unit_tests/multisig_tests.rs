@@ -0,0 +1,150 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use signature::Signer;
+
+use crate::crypto::bcs_signable_test::{get_obligation_input, Foo};
+use crate::crypto::{get_key_pair, AccountKeyPair, Secp256k1KeyPair, SuiKeyPair};
+use crate::sui_serde::Encoding;
+
+use super::*;
+
+fn keys() -> Vec<SuiKeyPair> {
+    let (_, k1): (_, AccountKeyPair) = get_key_pair();
+    let (_, k2): (_, Secp256k1KeyPair) = get_key_pair();
+    let (_, k3): (_, AccountKeyPair) = get_key_pair();
+    vec![k1.into(), k2.into(), k3.into()]
+}
+
+fn sign(key: &SuiKeyPair, value: &Foo) -> Signature {
+    let mut message = Vec::new();
+    value.write(&mut message);
+    key.sign(&message)
+}
+
+#[test]
+fn test_multisig_address_is_deterministic() {
+    let keys = keys();
+    let pks: Vec<_> = keys.iter().map(|k| k.public()).collect();
+    let address = SuiAddress::from(&MultiSigPublicKey::new(pks.clone(), 2).unwrap());
+
+    let reversed = pks.iter().rev().cloned().collect();
+    assert_eq!(
+        SuiAddress::from(&MultiSigPublicKey::new(reversed, 2).unwrap()),
+        address
+    );
+    assert_ne!(
+        SuiAddress::from(&MultiSigPublicKey::new(pks.clone(), 3).unwrap()),
+        address
+    );
+    // A multisig of a single key does not share the address of that key.
+    let single = MultiSigPublicKey::new(vec![pks[0].clone()], 1).unwrap();
+    assert_ne!(SuiAddress::from(&single), SuiAddress::from(&pks[0]));
+
+    assert!(MultiSigPublicKey::new(pks.clone(), 0).is_err());
+    assert!(MultiSigPublicKey::new(pks.clone(), 4).is_err());
+    assert!(MultiSigPublicKey::new(vec![pks[0].clone(), pks[0].clone()], 1).is_err());
+}
+
+#[test]
+fn test_multisig_2_of_3() {
+    let keys = keys();
+    let multisig_pk = MultiSigPublicKey::new(keys.iter().map(|k| k.public()).collect(), 2).unwrap();
+    let address = SuiAddress::from(&multisig_pk);
+    let value = Foo("hello".to_string());
+
+    for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+        let signatures = vec![sign(&keys[a], &value), sign(&keys[b], &value)];
+        let signature: Signature = MultiSigSuiSignature::new(signatures, &multisig_pk)
+            .unwrap()
+            .into();
+        assert_eq!(signature.scheme().flag(), SignatureScheme::MultiSig.flag());
+        signature.verify(&value, address).unwrap();
+
+        let (mut obligation, idx) = get_obligation_input(&value);
+        signature
+            .add_to_verification_obligation_or_verify(address, &mut obligation, idx)
+            .unwrap();
+        obligation.verify_all().unwrap();
+
+        // The signature survives being split into its parts for the execution APIs.
+        let bytes = [
+            &[signature.scheme().flag()],
+            signature.signature_bytes(),
+            signature.public_key_bytes(),
+        ]
+        .concat();
+        let decoded = <Signature as signature::Signature>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, signature);
+    }
+
+    // Signing for the wrong address or value fails.
+    let signatures = vec![sign(&keys[0], &value), sign(&keys[1], &value)];
+    let signature: Signature = MultiSigSuiSignature::new(signatures, &multisig_pk)
+        .unwrap()
+        .into();
+    assert!(signature
+        .verify(&value, SuiAddress::from(&keys[0].public()))
+        .is_err());
+    assert!(signature.verify(&Foo("bye".to_string()), address).is_err());
+}
+
+#[test]
+fn test_multisig_threshold_enforced() {
+    let keys = keys();
+    let multisig_pk = MultiSigPublicKey::new(keys.iter().map(|k| k.public()).collect(), 2).unwrap();
+    let address = SuiAddress::from(&multisig_pk);
+    let value = Foo("hello".to_string());
+
+    let signature: Signature =
+        MultiSigSuiSignature::new(vec![sign(&keys[0], &value)], &multisig_pk)
+            .unwrap()
+            .into();
+    assert_eq!(
+        signature.verify(&value, address),
+        Err(SuiError::MultiSigThresholdNotMet {
+            signatures: 1,
+            threshold: 2
+        })
+    );
+
+    // The same key signing twice does not count twice.
+    let signatures = vec![sign(&keys[0], &value), sign(&keys[0], &value)];
+    let signature: Signature = MultiSigSuiSignature::new(signatures, &multisig_pk)
+        .unwrap()
+        .into();
+    assert!(signature.verify(&value, address).is_err());
+
+    // Nor does a key outside of the multisig.
+    let (_, outsider): (_, AccountKeyPair) = get_key_pair();
+    let signatures = vec![
+        sign(&keys[0], &value),
+        sign(&SuiKeyPair::from(outsider), &value),
+    ];
+    let signature: Signature = MultiSigSuiSignature::new(signatures, &multisig_pk)
+        .unwrap()
+        .into();
+    assert!(signature.verify(&value, address).is_err());
+}
+
+#[test]
+fn test_multisig_malformed_input_is_rejected() {
+    let pks: Vec<_> = keys().iter().map(|k| k.public()).collect();
+    // The public key is validated when deserialized, not only when built by `new`.
+    let unchecked_pk = bcs::to_bytes(&(pks, 0u16)).unwrap();
+    assert!(bcs::from_bytes::<MultiSigPublicKey>(&unchecked_pk).is_err());
+
+    let flag = SignatureScheme::MultiSig.flag();
+    let no_signatures = bcs::to_bytes(&Vec::<Vec<u8>>::new()).unwrap();
+    for bytes in [
+        vec![],
+        vec![flag],
+        [&[flag][..], &no_signatures, &unchecked_pk].concat(),
+    ] {
+        assert!(<MultiSigSuiSignature as signature::Signature>::from_bytes(&bytes).is_err());
+        let serialized = bcs::to_bytes(&bytes).unwrap();
+        assert!(bcs::from_bytes::<MultiSigSuiSignature>(&serialized).is_err());
+        let encoded = serde_json::to_string(&Base64::encode(&bytes)).unwrap();
+        assert!(serde_json::from_str::<MultiSigSuiSignature>(&encoded).is_err());
+    }
+}
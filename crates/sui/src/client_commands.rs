@@ -3,49 +3,86 @@
 
 use core::fmt;
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    convert::Infallible,
     fmt::{Debug, Display, Formatter, Write},
+    fs,
+    io::{self, stdout, BufRead, Write as IoWrite},
+    net::{Ipv4Addr, SocketAddr},
     path::{Path, PathBuf},
-    time::Instant,
+    str::FromStr,
+    time::{Duration, Instant},
 };
 
 use anyhow::anyhow;
 use clap::*;
 use colored::Colorize;
-use move_core_types::language_storage::TypeTag;
+use futures::{pin_mut, stream, Stream, StreamExt};
+use move_core_types::language_storage::{StructTag, TypeTag};
 use move_package::BuildConfig;
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 use serde_json::json;
+use sui_config::sui_config_dir;
+use sui_core::gateway_state::{GatewayClient, GatewayTxSeqNumber};
 use sui_framework::build_move_package_to_bytes;
 use sui_json::SuiJsonValue;
+use sui_json_rpc::bcs_api::BcsApiImpl;
+use sui_json_rpc::gateway_api::{
+    GatewayReadApiImpl, GatewayWalletSyncApiImpl, RpcGatewayImpl, TransactionBuilderImpl,
+};
+use sui_json_rpc::http_server::HttpServerHandle;
+use sui_json_rpc::JsonRpcServerBuilder;
 use sui_json_rpc_types::SuiData;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, SuiExecuteTransactionResponse, SuiObjectInfo, SuiParsedObject,
-    SuiTransactionResponse,
+    GetObjectDataResponse, MoveCallParams, RPCTransactionRequestParams, SuiEvent,
+    SuiExecuteTransactionResponse, SuiObjectInfo, SuiParsedData, SuiParsedObject,
+    SuiTransactionFilter, SuiTransactionKind, SuiTransactionResponse, SuiTransferSui, SyncProgress,
+    SystemState, TransferObjectParams,
 };
 use sui_json_rpc_types::{SuiCertifiedTransaction, SuiExecutionStatus, SuiTransactionEffects};
-use sui_sdk::crypto::SuiKeystore;
+use sui_sdk::crypto::{KeystoreType, SuiKeystore};
 use sui_sdk::{ClientType, SuiClient};
 use sui_types::{
-    base_types::{ObjectID, SuiAddress},
-    gas_coin::GasCoin,
+    base_types::{AuthorityName, ObjectID, SuiAddress, TransactionDigest},
+    coin::{self, Coin},
+    committee::EpochId,
+    gas_coin::{GasCoin, GAS},
     messages::ExecuteTransactionRequestType,
-    messages::Transaction,
+    messages::{
+        ObjectOwnershipProof, ObjectOwnershipStatement, Transaction, TransactionData,
+        TransactionEffects, TransactionInfoResponse,
+    },
+    messages_checkpoint::CheckpointSequenceNumber,
     object::Owner,
-    parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS,
+    parse_sui_struct_tag, parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS, SUI_FRAMEWORK_OBJECT_ID,
 };
 use sui_types::{
-    crypto::SignatureScheme,
+    crypto::{
+        EncodeDecodeBase64, PublicKey, SignableBytes, Signature, SignatureScheme, SuiSignature,
+    },
+    multisig::{MultiSigPublicKey, MultiSigSuiSignature},
     sui_serde::{Base64, Encoding},
 };
 use tracing::info;
 
-use crate::config::{Config, PersistedConfig, SuiClientConfig};
+use crate::config::{AddressBook, ClientProfile, Config, PersistedConfig, SuiClientConfig};
 
 pub const EXAMPLE_NFT_NAME: &str = "Example NFT";
 pub const EXAMPLE_NFT_DESCRIPTION: &str = "An NFT created by the Sui Command Line Tool";
 pub const EXAMPLE_NFT_URL: &str =
     "ipfs://bafkreibngqhl3gaa7daob4i2vccziay2jjlp435cf66vhono7nrvww53ty";
+/// How long `--wait-for-checkpoint` waits for a transaction to be checkpointed.
+pub const CHECKPOINT_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+/// How many objects `sui client sync` syncs between progress reports.
+pub const SYNC_STEP_SIZE: usize = 50;
+/// How many objects `sui client objects` requests at a time when paging without `--limit`.
+pub const OBJECTS_PAGE_SIZE: usize = 500;
+/// The most coins `coin-flow` traces the lineage of.
+pub const MAX_COIN_FLOW_COINS: usize = 1_000;
+/// The most transactions the gateway returns for a range query.
+const TX_RANGE_PAGE_SIZE: u64 = 4096;
+/// How long `balance --watch` waits for further transactions before printing the balance.
+pub const BALANCE_WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
 
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case")]
@@ -54,9 +91,9 @@ pub enum SuiClientCommands {
     #[clap(name = "switch")]
     Switch {
         /// An Sui address to be used as the active address for subsequent
-        /// commands.
+        /// commands, or its name in the address book.
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrName>,
         /// The gateway URL (e.g., local rpc server, devnet rpc server, etc) to be
         /// used for subsequent commands.
         #[clap(long, value_hint = ValueHint::Url)]
@@ -70,12 +107,33 @@ pub enum SuiClientCommands {
     #[clap(name = "active-address")]
     ActiveAddress,
 
+    /// Name addresses, so that their names can be used wherever an address is expected
+    #[clap(name = "address-book")]
+    AddressBook {
+        #[clap(subcommand)]
+        cmd: AddressBookCommand,
+    },
+
+    /// Manage named profiles, e.g. for a local network and devnet, each with its own keys,
+    /// gateway and active address
+    #[clap(name = "profile")]
+    Profile {
+        #[clap(subcommand)]
+        cmd: ProfileCommand,
+    },
+
     /// Get object info
     #[clap(name = "object")]
     Object {
         /// Object ID of the object to fetch
         #[clap(long)]
         id: ObjectID,
+
+        /// Keep watching the object, printing it again each time a transaction changes it.
+        /// Requires a websocket connection to the fullnode or gateway, runs until interrupted or
+        /// until the object is deleted or wrapped
+        #[clap(long)]
+        follow: bool,
     },
 
     /// Publish Move modules
@@ -103,6 +161,11 @@ pub enum SuiClientCommands {
         /// Gas budget for running module initializers
         #[clap(long)]
         gas_budget: u64,
+
+        /// Gas price for this publish
+        /// If not provided, the reference gas price of the network will be used
+        #[clap(long)]
+        gas_price: Option<u64>,
     },
 
     /// Call Move function
@@ -135,16 +198,21 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
         /// Gas budget for this call
+        /// If not provided, it is estimated by a dry run plus the client's gas budget margin
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        /// Gas price for this call
+        /// If not provided, the reference gas price of the network will be used
+        #[clap(long)]
+        gas_price: Option<u64>,
     },
 
     /// Transfer object
     #[clap(name = "transfer")]
     Transfer {
-        /// Recipient address
+        /// Recipient address, or a name registered with the gateway's name service
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrName,
 
         /// Object to transfer, in 20 bytes Hex string
         #[clap(long)]
@@ -155,36 +223,117 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
+        /// Gas budget for this transfer
+        /// If not provided, it is estimated by a dry run plus the client's gas budget margin
+        #[clap(long)]
+        gas_budget: Option<u64>,
+
+        /// Gas price for this transfer
+        /// If not provided, the reference gas price of the network will be used
+        #[clap(long)]
+        gas_price: Option<u64>,
+
+        /// Skip the confirmation prompt, required when not running in a terminal
+        #[clap(long)]
+        yes: bool,
+
+        /// Debug only: submit the transaction to this authority alone, identified by its hex
+        /// encoded public key, and print its raw response. Quorum is bypassed, so the transfer
+        /// is not finalized.
+        #[clap(long)]
+        authority: Option<AuthorityName>,
+    },
+    /// Transfer several objects to the same recipient in a single transaction, so that either
+    /// all of them or none are transferred. All objects must be owned by the same address.
+    #[clap(name = "transfer-objects")]
+    TransferObjects {
+        /// Recipient address, or a name registered with the gateway's name service
+        #[clap(long)]
+        to: AddressOrName,
+
+        /// Objects to transfer, in 20 bytes Hex string
+        #[clap(
+            long,
+            required = true,
+            multiple_occurrences = false,
+            multiple_values = true,
+            use_value_delimiter = true
+        )]
+        object_ids: Vec<ObjectID>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
         /// Gas budget for this transfer
         #[clap(long)]
         gas_budget: u64,
+
+        /// Gas price for this transfer
+        /// If not provided, the reference gas price of the network will be used
+        #[clap(long)]
+        gas_price: Option<u64>,
+
+        /// Skip the confirmation prompt, required when not running in a terminal
+        #[clap(long)]
+        yes: bool,
     },
+
     /// Transfer SUI, and pay gas with the same SUI coin object.
     /// If amount is specified, only the amount is transferred; otherwise the entire object
     /// is transferred.
     #[clap(name = "transfer-sui")]
+    #[clap(group(ArgGroup::new("coins").required(true).args(&["sui-coin-object-id", "input-coins"])))]
     TransferSui {
-        /// Recipient address
+        /// Recipient address, or a name registered with the gateway's name service
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrName,
 
         /// Sui coin object to transfer, ID in 20 bytes Hex string. This is also the gas object.
         #[clap(long)]
-        sui_coin_object_id: ObjectID,
+        sui_coin_object_id: Option<ObjectID>,
+
+        /// Fund the transfer from exactly these SUI coins instead of a single coin. The coins are
+        /// sent whole in order, the last one needed being split to send the remainder, in a
+        /// single transaction whose gas is paid by another gas object of the owner.
+        #[clap(
+            long,
+            multiple_occurrences = false,
+            multiple_values = true,
+            use_value_delimiter = true
+        )]
+        input_coins: Option<Vec<ObjectID>>,
 
         /// Gas budget for this transfer
         #[clap(long)]
         gas_budget: u64,
 
+        /// Gas price for this transfer
+        /// If not provided, the reference gas price of the network will be used
+        #[clap(long)]
+        gas_price: Option<u64>,
+
         /// The amount to transfer, if not specified, the entire coin object will be transferred.
         #[clap(long)]
         amount: Option<u64>,
+
+        /// Skip the confirmation prompt, required when not running in a terminal
+        #[clap(long)]
+        yes: bool,
     },
     /// Synchronize client state with authorities.
     #[clap(name = "sync")]
     SyncClientState {
+        /// Address to sync, or its name in the address book
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrName>,
+        /// Give up after this many seconds, reporting where to resume from
+        #[clap(long)]
+        timeout: Option<u64>,
+        /// Resume an interrupted sync after this object
+        #[clap(long)]
+        from: Option<ObjectID>,
     },
 
     /// Obtain the Addresses managed by the client.
@@ -193,22 +342,170 @@ pub enum SuiClientCommands {
 
     /// Generate new address and keypair with keypair scheme flag {ed25519 | secp256k1}.
     #[clap(name = "new-address")]
-    NewAddress { key_scheme: SignatureScheme },
+    NewAddress {
+        #[clap(long = "scheme", default_value = "ed25519")]
+        key_scheme: SignatureScheme,
+    },
+
+    /// Derive the address controlled by a set of public keys, `threshold` of which must sign for
+    /// it. The address only depends on the set of keys and the threshold.
+    #[clap(name = "multisig-address")]
+    MultisigAddress {
+        /// Base64 encoded public keys with their scheme flag, as listed by `sui keytool list`
+        #[clap(long, multiple_occurrences = false, multiple_values = true, parse(try_from_str = parse_public_key))]
+        public_keys: Vec<PublicKey>,
+        /// Number of keys which must sign for the address
+        #[clap(long)]
+        threshold: u16,
+    },
+
+    /// Combine signatures of transaction bytes by keys of a multisig address, and execute the
+    /// transaction once they meet its threshold. The transaction must be sent by the multisig
+    /// address, and each key signs the transaction bytes with `sui keytool sign`.
+    #[clap(name = "multisig-execute")]
+    MultisigExecute {
+        /// Transaction bytes to execute, as base-64 encoded string
+        #[clap(long)]
+        tx_bytes: String,
+        /// Base64 encoded public keys of the multisig address
+        #[clap(long, multiple_occurrences = false, multiple_values = true, parse(try_from_str = parse_public_key))]
+        public_keys: Vec<PublicKey>,
+        /// Number of keys which must sign for the multisig address
+        #[clap(long)]
+        threshold: u16,
+        /// Base64 encoded serialized signatures of the transaction bytes (flag, signature and
+        /// public key), as printed by `sui keytool sign`
+        #[clap(long, multiple_occurrences = false, multiple_values = true, parse(try_from_str = parse_signature))]
+        signatures: Vec<Signature>,
+    },
+
+    /// Execute signed transactions read from stdin, one per line. Each line holds the base-64
+    /// encoded transaction bytes and serialized signature (as printed by `sui keytool sign`),
+    /// separated by whitespace. A result is printed per line, and a failing line does not stop
+    /// the remaining lines from being submitted.
+    #[clap(name = "batch-submit")]
+    BatchSubmit {
+        /// Dry run each transaction instead of submitting it, and report the gas each would use
+        /// and their total. The signature of each line may then be omitted.
+        #[clap(long)]
+        estimate_only: bool,
+    },
+
+    /// Execute a transaction signed elsewhere, e.g. on an offline machine. The transaction bytes
+    /// are printed by `--serialize-transaction` and signed with `sui keytool sign`.
+    #[clap(name = "execute-signed")]
+    ExecuteSigned {
+        /// Transaction bytes to execute, as base-64 encoded string
+        #[clap(long)]
+        tx_bytes: String,
+        /// Base64 encoded serialized signature of the transaction bytes (flag, signature and
+        /// public key), as printed by `sui keytool sign`
+        #[clap(long, parse(try_from_str = parse_signature))]
+        signature: Signature,
+    },
+
+    /// Sign a serialized transaction with the key of the active address and execute it. The file
+    /// holds the base-64 encoded transaction bytes, and the active address must be the sender of
+    /// the transaction.
+    #[clap(name = "sign-and-submit")]
+    SignAndSubmit {
+        /// File holding the base-64 encoded transaction bytes
+        #[clap(long)]
+        file: PathBuf,
+    },
+
+    /// Sign a statement that the active address owns an object at its current version, to prove
+    /// ownership of the object to an off-chain service. Prints the base-64 encoded proof.
+    #[clap(name = "prove-object-ownership")]
+    ProveObjectOwnership {
+        /// ID of the owned object, in 20 bytes Hex string
+        #[clap(long)]
+        object_id: ObjectID,
+
+        /// The challenge given by the service the proof is for, e.g. a random nonce
+        #[clap(long)]
+        challenge: String,
+    },
+
+    /// Verify a proof of object ownership made by `prove-object-ownership`.
+    #[clap(name = "verify-object-ownership")]
+    VerifyObjectOwnership {
+        /// The base-64 encoded proof
+        #[clap(long)]
+        proof: String,
+
+        /// The challenge the proof must answer, as given to the prover
+        #[clap(long)]
+        challenge: String,
+
+        /// Also check with the network that the prover still owns the object
+        #[clap(long)]
+        check_current: bool,
+    },
 
     /// Obtain all objects owned by the address.
     #[clap(name = "objects")]
     Objects {
-        /// Address owning the objects
+        /// Address owning the objects, or its name in the address book
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrName>,
+        /// Show at most this many objects owned by the address, in object id order, and the cursor
+        /// to show the next ones from
+        #[clap(long)]
+        limit: Option<usize>,
+        /// Show the objects owned by the address after this one, in object id order, as printed
+        /// by a previous page
+        #[clap(long)]
+        cursor: Option<ObjectID>,
+        /// Only show the objects owned by the address of this type, e.g.
+        /// `0x2::coin::Coin<0x2::sui::SUI>`, or of any instantiation of it if it has no type
+        /// arguments, e.g. `0x2::coin::Coin`
+        #[clap(long = "type", parse(try_from_str = parse_sui_struct_tag))]
+        type_: Option<StructTag>,
     },
 
     /// Obtain all gas objects owned by the address.
     #[clap(name = "gas")]
     Gas {
-        /// Address owning the objects
+        /// Address owning the objects, or its name in the address book
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrName>,
+
+        /// Skip gas objects worth less than this, e.g. to leave out dust
+        #[clap(long)]
+        min_balance: Option<u64>,
+
+        /// Also show the storage rebate each gas object would return when a transaction deletes
+        /// it, e.g. by merging it into another coin
+        #[clap(long)]
+        refund_estimate: bool,
+    },
+
+    /// Obtain the total value of the gas objects owned by the address.
+    #[clap(name = "balance")]
+    Balance {
+        /// Address owning the objects, or its name in the address book
+        #[clap(long)]
+        address: Option<AddressOrName>,
+
+        /// Keep watching the balance, printing it again each time a transaction sent by the
+        /// address or transferring objects to it lands. Requires a websocket connection to the
+        /// fullnode or gateway, runs until interrupted
+        #[clap(long)]
+        watch: bool,
+    },
+
+    /// Obtain the coins of one currency owned by the address, e.g. the fungible tokens created by
+    /// a published package, and their total balance.
+    #[clap(name = "coins")]
+    Coins {
+        /// The currency of the coins, e.g. `0x2::sui::SUI` for the gas coins
+        #[clap(long, parse(try_from_str = parse_sui_struct_tag))]
+        coin_type: StructTag,
+
+        /// Address owning the coins, or its name in the address book
+        #[clap(long)]
+        address: Option<AddressOrName>,
     },
 
     /// Split a coin object into multiple coins.
@@ -220,16 +517,21 @@ pub enum SuiClientCommands {
         /// Specific amounts to split out from the coin
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
         amounts: Option<Vec<u64>>,
-        /// Count of equal-size coins to split into
+        /// Count of equal-size coins to split into, the remainder is left in the split coin
         #[clap(long)]
-        count: u64,
+        count: Option<u64>,
         /// ID of the gas object for gas payment, in 20 bytes Hex string
         /// If not provided, a gas object with at least gas_budget value will be selected
         #[clap(long)]
         gas: Option<ObjectID>,
         /// Gas budget for this call
+        /// If not provided, it is estimated by a dry run plus the client's gas budget margin
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        /// Gas price for this call
+        /// If not provided, the reference gas price of the network will be used
+        #[clap(long)]
+        gas_price: Option<u64>,
     },
 
     /// Merge two coin objects into one coin
@@ -245,8 +547,95 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
         /// Gas budget for this call
+        /// If not provided, it is estimated by a dry run plus the client's gas budget margin
+        #[clap(long)]
+        gas_budget: Option<u64>,
+        /// Gas price for this call
+        /// If not provided, the reference gas price of the network will be used
+        #[clap(long)]
+        gas_price: Option<u64>,
+        /// Skip the confirmation prompt, required when not running in a terminal
+        #[clap(long)]
+        yes: bool,
+    },
+
+    /// Merge all SUI coins of the active address into its largest one, one merge transaction per
+    /// coin. The gas object is left out of the merge
+    #[clap(name = "merge-all-coins")]
+    MergeAllCoins {
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, the smallest coin holding gas_budget for every merge will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+        /// Gas budget for each merge
         #[clap(long)]
         gas_budget: u64,
+        /// Gas price for each merge
+        /// If not provided, the reference gas price of the network will be used
+        #[clap(long)]
+        gas_price: Option<u64>,
+        /// Skip the confirmation prompt, required when not running in a terminal
+        #[clap(long)]
+        yes: bool,
+    },
+
+    /// Show the current epoch, committee and gas parameters of the network.
+    #[clap(name = "system-state")]
+    SystemState,
+
+    /// Re-execute a transaction against the objects it read and compare the resulting effects with
+    /// the effects stored by the network. Requires the client to be connected to a full node.
+    #[clap(name = "replay-from-effects")]
+    ReplayFromEffects {
+        /// Digest of the transaction to replay
+        #[clap(long)]
+        digest: TransactionDigest,
+    },
+
+    /// Print the events emitted by a transaction: the type, sender and fields of each Move event,
+    /// and the objects transferred, created or deleted.
+    #[clap(name = "events")]
+    Events {
+        /// Digest of the transaction
+        #[clap(long)]
+        digest: TransactionDigest,
+    },
+
+    /// Trace the lineage of a coin: the coin it was split from, and recursively the coins split
+    /// off it, the transfers of it and the coin it was merged into. Uses the transaction index of
+    /// the full node when connected to one, otherwise the transactions executed through the
+    /// embedded gateway.
+    #[clap(name = "coin-flow")]
+    CoinFlow {
+        /// ID of the coin to trace, in 20 bytes Hex string
+        #[clap(long)]
+        coin_id: ObjectID,
+    },
+
+    /// List the transactions an address sent, or which left it owning an object they created or
+    /// mutated, in execution order. Uses the transaction index of the full node, so the client
+    /// must be connected to one.
+    #[clap(name = "history")]
+    History {
+        /// Address to list the transactions of, defaults to the active address
+        #[clap(long)]
+        address: Option<SuiAddress>,
+        /// Sequence number of the first transaction to list, to page through the history
+        #[clap(long, default_value = "0")]
+        start: GatewayTxSeqNumber,
+        /// Maximum number of transactions to list
+        #[clap(long, default_value = "100")]
+        count: u64,
+    },
+
+    /// Serve the embedded gateway over JSON-RPC on a local port until interrupted, so that
+    /// repeated client invocations reuse its connections to the authorities instead of
+    /// establishing new ones. Connect other invocations with `switch --gateway <url>`.
+    #[clap(name = "serve")]
+    Serve {
+        /// Local port to serve the gateway on
+        #[clap(long, default_value = "5002")]
+        port: u16,
     },
 
     /// Create an example NFT
@@ -272,30 +661,106 @@ pub enum SuiClientCommands {
         /// Gas budget for this transfer
         #[clap(long)]
         gas_budget: Option<u64>,
+
+        /// Gas price for this transfer
+        /// If not provided, the reference gas price of the network will be used
+        #[clap(long)]
+        gas_price: Option<u64>,
     },
 }
 
 impl SuiClientCommands {
+    /// The gas budget of commands that submit a transaction.
+    fn gas_budget(&self) -> Option<u64> {
+        match self {
+            SuiClientCommands::Publish { gas_budget, .. }
+            | SuiClientCommands::TransferObjects { gas_budget, .. }
+            | SuiClientCommands::TransferSui { gas_budget, .. }
+            | SuiClientCommands::MergeAllCoins { gas_budget, .. } => Some(*gas_budget),
+            SuiClientCommands::Call { gas_budget, .. }
+            | SuiClientCommands::Transfer { gas_budget, .. }
+            | SuiClientCommands::SplitCoin { gas_budget, .. }
+            | SuiClientCommands::MergeCoin { gas_budget, .. }
+            | SuiClientCommands::CreateExampleNFT { gas_budget, .. } => *gas_budget,
+            _ => None,
+        }
+    }
+
+    /// Whether the command builds a single transaction, which `--dry-run` and
+    /// `--serialize-transaction` can apply to, see `WalletContext::preview_transaction`.
+    fn builds_single_transaction(&self) -> bool {
+        match self {
+            SuiClientCommands::Publish { .. }
+            | SuiClientCommands::Call { .. }
+            | SuiClientCommands::Transfer { .. }
+            | SuiClientCommands::TransferObjects { .. }
+            | SuiClientCommands::TransferSui { .. }
+            | SuiClientCommands::SplitCoin { .. }
+            | SuiClientCommands::MergeCoin { .. }
+            | SuiClientCommands::CreateExampleNFT { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the command submits a single transaction, built by it or given to it, which
+    /// `--dry-run` can apply to.
+    fn submits_single_transaction(&self) -> bool {
+        self.builds_single_transaction()
+            || matches!(
+                self,
+                SuiClientCommands::MultisigExecute { .. }
+                    | SuiClientCommands::ExecuteSigned { .. }
+                    | SuiClientCommands::SignAndSubmit { .. }
+            )
+    }
+
     pub async fn execute(
         self,
         context: &mut WalletContext,
     ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        // Checked again on submission, but failing here avoids building the transaction.
+        if let Some(gas_budget) = self.gas_budget() {
+            context.check_gas_budget(gas_budget)?;
+        }
+        if context.dry_run && !self.submits_single_transaction() {
+            return Err(anyhow!(
+                "--dry-run is only supported by commands submitting a single transaction, \
+                see `batch-submit --estimate-only` to dry run several"
+            ));
+        }
+        if context.serialize_transaction && !self.builds_single_transaction() {
+            return Err(anyhow!(
+                "--serialize-transaction is only supported by commands building a single transaction"
+            ));
+        }
+        if context.expiration_epoch.is_some() && !self.builds_single_transaction() {
+            return Err(anyhow!(
+                "--expiration-epoch is only supported by commands building a single transaction"
+            ));
+        }
+
         let ret = Ok(match self {
             SuiClientCommands::Publish {
                 package_path,
                 gas,
                 build_config,
                 gas_budget,
+                gas_price,
             } => {
                 let sender = context.try_get_object_owner(&gas).await?;
                 let sender = sender.unwrap_or(context.active_address()?);
 
                 let compiled_modules = build_move_package_to_bytes(&package_path, build_config)?;
-                let data = context
+                let mut data = context
                     .gateway
                     .transaction_builder()
                     .publish(sender, compiled_modules, gas, gas_budget)
                     .await?;
+                data.gas_price = context.resolve_gas_price(gas_price).await?;
+                let data = context.with_expiration_epoch(data);
+                if let Some(result) = context.preview_transaction(&data).await? {
+                    return Ok(result);
+                }
                 let signature = context.keystore.sign(&sender, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature))
@@ -304,11 +769,24 @@ impl SuiClientCommands {
                 SuiClientCommandResult::Publish(response)
             }
 
-            SuiClientCommands::Object { id } => {
+            SuiClientCommands::Object { id, follow: false } => {
                 // Fetch the object ref
                 let object_read = context.gateway.read_api().get_parsed_object(id).await?;
                 SuiClientCommandResult::Object(object_read)
             }
+            SuiClientCommands::Object { id, follow: true } => {
+                // Subscribe before fetching the object, so no change after the fetch is missed
+                let updates = context.follow_object(id).await?;
+                pin_mut!(updates);
+                let mut object_read = context.gateway.read_api().get_parsed_object(id).await?;
+                while let GetObjectDataResponse::Exists(_) = object_read {
+                    println!("{}", SuiClientCommandResult::Object(object_read));
+                    object_read = updates.next().await.ok_or_else(|| {
+                        anyhow!("Subscription to changes of object {id} closed")
+                    })??;
+                }
+                SuiClientCommandResult::Object(object_read)
+            }
             SuiClientCommands::Call {
                 package,
                 module,
@@ -316,12 +794,28 @@ impl SuiClientCommands {
                 type_args,
                 gas,
                 gas_budget,
+                gas_price,
                 args,
             } => {
-                let (cert, effects) = call_move(
-                    package, &module, &function, type_args, gas, gas_budget, args, context,
+                let max_gas_budget = context.config.max_gas_budget;
+                let (sender, data) = build_move_call(
+                    package,
+                    &module,
+                    &function,
+                    type_args,
+                    gas,
+                    gas_budget.unwrap_or(max_gas_budget),
+                    gas_price,
+                    args,
+                    context,
                 )
                 .await?;
+                let data = context.resolve_gas_budget(data, gas_budget).await?;
+                let data = context.with_expiration_epoch(data);
+                if let Some(result) = context.preview_transaction(&data).await? {
+                    return Ok(result);
+                }
+                let (cert, effects) = execute_move_call(sender, data, context).await?;
                 SuiClientCommandResult::Call(cert, effects)
             }
 
@@ -330,16 +824,49 @@ impl SuiClientCommands {
                 object_id,
                 gas,
                 gas_budget,
+                gas_price,
+                yes,
+                authority,
             } => {
+                let to = context.resolve_address(to).await?;
                 let from = context.get_object_owner(&object_id).await?;
                 let time_start = Instant::now();
 
-                let data = context
+                let build_budget = gas_budget.unwrap_or(context.config.max_gas_budget);
+                let gas = gas_for_transfer(context, from, object_id, gas, build_budget).await?;
+                let mut data = context
                     .gateway
                     .transaction_builder()
-                    .transfer_object(from, object_id, gas, gas_budget, to)
+                    .transfer_object(from, object_id, Some(gas), build_budget, to)
                     .await?;
+                data.gas_price = context.resolve_gas_price(gas_price).await?;
+                let data = context.resolve_gas_budget(data, gas_budget).await?;
+                let data = context.with_expiration_epoch(data);
+                if let Some(result) = context.preview_transaction(&data).await? {
+                    return Ok(result);
+                }
+                confirm_transaction(
+                    yes,
+                    atty::is(atty::Stream::Stdin),
+                    &format!(
+                        "Transfer object {object_id} from {from} to {to}, gas object {} with budget {}",
+                        data.gas().0,
+                        data.gas_budget
+                    ),
+                )?;
                 let signature = context.keystore.sign(&from, &data.to_bytes())?;
+                if let Some(authority) = authority {
+                    context.check_gas_budget(data.gas_budget)?;
+                    let response = context
+                        .gateway
+                        .quorum_driver()
+                        .execute_transaction_on_authority(
+                            Transaction::new(data, signature),
+                            authority,
+                        )
+                        .await?;
+                    return Ok(SuiClientCommandResult::AuthorityResponse(response));
+                }
                 let response = context
                     .execute_transaction(Transaction::new(data, signature))
                     .await?;
@@ -353,19 +880,121 @@ impl SuiClientCommands {
                 SuiClientCommandResult::Transfer(time_total, cert, effects)
             }
 
-            SuiClientCommands::TransferSui {
+            SuiClientCommands::TransferObjects {
                 to,
-                sui_coin_object_id: object_id,
+                object_ids,
+                gas,
                 gas_budget,
-                amount,
+                gas_price,
+                yes,
             } => {
-                let from = context.get_object_owner(&object_id).await?;
+                let to = context.resolve_address(to).await?;
+                let first = object_ids
+                    .first()
+                    .ok_or_else(|| anyhow!("At least one object must be provided"))?;
+                let from = context.get_object_owner(first).await?;
 
-                let data = context
+                let mut data = context
                     .gateway
                     .transaction_builder()
-                    .transfer_sui(from, object_id, gas_budget, to, amount)
+                    .batch_transfer_objects(from, object_ids.clone(), to, gas, gas_budget)
+                    .await?;
+                data.gas_price = context.resolve_gas_price(gas_price).await?;
+                let data = context.with_expiration_epoch(data);
+                if let Some(result) = context.preview_transaction(&data).await? {
+                    return Ok(result);
+                }
+                confirm_transaction(
+                    yes,
+                    atty::is(atty::Stream::Stdin),
+                    &format!(
+                        "Transfer objects {} from {from} to {to}, gas object {} with budget {gas_budget}",
+                        object_ids
+                            .iter()
+                            .map(|id| id.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        data.gas().0
+                    ),
+                )?;
+                let signature = context.keystore.sign(&from, &data.to_bytes())?;
+                let response = context
+                    .execute_transaction(Transaction::new(data, signature))
                     .await?;
+                let cert = response.certificate;
+                let effects = response.effects;
+
+                if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
+                    return Err(anyhow!("Error transferring objects: {:#?}", effects.status));
+                }
+                SuiClientCommandResult::TransferObjects(cert, effects)
+            }
+
+            SuiClientCommands::TransferSui {
+                to,
+                sui_coin_object_id,
+                input_coins,
+                gas_budget,
+                gas_price,
+                amount,
+                yes,
+            } => {
+                let to = context.resolve_address(to).await?;
+                let object_id = match (&input_coins, sui_coin_object_id) {
+                    (Some(input_coins), _) => *input_coins
+                        .first()
+                        .ok_or_else(|| anyhow!("At least one input coin must be provided"))?,
+                    (None, Some(object_id)) => object_id,
+                    (None, None) => {
+                        return Err(anyhow!(
+                            "Either a SUI coin object or input coins must be provided"
+                        ))
+                    }
+                };
+                let from = context.get_object_owner(&object_id).await?;
+                let mut data = match &input_coins {
+                    Some(input_coins) if input_coins.len() > 1 => {
+                        transfer_sui_from_coins(context, from, to, input_coins, amount, gas_budget)
+                            .await?
+                    }
+                    _ => {
+                        context
+                            .gateway
+                            .transaction_builder()
+                            .transfer_sui(from, object_id, gas_budget, to, amount)
+                            .await?
+                    }
+                };
+                data.gas_price = context.resolve_gas_price(gas_price).await?;
+                let data = context.with_expiration_epoch(data);
+                if let Some(result) = context.preview_transaction(&data).await? {
+                    return Ok(result);
+                }
+
+                let funding = match &input_coins {
+                    Some(input_coins) => format!(
+                        "coins {}",
+                        input_coins
+                            .iter()
+                            .map(|id| id.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    None => format!("coin {object_id}"),
+                };
+                let amount_str = match amount {
+                    Some(amount) => amount.to_string(),
+                    None => "the entire coin".to_string(),
+                };
+                confirm_transaction(
+                    yes,
+                    atty::is(atty::Stream::Stdin),
+                    &format!(
+                        "Transfer {amount_str} of SUI {funding} from {from} to {to}, gas budget {gas_budget}"
+                    ),
+                )?;
+
+                let time_start = Instant::now();
                 let signature = context.keystore.sign(&from, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature))
@@ -373,99 +1002,453 @@ impl SuiClientCommands {
                 let cert = response.certificate;
                 let effects = response.effects;
 
+                let time_total = time_start.elapsed().as_micros();
                 if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
                     return Err(anyhow!("Error transferring SUI: {:#?}", effects.status));
                 }
-                SuiClientCommandResult::TransferSui(cert, effects)
+                SuiClientCommandResult::TransferSui(time_total, cert, effects)
             }
 
             SuiClientCommands::Addresses => {
-                SuiClientCommandResult::Addresses(context.keystore.addresses())
+                let names = &context.config.address_book;
+                SuiClientCommandResult::Addresses(
+                    context
+                        .keystore
+                        .addresses()
+                        .into_iter()
+                        .map(|address| ManagedAddress {
+                            address,
+                            names: names
+                                .iter()
+                                .filter(|(_, named)| **named == address)
+                                .map(|(name, _)| name.clone())
+                                .collect(),
+                        })
+                        .collect(),
+                )
             }
 
-            SuiClientCommands::Objects { address } => {
-                let address = address.unwrap_or(context.active_address()?);
-                let mut address_object = context
-                    .gateway
-                    .read_api()
-                    .get_objects_owned_by_address(address)
-                    .await?;
-                let object_objects = context
-                    .gateway
-                    .read_api()
-                    .get_objects_owned_by_object(address.into())
-                    .await?;
-                address_object.extend(object_objects);
+            SuiClientCommands::Objects {
+                address,
+                limit,
+                cursor,
+                type_,
+            } => {
+                let address = context.resolve_address_or_active(address).await?;
+                if limit.is_none() && cursor.is_none() && type_.is_none() {
+                    let mut address_object = context
+                        .gateway
+                        .read_api()
+                        .get_objects_owned_by_address(address)
+                        .await?;
+                    let object_objects = context
+                        .gateway
+                        .read_api()
+                        .get_objects_owned_by_object(address.into())
+                        .await?;
+                    address_object.extend(object_objects);
+                    return Ok(SuiClientCommandResult::Objects(address_object, None));
+                }
 
-                SuiClientCommandResult::Objects(address_object)
+                // Pages are listed and filtered by type by the validators, which only index
+                // objects owned by an address.
+                let read_api = context.gateway.read_api();
+                let (objects, next_cursor) = match limit {
+                    Some(0) => return Err(anyhow!("--limit must be greater than 0")),
+                    Some(limit) => {
+                        let page = read_api
+                            .get_objects_owned_by_address_page(
+                                address,
+                                cursor,
+                                limit,
+                                type_.clone(),
+                            )
+                            .await?;
+                        (page.data, page.next_cursor)
+                    }
+                    None => {
+                        let mut objects = Vec::new();
+                        let mut cursor = cursor;
+                        loop {
+                            let page = read_api
+                                .get_objects_owned_by_address_page(
+                                    address,
+                                    cursor,
+                                    OBJECTS_PAGE_SIZE,
+                                    type_.clone(),
+                                )
+                                .await?;
+                            objects.extend(page.data);
+                            match page.next_cursor {
+                                Some(next_cursor) => cursor = Some(next_cursor),
+                                None => break,
+                            }
+                        }
+                        (objects, None)
+                    }
+                };
+                SuiClientCommandResult::Objects(objects, next_cursor)
             }
 
-            SuiClientCommands::SyncClientState { address } => {
-                let address = address.unwrap_or(context.active_address()?);
-                context
-                    .gateway
-                    .wallet_sync_api()
-                    .sync_account_state(address)
-                    .await?;
-                SuiClientCommandResult::SyncClientState
+            SuiClientCommands::SyncClientState {
+                address,
+                timeout,
+                from,
+            } => {
+                let address = context.resolve_address_or_active(address).await?;
+                let deadline =
+                    timeout.map(|secs| tokio::time::Instant::now() + Duration::from_secs(secs));
+                let mut cursor = from;
+                let mut processed_transactions = 0;
+                loop {
+                    let step = context
+                        .gateway
+                        .wallet_sync_api()
+                        .sync_account_state_incremental(address, cursor, SYNC_STEP_SIZE);
+                    let progress = match deadline {
+                        Some(deadline) => match tokio::time::timeout_at(deadline, step).await {
+                            Ok(progress) => progress?,
+                            Err(_) => {
+                                return Err(match cursor {
+                                    Some(cursor) => anyhow!(
+                                        "Sync timed out after syncing the objects up to {cursor}, \
+                                         resume it with `--from {cursor}`"
+                                    ),
+                                    None => anyhow!("Sync timed out before syncing any object"),
+                                })
+                            }
+                        },
+                        None => step.await?,
+                    };
+                    processed_transactions += progress.processed_transactions;
+                    if context.show_progress {
+                        eprintln!(
+                            "Synced {}/{} objects, processed {processed_transactions} transactions",
+                            progress.synced_objects, progress.total_objects
+                        );
+                    }
+                    cursor = progress.next_cursor;
+                    if cursor.is_none() {
+                        break SuiClientCommandResult::SyncClientState(SyncProgress {
+                            processed_transactions,
+                            ..progress
+                        });
+                    }
+                }
             }
             SuiClientCommands::NewAddress { key_scheme } => {
                 let (address, phrase, scheme) = context.keystore.generate_new_key(key_scheme)?;
                 SuiClientCommandResult::NewAddress((address, phrase, scheme))
             }
-            SuiClientCommands::Gas { address } => {
-                let address = address.unwrap_or(context.active_address()?);
-                let coins = context
-                    .gas_objects(address)
-                    .await?
-                    .iter()
-                    // Ok to unwrap() since `get_gas_objects` guarantees gas
-                    .map(|(_val, object, _object_ref)| GasCoin::try_from(object).unwrap())
-                    .collect();
-                SuiClientCommandResult::Gas(coins)
+            SuiClientCommands::MultisigAddress {
+                public_keys,
+                threshold,
+            } => {
+                let multisig_pk = MultiSigPublicKey::new(public_keys, threshold)?;
+                SuiClientCommandResult::MultisigAddress(SuiAddress::from(&multisig_pk))
             }
-            SuiClientCommands::SplitCoin {
-                coin_id,
-                amounts,
-                count,
-                gas,
-                gas_budget,
+            SuiClientCommands::MultisigExecute {
+                tx_bytes,
+                public_keys,
+                threshold,
+                signatures,
             } => {
-                let signer = context.get_object_owner(&coin_id).await?;
-                let data = if let Some(amounts) = amounts {
-                    context
-                        .gateway
-                        .transaction_builder()
-                        .split_coin(signer, coin_id, amounts, gas, gas_budget)
-                        .await?
-                } else {
-                    if count == 0 {
-                        return Err(anyhow!("Coin split count must be greater than 0"));
-                    }
-                    context
-                        .gateway
-                        .transaction_builder()
-                        .split_coin_equal(signer, coin_id, count, gas, gas_budget)
-                        .await?
-                };
-                let signature = context.keystore.sign(&signer, &data.to_bytes())?;
+                let data = TransactionData::from_signable_bytes(&Base64::decode(&tx_bytes)?)?;
+                let multisig_pk = MultiSigPublicKey::new(public_keys, threshold)?;
+                let signature: Signature =
+                    MultiSigSuiSignature::new(signatures, &multisig_pk)?.into();
+                // Check the signatures before submitting, so that a missing or wrong signature
+                // is reported as such rather than as a failed execution.
+                signature.verify(&data, data.signer())?;
+                if let Some(result) = context.preview_transaction(&data).await? {
+                    return Ok(result);
+                }
+
                 let response = context
                     .execute_transaction(Transaction::new(data, signature))
                     .await?;
-                SuiClientCommandResult::SplitCoin(response)
+                if matches!(response.effects.status, SuiExecutionStatus::Failure { .. }) {
+                    return Err(anyhow!(
+                        "Error executing multisig transaction: {:#?}",
+                        response.effects.status
+                    ));
+                }
+                SuiClientCommandResult::MultisigExecute(response)
             }
-            SuiClientCommands::MergeCoin {
+            SuiClientCommands::ExecuteSigned {
+                tx_bytes,
+                signature,
+            } => {
+                let data = TransactionData::from_signable_bytes(&Base64::decode(&tx_bytes)?)?;
+                // Check the signature before submitting, so that a wrong signature is reported as
+                // such rather than as a failed execution.
+                signature.verify(&data, data.signer())?;
+                if let Some(result) = context.preview_transaction(&data).await? {
+                    return Ok(result);
+                }
+                let response = context
+                    .execute_transaction(Transaction::new(data, signature))
+                    .await?;
+                SuiClientCommandResult::ExecuteSigned(response)
+            }
+            SuiClientCommands::BatchSubmit { estimate_only } => {
+                let lines = io::stdin().lock().lines().collect::<Result<Vec<_>, _>>()?;
+                if estimate_only {
+                    SuiClientCommandResult::BatchEstimate(context.batch_estimate(lines).await)
+                } else {
+                    SuiClientCommandResult::BatchSubmit(context.batch_submit(lines).await)
+                }
+            }
+            SuiClientCommands::ProveObjectOwnership {
+                object_id,
+                challenge,
+            } => {
+                let owner = context.active_address()?;
+                let object = context
+                    .gateway
+                    .read_api()
+                    .get_object(object_id)
+                    .await?
+                    .into_object()?;
+                if object.owner != Owner::AddressOwner(owner) {
+                    return Err(anyhow!(
+                        "Object {object_id} is owned by {}, not by the active address {owner}",
+                        object.owner
+                    ));
+                }
+                let statement = ObjectOwnershipStatement {
+                    owner,
+                    object_ref: object.reference.to_object_ref(),
+                    challenge,
+                };
+                let signature = context.keystore.sign(&owner, &statement.to_bytes())?;
+                let proof = ObjectOwnershipProof {
+                    statement,
+                    signature,
+                };
+                SuiClientCommandResult::ProveObjectOwnership(Base64::encode(bcs::to_bytes(&proof)?))
+            }
+            SuiClientCommands::VerifyObjectOwnership {
+                proof,
+                challenge,
+                check_current,
+            } => {
+                let proof: ObjectOwnershipProof = bcs::from_bytes(&Base64::decode(&proof)?)
+                    .map_err(|e| anyhow!("Invalid ownership proof: {e}"))?;
+                proof.verify(&challenge)?;
+                let statement = proof.statement;
+                let still_owned = if check_current {
+                    let object = context
+                        .gateway
+                        .read_api()
+                        .get_object(statement.object_ref.0)
+                        .await?
+                        .into_object()?;
+                    Some(object.owner == Owner::AddressOwner(statement.owner))
+                } else {
+                    None
+                };
+                SuiClientCommandResult::VerifyObjectOwnership(statement, still_owned)
+            }
+            SuiClientCommands::SignAndSubmit { file } => {
+                let tx_bytes = fs::read_to_string(&file)
+                    .map_err(|e| anyhow!("Cannot read transaction file {file:?}: {e}"))?;
+                let data = TransactionData::from_signable_bytes(&Base64::decode(tx_bytes.trim())?)?;
+                let active_address = context.active_address()?;
+                if data.signer() != active_address {
+                    return Err(anyhow!(
+                        "Transaction is sent by {}, but the active address is {active_address}",
+                        data.signer()
+                    ));
+                }
+                if let Some(result) = context.preview_transaction(&data).await? {
+                    return Ok(result);
+                }
+                let signature = context.keystore.sign(&active_address, &data.to_bytes())?;
+                let response = context
+                    .execute_transaction(Transaction::new(data, signature))
+                    .await?;
+                SuiClientCommandResult::SignAndSubmit(response)
+            }
+            SuiClientCommands::Gas {
+                address,
+                min_balance,
+                refund_estimate,
+            } => {
+                let address = context.resolve_address_or_active(address).await?;
+                let mut gas_objects = context.gas_objects(address).await?;
+                if let Some(min_balance) = min_balance {
+                    gas_objects.retain(|(value, _, _)| *value >= min_balance);
+                }
+                // Ok to unwrap() since `get_gas_objects` guarantees gas
+                let coins = gas_objects
+                    .iter()
+                    .map(|(_val, object, _object_ref)| GasCoin::try_from(object).unwrap());
+                if refund_estimate {
+                    let rebates = gas_objects
+                        .iter()
+                        .map(|(_val, object, _object_ref)| object.storage_rebate);
+                    SuiClientCommandResult::GasRefundEstimate(coins.zip(rebates).collect())
+                } else {
+                    SuiClientCommandResult::Gas(coins.collect())
+                }
+            }
+            SuiClientCommands::Balance {
+                address,
+                watch: false,
+            } => {
+                let address = context.resolve_address_or_active(address).await?;
+                SuiClientCommandResult::Balance(context.balance(address).await?)
+            }
+            SuiClientCommands::Balance {
+                address,
+                watch: true,
+            } => {
+                let address = context.resolve_address_or_active(address).await?;
+                // Subscribe before fetching the balance, so no change after the fetch is missed
+                let updates = context.watch_balance(address).await?;
+                pin_mut!(updates);
+                let mut balance = context.balance(address).await?;
+                loop {
+                    println!("{}", SuiClientCommandResult::Balance(balance.clone()));
+                    balance = tokio::select! {
+                        update = updates.next() => update.ok_or_else(|| {
+                            anyhow!("Subscription to transactions of {address} closed")
+                        })??,
+                        _ = tokio::signal::ctrl_c() => break,
+                    };
+                }
+                SuiClientCommandResult::Balance(balance)
+            }
+            SuiClientCommands::Coins { coin_type, address } => {
+                let address = context.resolve_address_or_active(address).await?;
+                let coin_type = Coin::type_(coin_type);
+                let coins = context
+                    .coin_objects(address, &coin_type)
+                    .await?
+                    .iter()
+                    // Ok to unwrap() since `coin_objects` guarantees coins
+                    .map(|(_, object, _)| coin_from_object(object).unwrap())
+                    .collect();
+                SuiClientCommandResult::Coins(CoinBalances {
+                    coin_type: coin_type.to_string(),
+                    coins,
+                })
+            }
+            SuiClientCommands::SplitCoin {
+                coin_id,
+                amounts,
+                count,
+                gas,
+                gas_budget,
+                gas_price,
+            } => {
+                let signer = context.get_object_owner(&coin_id).await?;
+                let build_budget = gas_budget.unwrap_or(context.config.max_gas_budget);
+                let balance = context.get_coin_balance(&coin_id).await?;
+                // The gas is only paid out of the split coin if it is also the gas object.
+                let gas_cost = if gas == Some(coin_id) {
+                    build_budget
+                } else {
+                    0
+                };
+                let mut data = match (amounts, count) {
+                    (Some(_), Some(_)) => {
+                        return Err(anyhow!("--amounts and --count cannot be used together"));
+                    }
+                    (None, None) => {
+                        return Err(anyhow!("Either --amounts or --count must be provided"));
+                    }
+                    (Some(amounts), None) => {
+                        let split_value = amounts
+                            .iter()
+                            .try_fold(0u64, |total, amount| total.checked_add(*amount))
+                            .ok_or_else(|| anyhow!("The split amounts overflow u64"))?;
+                        if split_value.saturating_add(gas_cost) > balance {
+                            return Err(anyhow!(
+                                "Coin {coin_id} has a balance of {balance}, which cannot cover \
+                                the split of {split_value} and gas of {gas_cost}"
+                            ));
+                        }
+                        context
+                            .gateway
+                            .transaction_builder()
+                            .split_coin(signer, coin_id, amounts, gas, build_budget)
+                            .await?
+                    }
+                    (None, Some(count)) => {
+                        if count == 0 {
+                            return Err(anyhow!("Coin split count must be greater than 0"));
+                        }
+                        if gas_cost >= balance {
+                            return Err(anyhow!(
+                                "Coin {coin_id} has a balance of {balance}, which cannot cover \
+                                gas of {gas_cost}"
+                            ));
+                        }
+                        // `coin::split_n` needs every coin to hold at least 1.
+                        let max_count = balance - gas_cost;
+                        if count > max_count {
+                            return Err(anyhow!(
+                                "Cannot split coin {coin_id} into {count} coins: after gas of \
+                                {gas_cost}, its balance of {balance} supports at most {max_count} \
+                                coins of the minimum coin value 1"
+                            ));
+                        }
+                        context
+                            .gateway
+                            .transaction_builder()
+                            .split_coin_equal(signer, coin_id, count, gas, build_budget)
+                            .await?
+                    }
+                };
+                data.gas_price = context.resolve_gas_price(gas_price).await?;
+                let data = context.resolve_gas_budget(data, gas_budget).await?;
+                let data = context.with_expiration_epoch(data);
+                if let Some(result) = context.preview_transaction(&data).await? {
+                    return Ok(result);
+                }
+                let signature = context.keystore.sign(&signer, &data.to_bytes())?;
+                let response = context
+                    .execute_transaction(Transaction::new(data, signature))
+                    .await?;
+                SuiClientCommandResult::SplitCoin(response)
+            }
+            SuiClientCommands::MergeCoin {
                 primary_coin,
                 coin_to_merge,
                 gas,
                 gas_budget,
+                gas_price,
+                yes,
             } => {
                 let signer = context.get_object_owner(&primary_coin).await?;
-                let data = context
+                let mut data = context
                     .gateway
                     .transaction_builder()
-                    .merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
+                    .merge_coins(
+                        signer,
+                        primary_coin,
+                        coin_to_merge,
+                        gas,
+                        gas_budget.unwrap_or(context.config.max_gas_budget),
+                    )
                     .await?;
+                data.gas_price = context.resolve_gas_price(gas_price).await?;
+                let data = context.resolve_gas_budget(data, gas_budget).await?;
+                let data = context.with_expiration_epoch(data);
+                if let Some(result) = context.preview_transaction(&data).await? {
+                    return Ok(result);
+                }
+                confirm_transaction(
+                    yes,
+                    atty::is(atty::Stream::Stdin),
+                    &format!(
+                        "Merge coin {coin_to_merge} into {primary_coin} owned by {signer}, gas object {} with budget {}",
+                        data.gas().0,
+                        data.gas_budget
+                    ),
+                )?;
                 let signature = context.keystore.sign(&signer, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature))
@@ -473,11 +1456,27 @@ impl SuiClientCommands {
 
                 SuiClientCommandResult::MergeCoin(response)
             }
+            SuiClientCommands::MergeAllCoins {
+                gas,
+                gas_budget,
+                gas_price,
+                yes,
+            } => {
+                let owner = context.active_address()?;
+                let gas_price = context.resolve_gas_price(gas_price).await?;
+                SuiClientCommandResult::MergeAllCoins(
+                    merge_all_coins(context, owner, gas, gas_budget, gas_price, yes).await?,
+                )
+            }
             SuiClientCommands::Switch {
                 address,
                 gateway,
                 fullnode,
             } => {
+                let address = match address {
+                    Some(address) => Some(context.resolve_address(address).await?),
+                    None => None,
+                };
                 if let Some(addr) = address {
                     if !context.keystore.addresses().contains(&addr) {
                         return Err(anyhow!("Address {} not managed by wallet", addr));
@@ -515,12 +1514,176 @@ impl SuiClientCommands {
             SuiClientCommands::ActiveAddress => {
                 SuiClientCommandResult::ActiveAddress(context.active_address().ok())
             }
+            SuiClientCommands::AddressBook { cmd } => {
+                let book = &mut context.config.address_book;
+                match cmd {
+                    AddressBookCommand::Add { name, address } => {
+                        check_address_book_name(&name)?;
+                        book.insert(name, address);
+                        context.config.save()?;
+                    }
+                    AddressBookCommand::Remove { name } => {
+                        if book.remove(&name).is_none() {
+                            return Err(anyhow!("No address named {name} in the address book"));
+                        }
+                        context.config.save()?;
+                    }
+                    AddressBookCommand::List => {}
+                    AddressBookCommand::Export { path } => {
+                        fs::write(&path, serde_json::to_string_pretty(book)?)?;
+                    }
+                    AddressBookCommand::Import { path } => {
+                        let imported: AddressBook = serde_json::from_slice(&fs::read(&path)?)
+                            .map_err(|e| anyhow!("Cannot read address book {path:?}: {e}"))?;
+                        // Nothing is imported if any name is invalid.
+                        for name in imported.keys() {
+                            check_address_book_name(name)?;
+                        }
+                        book.extend(imported);
+                        context.config.save()?;
+                    }
+                }
+                SuiClientCommandResult::AddressBook(context.config.address_book.clone())
+            }
+            SuiClientCommands::Profile { cmd } => {
+                match cmd {
+                    ProfileCommand::Create {
+                        name,
+                        gateway,
+                        fullnode,
+                        keystore,
+                    } => {
+                        let keystore = match keystore {
+                            Some(path) => path,
+                            None => sui_config_dir()?.join(format!("sui.{name}.keystore")),
+                        };
+                        context.config.add_profile(
+                            name,
+                            ClientProfile {
+                                keystore: KeystoreType::File(keystore),
+                                gateway: ClientType::RPC(gateway, None),
+                                active_address: None,
+                                fullnode: fullnode.map(|url| ClientType::RPC(url, None)),
+                            },
+                        )?;
+                        context.config.save()?;
+                    }
+                    ProfileCommand::Use { name } => {
+                        if name != context.config.active_profile {
+                            // Connect to the profile before switching to it, so that the config
+                            // is left as it was if the profile cannot be used.
+                            let profile = context
+                                .config
+                                .profiles
+                                .get(&name)
+                                .ok_or_else(|| anyhow!("No profile named {name}"))?;
+                            let keystore = profile.keystore.init()?;
+                            let gateway = profile.gateway.init().await?;
+                            let fullnode = match &profile.fullnode {
+                                Some(client) => Some(client.init().await?),
+                                None => None,
+                            };
+                            context.config.use_profile(&name)?;
+                            context.keystore = keystore;
+                            context.gateway = gateway;
+                            context.fullnode = fullnode;
+                            context.config.save()?;
+                        }
+                    }
+                    ProfileCommand::List => {}
+                }
+                let config = &context.config;
+                let mut profiles = vec![ProfileSummary {
+                    name: config.active_profile.clone(),
+                    active: true,
+                    active_address: config.active_address,
+                }];
+                profiles.extend(
+                    config
+                        .profiles
+                        .iter()
+                        .map(|(name, profile)| ProfileSummary {
+                            name: name.clone(),
+                            active: false,
+                            active_address: profile.active_address,
+                        }),
+                );
+                profiles.sort_by(|a, b| a.name.cmp(&b.name));
+                SuiClientCommandResult::Profiles(profiles)
+            }
+            SuiClientCommands::SystemState => {
+                let system_state = context.gateway.read_api().get_system_state().await?;
+                SuiClientCommandResult::SystemState(system_state)
+            }
+            SuiClientCommands::ReplayFromEffects { digest } => {
+                let raw_effects = context.gateway.read_api().get_raw_effects(digest).await?;
+                let effects: TransactionEffects = bcs::from_bytes(&raw_effects.to_vec()?)?;
+                let replayed_effects = context
+                    .gateway
+                    .full_node_api()
+                    .replay_transaction(digest)
+                    .await?;
+                let diff = effects.diff(&replayed_effects);
+                if !diff.is_empty() {
+                    return Err(anyhow!(
+                        "Replayed effects of transaction {digest:?} differ from the stored effects:\n{}",
+                        diff.join("\n")
+                    ));
+                }
+                SuiClientCommandResult::ReplayFromEffects(digest)
+            }
+            SuiClientCommands::Events { digest } => {
+                let response = context.gateway.read_api().get_transaction(digest).await?;
+                SuiClientCommandResult::Events(response.effects.events)
+            }
+            SuiClientCommands::CoinFlow { coin_id } => {
+                SuiClientCommandResult::CoinFlow(context.coin_flow(coin_id).await?)
+            }
+            SuiClientCommands::History {
+                address,
+                start,
+                count,
+            } => {
+                let address = match address {
+                    Some(address) => address,
+                    None => context.active_address()?,
+                };
+                let client = match (&context.fullnode, context.gateway.embedded_gateway()) {
+                    (Some(fullnode), _) => fullnode,
+                    (None, None) => &context.gateway,
+                    (None, Some(_)) => {
+                        return Err(anyhow!(
+                            "Listing the history of an address needs a full node, \
+                            connect to one with `sui client switch --fullnode <url>`"
+                        ))
+                    }
+                };
+                let transactions = client
+                    .full_node_api()
+                    .get_transactions_touching_addr(address, start, count)
+                    .await?;
+                SuiClientCommandResult::History(address, transactions)
+            }
+            SuiClientCommands::Serve { port } => {
+                let gateway = context.gateway.embedded_gateway().ok_or_else(|| {
+                    anyhow!("The active gateway is not embedded, there is no gateway to serve")
+                })?;
+                let address = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port);
+                let handle = start_gateway_server(gateway, address).await?;
+                println!(
+                    "Serving the gateway at http://{address}, \
+                    connect with `sui client switch --gateway http://{address}`"
+                );
+                handle.await;
+                SuiClientCommandResult::Serve(address)
+            }
             SuiClientCommands::CreateExampleNFT {
                 name,
                 description,
                 url,
                 gas,
                 gas_budget,
+                gas_price,
             } => {
                 let args_json = json!([
                     unwrap_or(&name, EXAMPLE_NFT_NAME),
@@ -531,17 +1694,23 @@ impl SuiClientCommands {
                 for a in args_json.as_array().unwrap() {
                     args.push(SuiJsonValue::new(a.clone()).unwrap());
                 }
-                let (_, effects) = call_move(
+                let (sender, data) = build_move_call(
                     ObjectID::from(SUI_FRAMEWORK_ADDRESS),
                     "devnet_nft",
                     "mint",
                     vec![],
                     gas,
                     gas_budget.unwrap_or(3000),
+                    gas_price,
                     args,
                     context,
                 )
                 .await?;
+                let data = context.with_expiration_epoch(data);
+                if let Some(result) = context.preview_transaction(&data).await? {
+                    return Ok(result);
+                }
+                let (cert, effects) = execute_move_call(sender, data, context).await?;
                 let nft_id = effects
                     .created
                     .first()
@@ -549,7 +1718,7 @@ impl SuiClientCommands {
                     .reference
                     .object_id;
                 let object_read = context.gateway.read_api().get_parsed_object(nft_id).await?;
-                SuiClientCommandResult::CreateExampleNFT(object_read)
+                SuiClientCommandResult::CreateExampleNFT(cert.transaction_digest, object_read)
             }
         });
         ret
@@ -561,6 +1730,22 @@ pub struct WalletContext {
     pub keystore: SuiKeystore,
     pub gateway: SuiClient,
     pub fullnode: Option<SuiClient>,
+    /// When set, executed transactions are only returned once included in a checkpoint, waiting
+    /// at most this long for it.
+    pub wait_for_checkpoint: Option<Duration>,
+    /// The checkpoint including the last transaction executed, when waiting for checkpoints.
+    pub last_checkpoint: Option<CheckpointSequenceNumber>,
+    /// When set, commands submitting a single transaction dry run it instead, and other commands
+    /// are rejected.
+    pub dry_run: bool,
+    /// When set, commands building a single transaction return its bytes unsigned instead of
+    /// signing and submitting it, and other commands are rejected.
+    pub serialize_transaction: bool,
+    /// When set, commands building a single transaction make it expire after this epoch, and
+    /// other commands are rejected.
+    pub expiration_epoch: Option<EpochId>,
+    /// When set, long running commands such as `sync` report their progress on stderr.
+    pub show_progress: bool,
 }
 
 impl WalletContext {
@@ -583,6 +1768,12 @@ impl WalletContext {
             keystore,
             gateway: client,
             fullnode: fullnode_client,
+            wait_for_checkpoint: None,
+            last_checkpoint: None,
+            dry_run: false,
+            serialize_transaction: false,
+            expiration_epoch: None,
+            show_progress: false,
         };
         Ok(context)
     }
@@ -609,6 +1800,17 @@ impl WalletContext {
         &self,
         address: SuiAddress,
     ) -> Result<Vec<(u64, SuiParsedObject, SuiObjectInfo)>, anyhow::Error> {
+        self.coin_objects(address, &GasCoin::type_()).await
+    }
+
+    /// Get all the coin objects of the type `coin_type`, e.g. `0x2::coin::Coin<0x2::sui::SUI>`,
+    /// along with their values, for the address
+    pub async fn coin_objects(
+        &self,
+        address: SuiAddress,
+        coin_type: &StructTag,
+    ) -> Result<Vec<(u64, SuiParsedObject, SuiObjectInfo)>, anyhow::Error> {
+        let coin_type = coin_type.to_string();
         let object_refs = self
             .gateway
             .read_api()
@@ -617,7 +1819,10 @@ impl WalletContext {
 
         // TODO: We should ideally fetch the objects from local cache
         let mut values_objects = Vec::new();
-        for oref in object_refs {
+        for oref in object_refs
+            .into_iter()
+            .filter(|oref| oref.type_ == coin_type)
+        {
             let response = self
                 .gateway
                 .read_api()
@@ -625,10 +1830,9 @@ impl WalletContext {
                 .await?;
             match response {
                 GetObjectDataResponse::Exists(o) => {
-                    if matches!( o.data.type_(), Some(v)  if *v == GasCoin::type_().to_string()) {
-                        // Okay to unwrap() since we already checked type
-                        let gas_coin = GasCoin::try_from(&o)?;
-                        values_objects.push((gas_coin.value(), o, oref));
+                    if matches!(o.data.type_(), Some(v) if *v == coin_type) {
+                        let coin = coin_from_object(&o)?;
+                        values_objects.push((coin.value(), o, oref));
                     }
                 }
                 _ => continue,
@@ -648,6 +1852,17 @@ impl WalletContext {
         Ok(object.owner.get_owner_address()?)
     }
 
+    /// The balance of a coin object of any coin type.
+    pub async fn get_coin_balance(&self, id: &ObjectID) -> Result<u64, anyhow::Error> {
+        let object = self
+            .gateway
+            .read_api()
+            .get_parsed_object(*id)
+            .await?
+            .into_object()?;
+        Ok(coin_from_object(&object)?.value())
+    }
+
     pub async fn try_get_object_owner(
         &self,
         id: &Option<ObjectID>,
@@ -659,6 +1874,37 @@ impl WalletContext {
         }
     }
 
+    /// Resolve an address argument given as a name, by the address book first, then by the name
+    /// service of the gateway. Hex addresses are never looked up, see `AddressOrName::from_str`.
+    pub async fn resolve_address(
+        &self,
+        address: AddressOrName,
+    ) -> Result<SuiAddress, anyhow::Error> {
+        let name = match address {
+            AddressOrName::Address(address) => return Ok(address),
+            AddressOrName::Name(name) => name,
+        };
+        if let Some(address) = self.config.address_book.get(&name) {
+            return Ok(*address);
+        }
+        let resolved = match self.gateway.embedded_gateway() {
+            Some(gateway) => gateway.resolve_name(&name).await,
+            None => Err(anyhow!("Names can only be resolved by an embedded gateway")),
+        };
+        resolved.map_err(|e| anyhow!("Cannot resolve {name} to an address: {e}"))
+    }
+
+    /// Resolve an optional address argument, defaulting to the active address.
+    pub async fn resolve_address_or_active(
+        &mut self,
+        address: Option<AddressOrName>,
+    ) -> Result<SuiAddress, anyhow::Error> {
+        match address {
+            Some(address) => self.resolve_address(address).await,
+            None => self.active_address(),
+        }
+    }
+
     /// Find a gas object which fits the budget
     pub async fn gas_for_owner_budget(
         &self,
@@ -676,9 +1922,288 @@ impl WalletContext {
         ))
     }
 
-    /// A backward-compatible migration of transaction execution from gateway to fullnode
-    async fn execute_transaction(&self, tx: Transaction) -> anyhow::Result<SuiTransactionResponse> {
+    /// Reconstruct the lineage of `coin`, see `sui client coin-flow`. The walk stops after
+    /// `MAX_COIN_FLOW_COINS` coins, leaving the lineage of the remaining coins out.
+    pub async fn coin_flow(&self, coin: ObjectID) -> anyhow::Result<CoinFlow> {
+        let mut history = TransactionHistory {
+            fullnode: match (&self.fullnode, self.gateway.embedded_gateway()) {
+                (Some(fullnode), _) => Some(fullnode),
+                (None, None) => Some(&self.gateway),
+                (None, Some(_)) => None,
+            },
+            transactions: BTreeMap::new(),
+        };
+        if history.fullnode.is_none() {
+            let read_api = self.gateway.read_api();
+            let total = read_api.get_total_transaction_number().await?;
+            for start in (0..total).step_by(TX_RANGE_PAGE_SIZE as usize) {
+                let end = total.min(start + TX_RANGE_PAGE_SIZE);
+                for (seq, digest) in read_api.get_transactions_in_range(start, end).await? {
+                    let tx = read_api.get_transaction(digest).await?;
+                    history.transactions.insert(seq, tx);
+                }
+            }
+        }
+
+        // Trace the coins breadth first, then assemble the lineage from the root.
+        let mut flows = HashMap::new();
+        let mut queue = VecDeque::from([coin]);
+        while let Some(coin) = queue.pop_front() {
+            if flows.len() >= MAX_COIN_FLOW_COINS {
+                break;
+            }
+            if flows.contains_key(&coin) {
+                continue;
+            }
+            let flow = trace_coin(coin, &history.touching(coin).await?);
+            for event in &flow.events {
+                if let CoinFlowEvent::Split { coins, .. } = event {
+                    queue.extend(coins.iter().map(|c| c.coin));
+                }
+            }
+            flows.insert(coin, flow);
+        }
+        Ok(assemble_coin_flow(coin, &mut flows))
+    }
+
+    /// Execute the signed transactions of `lines`, as read by `sui client batch-submit`, in order.
+    /// Empty lines are skipped, and failures are reported per line instead of aborting the batch.
+    pub async fn batch_submit(
+        &mut self,
+        lines: impl IntoIterator<Item = String>,
+    ) -> Vec<BatchSubmitResult> {
+        let mut results = vec![];
+        for (index, line) in lines.into_iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut result = BatchSubmitResult {
+                line: index + 1,
+                digest: None,
+                status: None,
+                error: None,
+            };
+            match parse_signed_transaction(&line) {
+                Ok(tx) => {
+                    result.digest = Some(*tx.digest());
+                    match self.execute_transaction(tx).await {
+                        Ok(response) => result.status = Some(response.effects.status),
+                        Err(e) => result.error = Some(e.to_string()),
+                    }
+                }
+                Err(e) => result.error = Some(e.to_string()),
+            }
+            results.push(result);
+        }
+        results
+    }
+
+    /// Dry run the transactions of `lines`, as read by `sui client batch-submit --estimate-only`,
+    /// and sum the gas they would use. Each line is dry run against the current objects, not
+    /// against the effects of the lines before it.
+    pub async fn batch_estimate(&self, lines: impl IntoIterator<Item = String>) -> BatchEstimate {
+        let mut estimate = BatchEstimate {
+            total_gas_used: 0,
+            transactions: vec![],
+        };
+        for (index, line) in lines.into_iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut result = BatchEstimateResult {
+                line: index + 1,
+                gas_used: None,
+                status: None,
+                error: None,
+            };
+            match parse_transaction_data(&line) {
+                Ok(data) => match self.dry_run_transaction(&data).await {
+                    Ok(effects) => {
+                        let gas_used = effects.gas_used.gas_used();
+                        estimate.total_gas_used += gas_used;
+                        result.gas_used = Some(gas_used);
+                        result.status = Some(effects.status);
+                    }
+                    Err(e) => result.error = Some(e.to_string()),
+                },
+                Err(e) => result.error = Some(e.to_string()),
+            }
+            estimate.transactions.push(result);
+        }
+        estimate
+    }
+
+    /// Stream the object again each time a transaction creates, mutates, unwraps, wraps or
+    /// deletes it. Subscribes through the fullnode if one is set, or the gateway otherwise, either
+    /// of which must be connected over websocket.
+    pub async fn follow_object(
+        &self,
+        id: ObjectID,
+    ) -> anyhow::Result<impl Stream<Item = anyhow::Result<GetObjectDataResponse>> + '_> {
+        let client = self.fullnode.as_ref().unwrap_or(&self.gateway);
+        let transactions = client
+            .event_api()
+            .subscribe_transaction(SuiTransactionFilter::MutatedObject(id))
+            .await?;
+        Ok(transactions.then(move |transaction| async move {
+            transaction?;
+            client.read_api().get_parsed_object(id).await
+        }))
+    }
+
+    /// The total value of the gas objects owned by the address.
+    pub async fn balance(&self, address: SuiAddress) -> anyhow::Result<AddressBalance> {
+        let gas_objects = self.gas_objects(address).await?;
+        Ok(AddressBalance {
+            address,
+            balance: gas_objects.iter().map(|(value, _, _)| value).sum(),
+            coin_count: gas_objects.len(),
+        })
+    }
+
+    /// Stream the balance of the address again each time a transaction sent by it, or leaving
+    /// objects owned by it, lands. Transactions landing less than `BALANCE_WATCH_DEBOUNCE` apart
+    /// yield a single balance. Subscribes as `follow_object` does.
+    pub async fn watch_balance(
+        &self,
+        address: SuiAddress,
+    ) -> anyhow::Result<impl Stream<Item = anyhow::Result<AddressBalance>> + '_> {
+        let client = self.fullnode.as_ref().unwrap_or(&self.gateway);
+        let transactions = client
+            .event_api()
+            .subscribe_transaction(SuiTransactionFilter::AffectedAddress(address))
+            .await?;
+        Ok(stream::unfold(
+            Box::pin(transactions),
+            move |mut transactions| async move {
+                if let Err(e) = transactions.next().await? {
+                    return Some((Err(e), transactions));
+                }
+                while let Ok(Some(transaction)) =
+                    tokio::time::timeout(BALANCE_WATCH_DEBOUNCE, transactions.next()).await
+                {
+                    if let Err(e) = transaction {
+                        return Some((Err(e), transactions));
+                    }
+                }
+                Some((self.balance(address).await, transactions))
+            },
+        ))
+    }
+
+    /// Execute `data` on the gateway without signing or submitting it, leaving every object
+    /// untouched.
+    async fn dry_run_transaction(
+        &self,
+        data: &TransactionData,
+    ) -> anyhow::Result<SuiTransactionEffects> {
+        self.gateway
+            .quorum_driver()
+            .dry_run_transaction(Base64::from_bytes(&data.to_bytes()))
+            .await
+    }
+
+    /// `data` expiring after the epoch given with `--expiration-epoch`, if any. Commands building
+    /// their transaction call this before previewing and signing it.
+    fn with_expiration_epoch(&self, mut data: TransactionData) -> TransactionData {
+        data.expiration_epoch = self.expiration_epoch;
+        data
+    }
+
+    /// The dry run of `data`, or its unsigned bytes, as the result of the command when
+    /// `--dry-run` or `--serialize-transaction` is set. Commands call this once their transaction
+    /// is built, before confirming and signing it.
+    async fn preview_transaction(
+        &self,
+        data: &TransactionData,
+    ) -> anyhow::Result<Option<SuiClientCommandResult>> {
+        Ok(if self.dry_run {
+            Some(SuiClientCommandResult::DryRun(
+                self.dry_run_transaction(data).await?,
+            ))
+        } else if self.serialize_transaction {
+            Some(SuiClientCommandResult::SerializedTransaction(
+                Base64::encode(data.to_bytes()),
+            ))
+        } else {
+            None
+        })
+    }
+
+    /// Estimate the gas budget of `data` as the gas used by its dry run plus the configured margin,
+    /// capped at the max gas budget. A failed dry run is reported with its execution error.
+    async fn estimate_gas_budget(&self, data: &TransactionData) -> anyhow::Result<u64> {
+        let effects = self.dry_run_transaction(data).await?;
+        if let SuiExecutionStatus::Failure { error } = effects.status {
+            return Err(anyhow!(
+                "Dry run to estimate the gas budget failed: {error}"
+            ));
+        }
+        let gas_used = effects.gas_used.gas_used();
+        let margin = gas_used * self.config.gas_budget_margin_percent / 100;
+        Ok((gas_used + margin).min(self.config.max_gas_budget))
+    }
+
+    /// Keep `data` as is when a `gas_budget` was given. Otherwise `data` was built with the max
+    /// gas budget, which is replaced by an estimated one. The dry run estimating it is given all
+    /// the gas object holds, up to the max gas budget, as a gas object given on the command line
+    /// may hold less than the max gas budget.
+    async fn resolve_gas_budget(
+        &self,
+        mut data: TransactionData,
+        gas_budget: Option<u64>,
+    ) -> anyhow::Result<TransactionData> {
+        if gas_budget.is_none() {
+            let gas_balance = self.get_coin_balance(&data.gas().0).await?;
+            data.gas_budget = gas_balance.min(self.config.max_gas_budget);
+            data.gas_budget = self.estimate_gas_budget(&data).await?;
+        }
+        Ok(data)
+    }
+
+    /// The given gas price, or the reference gas price of the network when none is given.
+    async fn resolve_gas_price(&self, gas_price: Option<u64>) -> anyhow::Result<u64> {
+        Ok(match gas_price {
+            Some(gas_price) => gas_price,
+            None => self.gateway.read_api().get_reference_gas_price().await?,
+        })
+    }
+
+    /// Transactions with a gas budget above the configured max gas budget are not submitted.
+    fn check_gas_budget(&self, gas_budget: u64) -> anyhow::Result<()> {
+        let max_gas_budget = self.config.max_gas_budget;
+        if gas_budget > max_gas_budget {
+            return Err(anyhow!(
+                "Gas budget {gas_budget} is above the client's max_gas_budget of {max_gas_budget}. \
+                Raise max_gas_budget in the client config to submit this transaction."
+            ));
+        }
+        Ok(())
+    }
+
+    /// A backward-compatible migration of transaction execution from gateway to fullnode. Every
+    /// transaction the client submits goes through here, whichever command built or signed it,
+    /// so the max gas budget is enforced here.
+    async fn execute_transaction(
+        &mut self,
+        tx: Transaction,
+    ) -> anyhow::Result<SuiTransactionResponse> {
+        self.check_gas_budget(tx.signed_data.data.gas_budget)?;
         let tx_digest = *tx.digest();
+        if let Some(timeout) = self.wait_for_checkpoint {
+            if self.fullnode.is_some() {
+                return Err(anyhow!(
+                    "Waiting for checkpoints is only supported when executing through the gateway"
+                ));
+            }
+            let response = self
+                .gateway
+                .quorum_driver()
+                .execute_transaction_and_wait_for_checkpoint(tx, timeout)
+                .await?;
+            self.last_checkpoint = Some(response.checkpoint);
+            return Ok(response.response);
+        }
         match &self.fullnode {
             None => self.gateway.quorum_driver().execute_transaction(tx).await,
             Some(client) => {
@@ -732,20 +2257,42 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::Call(cert, effects) => {
                 write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
             }
-            SuiClientCommandResult::Transfer(time_elapsed, cert, effects) => {
+            SuiClientCommandResult::Transfer(time_elapsed, cert, effects)
+            | SuiClientCommandResult::TransferSui(time_elapsed, cert, effects) => {
                 writeln!(writer, "Transfer confirmed after {} us", time_elapsed)?;
                 write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
             }
-            SuiClientCommandResult::TransferSui(cert, effects) => {
+            SuiClientCommandResult::TransferObjects(cert, effects) => {
                 write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
             }
             SuiClientCommandResult::Addresses(addresses) => {
                 writeln!(writer, "Showing {} results.", addresses.len())?;
-                for address in addresses {
-                    writeln!(writer, "{}", address)?;
+                for ManagedAddress { address, names } in addresses {
+                    if names.is_empty() {
+                        writeln!(writer, "{address}")?;
+                    } else {
+                        writeln!(writer, "{address} ({})", names.join(", "))?;
+                    }
+                }
+            }
+            SuiClientCommandResult::AddressBook(book) => {
+                writeln!(writer, "Showing {} results.", book.len())?;
+                for (name, address) in book {
+                    writeln!(writer, "{name} : {address}")?;
                 }
             }
-            SuiClientCommandResult::Objects(object_refs) => {
+            SuiClientCommandResult::Profiles(profiles) => {
+                writeln!(writer, "Showing {} results.", profiles.len())?;
+                for profile in profiles {
+                    let marker = if profile.active { "*" } else { " " };
+                    let address = profile
+                        .active_address
+                        .map(|address| address.to_string())
+                        .unwrap_or_else(|| "no active address".to_string());
+                    writeln!(writer, "{marker} {} : {address}", profile.name)?;
+                }
+            }
+            SuiClientCommandResult::Objects(object_refs, next_cursor) => {
                 writeln!(
                     writer,
                     " {0: ^42} | {1: ^10} | {2: ^44} | {3: ^15} | {4: ^40}",
@@ -770,8 +2317,11 @@ impl Display for SuiClientCommandResult {
                     )?
                 }
                 writeln!(writer, "Showing {} results.", object_refs.len())?;
+                if let Some(next_cursor) = next_cursor {
+                    writeln!(writer, "Show the next objects with --cursor {next_cursor}")?;
+                }
             }
-            SuiClientCommandResult::SyncClientState => {
+            SuiClientCommandResult::SyncClientState(_) => {
                 writeln!(writer, "Client state sync complete.")?;
             }
             SuiClientCommandResult::NewAddress((address, recovery_phrase, scheme)) => {
@@ -782,6 +2332,81 @@ impl Display for SuiClientCommandResult {
                 )?;
                 writeln!(writer, "Secret Recovery Phrase : [{recovery_phrase}]")?;
             }
+            SuiClientCommandResult::MultisigAddress(address) => {
+                writeln!(writer, "Multisig address: [{address}]")?;
+            }
+            SuiClientCommandResult::ProveObjectOwnership(proof) => {
+                writeln!(writer, "Ownership proof: {proof}")?;
+            }
+            SuiClientCommandResult::VerifyObjectOwnership(statement, still_owned) => {
+                let (object_id, version, digest) = statement.object_ref;
+                writeln!(
+                    writer,
+                    "Valid proof: {} owned object {object_id} at version {} (digest {digest:?}), \
+                    answering the challenge {:?}",
+                    statement.owner,
+                    version.value(),
+                    statement.challenge
+                )?;
+                match still_owned {
+                    Some(true) => writeln!(writer, "The prover still owns the object")?,
+                    Some(false) => {
+                        writeln!(writer, "{}", "The prover no longer owns the object".red())?
+                    }
+                    None => {}
+                }
+            }
+            SuiClientCommandResult::BatchSubmit(results) => {
+                for result in results {
+                    write!(writer, "Line {}: ", result.line)?;
+                    if let Some(digest) = result.digest {
+                        write!(writer, "{digest:?} ")?;
+                    }
+                    match (&result.status, &result.error) {
+                        (Some(SuiExecutionStatus::Success), _) => writeln!(writer, "Success")?,
+                        (Some(SuiExecutionStatus::Failure { error }), _) | (_, Some(error)) => {
+                            writeln!(writer, "{}", format!("Failed: {error}").red())?
+                        }
+                        (None, None) => writeln!(writer)?,
+                    }
+                }
+                let failures = results
+                    .iter()
+                    .filter(|result| !matches!(result.status, Some(SuiExecutionStatus::Success)))
+                    .count();
+                writeln!(
+                    writer,
+                    "Submitted {} transactions, {failures} failed.",
+                    results.len()
+                )?;
+            }
+            SuiClientCommandResult::BatchEstimate(estimate) => {
+                for result in &estimate.transactions {
+                    write!(writer, "Line {}: ", result.line)?;
+                    match (result.gas_used, &result.status, &result.error) {
+                        (Some(gas_used), Some(SuiExecutionStatus::Success), _) => {
+                            writeln!(writer, "{gas_used} gas")?
+                        }
+                        (Some(gas_used), Some(SuiExecutionStatus::Failure { error }), _) => {
+                            writeln!(
+                                writer,
+                                "{}",
+                                format!("{gas_used} gas, would fail: {error}").red()
+                            )?
+                        }
+                        (_, _, Some(error)) => {
+                            writeln!(writer, "{}", format!("Failed: {error}").red())?
+                        }
+                        _ => writeln!(writer)?,
+                    }
+                }
+                writeln!(
+                    writer,
+                    "Estimated {} gas in total for {} transactions.",
+                    estimate.total_gas_used,
+                    estimate.transactions.len()
+                )?;
+            }
             SuiClientCommandResult::Gas(gases) => {
                 // TODO: generalize formatting of CLI
                 writeln!(writer, " {0: ^42} | {1: ^11}", "Object ID", "Gas Value")?;
@@ -792,25 +2417,95 @@ impl Display for SuiClientCommandResult {
                 for gas in gases {
                     writeln!(writer, " {0: ^42} | {1: ^11}", gas.id(), gas.value())?;
                 }
-            }
-            SuiClientCommandResult::SplitCoin(response) => {
-                write!(
+                let total_balance: u64 = gases.iter().map(|gas| gas.value()).sum();
+                writeln!(
                     writer,
-                    "{}",
-                    write_cert_and_effects(&response.certificate, &response.effects)?
+                    "{} gas objects with a total value of {total_balance}.",
+                    gases.len()
                 )?;
-                if let Some(parsed_resp) = &response.parsed_data {
-                    writeln!(writer, "{}", parsed_resp)?;
-                }
             }
-            SuiClientCommandResult::MergeCoin(response) => {
-                write!(
+            SuiClientCommandResult::Balance(balance) => {
+                writeln!(
                     writer,
-                    "{}",
-                    write_cert_and_effects(&response.certificate, &response.effects)?
+                    "Balance of {}: {} in {} gas objects",
+                    balance.address, balance.balance, balance.coin_count
                 )?;
-                if let Some(parsed_resp) = &response.parsed_data {
-                    writeln!(writer, "{}", parsed_resp)?;
+            }
+            SuiClientCommandResult::Coins(balances) => {
+                writeln!(writer, " {0: ^42} | {1: ^11}", "Object ID", "Balance")?;
+                writeln!(
+                    writer,
+                    "----------------------------------------------------------------------"
+                )?;
+                for coin in &balances.coins {
+                    writeln!(writer, " {0: ^42} | {1: ^11}", coin.id(), coin.value())?;
+                }
+                writeln!(
+                    writer,
+                    "{} coins of type {} with a total balance of {}.",
+                    balances.coins.len(),
+                    balances.coin_type,
+                    balances.total_balance()
+                )?;
+            }
+            SuiClientCommandResult::GasRefundEstimate(gases) => {
+                writeln!(
+                    writer,
+                    " {0: ^42} | {1: ^11} | {2: ^14}",
+                    "Object ID", "Gas Value", "Storage Rebate"
+                )?;
+                writeln!(
+                    writer,
+                    "-------------------------------------------------------------------------------------"
+                )?;
+                for (gas, rebate) in gases {
+                    writeln!(
+                        writer,
+                        " {0: ^42} | {1: ^11} | {2: ^14}",
+                        gas.id(),
+                        gas.value(),
+                        rebate
+                    )?;
+                }
+                let total_rebate: u64 = gases.iter().map(|(_, rebate)| rebate).sum();
+                writeln!(
+                    writer,
+                    "Deleting these objects would rebate {total_rebate} in storage fees."
+                )?;
+            }
+            SuiClientCommandResult::SplitCoin(response) => {
+                write!(
+                    writer,
+                    "{}",
+                    write_cert_and_effects(&response.certificate, &response.effects)?
+                )?;
+                if let Some(parsed_resp) = &response.parsed_data {
+                    writeln!(writer, "{}", parsed_resp)?;
+                }
+            }
+            SuiClientCommandResult::MergeAllCoins(result) => {
+                writeln!(
+                    writer,
+                    "Merged {} coins into {}, now worth {}:",
+                    result.merged_coins.len(),
+                    result.primary_coin,
+                    result.total_value
+                )?;
+                for coin in &result.merged_coins {
+                    writeln!(writer, "{coin}")?;
+                }
+            }
+            SuiClientCommandResult::MergeCoin(response)
+            | SuiClientCommandResult::MultisigExecute(response)
+            | SuiClientCommandResult::ExecuteSigned(response)
+            | SuiClientCommandResult::SignAndSubmit(response) => {
+                write!(
+                    writer,
+                    "{}",
+                    write_cert_and_effects(&response.certificate, &response.effects)?
+                )?;
+                if let Some(parsed_resp) = &response.parsed_data {
+                    writeln!(writer, "{}", parsed_resp)?;
                 }
             }
             SuiClientCommandResult::Switch(response) => {
@@ -822,12 +2517,67 @@ impl Display for SuiClientCommandResult {
                     None => write!(writer, "None")?,
                 };
             }
-            SuiClientCommandResult::CreateExampleNFT(object_read) => {
+            SuiClientCommandResult::SystemState(system_state) => {
+                write!(writer, "{}", system_state)?;
+            }
+            SuiClientCommandResult::Events(events) => {
+                writeln!(writer, " {0: ^70} | {1: ^42}", "Event Type", "Sender")?;
+                writeln!(writer, "{}", ["-"; 116].join(""))?;
+                for event in events {
+                    let (event_type, sender, details) = describe_event(event);
+                    let sender = sender.map(|sender| sender.to_string()).unwrap_or_default();
+                    writeln!(writer, " {0: ^70} | {1: ^42}", event_type, sender)?;
+                    for line in details.lines() {
+                        writeln!(writer, "     {line}")?;
+                    }
+                }
+            }
+            SuiClientCommandResult::History(address, transactions) => {
+                writeln!(writer, "Transactions of address {address}:")?;
+                writeln!(
+                    writer,
+                    " {0: ^10} | {1: ^44}",
+                    "Sequence", "Transaction Digest"
+                )?;
+                writeln!(writer, "{}", ["-"; 57].join(""))?;
+                for (seq, digest) in transactions {
+                    writeln!(writer, " {0: ^10} | {1:?}", seq, digest)?;
+                }
+                write!(writer, "Showing {} results.", transactions.len())?;
+            }
+            SuiClientCommandResult::Serve(address) => {
+                write!(writer, "Stopped serving the gateway at http://{address}")?;
+            }
+            SuiClientCommandResult::ReplayFromEffects(digest) => {
+                write!(
+                    writer,
+                    "Replayed effects of transaction {digest:?} match the stored effects"
+                )?;
+            }
+            SuiClientCommandResult::CoinFlow(flow) => {
+                write_coin_flow(&mut writer, flow, 0)?;
+            }
+            SuiClientCommandResult::AuthorityResponse(response) => {
+                writeln!(writer, "{}", "----- Authority Response (debug) ----".bold())?;
+                writeln!(writer, "{:#?}", response)?;
+            }
+            SuiClientCommandResult::CreateExampleNFT(_, object_read) => {
                 // TODO: display the content of the object
                 let object = unwrap_err_to_string(|| Ok(object_read.object()?));
                 writeln!(writer, "{}\n", "Successfully created an ExampleNFT:".bold())?;
                 writeln!(writer, "{}", object)?;
             }
+            SuiClientCommandResult::DryRun(effects) => {
+                writeln!(
+                    writer,
+                    "{}",
+                    "----- Dry Run Effects (not submitted) ----".bold()
+                )?;
+                write!(writer, "{}", effects)?;
+            }
+            SuiClientCommandResult::SerializedTransaction(tx_bytes) => {
+                writeln!(writer, "{tx_bytes}")?;
+            }
         }
         write!(f, "{}", writer.trim_end_matches('\n'))
     }
@@ -840,13 +2590,52 @@ pub async fn call_move(
     type_args: Vec<TypeTag>,
     gas: Option<ObjectID>,
     gas_budget: u64,
+    gas_price: Option<u64>,
     args: Vec<SuiJsonValue>,
     context: &mut WalletContext,
 ) -> Result<(SuiCertifiedTransaction, SuiTransactionEffects), anyhow::Error> {
+    let (sender, data) = build_move_call(
+        package, module, function, type_args, gas, gas_budget, gas_price, args, context,
+    )
+    .await?;
+    execute_move_call(sender, data, context).await
+}
+
+/// Sign and execute the transaction data of a Move call, failing if the call aborts.
+async fn execute_move_call(
+    sender: SuiAddress,
+    data: TransactionData,
+    context: &mut WalletContext,
+) -> Result<(SuiCertifiedTransaction, SuiTransactionEffects), anyhow::Error> {
+    let signature = context.keystore.sign(&sender, &data.to_bytes())?;
+    let transaction = Transaction::new(data, signature);
+
+    let response = context.execute_transaction(transaction).await?;
+    let cert = response.certificate;
+    let effects = response.effects;
+
+    if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
+        return Err(anyhow!("Error calling module: {:#?}", effects.status));
+    }
+    Ok((cert, effects))
+}
+
+/// Build the transaction data of a Move call, returning it with the address to sign it.
+async fn build_move_call(
+    package: ObjectID,
+    module: &str,
+    function: &str,
+    type_args: Vec<TypeTag>,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    gas_price: Option<u64>,
+    args: Vec<SuiJsonValue>,
+    context: &mut WalletContext,
+) -> Result<(SuiAddress, TransactionData), anyhow::Error> {
     let gas_owner = context.try_get_object_owner(&gas).await?;
     let sender = gas_owner.unwrap_or(context.active_address()?);
 
-    let data = context
+    let mut data = context
         .gateway
         .transaction_builder()
         .move_call(
@@ -863,17 +2652,517 @@ pub async fn call_move(
             gas_budget,
         )
         .await?;
-    let signature = context.keystore.sign(&sender, &data.to_bytes())?;
-    let transaction = Transaction::new(data, signature);
+    data.gas_price = context.resolve_gas_price(gas_price).await?;
+    Ok((sender, data))
+}
 
-    let response = context.execute_transaction(transaction).await?;
-    let cert = response.certificate;
-    let effects = response.effects;
+/// Merges every SUI coin of `owner` but the gas object into the largest one. A failing merge
+/// stops the remaining ones, and the error names the coins merged before it.
+async fn merge_all_coins(
+    context: &mut WalletContext,
+    owner: SuiAddress,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    gas_price: u64,
+    yes: bool,
+) -> Result<MergeAllCoinsResult, anyhow::Error> {
+    let mut coins = context
+        .gas_objects(owner)
+        .await?
+        .into_iter()
+        .map(|(value, object, _)| (value, object.id()))
+        .collect::<Vec<_>>();
+    // Largest first, so the primary coin is the first one left once the gas object is taken out.
+    coins.sort_by(|a, b| b.cmp(a));
+    // Each merge is a transaction paid by the same gas object, so it has to hold the budget of
+    // every merge: one per coin besides itself and the primary coin.
+    let merges = coins.len().saturating_sub(2) as u64;
+    let total_budget = gas_budget
+        .checked_mul(merges)
+        .ok_or_else(|| anyhow!("The gas budget of {merges} merges overflows u64"))?;
+    let gas = match gas {
+        Some(gas) => {
+            let gas_value = coins
+                .iter()
+                .find(|(_, id)| *id == gas)
+                .map(|(value, _)| *value)
+                .ok_or_else(|| anyhow!("Gas object {gas} is not a SUI coin of {owner}"))?;
+            if gas_value < total_budget {
+                return Err(anyhow!(
+                    "Gas object {gas} holds {gas_value}, less than the gas budget {gas_budget} of \
+                    each of the {merges} merges"
+                ));
+            }
+            gas
+        }
+        None => coins
+            .iter()
+            .rev()
+            .find(|(value, _)| *value >= total_budget)
+            .map(|(_, id)| *id)
+            .ok_or_else(|| {
+                anyhow!(
+                    "No gas object of {owner} holds the gas budget {gas_budget} of each of the \
+                    {merges} merges"
+                )
+            })?,
+    };
+    coins.retain(|(_, id)| *id != gas);
+    let ((primary_value, primary_coin), coins_to_merge) = match coins.split_first() {
+        Some((primary, rest)) => (*primary, rest),
+        None => {
+            return Err(anyhow!(
+                "{owner} has no SUI coins to merge besides gas {gas}"
+            ))
+        }
+    };
+
+    confirm_transaction(
+        yes,
+        atty::is(atty::Stream::Stdin),
+        &format!(
+            "Merge {} coins of {owner} into {primary_coin}, gas object {gas} with budget {gas_budget} per merge",
+            coins_to_merge.len()
+        ),
+    )?;
+    let mut result = MergeAllCoinsResult {
+        primary_coin,
+        merged_coins: vec![],
+        total_value: primary_value,
+        digests: vec![],
+    };
+    for (value, coin_to_merge) in coins_to_merge {
+        let digest = match merge_coin(
+            context,
+            owner,
+            primary_coin,
+            *coin_to_merge,
+            gas,
+            gas_budget,
+            gas_price,
+        )
+        .await
+        {
+            Ok(digest) => digest,
+            Err(e) => {
+                return Err(anyhow!(
+                    "Merging coin {coin_to_merge} into {primary_coin} failed: {e}. Coins merged \
+                    before the failure: {:?}",
+                    result.merged_coins
+                ))
+            }
+        };
+        result.merged_coins.push(*coin_to_merge);
+        result.digests.push(digest);
+        result.total_value += value;
+    }
+    Ok(result)
+}
 
-    if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
-        return Err(anyhow!("Error calling module: {:#?}", effects.status));
+/// The gas object paying for the transfer of `object_id`, selected from the gas objects of `owner`
+/// unless given. The transferred object cannot pay for its own transfer, which is reported with a
+/// pointer to `transfer-sui` rather than left to fail in the gateway.
+async fn gas_for_transfer(
+    context: &WalletContext,
+    owner: SuiAddress,
+    object_id: ObjectID,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+) -> Result<ObjectID, anyhow::Error> {
+    let pay_with_transferred_coin = || {
+        anyhow!(
+            "The transferred coin {object_id} cannot also pay for the gas of its transfer. Use \
+            `transfer-sui` to transfer SUI and pay gas out of the same coin."
+        )
+    };
+    match gas {
+        Some(gas) if gas == object_id => Err(pay_with_transferred_coin()),
+        Some(gas) => Ok(gas),
+        None => match context
+            .gas_for_owner_budget(owner, gas_budget, BTreeSet::from([object_id]))
+            .await
+        {
+            Ok((_, gas)) => Ok(gas.id()),
+            Err(e) => {
+                // Tell apart an owner whose only coin fitting the budget is the transferred one.
+                let transferred_coin_fits = context
+                    .gas_for_owner_budget(owner, gas_budget, BTreeSet::new())
+                    .await
+                    .map_or(false, |(_, gas)| gas.id() == object_id);
+                if transferred_coin_fits {
+                    Err(pay_with_transferred_coin())
+                } else {
+                    Err(e)
+                }
+            }
+        },
     }
-    Ok((cert, effects))
+}
+
+/// Merges `coin_to_merge` into `primary_coin`, failing if the merge does not execute successfully,
+/// and returns the digest of the merge.
+async fn merge_coin(
+    context: &mut WalletContext,
+    owner: SuiAddress,
+    primary_coin: ObjectID,
+    coin_to_merge: ObjectID,
+    gas: ObjectID,
+    gas_budget: u64,
+    gas_price: u64,
+) -> Result<TransactionDigest, anyhow::Error> {
+    let mut data = context
+        .gateway
+        .transaction_builder()
+        .merge_coins(owner, primary_coin, coin_to_merge, Some(gas), gas_budget)
+        .await?;
+    data.gas_price = gas_price;
+    let signature = context.keystore.sign(&owner, &data.to_bytes())?;
+    let response = context
+        .execute_transaction(Transaction::new(data, signature))
+        .await?;
+    if let SuiExecutionStatus::Failure { error } = response.effects.status {
+        return Err(anyhow!(error));
+    }
+    Ok(response.certificate.transaction_digest)
+}
+
+/// Builds a single transaction sending `amount` of SUI, or all of it, from exactly `input_coins`
+/// to `recipient`. The coins are sent whole in order, the last one needed being split to send the
+/// remainder, so that either the whole amount is sent or nothing is. Gas is paid by a coin of
+/// `owner` outside `input_coins`.
+async fn transfer_sui_from_coins(
+    context: &WalletContext,
+    owner: SuiAddress,
+    recipient: SuiAddress,
+    input_coins: &[ObjectID],
+    amount: Option<u64>,
+    gas_budget: u64,
+) -> Result<TransactionData, anyhow::Error> {
+    let input_set = input_coins.iter().cloned().collect::<BTreeSet<_>>();
+    if input_set.len() != input_coins.len() {
+        return Err(anyhow!("Input coins must not contain duplicates"));
+    }
+
+    let balances = context
+        .gas_objects(owner)
+        .await?
+        .into_iter()
+        .map(|(value, object, _)| (object.id(), value))
+        .collect::<BTreeMap<_, _>>();
+    let mut total: u64 = 0;
+    for coin in input_coins {
+        let balance = balances
+            .get(coin)
+            .ok_or_else(|| anyhow!("Input coin {coin} is not a SUI coin owned by {owner}"))?;
+        total = total.saturating_add(*balance);
+    }
+    let required = amount.unwrap_or(total);
+    if total < required {
+        return Err(anyhow!(
+            "Input coins hold {total} in total, which is insufficient for amount {required}"
+        ));
+    }
+
+    let mut transfers = Vec::new();
+    let mut remaining = required;
+    for coin in input_coins {
+        if remaining == 0 {
+            break;
+        }
+        let balance = balances[coin];
+        if balance <= remaining {
+            transfers.push(RPCTransactionRequestParams::TransferObjectRequestParams(
+                TransferObjectParams {
+                    recipient,
+                    object_id: *coin,
+                },
+            ));
+            remaining -= balance;
+        } else {
+            transfers.push(RPCTransactionRequestParams::MoveCallRequestParams(
+                MoveCallParams {
+                    package_object_id: SUI_FRAMEWORK_OBJECT_ID,
+                    module: coin::COIN_MODULE_NAME.to_string(),
+                    function: coin::COIN_SPLIT_AND_TRANSFER_FUNC_NAME.to_string(),
+                    type_arguments: vec![GAS::type_tag().into()],
+                    arguments: vec![
+                        SuiJsonValue::new(json!(coin.to_string()))?,
+                        SuiJsonValue::new(json!(remaining.to_string()))?,
+                        SuiJsonValue::new(json!(recipient.to_string()))?,
+                    ],
+                },
+            ));
+            remaining = 0;
+        }
+    }
+
+    let gas = context
+        .gas_for_owner_budget(owner, gas_budget, input_set)
+        .await?
+        .1
+        .id();
+    context
+        .gateway
+        .transaction_builder()
+        .batch_transaction(owner, transfers, Some(gas), gas_budget)
+        .await
+}
+
+/// Show the resolved transaction to the user and wait for an explicit confirmation before it is
+/// signed and submitted. `--yes` skips the prompt; without a terminal there is nobody to answer,
+/// so `--yes` is required.
+pub fn confirm_transaction(
+    yes: bool,
+    interactive: bool,
+    summary: &str,
+) -> Result<(), anyhow::Error> {
+    if yes {
+        return Ok(());
+    }
+    if !interactive {
+        return Err(anyhow!(
+            "Cannot prompt for confirmation without a terminal, re-run with --yes to submit: {summary}"
+        ));
+    }
+    println!("{summary}");
+    print!("Confirm transaction [y/N]: ");
+    let _ = stdout().flush();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    match input.trim().to_lowercase().as_str() {
+        "y" | "yes" => Ok(()),
+        _ => Err(anyhow!("Transaction aborted by user.")),
+    }
+}
+
+/// Serve `gateway` over JSON-RPC at `address`. Clients connected to the server share the
+/// gateway, and therefore its connections to the authorities.
+pub async fn start_gateway_server(
+    gateway: GatewayClient,
+    address: SocketAddr,
+) -> Result<HttpServerHandle, anyhow::Error> {
+    let prometheus_registry = prometheus::Registry::default();
+    let mut server = JsonRpcServerBuilder::new(false, &prometheus_registry)?;
+    server.register_module(RpcGatewayImpl::new(gateway.clone()))?;
+    server.register_module(GatewayReadApiImpl::new(gateway.clone()))?;
+    server.register_module(TransactionBuilderImpl::new(gateway.clone()))?;
+    server.register_module(BcsApiImpl::new_with_gateway(gateway.clone()))?;
+    server.register_module(GatewayWalletSyncApiImpl::new(gateway))?;
+    server
+        .start(address)
+        .await?
+        .into_http_server_handle()
+        .ok_or_else(|| anyhow!("Expected a http server handle"))
+}
+
+/// Names which are addresses are rejected, as hex addresses are never looked up as names.
+fn check_address_book_name(name: &str) -> Result<(), anyhow::Error> {
+    if SuiAddress::from_str(name).is_ok() {
+        return Err(anyhow!("Name {name} is itself an address"));
+    }
+    Ok(())
+}
+
+fn parse_public_key(s: &str) -> Result<PublicKey, anyhow::Error> {
+    PublicKey::decode_base64(s).map_err(|e| anyhow!("Invalid public key {s}: {e}"))
+}
+
+fn parse_signature(s: &str) -> Result<Signature, anyhow::Error> {
+    let bytes = Base64::decode(s).map_err(|e| anyhow!("Invalid signature {s}: {e}"))?;
+    <Signature as signature::Signature>::from_bytes(&bytes)
+        .map_err(|e| anyhow!("Invalid signature {s}: {e}"))
+}
+
+/// Parse the transaction bytes of a `batch-submit` line, ignoring its signature if any.
+fn parse_transaction_data(line: &str) -> Result<TransactionData, anyhow::Error> {
+    let tx_bytes = line
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| anyhow!("Expected transaction bytes"))?;
+    Ok(TransactionData::from_signable_bytes(&Base64::decode(
+        tx_bytes,
+    )?)?)
+}
+
+fn parse_signed_transaction(line: &str) -> Result<Transaction, anyhow::Error> {
+    let parts: Vec<_> = line.split_whitespace().collect();
+    let (tx_bytes, signature) = match parts[..] {
+        [tx_bytes, signature] => (tx_bytes, signature),
+        _ => {
+            return Err(anyhow!(
+                "Expected transaction bytes and a signature separated by whitespace, got {} fields",
+                parts.len()
+            ))
+        }
+    };
+    let data = TransactionData::from_signable_bytes(&Base64::decode(tx_bytes)?)?;
+    Ok(Transaction::new(data, parse_signature(signature)?))
+}
+
+/// The executed transactions `sui client coin-flow` walks through.
+struct TransactionHistory<'a> {
+    /// The full node whose transaction index finds the transactions touching an object. Without
+    /// one, `transactions` holds all the transactions executed through the gateway.
+    fullnode: Option<&'a SuiClient>,
+    /// The transactions read so far, by sequence number.
+    transactions: BTreeMap<GatewayTxSeqNumber, SuiTransactionResponse>,
+}
+
+impl TransactionHistory<'_> {
+    /// The transactions which created, mutated or deleted `object_id`, in execution order.
+    async fn touching(
+        &mut self,
+        object_id: ObjectID,
+    ) -> anyhow::Result<Vec<&SuiTransactionResponse>> {
+        if let Some(client) = self.fullnode {
+            let index = client.full_node_api();
+            let mut found = index.get_transactions_by_input_object(object_id).await?;
+            found.extend(index.get_transactions_by_mutated_object(object_id).await?);
+            for (seq, digest) in found {
+                if !self.transactions.contains_key(&seq) {
+                    let tx = client.read_api().get_transaction(digest).await?;
+                    self.transactions.insert(seq, tx);
+                }
+            }
+        }
+        Ok(self
+            .transactions
+            .values()
+            .filter(|tx| {
+                let effects = &tx.effects;
+                effects
+                    .created
+                    .iter()
+                    .chain(&effects.mutated)
+                    .any(|o| o.reference.object_id == object_id)
+                    || effects.deleted.iter().any(|o| o.object_id == object_id)
+            })
+            .collect())
+    }
+}
+
+/// What happened to `coin` in `transactions`, which touched it, in execution order. Coins split
+/// off it are left to be traced.
+fn trace_coin(coin: ObjectID, transactions: &[&SuiTransactionResponse]) -> CoinFlow {
+    let mut flow = CoinFlow::new(coin);
+    let mut owner = None;
+    for tx in transactions {
+        let effects = &tx.effects;
+        let digest = effects.transaction_digest;
+        let split_from = split_source(tx);
+        if let Some(created) = effects
+            .created
+            .iter()
+            .find(|o| o.reference.object_id == coin)
+        {
+            flow.created_by = Some(CoinOrigin { digest, split_from });
+            owner = Some(created.owner);
+        } else if effects.deleted.iter().any(|o| o.object_id == coin) {
+            let into = effects
+                .mutated_excluding_gas()
+                .next()
+                .unwrap_or(&effects.gas_object)
+                .reference
+                .object_id;
+            flow.events.push(CoinFlowEvent::Merged { digest, into });
+            break;
+        } else if let Some(mutated) = effects
+            .mutated
+            .iter()
+            .find(|o| o.reference.object_id == coin)
+        {
+            if split_from == Some(coin) {
+                let coins = effects
+                    .created
+                    .iter()
+                    .map(|o| CoinFlow::new(o.reference.object_id))
+                    .collect();
+                flow.events.push(CoinFlowEvent::Split { digest, coins });
+            }
+            if owner.map_or(false, |owner| owner != mutated.owner) {
+                flow.events.push(CoinFlowEvent::Transferred {
+                    digest,
+                    recipient: mutated.owner,
+                });
+            }
+            owner = Some(mutated.owner);
+        }
+    }
+    flow
+}
+
+/// The coin `tx` split new coins off, if it is a split: a transfer of part of a SUI coin, or a
+/// call to one of the split functions of the coin module.
+fn split_source(tx: &SuiTransactionResponse) -> Option<ObjectID> {
+    let effects = &tx.effects;
+    tx.certificate
+        .data
+        .transactions
+        .iter()
+        .find_map(|kind| match kind {
+            SuiTransactionKind::TransferSui(SuiTransferSui {
+                amount: Some(_), ..
+            }) => Some(effects.gas_object.reference.object_id),
+            SuiTransactionKind::Call(call)
+                if call.package.object_id == SUI_FRAMEWORK_OBJECT_ID
+                    && call.module == coin::COIN_MODULE_NAME.as_str()
+                    && (call.function == coin::COIN_SPLIT_VEC_FUNC_NAME.as_str()
+                        || call.function == coin::COIN_SPLIT_N_FUNC_NAME.as_str()) =>
+            {
+                effects
+                    .mutated_excluding_gas()
+                    .next()
+                    .map(|o| o.reference.object_id)
+            }
+            _ => None,
+        })
+}
+
+/// Replace the coins split off in the traced `flows` by their own traced flows, recursively.
+fn assemble_coin_flow(coin: ObjectID, flows: &mut HashMap<ObjectID, CoinFlow>) -> CoinFlow {
+    // Removing the flow from the map ensures each coin is only expanded once.
+    let mut flow = flows.remove(&coin).unwrap_or_else(|| CoinFlow::new(coin));
+    for event in &mut flow.events {
+        if let CoinFlowEvent::Split { coins, .. } = event {
+            for split in coins.iter_mut() {
+                *split = assemble_coin_flow(split.coin, flows);
+            }
+        }
+    }
+    flow
+}
+
+fn write_coin_flow(writer: &mut String, flow: &CoinFlow, depth: usize) -> fmt::Result {
+    let indent = "  ".repeat(depth);
+    write!(writer, "{indent}Coin {}", flow.coin)?;
+    match &flow.created_by {
+        Some(CoinOrigin {
+            digest,
+            split_from: Some(source),
+        }) => writeln!(writer, " (split from {source} in {digest:?})")?,
+        Some(CoinOrigin {
+            digest,
+            split_from: None,
+        }) => writeln!(writer, " (created in {digest:?})")?,
+        None => writeln!(writer)?,
+    }
+    for event in &flow.events {
+        match event {
+            CoinFlowEvent::Split { digest, coins } => {
+                writeln!(writer, "{indent}  Split in {digest:?} into:")?;
+                for coin in coins {
+                    write_coin_flow(writer, coin, depth + 2)?;
+                }
+            }
+            CoinFlowEvent::Transferred { digest, recipient } => {
+                writeln!(writer, "{indent}  Transferred to {recipient} in {digest:?}")?
+            }
+            CoinFlowEvent::Merged { digest, into } => {
+                writeln!(writer, "{indent}  Merged into {into} in {digest:?}")?
+            }
+        }
+    }
+    Ok(())
 }
 
 fn unwrap_or<'a>(val: &'a Option<String>, default: &'a str) -> &'a str {
@@ -895,19 +3184,164 @@ fn write_cert_and_effects(
     Ok(writer)
 }
 
+/// Formats the result as JSON, on a single line with `{:?}` and pretty printed with `{:#?}`.
 impl Debug for SuiClientCommandResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let pretty = f.alternate();
         let s = unwrap_err_to_string(|| match self {
-            SuiClientCommandResult::Object(object_read) => {
-                let object = object_read.object()?;
-                Ok(serde_json::to_string_pretty(&object)?)
-            }
-            _ => Ok(serde_json::to_string_pretty(self)?),
+            SuiClientCommandResult::Object(object_read) => to_json(object_read.object()?, pretty),
+            SuiClientCommandResult::Events(events) => to_json(events, pretty),
+            _ => to_json(self, pretty),
         });
         write!(f, "{}", s)
     }
 }
 
+/// The type of the event, its sender if it has one, and its other fields, one per line.
+fn describe_event(event: &SuiEvent) -> (String, Option<SuiAddress>, String) {
+    match event {
+        SuiEvent::MoveEvent {
+            sender,
+            type_,
+            fields,
+            ..
+        } => (
+            type_.clone(),
+            Some(*sender),
+            fields
+                .as_ref()
+                .map(|fields| fields.to_string())
+                .unwrap_or_default(),
+        ),
+        SuiEvent::Publish { sender, package_id } => (
+            "Publish".to_string(),
+            Some(*sender),
+            format!("package: {package_id}"),
+        ),
+        SuiEvent::TransferObject {
+            sender,
+            recipient,
+            object_id,
+            version,
+            type_,
+            amount,
+            ..
+        } => {
+            let mut details =
+                format!("object: {object_id}\nversion: {version:?}\nrecipient: {recipient}\ntransfer type: {type_:?}");
+            if let Some(amount) = amount {
+                details.push_str(&format!("\namount: {amount}"));
+            }
+            ("TransferObject".to_string(), Some(*sender), details)
+        }
+        SuiEvent::DeleteObject {
+            sender, object_id, ..
+        } => (
+            "DeleteObject".to_string(),
+            Some(*sender),
+            format!("object: {object_id}"),
+        ),
+        SuiEvent::NewObject {
+            sender,
+            recipient,
+            object_id,
+            ..
+        } => (
+            "NewObject".to_string(),
+            Some(*sender),
+            format!("object: {object_id}\nrecipient: {recipient}"),
+        ),
+        SuiEvent::EpochChange(epoch) => {
+            ("EpochChange".to_string(), None, format!("epoch: {epoch}"))
+        }
+        SuiEvent::Checkpoint(checkpoint) => (
+            "Checkpoint".to_string(),
+            None,
+            format!("checkpoint: {checkpoint}"),
+        ),
+    }
+}
+
+/// Serialize gas coins along with their total value, as `{"coins": [..], "total_balance": ..}`.
+fn serialize_gas_coins<S: Serializer>(coins: &[GasCoin], serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct GasCoins<'a> {
+        coins: &'a [GasCoin],
+        total_balance: u64,
+    }
+    GasCoins {
+        coins,
+        total_balance: coins.iter().map(|coin| coin.value()).sum(),
+    }
+    .serialize(serializer)
+}
+
+/// Serialize managed addresses as a list of addresses, as before they had names. The names are
+/// only shown in the human readable output.
+fn serialize_managed_addresses<S: Serializer>(
+    addresses: &[ManagedAddress],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(addresses.iter().map(|managed| managed.address))
+}
+
+/// The coin held by the object, of any currency.
+fn coin_from_object(object: &SuiParsedObject) -> Result<Coin, anyhow::Error> {
+    let id = object.id();
+    match &object.data {
+        SuiParsedData::MoveObject(o) => Coin::try_from(&o.fields)
+            .map_err(|_| anyhow!("Object {id} of type {} is not a coin", o.type_)),
+        SuiParsedData::Package(_) => Err(anyhow!("Object {id} is a package, not a coin")),
+    }
+}
+
+/// Serialize coins along with their total balance, as
+/// `{"coin_type": .., "coins": [..], "total_balance": ..}`.
+fn serialize_coin_balances<S: Serializer>(
+    balances: &CoinBalances,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Coins<'a> {
+        coin_type: &'a str,
+        coins: &'a [Coin],
+        total_balance: u128,
+    }
+    Coins {
+        coin_type: &balances.coin_type,
+        coins: &balances.coins,
+        total_balance: balances.total_balance(),
+    }
+    .serialize(serializer)
+}
+
+/// Serialize a page of objects along with the cursor of the next page, as
+/// `{"objects": [..], "next_cursor": ..}`.
+fn serialize_objects_page<S: Serializer>(
+    objects: &[SuiObjectInfo],
+    next_cursor: &Option<ObjectID>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct ObjectsPage<'a> {
+        objects: &'a [SuiObjectInfo],
+        next_cursor: &'a Option<ObjectID>,
+    }
+    ObjectsPage {
+        objects,
+        next_cursor,
+    }
+    .serialize(serializer)
+}
+
+fn to_json<T: Serialize>(value: &T, pretty: bool) -> Result<String, anyhow::Error> {
+    Ok(if pretty {
+        serde_json::to_string_pretty(value)?
+    } else {
+        serde_json::to_string(value)?
+    })
+}
+
 fn unwrap_err_to_string<T: Display, F: FnOnce() -> Result<T, anyhow::Error>>(func: F) -> String {
     match func() {
         Ok(s) => format!("{s}"),
@@ -915,12 +3349,35 @@ fn unwrap_err_to_string<T: Display, F: FnOnce() -> Result<T, anyhow::Error>>(fun
     }
 }
 
-impl SuiClientCommandResult {
-    pub fn print(&self, pretty: bool) {
-        let line = if pretty {
-            format!("{self}")
+/// How the result of a client command is printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable text.
+    Human,
+    /// Pretty printed JSON.
+    Json,
+    /// JSON on a single line, e.g. for log pipelines.
+    JsonCompact,
+}
+
+impl OutputFormat {
+    pub fn new(json: bool, json_compact: bool) -> Self {
+        if json_compact {
+            OutputFormat::JsonCompact
+        } else if json {
+            OutputFormat::Json
         } else {
-            format!("{:?}", self)
+            OutputFormat::Human
+        }
+    }
+}
+
+impl SuiClientCommandResult {
+    pub fn print(&self, format: OutputFormat) {
+        let line = match format {
+            OutputFormat::Human => format!("{self}"),
+            OutputFormat::Json => format!("{self:#?}"),
+            OutputFormat::JsonCompact => format!("{self:?}"),
         };
         // Log line by line
         for line in line.lines() {
@@ -929,6 +3386,44 @@ impl SuiClientCommandResult {
             info!("{line}")
         }
     }
+
+    /// Write the result to `path` as pretty printed JSON, replacing the file if it exists. Like
+    /// the other serialized outputs, it leaves out the elapsed time of transfers.
+    pub fn write_json(&self, path: &Path) -> Result<(), anyhow::Error> {
+        let file = fs::File::create(path)
+            .map_err(|e| anyhow!("Cannot create the output file {path:?}: {e}"))?;
+        let mut writer = io::BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// The digests of the transactions this command executed, in order.
+    pub fn transaction_digests(&self) -> Vec<TransactionDigest> {
+        match self {
+            SuiClientCommandResult::Call(cert, _)
+            | SuiClientCommandResult::Transfer(_, cert, _)
+            | SuiClientCommandResult::TransferObjects(cert, _)
+            | SuiClientCommandResult::TransferSui(_, cert, _) => vec![cert.transaction_digest],
+            SuiClientCommandResult::Publish(response)
+            | SuiClientCommandResult::SplitCoin(response)
+            | SuiClientCommandResult::MergeCoin(response)
+            | SuiClientCommandResult::MultisigExecute(response)
+            | SuiClientCommandResult::ExecuteSigned(response)
+            | SuiClientCommandResult::SignAndSubmit(response) => {
+                vec![response.certificate.transaction_digest]
+            }
+            SuiClientCommandResult::CreateExampleNFT(digest, _) => vec![*digest],
+            SuiClientCommandResult::MergeAllCoins(result) => result.digests.clone(),
+            // Lines which could not be executed have no effects.
+            SuiClientCommandResult::BatchSubmit(results) => results
+                .iter()
+                .filter(|result| result.status.is_some())
+                .filter_map(|result| result.digest)
+                .collect(),
+            _ => vec![],
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -943,17 +3438,269 @@ pub enum SuiClientCommandResult {
         SuiCertifiedTransaction,
         SuiTransactionEffects,
     ),
-    TransferSui(SuiCertifiedTransaction, SuiTransactionEffects),
-    Addresses(Vec<SuiAddress>),
-    Objects(Vec<SuiObjectInfo>),
-    SyncClientState,
+    TransferObjects(SuiCertifiedTransaction, SuiTransactionEffects),
+    TransferSui(
+        // Skipping serialisation for elapsed time.
+        #[serde(skip)] u128,
+        SuiCertifiedTransaction,
+        SuiTransactionEffects,
+    ),
+    #[serde(serialize_with = "serialize_managed_addresses")]
+    Addresses(Vec<ManagedAddress>),
+    #[serde(serialize_with = "serialize_objects_page")]
+    Objects(Vec<SuiObjectInfo>, Option<ObjectID>),
+    SyncClientState(SyncProgress),
     NewAddress((SuiAddress, String, SignatureScheme)),
-    Gas(Vec<GasCoin>),
+    MultisigAddress(SuiAddress),
+    ProveObjectOwnership(String),
+    /// The verified statement, and whether its owner still owns the object if that was checked.
+    VerifyObjectOwnership(ObjectOwnershipStatement, Option<bool>),
+    MultisigExecute(SuiTransactionResponse),
+    BatchSubmit(Vec<BatchSubmitResult>),
+    BatchEstimate(BatchEstimate),
+    SignAndSubmit(SuiTransactionResponse),
+    ExecuteSigned(SuiTransactionResponse),
+    Gas(#[serde(serialize_with = "serialize_gas_coins")] Vec<GasCoin>),
+    Balance(AddressBalance),
+    #[serde(serialize_with = "serialize_coin_balances")]
+    Coins(CoinBalances),
+    GasRefundEstimate(Vec<(GasCoin, u64)>),
     SplitCoin(SuiTransactionResponse),
     MergeCoin(SuiTransactionResponse),
+    MergeAllCoins(MergeAllCoinsResult),
     Switch(SwitchResponse),
     ActiveAddress(Option<SuiAddress>),
-    CreateExampleNFT(GetObjectDataResponse),
+    AddressBook(AddressBook),
+    Profiles(Vec<ProfileSummary>),
+    SystemState(SystemState),
+    ReplayFromEffects(TransactionDigest),
+    Events(Vec<SuiEvent>),
+    CoinFlow(CoinFlow),
+    History(SuiAddress, Vec<(GatewayTxSeqNumber, TransactionDigest)>),
+    Serve(SocketAddr),
+    AuthorityResponse(TransactionInfoResponse),
+    CreateExampleNFT(
+        // Skipping serialisation for the transaction digest.
+        #[serde(skip)] TransactionDigest,
+        GetObjectDataResponse,
+    ),
+    DryRun(SuiTransactionEffects),
+    SerializedTransaction(String),
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum AddressBookCommand {
+    /// Name an address. The name replaces any address it was given before
+    Add {
+        #[clap(long)]
+        name: String,
+        #[clap(long)]
+        address: SuiAddress,
+    },
+    /// Forget a named address
+    Remove {
+        #[clap(long)]
+        name: String,
+    },
+    /// List the named addresses
+    List,
+    /// Write the address book to a JSON file, mapping names to addresses, to share it
+    Export {
+        #[clap(long)]
+        path: PathBuf,
+    },
+    /// Add the addresses of a JSON file written by `export`. Imported names replace existing
+    /// ones
+    Import {
+        #[clap(long)]
+        path: PathBuf,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum ProfileCommand {
+    /// Add a profile using a gateway over JSON-RPC. Switch to it with `use`
+    Create {
+        #[clap(long)]
+        name: String,
+        /// The gateway URL of the profile
+        #[clap(long, value_hint = ValueHint::Url)]
+        gateway: String,
+        /// The fullnode URL of the profile
+        #[clap(long, value_hint = ValueHint::Url)]
+        fullnode: Option<String>,
+        /// The keystore holding the profile's keys, created if missing. Defaults to
+        /// `sui.<name>.keystore` in the Sui config directory
+        #[clap(long, parse(from_os_str))]
+        keystore: Option<PathBuf>,
+    },
+    /// Make a profile the active one, keeping the active address of the one switched from
+    Use {
+        #[clap(long)]
+        name: String,
+    },
+    /// List the profiles, marking the active one
+    List,
+}
+
+/// The coins merged by `merge-all-coins`.
+#[derive(Serialize, Clone, Debug)]
+pub struct MergeAllCoinsResult {
+    /// The coin the others were merged into
+    pub primary_coin: ObjectID,
+    pub merged_coins: Vec<ObjectID>,
+    /// The value of the primary coin after the merges
+    pub total_value: u64,
+    /// Digests of the merge transactions, in the order of `merged_coins`
+    pub digests: Vec<TransactionDigest>,
+}
+
+/// An address of the keystore as listed by the `addresses` command, with its names in the
+/// address book.
+#[derive(Serialize, Clone, Debug)]
+pub struct ManagedAddress {
+    pub address: SuiAddress,
+    pub names: Vec<String>,
+}
+
+/// A profile as listed by the `profile` command.
+#[derive(Serialize, Clone, Debug)]
+pub struct ProfileSummary {
+    pub name: String,
+    pub active: bool,
+    pub active_address: Option<SuiAddress>,
+}
+
+/// An address argument, given either as a hex address, as a name from the client's address book,
+/// or as a name registered with the name service of the gateway, e.g. `alice.sui`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AddressOrName {
+    Address(SuiAddress),
+    Name(String),
+}
+
+impl FromStr for AddressOrName {
+    type Err = Infallible;
+
+    /// Hex addresses are parsed first, so that no name can stand for another address. Anything
+    /// else is kept as a name until resolved, see `WalletContext::resolve_address`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match SuiAddress::from_str(s) {
+            Ok(address) => Self::Address(address),
+            Err(_) => Self::Name(s.to_string()),
+        })
+    }
+}
+
+impl From<SuiAddress> for AddressOrName {
+    fn from(address: SuiAddress) -> Self {
+        Self::Address(address)
+    }
+}
+
+/// The total value of the gas objects owned by an address, as shown by `sui client balance`.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct AddressBalance {
+    pub address: SuiAddress,
+    pub balance: u64,
+    pub coin_count: usize,
+}
+
+/// The coins of one currency owned by an address, as listed by `sui client coins`.
+#[derive(Clone, Debug)]
+pub struct CoinBalances {
+    /// The type of the coins, `0x2::coin::Coin<T>` for the currency `T`
+    pub coin_type: String,
+    pub coins: Vec<Coin>,
+}
+
+impl CoinBalances {
+    /// The sum of the coin balances, as `u128`: the supply of a custom coin may use all of `u64`.
+    pub fn total_balance(&self) -> u128 {
+        self.coins.iter().map(|coin| u128::from(coin.value())).sum()
+    }
+}
+
+/// The lineage of a coin, as traced by `sui client coin-flow`.
+#[derive(Serialize, Clone, Debug)]
+pub struct CoinFlow {
+    pub coin: ObjectID,
+    /// The transaction which created the coin, if it was found
+    pub created_by: Option<CoinOrigin>,
+    /// What happened to the coin, in execution order
+    pub events: Vec<CoinFlowEvent>,
+}
+
+impl CoinFlow {
+    fn new(coin: ObjectID) -> Self {
+        Self {
+            coin,
+            created_by: None,
+            events: vec![],
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CoinOrigin {
+    pub digest: TransactionDigest,
+    /// The coin this coin was split off, if it was created by a split
+    pub split_from: Option<ObjectID>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub enum CoinFlowEvent {
+    /// New coins were split off the coin
+    Split {
+        digest: TransactionDigest,
+        coins: Vec<CoinFlow>,
+    },
+    /// The coin changed owner
+    Transferred {
+        digest: TransactionDigest,
+        recipient: Owner,
+    },
+    /// The coin was merged into another coin, and deleted
+    Merged {
+        digest: TransactionDigest,
+        into: ObjectID,
+    },
+}
+
+/// The outcome of a line of `sui client batch-submit`.
+#[derive(Serialize, Clone, Debug)]
+pub struct BatchSubmitResult {
+    /// Line of the input the transaction was read from, starting at 1
+    pub line: usize,
+    /// Digest of the transaction, if the line could be parsed
+    pub digest: Option<TransactionDigest>,
+    /// Execution status of the transaction, if it was executed
+    pub status: Option<SuiExecutionStatus>,
+    /// Why the transaction could not be parsed or executed
+    pub error: Option<String>,
+}
+
+/// The gas estimated by `sui client batch-submit --estimate-only`.
+#[derive(Serialize, Clone, Debug)]
+pub struct BatchEstimate {
+    /// Sum of the gas used by the transactions that could be dry run
+    pub total_gas_used: u64,
+    pub transactions: Vec<BatchEstimateResult>,
+}
+
+/// The estimate of a line of `sui client batch-submit --estimate-only`.
+#[derive(Serialize, Clone, Debug)]
+pub struct BatchEstimateResult {
+    /// Line of the input the transaction was read from, starting at 1
+    pub line: usize,
+    /// Gas the transaction would use, if it could be dry run
+    pub gas_used: Option<u64>,
+    /// Execution status of the dry run. A failing transaction is charged gas all the same
+    pub status: Option<SuiExecutionStatus>,
+    /// Why the transaction could not be parsed or dry run
+    pub error: Option<String>,
 }
 
 #[derive(Serialize, Clone, Debug)]
@@ -2,8 +2,10 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter, Write};
 use sui_sdk::crypto::KeystoreType;
 use sui_types::base_types::*;
@@ -14,6 +16,15 @@ pub use sui_config::PersistedConfig;
 pub use sui_config::utils;
 use sui_sdk::ClientType;
 
+/// Default cap on the gas budget of transactions submitted by the client.
+pub const DEFAULT_MAX_GAS_BUDGET: u64 = 1_000_000;
+
+/// Name of the profile of a client config created without profiles.
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
+/// Default margin added to the gas used by a dry run when estimating a gas budget.
+pub const DEFAULT_GAS_BUDGET_MARGIN_PERCENT: u64 = 20;
+
 #[serde_as]
 #[derive(Serialize, Deserialize)]
 pub struct SuiClientConfig {
@@ -22,6 +33,78 @@ pub struct SuiClientConfig {
     pub active_address: Option<SuiAddress>,
     // Temporarily make this optional, until we fully deprecate gateway
     pub fullnode: Option<ClientType>,
+    /// Transactions with a larger gas budget are rejected by the client before submission
+    #[serde(default = "default_max_gas_budget")]
+    pub max_gas_budget: u64,
+    /// Percentage added to the gas used by a dry run when a command estimates its gas budget
+    #[serde(default = "default_gas_budget_margin_percent")]
+    pub gas_budget_margin_percent: u64,
+    /// Names of external addresses, usable wherever a recipient address is expected
+    #[serde(default, skip_serializing_if = "AddressBook::is_empty")]
+    pub address_book: AddressBook,
+    /// Name of the profile the keystore, gateway, active address and fullnode above belong to
+    #[serde(default = "default_profile_name")]
+    pub active_profile: String,
+    /// The profiles that can be switched to, by name, other than the active one
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, ClientProfile>,
+}
+
+/// An environment the client can switch to, e.g. a local network or devnet, with its own keys,
+/// gateway and active address.
+#[derive(Serialize, Deserialize)]
+pub struct ClientProfile {
+    pub keystore: KeystoreType,
+    pub gateway: ClientType,
+    pub active_address: Option<SuiAddress>,
+    pub fullnode: Option<ClientType>,
+}
+
+/// Recipient addresses by name. Unlike the keystore, it holds addresses the client has no key for.
+pub type AddressBook = BTreeMap<String, SuiAddress>;
+
+fn default_max_gas_budget() -> u64 {
+    DEFAULT_MAX_GAS_BUDGET
+}
+
+fn default_gas_budget_margin_percent() -> u64 {
+    DEFAULT_GAS_BUDGET_MARGIN_PERCENT
+}
+
+fn default_profile_name() -> String {
+    DEFAULT_PROFILE_NAME.to_string()
+}
+
+impl SuiClientConfig {
+    /// Add a profile to switch to later. Names must be unique, including the active profile's.
+    pub fn add_profile(&mut self, name: String, profile: ClientProfile) -> anyhow::Result<()> {
+        if name == self.active_profile || self.profiles.contains_key(&name) {
+            return Err(anyhow!("Profile {name} already exists"));
+        }
+        self.profiles.insert(name, profile);
+        Ok(())
+    }
+
+    /// Make the profile `name` the active one. The previously active profile is kept under its
+    /// name, with its active address as it was.
+    pub fn use_profile(&mut self, name: &str) -> anyhow::Result<()> {
+        if name == self.active_profile {
+            return Ok(());
+        }
+        let profile = self
+            .profiles
+            .remove(name)
+            .ok_or_else(|| anyhow!("No profile named {name}"))?;
+        let previous = ClientProfile {
+            keystore: std::mem::replace(&mut self.keystore, profile.keystore),
+            gateway: std::mem::replace(&mut self.gateway, profile.gateway),
+            active_address: std::mem::replace(&mut self.active_address, profile.active_address),
+            fullnode: std::mem::replace(&mut self.fullnode, profile.fullnode),
+        };
+        let previous_name = std::mem::replace(&mut self.active_profile, name.to_string());
+        self.profiles.insert(previous_name, previous);
+        Ok(())
+    }
 }
 
 impl Config for SuiClientConfig {}
@@ -30,6 +113,7 @@ impl Display for SuiClientConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut writer = String::new();
 
+        writeln!(writer, "Active profile: {}", self.active_profile)?;
         writeln!(
             writer,
             "Managed addresses : {}",
@@ -46,6 +130,13 @@ impl Display for SuiClientConfig {
         if let Some(fullnode_type) = &self.fullnode {
             write!(writer, "{}", fullnode_type)?;
         }
+        writeln!(writer, "Max gas budget: {}", self.max_gas_budget)?;
+        writeln!(
+            writer,
+            "Gas budget margin: {}%",
+            self.gas_budget_margin_percent
+        )?;
+        writeln!(writer, "Address book entries: {}", self.address_book.len())?;
 
         write!(f, "{}", writer)
     }
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::client_commands::SwitchResponse;
-use crate::client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext};
+use crate::client_commands::{
+    OutputFormat, SuiClientCommandResult, SuiClientCommands, WalletContext,
+};
 use crate::shell::{
     install_shell_plugins, AsyncHandler, CacheKey, CommandStructure, CompletionCache, Shell,
 };
@@ -28,6 +30,9 @@ pub struct ConsoleOpts {
     /// Returns command outputs in JSON format.
     #[clap(long, global = true)]
     pub json: bool,
+    /// Returns command outputs in JSON format, with each output on a single line.
+    #[clap(long, global = true, conflicts_with = "json")]
+    pub json_compact: bool,
 }
 
 pub async fn start_console(
@@ -100,12 +105,15 @@ async fn handle_command(
             SuiClientCommandResult::Addresses(ref addresses) => {
                 let addresses = addresses
                     .iter()
-                    .map(|addr| format!("{addr}"))
+                    .flat_map(|managed| {
+                        std::iter::once(format!("{}", managed.address))
+                            .chain(managed.names.iter().cloned())
+                    })
                     .collect::<Vec<_>>();
                 cache.insert(CacheKey::flag("--address"), addresses.clone());
                 cache.insert(CacheKey::flag("--to"), addresses);
             }
-            SuiClientCommandResult::Objects(ref objects) => {
+            SuiClientCommandResult::Objects(ref objects, _) => {
                 let objects = objects
                     .iter()
                     .map(|oref| format!("{}", oref.object_id))
@@ -117,7 +125,10 @@ async fn handle_command(
             _ => {}
         }
     }
-    result.print(!wallet_opts.json);
+    result.print(OutputFormat::new(
+        wallet_opts.json,
+        wallet_opts.json_compact,
+    ));
 
     // Quit shell after gateway switch
     if matches!(
@@ -6,13 +6,15 @@ use std::path::{Path, PathBuf};
 
 use anyhow::anyhow;
 use clap::*;
+use serde::Serialize;
 use tracing::info;
 
 use sui_sdk::crypto::SuiKeystore;
 use sui_types::base_types::SuiAddress;
 use sui_types::base_types::{decode_bytes_hex, encode_bytes_hex};
 use sui_types::crypto::{
-    random_key_pair_by_type, AuthorityKeyPair, EncodeDecodeBase64, SignatureScheme, SuiKeyPair,
+    random_key_pair_by_type, AuthorityKeyPair, EncodeDecodeBase64, PublicKey, SignatureScheme,
+    SuiKeyPair, SuiSignature,
 };
 use sui_types::sui_serde::{Base64, Encoding};
 
@@ -36,13 +38,37 @@ pub enum KeyToolCommand {
         keypair: SuiKeyPair,
     },
     /// List all keys by its address, public key, key scheme in the keystore
-    List,
+    List {
+        /// Print the keys as a JSON array to stdout instead of a table
+        #[clap(long)]
+        json: bool,
+    },
+    /// Derive the Sui address of a Base64 encoded public key with its scheme flag.
+    Address {
+        #[clap(parse(try_from_str = parse_public_key))]
+        public_key: PublicKey,
+        /// Print the address and public key as JSON to stdout
+        #[clap(long)]
+        json: bool,
+    },
+    /// Export the keypair of an address in the keystore, Base64 encoded with its scheme flag as
+    /// stored in the keystore file.
+    Export {
+        #[clap(long, parse(try_from_str = decode_bytes_hex))]
+        address: SuiAddress,
+        /// Print the address and keypair as JSON to stdout
+        #[clap(long)]
+        json: bool,
+    },
     /// Create signature using the sui keystore and provided data.
     Sign {
         #[clap(long, parse(try_from_str = decode_bytes_hex))]
         address: SuiAddress,
         #[clap(long)]
         data: String,
+        /// Print the address, public key and signature as JSON to stdout instead of logging them
+        #[clap(long)]
+        json: bool,
     },
     /// Import mnemonic phrase and generate keypair based on key scheme flag {ed25519 | secp256k1}.
     Import {
@@ -89,7 +115,16 @@ impl KeyToolCommand {
             KeyToolCommand::Unpack { keypair } => {
                 store_and_print_keypair((&keypair.public()).into(), keypair)
             }
-            KeyToolCommand::List => {
+            KeyToolCommand::List { json } => {
+                if json {
+                    let keys = keystore
+                        .keys()
+                        .iter()
+                        .map(KeyOutput::from)
+                        .collect::<Vec<_>>();
+                    println!("{}", serde_json::to_string_pretty(&keys)?);
+                    return Ok(());
+                }
                 println!(
                     " {0: ^42} | {1: ^45} | {2: ^1}",
                     "Sui Address", "Public Key (Base64)", "Flag"
@@ -104,26 +139,41 @@ impl KeyToolCommand {
                     );
                 }
             }
-            KeyToolCommand::Sign { address, data } => {
+            KeyToolCommand::Address { public_key, json } => {
+                let output = KeyOutput::from(&public_key);
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&output)?);
+                } else {
+                    println!("{}", output.address);
+                }
+            }
+            KeyToolCommand::Export { address, json } => {
+                let output = export_keypair(keystore, address)?;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&output)?);
+                } else {
+                    println!("{}", output.keypair_base64);
+                }
+            }
+            KeyToolCommand::Sign {
+                address,
+                data,
+                json,
+            } => {
                 info!("Data to sign : {}", data);
                 info!("Address : {}", address);
-                let message = Base64::decode(&data).map_err(|e| anyhow!(e))?;
-                let signature = keystore.sign(&address, &message)?;
-                // Separate pub key and signature string, signature and pub key are concatenated with an '@' symbol.
-                let signature_string = format!("{:?}", signature);
-                let sig_split = signature_string.split('@').collect::<Vec<_>>();
-                let flag = sig_split
-                    .first()
-                    .ok_or_else(|| anyhow!("Error creating signature."))?;
-                let signature = sig_split
-                    .get(1)
-                    .ok_or_else(|| anyhow!("Error creating signature."))?;
-                let pub_key = sig_split
-                    .last()
-                    .ok_or_else(|| anyhow!("Error creating signature."))?;
-                info!("Flag Base64: {}", flag);
-                info!("Public Key Base64: {}", pub_key);
-                info!("Signature : {}", signature);
+                let output = sign_data(keystore, address, &data)?;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&output)?);
+                } else {
+                    info!("Flag Base64: {}", output.flag_base64);
+                    info!("Public Key Base64: {}", output.pub_key_base64);
+                    info!("Signature : {}", output.signature_base64);
+                    info!(
+                        "Serialized signature (Base64) : {}",
+                        output.serialized_signature_base64
+                    );
+                }
             }
             KeyToolCommand::Import {
                 mnemonic_phrase,
@@ -155,6 +205,80 @@ impl KeyToolCommand {
     }
 }
 
+/// A public key and the address it controls, as printed by `sui keytool list` and
+/// `sui keytool address` with `--json`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyOutput {
+    pub address: SuiAddress,
+    /// The public key prefixed with its scheme flag, as accepted by `sui keytool address`.
+    pub public_key_base64: String,
+    pub flag: u8,
+}
+
+impl From<&PublicKey> for KeyOutput {
+    fn from(public_key: &PublicKey) -> Self {
+        Self {
+            address: public_key.into(),
+            public_key_base64: public_key.encode_base64(),
+            flag: public_key.flag(),
+        }
+    }
+}
+
+/// A keypair exported by `sui keytool export`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedKeypair {
+    pub address: SuiAddress,
+    /// The keypair prefixed with its scheme flag, as stored in the keystore file.
+    pub keypair_base64: String,
+}
+
+fn export_keypair(
+    keystore: &SuiKeystore,
+    address: SuiAddress,
+) -> Result<ExportedKeypair, anyhow::Error> {
+    Ok(ExportedKeypair {
+        address,
+        keypair_base64: keystore.get_key(&address)?.encode_base64(),
+    })
+}
+
+fn parse_public_key(s: &str) -> Result<PublicKey, anyhow::Error> {
+    PublicKey::decode_base64(s).map_err(|e| anyhow!("Invalid public key {s}: {e}"))
+}
+
+/// The signing address and the Base64 encoded components of a signature made by
+/// `sui keytool sign`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureOutput {
+    pub address: SuiAddress,
+    pub flag_base64: String,
+    pub pub_key_base64: String,
+    pub signature_base64: String,
+    /// Flag, signature and public key concatenated, as expected by `sui client batch-submit` and
+    /// `sui client execute-signed`.
+    pub serialized_signature_base64: String,
+}
+
+fn sign_data(
+    keystore: &SuiKeystore,
+    address: SuiAddress,
+    data: &str,
+) -> Result<SignatureOutput, anyhow::Error> {
+    let message = Base64::decode(data)?;
+    let signature = keystore.sign(&address, &message)?;
+    Ok(SignatureOutput {
+        address,
+        flag_base64: Base64::encode([signature.scheme().flag()]),
+        pub_key_base64: Base64::encode(signature.public_key_bytes()),
+        signature_base64: Base64::encode(signature.signature_bytes()),
+        serialized_signature_base64: Base64::encode(&signature),
+    })
+}
+
 fn store_and_print_keypair(address: SuiAddress, keypair: SuiKeyPair) {
     let path_str = format!("{}.key", address).to_lowercase();
     let path = Path::new(&path_str);
@@ -1,30 +1,53 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::client_commands::{SuiClientCommands, WalletContext};
-use crate::config::SuiClientConfig;
+use crate::client_commands::{
+    OutputFormat, SuiClientCommands, WalletContext, CHECKPOINT_WAIT_TIMEOUT,
+};
+use crate::config::{
+    SuiClientConfig, DEFAULT_GAS_BUDGET_MARGIN_PERCENT, DEFAULT_MAX_GAS_BUDGET,
+    DEFAULT_PROFILE_NAME,
+};
 use crate::console::start_console;
 use crate::genesis_ceremony::{run, Ceremony};
 use crate::keytool::KeyToolCommand;
 use crate::sui_move::{self, execute_move_command};
 use anyhow::{anyhow, bail};
 use clap::*;
+use futures::future::join_all;
 use move_package::BuildConfig;
 use std::io::{stderr, stdout, Write};
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use std::{fs, io};
 use sui_config::gateway::GatewayConfig;
 use sui_config::{builder::ConfigBuilder, NetworkConfig, SUI_DEV_NET_URL, SUI_KEYSTORE_FILENAME};
-use sui_config::{genesis_config::GenesisConfig, SUI_GENESIS_FILENAME};
+use sui_config::{
+    genesis,
+    genesis_config::{AccountConfig, GenesisConfig},
+    AUTHORITIES_DB_NAME, CONSENSUS_DB_NAME, FULL_NODE_DB_PATH, SUI_GENESIS_FILENAME,
+};
 use sui_config::{
     sui_config_dir, Config, PersistedConfig, SUI_CLIENT_CONFIG, SUI_FULLNODE_CONFIG,
     SUI_GATEWAY_CONFIG, SUI_NETWORK_CONFIG,
 };
+use sui_core::authority_client::{
+    AuthorityAPI, NetworkAuthorityClient, NetworkAuthorityClientMetrics,
+};
+use sui_json_rpc::sui_rpc_spec;
 use sui_sdk::crypto::KeystoreType;
 use sui_sdk::ClientType;
 use sui_swarm::memory::Swarm;
-use sui_types::crypto::{KeypairTraits, SignatureScheme, SuiKeyPair};
+use sui_types::base_types::SuiAddress;
+use sui_types::committee::{EpochId, StakeUnit};
+use sui_types::crypto::{
+    generate_proof_of_possession, get_key_pair, AccountKeyPair, AuthorityPublicKeyBytes,
+    KeypairTraits, SignatureScheme, SuiKeyPair,
+};
+use sui_types::messages::{AccountInfoRequest, NodeStatsRequest};
+use sui_types::object::{Object, Owner};
 use tracing::info;
 
 #[allow(clippy::large_enum_variant)]
@@ -42,6 +65,16 @@ pub enum SuiCommand {
     Start {
         #[clap(long = "network.config")]
         config: Option<PathBuf>,
+        /// Public keys of the authorities to start, as listed by `sui network --dump-addresses`.
+        /// All the authorities of the network config are started if unspecified.
+        #[clap(
+            long,
+            multiple_occurrences = false,
+            multiple_values = true,
+            use_value_delimiter = true,
+            parse(try_from_str = parse_authority_key)
+        )]
+        authorities: Vec<AuthorityPublicKeyBytes>,
     },
     #[clap(name = "network")]
     Network {
@@ -49,6 +82,14 @@ pub enum SuiCommand {
         config: Option<PathBuf>,
         #[clap(short, long, help = "Dump the public keys of all authorities")]
         dump_addresses: bool,
+        /// Check that every authority is reachable, reporting the round-trip latency of a
+        /// lightweight request to each, and whether the reachable ones hold a quorum of stake.
+        #[clap(long)]
+        health: bool,
+        /// Print the recent activity of every authority: its certificates per second over the
+        /// last minute, the certificates it executed and its batch watermark.
+        #[clap(long)]
+        stats: bool,
     },
     /// Bootstrap and initialize a new sui network
     #[clap(name = "genesis")]
@@ -64,6 +105,17 @@ pub enum SuiCommand {
         working_dir: Option<PathBuf>,
         #[clap(short, long, help = "Forces overwriting existing configuration")]
         force: bool,
+        /// Add a funded account to the genesis of the existing configuration, keeping the keys
+        /// and accounts it holds. Without an address, a new key is generated into the client
+        /// keystore; an address without a key in the keystore is added to the address book.
+        /// The network restarts from the new genesis, so existing network databases are only
+        /// removed with --force/-f.
+        #[clap(
+            long,
+            min_values = 0,
+            conflicts_with_all = &["from-config", "write-config"]
+        )]
+        append_account: Option<Option<SuiAddress>>,
     },
     GenesisCeremony(Ceremony),
     /// Sui keystore tool.
@@ -93,6 +145,33 @@ pub enum SuiCommand {
         /// Return command outputs in json format.
         #[clap(long, global = true)]
         json: bool,
+        /// Return command outputs in json format, with each output on a single line.
+        #[clap(long, global = true, conflicts_with = "json")]
+        json_compact: bool,
+        /// Print the base64 encoded BCS bytes of the transaction effects instead of the
+        /// command output, for commands executing transactions, one line per transaction.
+        #[clap(long, global = true)]
+        raw_effects: bool,
+        /// For commands executing a transaction, wait for the transaction to be included in a
+        /// checkpoint before returning, and report the checkpoint.
+        #[clap(long, global = true)]
+        wait_for_checkpoint: bool,
+        /// For commands submitting a single transaction, execute it locally on the gateway and
+        /// print its effects instead of signing and submitting it. Other commands are rejected.
+        #[clap(long, global = true)]
+        dry_run: bool,
+        /// For commands building a single transaction, print the base64 encoded transaction
+        /// bytes without signing or submitting them, to sign them elsewhere and submit them with
+        /// `execute-signed`. Other commands are rejected.
+        #[clap(long, global = true, conflicts_with = "dry-run")]
+        serialize_transaction: bool,
+        /// For commands building a single transaction, the last epoch in which authorities
+        /// accept the transaction. It never expires if not set. Other commands are rejected.
+        #[clap(long, global = true)]
+        expiration_epoch: Option<EpochId>,
+        /// Also write the command result as pretty printed JSON to the given file.
+        #[clap(long, global = true)]
+        serialize_output: Option<PathBuf>,
     },
 
     /// Tool to build and test Move applications.
@@ -108,12 +187,24 @@ pub enum SuiCommand {
         #[clap(subcommand)]
         cmd: sui_move::Command,
     },
+
+    /// Write the OpenRPC document describing every JSON-RPC method, with the JSON schema of its
+    /// params and result, e.g. to generate clients from it.
+    #[clap(name = "rpc-schema")]
+    RpcSchema {
+        /// The file to write the document to, printed to the standard output if unspecified.
+        #[clap(long, short)]
+        output: Option<PathBuf>,
+    },
 }
 
 impl SuiCommand {
     pub async fn execute(self) -> Result<(), anyhow::Error> {
         match self {
-            SuiCommand::Start { config } => {
+            SuiCommand::Start {
+                config,
+                authorities,
+            } => {
                 // Load the config of the Sui authority.
                 let network_config_path = config
                     .clone()
@@ -128,6 +219,9 @@ impl SuiCommand {
 
                 let mut swarm =
                     Swarm::builder().from_network_config(sui_config_dir()?, network_config);
+                if !authorities.is_empty() {
+                    swarm.retain_validators(&authorities)?;
+                }
                 swarm.launch().await?;
 
                 let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
@@ -142,6 +236,8 @@ impl SuiCommand {
             SuiCommand::Network {
                 config,
                 dump_addresses,
+                health,
+                stats,
             } => {
                 let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_NETWORK_CONFIG));
                 let config: NetworkConfig = PersistedConfig::read(&config_path).map_err(|err| {
@@ -154,12 +250,21 @@ impl SuiCommand {
                 if dump_addresses {
                     for validator in config.validator_configs() {
                         println!(
-                            "{} - {}",
+                            "{} - {} - {}",
                             validator.network_address(),
-                            validator.sui_address()
+                            validator.sui_address(),
+                            validator.protocol_public_key()
                         );
                     }
                 }
+                let registry = prometheus::Registry::new();
+                let metrics = Arc::new(NetworkAuthorityClientMetrics::new(&registry));
+                if health {
+                    check_network_health(&config, metrics.clone()).await?;
+                }
+                if stats {
+                    print_network_stats(&config, metrics).await?;
+                }
                 Ok(())
             }
             SuiCommand::Genesis {
@@ -167,6 +272,7 @@ impl SuiCommand {
                 force,
                 from_config,
                 write_config,
+                append_account,
             } => {
                 let sui_config_dir = &match working_dir {
                     // if a directory is specified, it must exist (it
@@ -182,6 +288,22 @@ impl SuiCommand {
                     }
                 };
 
+                if let Some(address) = append_account {
+                    return append_genesis_account(sui_config_dir, address, force);
+                }
+
+                let mut genesis_conf = match from_config {
+                    Some(path) => PersistedConfig::read(&path)?,
+                    None => GenesisConfig::for_local_testing(),
+                };
+                // Validate before any existing configuration gets removed. Without explicit
+                // validators, `committee_size` validators are created with equal stakes.
+                let stakes: Vec<_> = match &genesis_conf.validator_genesis_info {
+                    Some(validators) => validators.iter().map(|v| v.stake).collect(),
+                    None => vec![1; genesis_conf.committee_size],
+                };
+                validate_genesis_stake(&stakes)?;
+
                 // if Sui config dir is not empty then either clean it
                 // up (if --force/-f option was specified or report an
                 // error
@@ -221,11 +343,6 @@ impl SuiCommand {
                 let db_folder_path = sui_config_dir.join("client_db");
                 let gateway_db_folder_path = sui_config_dir.join("gateway_client_db");
 
-                let mut genesis_conf = match from_config {
-                    Some(path) => PersistedConfig::read(&path)?,
-                    None => GenesisConfig::for_local_testing(),
-                };
-
                 if let Some(path) = write_config {
                     let persisted = genesis_conf.persisted(&path);
                     persisted.save()?;
@@ -285,6 +402,11 @@ impl SuiCommand {
                     gateway: ClientType::Embedded(wallet_gateway_config),
                     active_address,
                     fullnode: None,
+                    max_gas_budget: DEFAULT_MAX_GAS_BUDGET,
+                    gas_budget_margin_percent: DEFAULT_GAS_BUDGET_MARGIN_PERCENT,
+                    address_book: Default::default(),
+                    active_profile: DEFAULT_PROFILE_NAME.to_string(),
+                    profiles: Default::default(),
                 };
 
                 wallet_config.save(&client_path)?;
@@ -317,13 +439,34 @@ impl SuiCommand {
                 let config = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config).await?;
                 let mut context = WalletContext::new(&config).await?;
+                context.show_progress = true;
                 sync_accounts(&mut context).await?;
                 start_console(context, &mut stdout(), &mut stderr()).await
             }
-            SuiCommand::Client { config, cmd, json } => {
+            SuiCommand::Client {
+                config,
+                cmd,
+                json,
+                json_compact,
+                raw_effects,
+                wait_for_checkpoint,
+                dry_run,
+                serialize_transaction,
+                expiration_epoch,
+                serialize_output,
+            } => {
                 let config = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config).await?;
                 let mut context = WalletContext::new(&config).await?;
+                if wait_for_checkpoint {
+                    context.wait_for_checkpoint = Some(CHECKPOINT_WAIT_TIMEOUT);
+                }
+                context.dry_run = dry_run;
+                context.serialize_transaction = serialize_transaction;
+                context.expiration_epoch = expiration_epoch;
+                // Progress would corrupt the JSON output.
+                context.show_progress =
+                    OutputFormat::new(json, json_compact) == OutputFormat::Human;
 
                 if let Some(cmd) = cmd {
                     // Do not sync if command is a gateway switch, as the current gateway might be unreachable and causes sync to panic.
@@ -336,7 +479,33 @@ impl SuiCommand {
                     ) {
                         sync_accounts(&mut context).await?;
                     }
-                    cmd.execute(&mut context).await?.print(!json);
+                    let result = cmd.execute(&mut context).await?;
+                    if let Some(path) = serialize_output {
+                        result.write_json(&path)?;
+                    }
+                    if raw_effects {
+                        let digests = result.transaction_digests();
+                        if digests.is_empty() {
+                            return Err(anyhow!(
+                                "--raw-effects is only supported by commands executing a transaction"
+                            ));
+                        }
+                        // One line per transaction, in the order they were executed.
+                        for digest in digests {
+                            let raw_effects =
+                                context.gateway.read_api().get_raw_effects(digest).await?;
+                            println!("{}", raw_effects.encoded());
+                        }
+                    } else {
+                        let format = OutputFormat::new(json, json_compact);
+                        result.print(format);
+                        if let Some(checkpoint) = context
+                            .last_checkpoint
+                            .filter(|_| format == OutputFormat::Human)
+                        {
+                            println!("Transaction included in checkpoint {checkpoint}");
+                        }
+                    }
                 } else {
                     // Print help
                     let mut app: Command = SuiCommand::command();
@@ -350,6 +519,19 @@ impl SuiCommand {
                 build_config,
                 cmd,
             } => execute_move_command(package_path, build_config, cmd),
+            SuiCommand::RpcSchema { output } => {
+                let content = serde_json::to_string_pretty(&sui_rpc_spec())?;
+                match output {
+                    Some(path) => {
+                        fs::write(&path, content + "\n").map_err(|err| {
+                            anyhow!(err).context(format!("Cannot write the schema to {:?}", path))
+                        })?;
+                        info!("RPC schema written to {:?}.", path);
+                    }
+                    None => println!("{content}"),
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -358,7 +540,9 @@ impl SuiCommand {
 async fn sync_accounts(context: &mut WalletContext) -> Result<(), anyhow::Error> {
     for address in context.keystore.addresses().clone() {
         SuiClientCommands::SyncClientState {
-            address: Some(address),
+            address: Some(address.into()),
+            timeout: None,
+            from: None,
         }
         .execute(context)
         .await?;
@@ -416,6 +600,11 @@ async fn prompt_if_no_config(wallet_conf_path: &Path) -> Result<(), anyhow::Erro
                 gateway: client,
                 active_address: Some(new_address),
                 fullnode: None,
+                max_gas_budget: DEFAULT_MAX_GAS_BUDGET,
+                gas_budget_margin_percent: DEFAULT_GAS_BUDGET_MARGIN_PERCENT,
+                address_book: Default::default(),
+                active_profile: DEFAULT_PROFILE_NAME.to_string(),
+                profiles: Default::default(),
             }
             .persisted(wallet_conf_path)
             .save()?;
@@ -424,9 +613,281 @@ async fn prompt_if_no_config(wallet_conf_path: &Path) -> Result<(), anyhow::Erro
     Ok(())
 }
 
+/// Check that validators with the given stakes can form a quorum, and that no validator holds a
+/// third of the stake or more, enough to block every quorum on its own, unless it is the only
+/// validator.
+fn validate_genesis_stake(stakes: &[StakeUnit]) -> Result<(), anyhow::Error> {
+    if stakes.is_empty() {
+        bail!("Genesis requires at least one validator");
+    }
+    let total: u128 = stakes.iter().map(|stake| *stake as u128).sum();
+    if total == 0 {
+        bail!("The total stake of validators is zero, no quorum can ever be formed");
+    }
+    // Same as `Committee::validity_threshold`.
+    let validity_threshold = (total + 2) / 3;
+    if stakes.len() > 1 {
+        for (i, stake) in stakes.iter().enumerate() {
+            if *stake as u128 >= validity_threshold {
+                bail!(
+                    "Validator {i} has a stake of {stake} out of {total}, at least a third \
+                    ({validity_threshold}), enough to block any quorum on its own"
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rebuild the genesis of the network configured in `sui_config_dir` with a funded account for
+/// `address`, or for a new key added to the client keystore if no address is given. The
+/// validators and the objects of existing accounts are kept as they are.
+///
+/// The network databases hold the state built from the previous genesis and are removed, which
+/// is refused unless `force` is set.
+fn append_genesis_account(
+    sui_config_dir: &Path,
+    address: Option<SuiAddress>,
+    force: bool,
+) -> Result<(), anyhow::Error> {
+    let network_path = sui_config_dir.join(SUI_NETWORK_CONFIG);
+    let genesis_path = sui_config_dir.join(SUI_GENESIS_FILENAME);
+    let client_path = sui_config_dir.join(SUI_CLIENT_CONFIG);
+
+    let databases: Vec<_> = [
+        AUTHORITIES_DB_NAME,
+        CONSENSUS_DB_NAME,
+        FULL_NODE_DB_PATH,
+        "client_db",
+        "gateway_client_db",
+    ]
+    .into_iter()
+    .map(|db| sui_config_dir.join(db))
+    .filter(|path| path.exists())
+    .collect();
+    if !databases.is_empty() && !force {
+        bail!(
+            "Appending an account resets the network, please use --force/-f option to remove \
+            its existing databases {:?}",
+            databases
+        );
+    }
+
+    let mut client_config: SuiClientConfig =
+        PersistedConfig::read(&client_path).map_err(|err| {
+            err.context(format!(
+                "Cannot append an account without an existing client config {:?}",
+                client_path
+            ))
+        })?;
+    let mut keystore = client_config.keystore.init()?;
+    // The key is only added to the keystore once the account is in the genesis.
+    let (address, new_key) = match address {
+        Some(address) => (address, None),
+        None => {
+            let (address, key_pair): (_, AccountKeyPair) = get_key_pair();
+            (address, Some(SuiKeyPair::Ed25519SuiKeyPair(key_pair)))
+        }
+    };
+    let mut network_config: NetworkConfig =
+        PersistedConfig::read(&network_path).map_err(|err| {
+            err.context(format!(
+                "Cannot append an account without an existing network config {:?}",
+                network_path
+            ))
+        })?;
+
+    let genesis = &network_config.genesis;
+    if genesis
+        .objects()
+        .iter()
+        .any(|object| object.owner == Owner::AddressOwner(address))
+        || genesis
+            .validator_set()
+            .iter()
+            .any(|validator| validator.sui_address() == address)
+    {
+        bail!("Account {address} is already present in the genesis");
+    }
+
+    let new_objects = AccountConfig::for_local_testing(address)
+        .gas_objects
+        .into_iter()
+        .map(|gas| Object::with_id_owner_gas_for_testing(gas.object_id, address, gas.gas_value));
+    // Accounts are the only objects owned by an address at genesis, everything else is either a
+    // package or the shared system state, which the builder creates again.
+    let objects = genesis
+        .objects()
+        .iter()
+        .filter(|object| matches!(object.owner, Owner::AddressOwner(_)))
+        .cloned()
+        .chain(new_objects)
+        .collect();
+
+    let mut builder = genesis::Builder::new().add_objects(objects);
+    for validator in genesis.validator_set() {
+        let validator_config = network_config
+            .validator_configs()
+            .iter()
+            .find(|config| config.protocol_public_key() == validator.protocol_key())
+            .ok_or_else(|| anyhow!("No config for validator {}", validator.name()))?;
+        let proof_of_possession = generate_proof_of_possession(
+            validator_config.protocol_key_pair(),
+            (&validator_config.account_key_pair.public()).into(),
+        );
+        builder = builder.add_validator(validator.clone(), proof_of_possession);
+    }
+    let genesis = builder.build();
+
+    // The validators and the full node load the genesis from this file.
+    genesis.save(&genesis_path)?;
+    network_config.genesis = genesis;
+    network_config.save(&network_path)?;
+    info!("Account {address} added to the network genesis.");
+
+    for path in databases {
+        fs::remove_dir_all(&path).map_err(|err| {
+            anyhow!(err).context(format!("Cannot remove the database {:?}", path))
+        })?;
+    }
+
+    if let Some(key) = new_key {
+        keystore.add_key(key)?;
+        info!("Key of account {address} added to the client keystore.");
+    } else if !keystore.addresses().contains(&address)
+        && !client_config
+            .address_book
+            .values()
+            .any(|known| *known == address)
+    {
+        // Without its key the account cannot be used by the client, naming it at least makes it
+        // usable as a recipient.
+        let name = (0..)
+            .map(|i| format!("genesis-account-{i}"))
+            .find(|name| !client_config.address_book.contains_key(name))
+            .unwrap();
+        info!("Account {address} added to the client address book as {name}.");
+        client_config.address_book.insert(name, address);
+    }
+    if client_config.active_address.is_none() {
+        client_config.active_address = Some(address);
+    }
+    client_config.save(&client_path)?;
+    Ok(())
+}
+
+/// Query every authority of the network with a lightweight account info request, printing
+/// whether it is reachable and its round-trip latency, then whether the reachable authorities
+/// hold a quorum of stake. Returns the reachable stake.
+pub async fn check_network_health(
+    config: &NetworkConfig,
+    metrics: Arc<NetworkAuthorityClientMetrics>,
+) -> Result<StakeUnit, anyhow::Error> {
+    let committee = config.committee();
+
+    let checks = network_clients(config, metrics)?
+        .into_iter()
+        .map(|client| async move {
+            let start = Instant::now();
+            client
+                .handle_account_info_request(AccountInfoRequest::from(SuiAddress::default()))
+                .await?;
+            Ok::<_, anyhow::Error>(start.elapsed())
+        });
+    let results = join_all(checks).await;
+
+    let mut reachable_stake = 0;
+    for (validator, result) in config.validator_configs().iter().zip(results) {
+        let name = validator.protocol_public_key();
+        match result {
+            Ok(latency) => {
+                reachable_stake += committee.weight(&name);
+                println!(
+                    "{} - {} - reachable in {} ms",
+                    validator.network_address(),
+                    name,
+                    latency.as_millis()
+                );
+            }
+            Err(err) => println!(
+                "{} - {} - unreachable: {}",
+                validator.network_address(),
+                name,
+                err
+            ),
+        }
+    }
+
+    let quorum_threshold = committee.quorum_threshold();
+    println!(
+        "Reachable stake: {reachable_stake} out of {}, quorum threshold {quorum_threshold}: {}",
+        committee.total_votes,
+        if reachable_stake >= quorum_threshold {
+            "quorum reachable"
+        } else {
+            "quorum NOT reachable"
+        }
+    );
+    Ok(reachable_stake)
+}
+
+/// Print the stats reported by every authority of the network.
+async fn print_network_stats(
+    config: &NetworkConfig,
+    metrics: Arc<NetworkAuthorityClientMetrics>,
+) -> Result<(), anyhow::Error> {
+    let requests = network_clients(config, metrics)?
+        .into_iter()
+        .map(|client| async move { client.handle_node_stats(NodeStatsRequest {}).await });
+    let results = join_all(requests).await;
+
+    for (validator, result) in config.validator_configs().iter().zip(results) {
+        let address = validator.network_address();
+        let name = validator.protocol_public_key();
+        match result {
+            Ok(stats) => println!(
+                "{address} - {name} - {:.2} tps, {} certificates, batch watermark {}",
+                stats.recent_tps,
+                stats.total_certificates,
+                stats
+                    .batch_watermark
+                    .map_or_else(|| "none".to_string(), |watermark| watermark.to_string())
+            ),
+            Err(err) => println!("{address} - {name} - unreachable: {err}"),
+        }
+    }
+    Ok(())
+}
+
+/// Clients to the authorities of the network, in the order of its validator configs.
+fn network_clients(
+    config: &NetworkConfig,
+    metrics: Arc<NetworkAuthorityClientMetrics>,
+) -> Result<Vec<NetworkAuthorityClient>, anyhow::Error> {
+    let mut net_config = mysten_network::config::Config::new();
+    net_config.connect_timeout = Some(Duration::from_secs(5));
+    net_config.request_timeout = Some(Duration::from_secs(5));
+
+    config
+        .validator_configs()
+        .iter()
+        .map(|validator| {
+            let channel = net_config
+                .connect_lazy(validator.network_address())
+                .map_err(|err| anyhow!(err.to_string()))?;
+            Ok(NetworkAuthorityClient::new(channel, metrics.clone()))
+        })
+        .collect()
+}
+
 fn read_line() -> Result<String, anyhow::Error> {
     let mut s = String::new();
     let _ = stdout().flush();
     io::stdin().read_line(&mut s)?;
     Ok(s.trim_end().to_string())
 }
+
+/// Parse an authority public key, with or without the `k#` prefix it is displayed with.
+fn parse_authority_key(s: &str) -> Result<AuthorityPublicKeyBytes, anyhow::Error> {
+    s.strip_prefix("k#").unwrap_or(s).parse()
+}
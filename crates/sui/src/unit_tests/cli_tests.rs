@@ -1,34 +1,73 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{fmt::Write, fs::read_dir, path::PathBuf, str, time::Duration};
+use std::{
+    fmt::Write,
+    fs::read_dir,
+    net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
+    str::{self, FromStr},
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::anyhow;
+use async_trait::async_trait;
 use move_package::BuildConfig;
 use serde_json::json;
 
 use sui::client_commands::SwitchResponse;
 use sui::{
-    client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext},
-    config::SuiClientConfig,
-    sui_commands::SuiCommand,
+    client_commands::{
+        confirm_transaction, start_gateway_server, AddressBookCommand, AddressOrName, CoinBalances,
+        CoinFlowEvent, OutputFormat, ProfileCommand, SuiClientCommandResult, SuiClientCommands,
+        WalletContext,
+    },
+    config::{
+        SuiClientConfig, DEFAULT_GAS_BUDGET_MARGIN_PERCENT, DEFAULT_MAX_GAS_BUDGET,
+        DEFAULT_PROFILE_NAME,
+    },
+    sui_commands::{check_network_health, SuiCommand},
 };
 use sui_config::gateway::GatewayConfig;
-use sui_config::genesis_config::{AccountConfig, GenesisConfig, ObjectConfig};
+use sui_config::genesis_config::{
+    AccountConfig, GenesisConfig, ObjectConfig, ValidatorGenesisInfo,
+};
+use sui_config::utils::{get_available_port, new_network_address};
 use sui_config::{
-    Config, NetworkConfig, PersistedConfig, ValidatorInfo, SUI_CLIENT_CONFIG, SUI_FULLNODE_CONFIG,
-    SUI_GATEWAY_CONFIG, SUI_GENESIS_FILENAME, SUI_KEYSTORE_FILENAME, SUI_NETWORK_CONFIG,
+    Config, NetworkConfig, PersistedConfig, ValidatorInfo, AUTHORITIES_DB_NAME, SUI_CLIENT_CONFIG,
+    SUI_FULLNODE_CONFIG, SUI_GATEWAY_CONFIG, SUI_GENESIS_FILENAME, SUI_KEYSTORE_FILENAME,
+    SUI_NETWORK_CONFIG,
 };
+use sui_core::authority_client::NetworkAuthorityClientMetrics;
+use sui_core::gateway_state::{GatewayAPI, NameResolver};
 use sui_json::SuiJsonValue;
-use sui_json_rpc_types::{GetObjectDataResponse, SuiData, SuiParsedObject, SuiTransactionEffects};
+use sui_json_rpc_types::{
+    GetObjectDataResponse, SuiData, SuiEvent, SuiExecutionStatus, SuiParsedObject,
+    SuiPublishedObject, SuiPublishedObjectKind, SuiTransactionEffects,
+};
 use sui_sdk::crypto::KeystoreType;
 use sui_sdk::ClientType;
+use sui_swarm::memory::Swarm;
+use sui_types::committee::StakeUnit;
 use sui_types::crypto::{
     AccountKeyPair, AuthorityKeyPair, Ed25519SuiSignature, KeypairTraits, Secp256k1SuiSignature,
-    SignatureScheme, SuiKeyPair, SuiSignatureInner,
+    SignableBytes, SignatureScheme, SuiKeyPair, SuiSignatureInner,
+};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress, TransactionDigest},
+    coin::Coin,
+    crypto::get_key_pair,
+    gas_coin::{GasCoin, GAS},
+    id::UID,
+    messages::{ObjectOwnershipProof, TransactionData},
+    object::Owner,
+    sui_serde::{Base64, Encoding},
+};
+use sui_types::{
+    parse_sui_struct_tag, parse_sui_type_tag, sui_framework_address_concat_string,
+    SUI_FRAMEWORK_ADDRESS, SUI_FRAMEWORK_OBJECT_ID,
 };
-use sui_types::{base_types::ObjectID, crypto::get_key_pair, gas_coin::GasCoin};
-use sui_types::{sui_framework_address_concat_string, SUI_FRAMEWORK_ADDRESS};
 use test_utils::network::{setup_network_and_wallet, start_test_network};
 
 const TEST_DATA_DIR: &str = "src/unit_tests/data/";
@@ -42,6 +81,7 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
     // Start network without authorities
     let start = SuiCommand::Start {
         config: Some(config),
+        authorities: vec![],
     }
     .execute()
     .await;
@@ -52,6 +92,7 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
         write_config: None,
         force: false,
         from_config: None,
+        append_account: None,
     }
     .execute()
     .await?;
@@ -94,6 +135,7 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
         write_config: None,
         force: false,
         from_config: None,
+        append_account: None,
     }
     .execute()
     .await;
@@ -103,6 +145,265 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_rpc_schema() -> Result<(), anyhow::Error> {
+    let temp_dir = tempfile::tempdir()?;
+    let output = temp_dir.path().join("openrpc.json");
+
+    SuiCommand::RpcSchema {
+        output: Some(output.clone()),
+    }
+    .execute()
+    .await?;
+
+    let schema: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output)?)?;
+    let methods = schema["methods"].as_array().unwrap();
+    for method in [
+        "sui_getObject",
+        "sui_getRawObject",
+        "sui_executeTransaction",
+    ] {
+        assert!(
+            methods.iter().any(|m| m["name"] == method),
+            "{method} missing"
+        );
+    }
+    let schemas = schema["components"]["schemas"].as_object().unwrap();
+    for type_ in ["ObjectRead", "TransactionBytes", "TransactionEffects"] {
+        assert!(schemas.contains_key(type_), "{type_} missing");
+    }
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_genesis_append_account() -> Result<(), anyhow::Error> {
+    let temp_dir = tempfile::tempdir()?;
+    let working_dir = temp_dir.path();
+    let genesis = |append_account, force| SuiCommand::Genesis {
+        working_dir: Some(working_dir.to_path_buf()),
+        write_config: None,
+        force,
+        from_config: None,
+        append_account,
+    };
+    genesis(None, false).execute().await?;
+    let network_path = working_dir.join(SUI_NETWORK_CONFIG);
+    let before = PersistedConfig::<NetworkConfig>::read(&network_path)?;
+    let keystore = KeystoreType::File(working_dir.join(SUI_KEYSTORE_FILENAME));
+    let addresses = keystore.init()?.addresses();
+
+    // The databases of the network are only removed on explicit request.
+    let db_path = working_dir.join(AUTHORITIES_DB_NAME);
+    std::fs::create_dir(&db_path)?;
+    let address = SuiAddress::random_for_testing_only();
+    let err = genesis(Some(Some(address)), false)
+        .execute()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("--force"));
+    assert!(db_path.exists());
+    assert_eq!(
+        before.genesis,
+        PersistedConfig::<NetworkConfig>::read(&network_path)?.genesis
+    );
+
+    genesis(Some(Some(address)), true).execute().await?;
+    assert!(!db_path.exists());
+    let after = PersistedConfig::<NetworkConfig>::read(&network_path)?;
+    let owned_by = |config: &NetworkConfig, address| {
+        config
+            .genesis
+            .objects()
+            .iter()
+            .filter(|object| object.owner == Owner::AddressOwner(address))
+            .map(|object| object.id())
+            .collect::<Vec<_>>()
+    };
+
+    // The new account is funded, and existing accounts and validators are kept.
+    assert_eq!(5, owned_by(&after, address).len());
+    for existing in &addresses {
+        assert_eq!(owned_by(&before, *existing), owned_by(&after, *existing));
+    }
+    assert_eq!(before.validator_set(), after.validator_set());
+    assert_eq!(
+        after.genesis,
+        sui_config::genesis::Genesis::load(working_dir.join(SUI_GENESIS_FILENAME))?
+    );
+    // Without its key, the account is only known to the client by name.
+    assert_eq!(addresses, keystore.init()?.addresses());
+    let client_config =
+        PersistedConfig::<SuiClientConfig>::read(&working_dir.join(SUI_CLIENT_CONFIG))?;
+    assert_eq!(
+        Some(&address),
+        client_config.address_book.get("genesis-account-0")
+    );
+
+    // An account cannot be added twice.
+    let err = genesis(Some(Some(address)), false)
+        .execute()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("already present"));
+
+    // Without an address, the account gets a new key in the keystore.
+    genesis(Some(None), false).execute().await?;
+    let genesis_config = PersistedConfig::<NetworkConfig>::read(&network_path)?;
+    let new_addresses: Vec<_> = keystore
+        .init()?
+        .addresses()
+        .into_iter()
+        .filter(|address| !addresses.contains(address))
+        .collect();
+    assert_eq!(1, new_addresses.len());
+    assert_eq!(5, owned_by(&genesis_config, new_addresses[0]).len());
+
+    temp_dir.close()?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_start_subset_of_authorities() -> Result<(), anyhow::Error> {
+    let temp_dir = tempfile::tempdir()?;
+    let working_dir = temp_dir.path();
+    let config = working_dir.join(SUI_NETWORK_CONFIG);
+    SuiCommand::Genesis {
+        working_dir: Some(working_dir.to_path_buf()),
+        write_config: None,
+        force: false,
+        from_config: None,
+        append_account: None,
+    }
+    .execute()
+    .await?;
+    let network_config = PersistedConfig::<NetworkConfig>::read(&config)?;
+    assert_eq!(4, network_config.validator_configs().len());
+
+    // Keys of authorities not in the config are rejected before starting anything.
+    let unknown_key = get_key_pair::<AuthorityKeyPair>().1.public().into();
+    let start = SuiCommand::Start {
+        config: Some(config),
+        authorities: vec![unknown_key],
+    }
+    .execute()
+    .await;
+    assert!(matches!(start, Err(..)));
+
+    let key = network_config.validator_configs()[0].protocol_public_key();
+    let mut swarm = Swarm::builder().from_network_config(working_dir.to_path_buf(), network_config);
+    swarm.retain_validators(&[key])?;
+    swarm.launch().await?;
+    assert_eq!(1, swarm.validators().count());
+    for node in swarm.validators_mut() {
+        assert_eq!(key, node.protocol_public_key());
+        node.health_check().await?;
+    }
+
+    Ok(())
+}
+
+fn validator_genesis_info(stake: StakeUnit) -> ValidatorGenesisInfo {
+    ValidatorGenesisInfo {
+        key_pair: get_key_pair::<AuthorityKeyPair>().1,
+        account_key_pair: get_key_pair::<AccountKeyPair>().1.into(),
+        network_key_pair: get_key_pair::<AccountKeyPair>().1.into(),
+        network_address: new_network_address(),
+        stake,
+        gas_price: 1,
+        narwhal_primary_to_primary: new_network_address(),
+        narwhal_worker_to_primary: new_network_address(),
+        narwhal_primary_to_worker: new_network_address(),
+        narwhal_worker_to_worker: new_network_address(),
+        narwhal_consensus_address: new_network_address(),
+    }
+}
+
+async fn genesis_with_stakes(stakes: &[StakeUnit]) -> Result<(), anyhow::Error> {
+    let temp_dir = tempfile::tempdir()?;
+    let working_dir = temp_dir.path();
+    let config_path = working_dir.join("genesis.yaml");
+    let mut config = GenesisConfig::for_local_testing();
+    config.validator_genesis_info = Some(
+        stakes
+            .iter()
+            .map(|stake| validator_genesis_info(*stake))
+            .collect(),
+    );
+    config.persisted(&config_path).save()?;
+    let config_dir = working_dir.join("config");
+    std::fs::create_dir(&config_dir)?;
+
+    SuiCommand::Genesis {
+        working_dir: Some(config_dir),
+        write_config: None,
+        force: false,
+        from_config: Some(config_path),
+        append_account: None,
+    }
+    .execute()
+    .await
+}
+
+#[tokio::test]
+async fn test_genesis_stake_validation() -> Result<(), anyhow::Error> {
+    let err = genesis_with_stakes(&[0, 0, 0, 0]).await.unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("total stake of validators is zero"));
+
+    // 7 out of 10 is enough for a quorum, and 4 out of 10 to block one.
+    let err = genesis_with_stakes(&[7, 1, 1, 1]).await.unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Validator 0 has a stake of 7 out of 10"));
+    let err = genesis_with_stakes(&[2, 4, 2, 2]).await.unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Validator 1 has a stake of 4 out of 10"));
+
+    let err = genesis_with_stakes(&[]).await.unwrap_err();
+    assert!(err.to_string().contains("at least one validator"));
+
+    // 3 out of 10 is not.
+    genesis_with_stakes(&[3, 3, 2, 2]).await
+}
+
+#[tokio::test]
+async fn test_network_health() -> Result<(), anyhow::Error> {
+    let mut network = start_test_network(None).await?;
+    let committee = network.config().committee();
+    let registry = prometheus::Registry::new();
+    let metrics = Arc::new(NetworkAuthorityClientMetrics::new(&registry));
+
+    let reachable = check_network_health(network.config(), metrics.clone()).await?;
+    assert_eq!(reachable, committee.total_votes);
+
+    // A stopped authority is reported as unreachable.
+    let stopped = network.validators_mut().next().unwrap();
+    stopped.stop();
+    let stopped_weight = committee.weight(&stopped.protocol_public_key());
+    let reachable = check_network_health(network.config(), metrics).await?;
+    assert_eq!(reachable, committee.total_votes - stopped_weight);
+
+    // Every request, successful or not, is recorded in the client metrics.
+    let requests = registry
+        .gather()
+        .into_iter()
+        .find(|family| family.get_name() == "handle_account_info_request_latency")
+        .unwrap()
+        .get_metric()[0]
+        .get_histogram()
+        .get_sample_count();
+    assert_eq!(
+        requests,
+        2 * network.config().validator_configs().len() as u64
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_addresses_command() -> Result<(), anyhow::Error> {
     let temp_dir = tempfile::tempdir().unwrap();
@@ -133,6 +434,11 @@ async fn test_addresses_command() -> Result<(), anyhow::Error> {
         }),
         active_address: None,
         fullnode: None,
+        max_gas_budget: DEFAULT_MAX_GAS_BUDGET,
+        gas_budget_margin_percent: DEFAULT_GAS_BUDGET_MARGIN_PERCENT,
+        address_book: Default::default(),
+        active_profile: DEFAULT_PROFILE_NAME.to_string(),
+        profiles: Default::default(),
     };
     let wallet_conf_path = working_dir.join(SUI_CLIENT_CONFIG);
     let wallet_config = wallet_config.persisted(&wallet_conf_path);
@@ -151,7 +457,7 @@ async fn test_addresses_command() -> Result<(), anyhow::Error> {
         .execute(&mut context)
         .await
         .unwrap()
-        .print(true);
+        .print(OutputFormat::Human);
 
     Ok(())
 }
@@ -162,11 +468,14 @@ async fn test_objects_command() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address`
     SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
+        limit: None,
+        cursor: None,
+        type_: None,
     }
     .execute(&mut context)
     .await?
-    .print(true);
+    .print(OutputFormat::Human);
 
     let _object_refs = context
         .gateway
@@ -177,6 +486,398 @@ async fn test_objects_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_objects_command_pages() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let mut object_ids: Vec<_> = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?
+        .into_iter()
+        .map(|object| object.object_id)
+        .collect();
+    object_ids.sort();
+
+    let mut paged_ids = vec![];
+    let mut cursor = None;
+    loop {
+        let resp = SuiClientCommands::Objects {
+            address: Some(address.into()),
+            limit: Some(2),
+            cursor,
+            type_: None,
+        }
+        .execute(&mut context)
+        .await?;
+        let (objects, next_cursor) =
+            if let SuiClientCommandResult::Objects(objects, next_cursor) = resp {
+                (objects, next_cursor)
+            } else {
+                panic!("Command failed")
+            };
+        assert!(objects.len() <= 2);
+        paged_ids.extend(objects.iter().map(|object| object.object_id));
+        cursor = next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(paged_ids, object_ids);
+
+    // Without a limit, every object after the cursor is shown.
+    let resp = SuiClientCommands::Objects {
+        address: Some(address.into()),
+        limit: None,
+        cursor: Some(object_ids[0]),
+        type_: None,
+    }
+    .execute(&mut context)
+    .await?;
+    if let SuiClientCommandResult::Objects(objects, next_cursor) = resp {
+        let ids: Vec<_> = objects.iter().map(|object| object.object_id).collect();
+        assert_eq!(ids, object_ids[1..]);
+        assert_eq!(next_cursor, None);
+    } else {
+        panic!("Command failed")
+    };
+
+    assert!(SuiClientCommands::Objects {
+        address: Some(address.into()),
+        limit: Some(0),
+        cursor: None,
+        type_: None,
+    }
+    .execute(&mut context)
+    .await
+    .is_err());
+
+    Ok(())
+}
+
+/// The ids of the objects of the type owned by the address, as listed by the objects command.
+async fn object_ids_of_type(
+    context: &mut WalletContext,
+    address: SuiAddress,
+    type_: &str,
+) -> Result<Vec<ObjectID>, anyhow::Error> {
+    let resp = SuiClientCommands::Objects {
+        address: Some(address.into()),
+        limit: None,
+        cursor: None,
+        type_: Some(parse_sui_struct_tag(type_)?),
+    }
+    .execute(context)
+    .await?;
+    let mut ids: Vec<_> = if let SuiClientCommandResult::Objects(objects, _) = resp {
+        objects.iter().map(|object| object.object_id).collect()
+    } else {
+        panic!("Command failed")
+    };
+    ids.sort();
+    Ok(ids)
+}
+
+#[tokio::test]
+async fn test_objects_command_by_type() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let mut coin_ids: Vec<_> = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?
+        .into_iter()
+        .map(|object| object.object_id)
+        .collect();
+    coin_ids.sort();
+    let resp = SuiClientCommands::CreateExampleNFT {
+        name: None,
+        description: None,
+        url: None,
+        gas: None,
+        gas_budget: None,
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await?;
+    let nft_id = match resp {
+        SuiClientCommandResult::CreateExampleNFT(_, GetObjectDataResponse::Exists(obj)) => obj.id(),
+        _ => panic!("Command failed"),
+    };
+
+    assert_eq!(
+        object_ids_of_type(&mut context, address, "0x2::devnet_nft::DevNetNFT").await?,
+        vec![nft_id]
+    );
+    // The coins paid for the NFT, but none was deleted.
+    assert_eq!(
+        object_ids_of_type(&mut context, address, "0x2::coin::Coin").await?,
+        coin_ids
+    );
+    assert_eq!(
+        object_ids_of_type(&mut context, address, "0x2::coin::Coin<0x2::sui::SUI>").await?,
+        coin_ids
+    );
+    assert!(object_ids_of_type(
+        &mut context,
+        address,
+        "0x2::coin::Coin<0x2::devnet_nft::DevNetNFT>"
+    )
+    .await?
+    .is_empty());
+
+    // Pages hold the objects of the type only, so the cursor follows them.
+    let mut paged_ids = vec![];
+    let mut cursor = None;
+    loop {
+        let resp = SuiClientCommands::Objects {
+            address: Some(address.into()),
+            limit: Some(1),
+            cursor,
+            type_: Some(parse_sui_struct_tag("0x2::coin::Coin")?),
+        }
+        .execute(&mut context)
+        .await?;
+        let (objects, next_cursor) =
+            if let SuiClientCommandResult::Objects(objects, next_cursor) = resp {
+                (objects, next_cursor)
+            } else {
+                panic!("Command failed")
+            };
+        assert!(objects.len() <= 1);
+        paged_ids.extend(objects.iter().map(|object| object.object_id));
+        cursor = next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(paged_ids, coin_ids);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sync_progress_and_timeout() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+
+    // Syncing one object at a time reports each step of the progress.
+    let mut cursor = None;
+    let mut steps = 0;
+    loop {
+        let progress = context
+            .gateway
+            .wallet_sync_api()
+            .sync_account_state_incremental(address, cursor, 1)
+            .await?;
+        steps += 1;
+        assert_eq!(progress.synced_objects, steps);
+        cursor = progress.next_cursor;
+        if cursor.is_none() {
+            assert_eq!(progress.synced_objects, progress.total_objects);
+            break;
+        }
+    }
+    assert!(steps > 1);
+
+    let resp = SuiClientCommands::SyncClientState {
+        address: Some(address.into()),
+        timeout: None,
+        from: None,
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(matches!(
+        resp,
+        SuiClientCommandResult::SyncClientState(progress)
+            if progress.synced_objects == steps && progress.next_cursor.is_none()
+    ));
+
+    // Resuming after the last object has nothing left to sync.
+    let last = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?
+        .into_iter()
+        .map(|info| info.object_id)
+        .max();
+    let resp = SuiClientCommands::SyncClientState {
+        address: Some(address.into()),
+        timeout: None,
+        from: last,
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(matches!(
+        resp,
+        SuiClientCommandResult::SyncClientState(progress)
+            if progress.synced_objects == steps && progress.processed_transactions == 0
+    ));
+
+    // A sync which cannot complete in time fails rather than hanging.
+    let resp = SuiClientCommands::SyncClientState {
+        address: Some(address.into()),
+        timeout: Some(0),
+        from: None,
+    }
+    .execute(&mut context)
+    .await;
+    assert!(resp.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_events_command() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = context.keystore.addresses().get(1).cloned().unwrap();
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let object_to_send = object_refs.first().unwrap().object_id;
+
+    let digest = SuiClientCommands::Transfer {
+        to: recipient.into(),
+        object_id: object_to_send,
+        gas: None,
+        gas_budget: Some(50000),
+        gas_price: None,
+        yes: true,
+        authority: None,
+    }
+    .execute(&mut context)
+    .await?
+    .transaction_digests()
+    .pop()
+    .unwrap();
+
+    let result = SuiClientCommands::Events { digest }
+        .execute(&mut context)
+        .await?;
+    let events = match &result {
+        SuiClientCommandResult::Events(events) => events.clone(),
+        _ => panic!("Command returned unexpected result {result}"),
+    };
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SuiEvent::TransferObject { sender, object_id, .. }
+            if *sender == address && *object_id == object_to_send
+    )));
+
+    let output = format!("{result}");
+    assert!(output.contains("TransferObject"));
+    assert!(output.contains(&address.to_string()));
+    assert!(output.contains(&object_to_send.to_string()));
+    // With --json, the raw events are printed
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&format!("{result:?}"))?,
+        serde_json::to_value(&events)?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_serialize_output() -> Result<(), anyhow::Error> {
+    let (network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = context.keystore.addresses().get(1).cloned().unwrap();
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let object_to_send = object_refs.first().unwrap().object_id;
+    let path = tempfile::tempdir()?.into_path().join("result.json");
+    let client = |cmd, serialize_output| SuiCommand::Client {
+        config: Some(network.dir().join(SUI_CLIENT_CONFIG)),
+        cmd: Some(cmd),
+        json: false,
+        json_compact: false,
+        raw_effects: false,
+        wait_for_checkpoint: false,
+        dry_run: false,
+        serialize_transaction: false,
+        expiration_epoch: None,
+        serialize_output,
+    };
+
+    client(
+        SuiClientCommands::Transfer {
+            to: recipient.into(),
+            object_id: object_to_send,
+            gas: None,
+            gas_budget: Some(50000),
+            gas_price: None,
+            yes: true,
+            authority: None,
+        },
+        Some(path.clone()),
+    )
+    .execute()
+    .await?;
+
+    // The elapsed time is left out, leaving the certificate and the effects
+    let written: serde_json::Value = serde_json::from_slice(&std::fs::read(&path)?)?;
+    let digest: TransactionDigest =
+        serde_json::from_value(written[0]["transactionDigest"].clone())?;
+    let response = context.gateway.read_api().get_transaction(digest).await?;
+    assert_eq!(written, json!([response.certificate, response.effects]));
+
+    // An existing file is replaced
+    client(SuiClientCommands::ActiveAddress, Some(path.clone()))
+        .execute()
+        .await?;
+    let written: serde_json::Value = serde_json::from_slice(&std::fs::read(&path)?)?;
+    assert_eq!(written, json!(address));
+
+    // Without the option, nothing is written
+    std::fs::remove_file(&path)?;
+    client(SuiClientCommands::ActiveAddress, None)
+        .execute()
+        .await?;
+    assert!(!path.exists());
+
+    // The file cannot be created in a missing directory
+    let err = client(
+        SuiClientCommands::ActiveAddress,
+        Some(path.with_file_name("missing").join("result.json")),
+    )
+    .execute()
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Cannot create the output file"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_json_compact_output() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+
+    let result = SuiClientCommands::Objects {
+        address: Some(address.into()),
+        limit: None,
+        cursor: None,
+        type_: None,
+    }
+    .execute(&mut context)
+    .await?;
+    let pretty = format!("{result:#?}");
+    let compact = format!("{result:?}");
+    assert!(pretty.contains('\n'));
+    assert!(!compact.contains('\n'));
+    // Both formats hold the same JSON, only the whitespace differs.
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&compact)?,
+        serde_json::from_str::<serde_json::Value>(&pretty)?
+    );
+    result.print(OutputFormat::JsonCompact);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_create_example_nft_command() {
     let (_network, mut context, address) = setup_network_and_wallet().await.unwrap();
@@ -187,13 +888,14 @@ async fn test_create_example_nft_command() {
         url: None,
         gas: None,
         gas_budget: None,
+        gas_price: None,
     }
     .execute(&mut context)
     .await
     .unwrap();
 
     match result {
-        SuiClientCommandResult::CreateExampleNFT(GetObjectDataResponse::Exists(obj)) => {
+        SuiClientCommandResult::CreateExampleNFT(_, GetObjectDataResponse::Exists(obj)) => {
             assert_eq!(obj.owner, address);
             assert_eq!(
                 obj.data.type_().unwrap(),
@@ -234,19 +936,24 @@ async fn test_custom_genesis() -> Result<(), anyhow::Error> {
 
     // Sync client to retrieve objects from the network.
     SuiClientCommands::SyncClientState {
-        address: Some(address),
+        address: Some(address.into()),
+        timeout: None,
+        from: None,
     }
     .execute(&mut context)
     .await?
-    .print(true);
+    .print(OutputFormat::Human);
 
     // Print objects owned by `address`
     SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
+        limit: None,
+        cursor: None,
+        type_: None,
     }
     .execute(&mut context)
     .await?
-    .print(true);
+    .print(OutputFormat::Human);
 
     Ok(())
 }
@@ -264,10 +971,13 @@ async fn test_object_info_get_command() -> Result<(), anyhow::Error> {
     // Check log output contains all object ids.
     let object_id = object_refs.first().unwrap().object_id;
 
-    SuiClientCommands::Object { id: object_id }
-        .execute(&mut context)
-        .await?
-        .print(true);
+    SuiClientCommands::Object {
+        id: object_id,
+        follow: false,
+    }
+    .execute(&mut context)
+    .await?
+    .print(OutputFormat::Human);
 
     Ok(())
 }
@@ -287,856 +997,2953 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
     let object_to_send = object_refs.get(1).unwrap().object_id;
 
     SuiClientCommands::Gas {
-        address: Some(address),
+        address: Some(address.into()),
+        min_balance: None,
+        refund_estimate: false,
     }
     .execute(&mut context)
     .await?
-    .print(true);
+    .print(OutputFormat::Human);
 
     tokio::time::sleep(Duration::from_millis(100)).await;
 
     // Send an object
     SuiClientCommands::Transfer {
-        to: recipient,
+        to: recipient.into(),
         object_id: object_to_send,
         gas: Some(object_id),
-        gas_budget: 50000,
+        gas_budget: Some(50000),
+        gas_price: None,
+        yes: true,
+        authority: None,
     }
     .execute(&mut context)
     .await?;
 
     // Fetch gas again
     SuiClientCommands::Gas {
-        address: Some(address),
+        address: Some(address.into()),
+        min_balance: None,
+        refund_estimate: false,
     }
     .execute(&mut context)
     .await?
-    .print(true);
+    .print(OutputFormat::Human);
 
     Ok(())
 }
 
-#[allow(clippy::assertions_on_constants)]
 #[tokio::test]
-async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
-    let (_network, mut context, address1) = setup_network_and_wallet().await?;
-    let address2 = context.keystore.addresses().get(1).cloned().unwrap();
-
-    // publish the object basics package
+async fn test_transfer_single_coin_cannot_pay_own_gas() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
     let object_refs = context
         .gateway
         .read_api()
-        .get_objects_owned_by_address(address1)
+        .get_objects_owned_by_address(address)
         .await?;
-    let gas_obj_id = object_refs.first().unwrap().object_id;
-    let mut package_path = PathBuf::from(TEST_DATA_DIR);
-    package_path.push("move_call_args_linter");
-    let build_config = BuildConfig::default();
-    let resp = SuiClientCommands::Publish {
-        package_path,
-        build_config,
-        gas: Some(gas_obj_id),
-        gas_budget: 1000,
+    let gas = object_refs.first().unwrap().object_id;
+    let coin = object_refs.get(1).unwrap().object_id;
+
+    // Give a new address a single coin.
+    let (owner, keypair) = get_key_pair();
+    context
+        .keystore
+        .add_key(SuiKeyPair::Ed25519SuiKeyPair(keypair))?;
+    SuiClientCommands::Transfer {
+        to: owner.into(),
+        object_id: coin,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+        yes: true,
+        authority: None,
     }
     .execute(&mut context)
     .await?;
-    let package = if let SuiClientCommandResult::Publish(response) = resp {
-        let publish_resp = response.parsed_data.unwrap().to_publish_response().unwrap();
-        publish_resp.package.object_id
-    } else {
-        unreachable!("Invalid response");
-    };
 
-    // Sync client to retrieve objects from the network.
-    SuiClientCommands::SyncClientState {
-        address: Some(address2),
+    // The coin is the only one to pay gas with, whether selected or given.
+    for gas in [None, Some(coin)] {
+        let error = SuiClientCommands::Transfer {
+            to: address.into(),
+            object_id: coin,
+            gas,
+            gas_budget: Some(1000),
+            gas_price: None,
+            yes: true,
+            authority: None,
+        }
+        .execute(&mut context)
+        .await
+        .unwrap_err();
+        assert!(error.to_string().contains("transfer-sui"), "{error}");
     }
-    .execute(&mut context)
-    .await?
-    .print(true);
 
-    // Print objects owned by `address1`
-    SuiClientCommands::Objects {
-        address: Some(address1),
+    // `transfer-sui` pays gas out of the transferred coin.
+    SuiClientCommands::TransferSui {
+        to: address.into(),
+        sui_coin_object_id: Some(coin),
+        input_coins: None,
+        gas_budget: 1000,
+        gas_price: None,
+        amount: None,
+        yes: true,
     }
     .execute(&mut context)
-    .await?
-    .print(true);
-    tokio::time::sleep(Duration::from_millis(2000)).await;
+    .await?;
+    let object = get_object(coin, &mut context).await.unwrap();
+    assert_eq!(object.owner, Owner::AddressOwner(address));
+
+    Ok(())
+}
 
+#[tokio::test]
+async fn test_gas_price_option() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
     let object_refs = context
         .gateway
         .read_api()
-        .get_objects_owned_by_address(address1)
+        .get_objects_owned_by_address(address)
         .await?;
-
-    // Create an object for address1 using Move call
-
-    // Certain prep work
-    // Get a gas object
     let gas = object_refs.first().unwrap().object_id;
-    let obj = object_refs.get(1).unwrap().object_id;
-
-    // Create the args
-    let args = vec![
-        SuiJsonValue::new(json!(123u8))?,
-        SuiJsonValue::new(json!(address1))?,
-    ];
+    let coin = object_refs.get(1).unwrap().object_id;
+    let reference_gas_price = context.gateway.read_api().get_reference_gas_price().await?;
 
-    // Test case with no gas specified
-    let resp = SuiClientCommands::Call {
-        package,
-        module: "object_basics".to_string(),
-        function: "create".to_string(),
-        type_args: vec![],
-        args,
-        gas: None,
-        gas_budget: 1000,
+    // Without --gas-price the reference gas price is used, as for `call`.
+    let resp = SuiClientCommands::Transfer {
+        to: address.into(),
+        object_id: coin,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+        yes: true,
+        authority: None,
     }
     .execute(&mut context)
     .await?;
-    resp.print(true);
-
-    // Get the created object
-    let created_obj: ObjectID = if let SuiClientCommandResult::Call(
-        _,
-        SuiTransactionEffects {
-            created: new_objs, ..
-        },
-    ) = resp
-    {
-        new_objs.first().unwrap().reference.object_id
+    if let SuiClientCommandResult::Transfer(_, cert, _) = resp {
+        assert_eq!(cert.data.gas_price, reference_gas_price);
     } else {
-        // User assert since panic causes test issues
-        assert!(false);
-        // Use this to satisfy type checker
-        ObjectID::random()
-    };
-
-    // Try a bad argument: decimal
-    let args_json = json!([0.3f32, address1]);
-    assert!(SuiJsonValue::new(args_json.as_array().unwrap().get(0).unwrap().clone()).is_err());
-
-    // Try a bad argument: too few args
-    let args_json = json!([300usize]);
-    let mut args = vec![];
-    for a in args_json.as_array().unwrap() {
-        args.push(SuiJsonValue::new(a.clone()).unwrap());
+        panic!("Command failed")
     }
 
-    let resp = SuiClientCommands::Call {
-        package,
-        module: "object_basics".to_string(),
-        function: "create".to_string(),
-        type_args: vec![],
-        args: args.to_vec(),
+    let gas_price = reference_gas_price + 1;
+    let resp = SuiClientCommands::Transfer {
+        to: address.into(),
+        object_id: coin,
         gas: Some(gas),
-        gas_budget: 1000,
+        gas_budget: Some(1000),
+        gas_price: Some(gas_price),
+        yes: true,
+        authority: None,
     }
     .execute(&mut context)
-    .await;
-
-    assert!(resp.is_err());
-
-    let err_string = format!("{} ", resp.err().unwrap());
-    assert!(err_string.contains("Expected 2 args, found 1"));
-
-    // Try a transfer
-    // This should fail due to mismatch of object being sent
-    let args = vec![
-        SuiJsonValue::new(json!(obj))?,
-        SuiJsonValue::new(json!(address2))?,
-    ];
-
-    let resp = SuiClientCommands::Call {
-        package,
-        module: "object_basics".to_string(),
-        function: "transfer".to_string(),
-        type_args: vec![],
-        args: args.to_vec(),
-        gas: Some(gas),
-        gas_budget: 1000,
+    .await?;
+    if let SuiClientCommandResult::Transfer(_, cert, _) = resp {
+        assert_eq!(cert.data.gas_price, gas_price);
+    } else {
+        panic!("Command failed")
     }
-    .execute(&mut context)
-    .await;
-
-    assert!(resp.is_err());
-
-    let err_string = format!("{} ", resp.err().unwrap());
-    let framework_addr = SUI_FRAMEWORK_ADDRESS.to_hex_literal();
-    let package_addr = package.to_hex_literal();
-    assert!(err_string.contains(&format!("Expected argument of type {package_addr}::object_basics::Object, but found type {framework_addr}::coin::Coin<{framework_addr}::sui::SUI>")));
-
-    // Try a proper transfer
-    let args = vec![
-        SuiJsonValue::new(json!(created_obj))?,
-        SuiJsonValue::new(json!(address2))?,
-    ];
 
-    SuiClientCommands::Call {
-        package,
-        module: "object_basics".to_string(),
-        function: "transfer".to_string(),
-        type_args: vec![],
-        args: args.to_vec(),
+    let resp = SuiClientCommands::SplitCoin {
+        coin_id: coin,
+        amounts: Some(vec![10]),
+        count: None,
         gas: Some(gas),
-        gas_budget: 1000,
+        gas_budget: Some(1000),
+        gas_price: Some(gas_price),
     }
     .execute(&mut context)
     .await?;
+    if let SuiClientCommandResult::SplitCoin(response) = resp {
+        assert_eq!(response.certificate.data.gas_price, gas_price);
+    } else {
+        panic!("Command failed")
+    }
 
     Ok(())
 }
 
-#[allow(clippy::assertions_on_constants)]
 #[tokio::test]
-async fn test_package_publish_command() -> Result<(), anyhow::Error> {
+async fn test_gas_command_min_balance() -> Result<(), anyhow::Error> {
     let (_network, mut context, address) = setup_network_and_wallet().await?;
-
     let object_refs = context
         .gateway
         .read_api()
         .get_objects_owned_by_address(address)
         .await?;
+    let gas = object_refs.first().unwrap().object_id;
+    let coin = object_refs.get(1).unwrap().object_id;
 
-    // Check log output contains all object ids.
-    let gas_obj_id = object_refs.first().unwrap().object_id;
+    // Split out a dust coin.
+    SuiClientCommands::SplitCoin {
+        coin_id: coin,
+        amounts: Some(vec![10]),
+        count: None,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await?;
 
-    // Provide path to well formed package sources
+    let gas_coins = |min_balance| SuiClientCommands::Gas {
+        address: Some(address.into()),
+        min_balance,
+        refund_estimate: false,
+    };
+    let all = gas_coins(None).execute(&mut context).await?;
+    let filtered = gas_coins(Some(11)).execute(&mut context).await?;
+    let (all_coins, filtered_coins) = match (&all, &filtered) {
+        (SuiClientCommandResult::Gas(all), SuiClientCommandResult::Gas(filtered)) => {
+            (all, filtered)
+        }
+        _ => panic!("Command failed"),
+    };
+    assert_eq!(filtered_coins.len() + 1, all_coins.len());
+    assert!(filtered_coins.iter().all(|coin| coin.value() >= 11));
+
+    // The total is part of both the table and the JSON output.
+    let total_balance: u64 = filtered_coins.iter().map(|coin| coin.value()).sum();
+    assert!(format!("{filtered}").ends_with(&format!(
+        "{} gas objects with a total value of {total_balance}.",
+        filtered_coins.len()
+    )));
+    let json: serde_json::Value = serde_json::from_str(&format!("{filtered:?}"))?;
+    assert_eq!(json["total_balance"], json!(total_balance));
+    assert_eq!(
+        json["coins"].as_array().unwrap().len(),
+        filtered_coins.len()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_coins_command() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+
+    // Publish a package defining a currency, and mint two of its coins.
     let mut package_path = PathBuf::from(TEST_DATA_DIR);
     package_path.push("dummy_modules_publish");
-    let build_config = BuildConfig::default();
     let resp = SuiClientCommands::Publish {
         package_path,
-        build_config,
-        gas: Some(gas_obj_id),
+        build_config: BuildConfig::default(),
+        gas: None,
         gas_budget: 1000,
+        gas_price: None,
     }
     .execute(&mut context)
     .await?;
-
-    // Print it out to CLI/logs
-    resp.print(true);
-
-    let (package, created_obj) = if let SuiClientCommandResult::Publish(response) = resp {
+    let (package, treasury_cap) = if let SuiClientCommandResult::Publish(response) = resp {
         let publish_resp = response.parsed_data.unwrap().to_publish_response().unwrap();
         (
-            publish_resp.package,
-            publish_resp.created_objects[0].reference.clone(),
+            publish_resp.package.object_id,
+            publish_resp.created_objects[0].id(),
         )
     } else {
-        unreachable!("Invalid response");
+        panic!("Command failed")
     };
+    for amount in [100u64, 250] {
+        SuiClientCommands::Call {
+            package,
+            module: "trusted_coin".to_string(),
+            function: "mint".to_string(),
+            type_args: vec![],
+            args: vec![
+                SuiJsonValue::new(json!(treasury_cap))?,
+                SuiJsonValue::new(json!(amount))?,
+            ],
+            gas: None,
+            gas_budget: Some(1000),
+            gas_price: None,
+        }
+        .execute(&mut context)
+        .await?;
+    }
 
-    // Check the objects
-    let resp = SuiClientCommands::Object {
-        id: package.object_id,
+    let coin_type = parse_sui_struct_tag(&format!("{package}::trusted_coin::TRUSTED_COIN"))?;
+    let resp = SuiClientCommands::Coins {
+        coin_type,
+        address: Some(address.into()),
     }
     .execute(&mut context)
     .await?;
-    assert!(matches!(
-        resp,
-        SuiClientCommandResult::Object(GetObjectDataResponse::Exists(..))
-    ));
-
-    let resp = SuiClientCommands::Object {
-        id: created_obj.object_id,
+    let balances = if let SuiClientCommandResult::Coins(balances) = &resp {
+        balances
+    } else {
+        panic!("Command failed")
+    };
+    let mut values: Vec<_> = balances.coins.iter().map(|coin| coin.value()).collect();
+    values.sort();
+    assert_eq!(values, vec![100, 250]);
+    assert_eq!(balances.total_balance(), 350);
+    assert!(balances
+        .coin_type
+        .ends_with("::trusted_coin::TRUSTED_COIN>"));
+
+    // The total is part of both the table and the JSON output.
+    assert!(format!("{resp}").ends_with(&format!(
+        "2 coins of type {} with a total balance of 350.",
+        balances.coin_type
+    )));
+    let json: serde_json::Value = serde_json::from_str(&format!("{resp:?}"))?;
+    assert_eq!(json["total_balance"], json!(350));
+    assert_eq!(json["coins"].as_array().unwrap().len(), 2);
+
+    // The gas coins are listed as coins of SUI.
+    let resp = SuiClientCommands::Coins {
+        coin_type: GAS::type_(),
+        address: Some(address.into()),
     }
     .execute(&mut context)
     .await?;
-    assert!(matches!(
-        resp,
-        SuiClientCommandResult::Object(GetObjectDataResponse::Exists(..))
-    ));
+    let gas_balance = context.balance(address).await?;
+    match resp {
+        SuiClientCommandResult::Coins(balances) => {
+            assert_eq!(balances.coins.len(), gas_balance.coin_count);
+            assert_eq!(balances.total_balance(), u128::from(gas_balance.balance));
+        }
+        _ => panic!("Command failed"),
+    }
 
     Ok(())
 }
 
-#[allow(clippy::assertions_on_constants)]
+#[test]
+fn test_coin_balances_total_above_u64() {
+    let balances = CoinBalances {
+        coin_type: "0x2::coin::Coin<0x2::custom::CUSTOM>".to_string(),
+        coins: vec![
+            Coin::new(UID::new(ObjectID::random()), u64::MAX),
+            Coin::new(UID::new(ObjectID::random()), 1),
+        ],
+    };
+    assert_eq!(balances.total_balance(), u128::from(u64::MAX) + 1);
+}
+
 #[tokio::test]
-async fn test_native_transfer() -> Result<(), anyhow::Error> {
+async fn test_max_gas_budget() -> Result<(), anyhow::Error> {
     let (_network, mut context, address) = setup_network_and_wallet().await?;
-    let recipient = context.keystore.addresses().get(1).cloned().unwrap();
+    context.config.max_gas_budget = 1000;
+
+    // The objects do not exist, so the command can only fail on the gas budget if it is
+    // rejected before reaching the network.
+    let result = SuiClientCommands::Transfer {
+        to: SuiAddress::random_for_testing_only().into(),
+        object_id: ObjectID::random(),
+        gas: Some(ObjectID::random()),
+        gas_budget: Some(1001),
+        gas_price: None,
+        yes: true,
+        authority: None,
+    }
+    .execute(&mut context)
+    .await;
+    assert!(result.unwrap_err().to_string().contains("max_gas_budget"));
 
+    // Transactions signed elsewhere are checked on submission.
     let object_refs = context
         .gateway
         .read_api()
         .get_objects_owned_by_address(address)
         .await?;
-
-    // Check log output contains all object ids.
-    let gas_obj_id = object_refs.first().unwrap().object_id;
-    let obj_id = object_refs.get(1).unwrap().object_id;
-
-    let resp = SuiClientCommands::Transfer {
-        gas: Some(gas_obj_id),
-        to: recipient,
-        object_id: obj_id,
-        gas_budget: 50000,
+    let data = context
+        .gateway
+        .transaction_builder()
+        .transfer_object(
+            address,
+            object_refs[1].object_id,
+            Some(object_refs[0].object_id),
+            1001,
+            SuiAddress::random_for_testing_only(),
+        )
+        .await?;
+    let tx_bytes = Base64::encode(data.to_bytes());
+    let signature = context.keystore.sign(&address, &data.to_bytes())?;
+    let result = SuiClientCommands::ExecuteSigned {
+        tx_bytes: tx_bytes.clone(),
+        signature: signature.clone(),
     }
     .execute(&mut context)
-    .await?;
+    .await;
+    assert!(result.unwrap_err().to_string().contains("max_gas_budget"));
+    let line = format!("{tx_bytes} {}", Base64::encode(signature));
+    let results = context.batch_submit(vec![line]).await;
+    assert!(results[0]
+        .error
+        .as_ref()
+        .unwrap()
+        .contains("max_gas_budget"));
+    let file = tempfile::tempdir()?.into_path().join("tx_bytes");
+    std::fs::write(&file, &tx_bytes)?;
+    let result = SuiClientCommands::SignAndSubmit { file }
+        .execute(&mut context)
+        .await;
+    assert!(result.unwrap_err().to_string().contains("max_gas_budget"));
 
-    // Print it out to CLI/logs
-    resp.print(true);
+    Ok(())
+}
 
-    // Get the mutated objects
-    let (mut_obj1, mut_obj2) =
-        if let SuiClientCommandResult::Transfer(_, _, SuiTransactionEffects { mutated, .. }) = resp
-        {
-            (
-                mutated.get(0).unwrap().reference.object_id,
-                mutated.get(1).unwrap().reference.object_id,
-            )
+#[tokio::test]
+async fn test_estimated_gas_budget() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let gas = object_refs.first().unwrap().object_id;
+    let primary_coin = object_refs.get(1).unwrap().object_id;
+    let coin_to_merge = object_refs.get(2).unwrap().object_id;
+
+    let data = context
+        .gateway
+        .transaction_builder()
+        .merge_coins(
+            address,
+            primary_coin,
+            coin_to_merge,
+            Some(gas),
+            DEFAULT_MAX_GAS_BUDGET,
+        )
+        .await?;
+    let gas_used = context
+        .gateway
+        .quorum_driver()
+        .dry_run_transaction(Base64::from_bytes(&data.to_bytes()))
+        .await?
+        .gas_used
+        .gas_used();
+
+    // Without a gas budget, the one used is the dry run's gas plus the margin.
+    let resp = SuiClientCommands::MergeCoin {
+        primary_coin,
+        coin_to_merge,
+        gas: Some(gas),
+        gas_budget: None,
+        gas_price: None,
+        yes: true,
+    }
+    .execute(&mut context)
+    .await?;
+    let response = if let SuiClientCommandResult::MergeCoin(response) = resp {
+        response
+    } else {
+        panic!("Command failed")
+    };
+    assert_eq!(response.effects.status, SuiExecutionStatus::Success);
+    assert_eq!(
+        response.certificate.data.gas_budget,
+        gas_used + gas_used * DEFAULT_GAS_BUDGET_MARGIN_PERCENT / 100
+    );
+
+    // Splitting out more than the coin holds aborts, which the dry run reports.
+    let value = get_gas_value(&get_object(primary_coin, &mut context).await.unwrap());
+    let result = SuiClientCommands::Call {
+        package: SUI_FRAMEWORK_OBJECT_ID,
+        module: "coin".to_string(),
+        function: "split".to_string(),
+        type_args: vec![parse_sui_type_tag("0x2::sui::SUI")?],
+        args: vec![
+            SuiJsonValue::new(json!(primary_coin))?,
+            SuiJsonValue::new(json!(value + 1))?,
+        ],
+        gas: Some(gas),
+        gas_budget: None,
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await;
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("Dry run to estimate the gas budget failed"));
+    assert!(error.contains("MoveAbort"), "{error}");
+
+    // A gas object holding less than the max gas budget can still pay for the estimate.
+    let resp = SuiClientCommands::SplitCoin {
+        coin_id: object_refs.get(3).unwrap().object_id,
+        amounts: Some(vec![DEFAULT_MAX_GAS_BUDGET / 10]),
+        count: None,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await?;
+    let small_gas = if let SuiClientCommandResult::SplitCoin(response) = resp {
+        response.effects.created[0].reference.object_id
+    } else {
+        panic!("Command failed")
+    };
+    let resp = SuiClientCommands::MergeCoin {
+        primary_coin,
+        coin_to_merge: object_refs.get(4).unwrap().object_id,
+        gas: Some(small_gas),
+        gas_budget: None,
+        gas_price: None,
+        yes: true,
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(matches!(resp, SuiClientCommandResult::MergeCoin(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_gas_refund_estimate() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let gas = object_refs.first().unwrap().object_id;
+    let coin = object_refs.get(1).unwrap().object_id;
+
+    // Genesis coins carry no storage rebate, so create a coin by splitting one.
+    let resp = SuiClientCommands::SplitCoin {
+        coin_id: coin,
+        amounts: Some(vec![1000]),
+        count: None,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await?;
+    let new_coin = if let SuiClientCommandResult::SplitCoin(r) = resp {
+        r.parsed_data
+            .unwrap()
+            .to_split_coin_response()
+            .unwrap()
+            .new_coins[0]
+            .id()
+    } else {
+        panic!("Command failed")
+    };
+
+    SuiClientCommands::SyncClientState {
+        address: Some(address.into()),
+        timeout: None,
+        from: None,
+    }
+    .execute(&mut context)
+    .await?;
+
+    let resp = SuiClientCommands::Gas {
+        address: Some(address.into()),
+        min_balance: None,
+        refund_estimate: true,
+    }
+    .execute(&mut context)
+    .await?;
+    let rebate_estimate = if let SuiClientCommandResult::GasRefundEstimate(gases) = resp {
+        gases
+            .iter()
+            .find(|(gas_coin, _)| *gas_coin.id() == new_coin)
+            .unwrap()
+            .1
+    } else {
+        panic!("Command failed")
+    };
+    assert!(rebate_estimate > 0);
+
+    // Deleting the new coin by merging it yields at least the estimated rebate.
+    let resp = SuiClientCommands::MergeCoin {
+        primary_coin: coin,
+        coin_to_merge: new_coin,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+        yes: true,
+    }
+    .execute(&mut context)
+    .await?;
+    let storage_rebate = if let SuiClientCommandResult::MergeCoin(r) = resp {
+        r.effects.gas_used.storage_rebate
+    } else {
+        panic!("Command failed")
+    };
+    assert!(storage_rebate >= rebate_estimate);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_serve_gateway() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = context.keystore.addresses().get(1).cloned().unwrap();
+
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let object_id = object_refs.first().unwrap().object_id;
+    let object_to_send = object_refs.get(1).unwrap().object_id;
+
+    // Serve the embedded gateway and connect the client to it
+    let gateway = context.gateway.embedded_gateway().unwrap();
+    let server_address = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), get_available_port());
+    let _handle = start_gateway_server(gateway.clone(), server_address).await?;
+    context.gateway = ClientType::RPC(format!("http://{server_address}"), None)
+        .init()
+        .await?;
+    assert!(context.gateway.embedded_gateway().is_none());
+
+    let transaction_count = gateway.get_total_transaction_number()?;
+
+    // First command executes a transaction through the served gateway
+    SuiClientCommands::Transfer {
+        to: recipient.into(),
+        object_id: object_to_send,
+        gas: Some(object_id),
+        gas_budget: Some(50000),
+        gas_price: None,
+        yes: true,
+        authority: None,
+    }
+    .execute(&mut context)
+    .await?;
+    assert_eq!(
+        gateway.get_total_transaction_number()?,
+        transaction_count + 1
+    );
+
+    // The second command is served by the same warm gateway, which already knows about the
+    // transaction executed by the first one
+    let resp = SuiClientCommands::Objects {
+        address: Some(address.into()),
+        limit: None,
+        cursor: None,
+        type_: None,
+    }
+    .execute(&mut context)
+    .await?;
+    let objects = if let SuiClientCommandResult::Objects(objects, _) = resp {
+        objects
+    } else {
+        panic!("Unexpected command result")
+    };
+    assert!(objects.iter().all(|o| o.object_id != object_to_send));
+
+    Ok(())
+}
+
+#[allow(clippy::assertions_on_constants)]
+#[tokio::test]
+async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address1) = setup_network_and_wallet().await?;
+    let address2 = context.keystore.addresses().get(1).cloned().unwrap();
+
+    // publish the object basics package
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address1)
+        .await?;
+    let gas_obj_id = object_refs.first().unwrap().object_id;
+    let mut package_path = PathBuf::from(TEST_DATA_DIR);
+    package_path.push("move_call_args_linter");
+    let build_config = BuildConfig::default();
+    let resp = SuiClientCommands::Publish {
+        package_path,
+        build_config,
+        gas: Some(gas_obj_id),
+        gas_budget: 1000,
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await?;
+    let package = if let SuiClientCommandResult::Publish(response) = resp {
+        let publish_resp = response.parsed_data.unwrap().to_publish_response().unwrap();
+        publish_resp.package.object_id
+    } else {
+        unreachable!("Invalid response");
+    };
+
+    // Sync client to retrieve objects from the network.
+    SuiClientCommands::SyncClientState {
+        address: Some(address2.into()),
+        timeout: None,
+        from: None,
+    }
+    .execute(&mut context)
+    .await?
+    .print(OutputFormat::Human);
+
+    // Print objects owned by `address1`
+    SuiClientCommands::Objects {
+        address: Some(address1.into()),
+        limit: None,
+        cursor: None,
+        type_: None,
+    }
+    .execute(&mut context)
+    .await?
+    .print(OutputFormat::Human);
+    tokio::time::sleep(Duration::from_millis(2000)).await;
+
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address1)
+        .await?;
+
+    // Create an object for address1 using Move call
+
+    // Certain prep work
+    // Get a gas object
+    let gas = object_refs.first().unwrap().object_id;
+    let obj = object_refs.get(1).unwrap().object_id;
+
+    // Create the args
+    let args = vec![
+        SuiJsonValue::new(json!(123u8))?,
+        SuiJsonValue::new(json!(address1))?,
+    ];
+
+    // Test case with no gas specified
+    let resp = SuiClientCommands::Call {
+        package,
+        module: "object_basics".to_string(),
+        function: "create".to_string(),
+        type_args: vec![],
+        args,
+        gas: None,
+        gas_budget: Some(1000),
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await?;
+    resp.print(OutputFormat::Human);
+
+    // Without an explicit gas price the reference gas price is used
+    let reference_gas_price = context.gateway.read_api().get_reference_gas_price().await?;
+
+    // Get the created object
+    let created_obj: ObjectID = if let SuiClientCommandResult::Call(
+        cert,
+        SuiTransactionEffects {
+            created: new_objs, ..
+        },
+    ) = resp
+    {
+        assert_eq!(cert.data.gas_price, reference_gas_price);
+        new_objs.first().unwrap().reference.object_id
+    } else {
+        // User assert since panic causes test issues
+        assert!(false);
+        // Use this to satisfy type checker
+        ObjectID::random()
+    };
+
+    // Pass vectors: the bytes and the IDs are counted into the value of the object
+    let args = vec![
+        SuiJsonValue::new(json!(created_obj))?,
+        SuiJsonValue::new(json!([1, 2, 3]))?,
+    ];
+    SuiClientCommands::Call {
+        package,
+        module: "object_basics".to_string(),
+        function: "set_value_from_bytes".to_string(),
+        type_args: vec![],
+        args,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await?;
+    assert_eq!(get_object_value(created_obj, &mut context).await, 3);
+
+    let args = vec![
+        SuiJsonValue::new(json!(created_obj))?,
+        SuiJsonValue::new(json!([obj, gas]))?,
+    ];
+    SuiClientCommands::Call {
+        package,
+        module: "object_basics".to_string(),
+        function: "set_value_from_ids".to_string(),
+        type_args: vec![],
+        args,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await?;
+    assert_eq!(get_object_value(created_obj, &mut context).await, 2);
+
+    // Try a bad argument: an array for a single value
+    let args = vec![
+        SuiJsonValue::new(json!(created_obj))?,
+        SuiJsonValue::new(json!([4, 5]))?,
+    ];
+    let resp = SuiClientCommands::Call {
+        package,
+        module: "object_basics".to_string(),
+        function: "set_value".to_string(),
+        type_args: vec![],
+        args,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await;
+
+    let err_string = format!("{} ", resp.err().unwrap());
+    assert!(
+        err_string.contains("Unexpected array [4,5] at pos 1. Expected a single value of type u64")
+    );
+
+    // Try a bad argument: decimal
+    let args_json = json!([0.3f32, address1]);
+    assert!(SuiJsonValue::new(args_json.as_array().unwrap().get(0).unwrap().clone()).is_err());
+
+    // Try a bad argument: too few args
+    let args_json = json!([300usize]);
+    let mut args = vec![];
+    for a in args_json.as_array().unwrap() {
+        args.push(SuiJsonValue::new(a.clone()).unwrap());
+    }
+
+    let resp = SuiClientCommands::Call {
+        package,
+        module: "object_basics".to_string(),
+        function: "create".to_string(),
+        type_args: vec![],
+        args: args.to_vec(),
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await;
+
+    assert!(resp.is_err());
+
+    let err_string = format!("{} ", resp.err().unwrap());
+    assert!(err_string.contains("Expected 2 args, found 1"));
+
+    // Try a transfer
+    // This should fail due to mismatch of object being sent
+    let args = vec![
+        SuiJsonValue::new(json!(obj))?,
+        SuiJsonValue::new(json!(address2))?,
+    ];
+
+    let resp = SuiClientCommands::Call {
+        package,
+        module: "object_basics".to_string(),
+        function: "transfer".to_string(),
+        type_args: vec![],
+        args: args.to_vec(),
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await;
+
+    assert!(resp.is_err());
+
+    let err_string = format!("{} ", resp.err().unwrap());
+    let framework_addr = SUI_FRAMEWORK_ADDRESS.to_hex_literal();
+    let package_addr = package.to_hex_literal();
+    assert!(err_string.contains(&format!("Expected argument of type {package_addr}::object_basics::Object, but found type {framework_addr}::coin::Coin<{framework_addr}::sui::SUI>")));
+
+    // Try a proper transfer
+    let args = vec![
+        SuiJsonValue::new(json!(created_obj))?,
+        SuiJsonValue::new(json!(address2))?,
+    ];
+
+    SuiClientCommands::Call {
+        package,
+        module: "object_basics".to_string(),
+        function: "transfer".to_string(),
+        type_args: vec![],
+        args: args.to_vec(),
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await?;
+
+    Ok(())
+}
+
+#[allow(clippy::assertions_on_constants)]
+#[tokio::test]
+async fn test_package_publish_command() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+
+    // Check log output contains all object ids.
+    let gas_obj_id = object_refs.first().unwrap().object_id;
+
+    // Provide path to well formed package sources
+    let mut package_path = PathBuf::from(TEST_DATA_DIR);
+    package_path.push("dummy_modules_publish");
+    let build_config = BuildConfig::default();
+    let resp = SuiClientCommands::Publish {
+        package_path,
+        build_config,
+        gas: Some(gas_obj_id),
+        gas_budget: 1000,
+        gas_price: None,
+    }
+    .execute(&mut context)
+    .await?;
+
+    // Print it out to CLI/logs
+    resp.print(OutputFormat::Human);
+
+    let (package, created_obj) = if let SuiClientCommandResult::Publish(response) = resp {
+        let publish_resp = response.parsed_data.unwrap().to_publish_response().unwrap();
+        // The package and the TreasuryCap created by the module initializer are classified.
+        assert_eq!(publish_resp.classified_objects.len(), 2);
+        assert_eq!(
+            publish_resp.classified_objects[0],
+            SuiPublishedObject {
+                object_id: publish_resp.package.object_id,
+                kind: SuiPublishedObjectKind::Package,
+            }
+        );
+        let treasury_cap = &publish_resp.classified_objects[1];
+        assert_eq!(treasury_cap.object_id, publish_resp.created_objects[0].id());
+        assert!(matches!(
+            &treasury_cap.kind,
+            SuiPublishedObjectKind::Other { object_type }
+                if object_type.contains("::coin::TreasuryCap<")
+                    && object_type.ends_with("::trusted_coin::TRUSTED_COIN>")
+        ));
+        // The kind is flattened into the object in the JSON output.
+        assert_eq!(
+            serde_json::to_value(&publish_resp.classified_objects)?,
+            json!([
+                {"objectId": publish_resp.package.object_id, "kind": "package"},
+                {
+                    "objectId": treasury_cap.object_id,
+                    "kind": "other",
+                    "objectType": publish_resp.created_objects[0].data.type_(),
+                },
+            ])
+        );
+        (
+            publish_resp.package,
+            publish_resp.created_objects[0].reference.clone(),
+        )
+    } else {
+        unreachable!("Invalid response");
+    };
+
+    // Check the objects
+    let resp = SuiClientCommands::Object {
+        id: package.object_id,
+        follow: false,
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(matches!(
+        resp,
+        SuiClientCommandResult::Object(GetObjectDataResponse::Exists(..))
+    ));
+
+    let resp = SuiClientCommands::Object {
+        id: created_obj.object_id,
+        follow: false,
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(matches!(
+        resp,
+        SuiClientCommandResult::Object(GetObjectDataResponse::Exists(..))
+    ));
+
+    Ok(())
+}
+
+#[allow(clippy::assertions_on_constants)]
+#[tokio::test]
+async fn test_native_transfer() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = context.keystore.addresses().get(1).cloned().unwrap();
+
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+
+    // Check log output contains all object ids.
+    let gas_obj_id = object_refs.first().unwrap().object_id;
+    let obj_id = object_refs.get(1).unwrap().object_id;
+
+    let resp = SuiClientCommands::Transfer {
+        gas: Some(gas_obj_id),
+        to: recipient.into(),
+        object_id: obj_id,
+        gas_budget: Some(50000),
+        gas_price: None,
+        yes: true,
+        authority: None,
+    }
+    .execute(&mut context)
+    .await?;
+
+    // Print it out to CLI/logs
+    resp.print(OutputFormat::Human);
+
+    // Get the mutated objects
+    let (mut_obj1, mut_obj2) =
+        if let SuiClientCommandResult::Transfer(_, _, SuiTransactionEffects { mutated, .. }) = resp
+        {
+            (
+                mutated.get(0).unwrap().reference.object_id,
+                mutated.get(1).unwrap().reference.object_id,
+            )
+        } else {
+            assert!(false);
+            panic!()
+        };
+
+    // Sync both to fetch objects
+    SuiClientCommands::SyncClientState {
+        address: Some(address.into()),
+        timeout: None,
+        from: None,
+    }
+    .execute(&mut context)
+    .await?
+    .print(OutputFormat::Human);
+    SuiClientCommands::SyncClientState {
+        address: Some(recipient.into()),
+        timeout: None,
+        from: None,
+    }
+    .execute(&mut context)
+    .await?
+    .print(OutputFormat::Human);
+
+    // Check the objects
+    let resp = SuiClientCommands::Object {
+        id: mut_obj1,
+        follow: false,
+    }
+    .execute(&mut context)
+    .await?;
+    let mut_obj1 =
+        if let SuiClientCommandResult::Object(GetObjectDataResponse::Exists(object)) = resp {
+            object
+        } else {
+            // Fail this way because Panic! causes test issues
+            assert!(false);
+            panic!()
+        };
+
+    let resp = SuiClientCommands::Object {
+        id: mut_obj2,
+        follow: false,
+    }
+    .execute(&mut context)
+    .await?;
+    let mut_obj2 =
+        if let SuiClientCommandResult::Object(GetObjectDataResponse::Exists(object)) = resp {
+            object
+        } else {
+            // Fail this way because Panic! causes test issues
+            assert!(false);
+            panic!()
+        };
+
+    let (gas, obj) = if mut_obj1.owner.get_owner_address().unwrap() == address {
+        (mut_obj1, mut_obj2)
+    } else {
+        (mut_obj2, mut_obj1)
+    };
+
+    assert_eq!(gas.owner.get_owner_address().unwrap(), address);
+    assert_eq!(obj.owner.get_owner_address().unwrap(), recipient);
+
+    // Sync client to retrieve objects from the network.
+    SuiClientCommands::SyncClientState {
+        address: Some(address.into()),
+        timeout: None,
+        from: None,
+    }
+    .execute(&mut context)
+    .await?
+    .print(OutputFormat::Human);
+
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+
+    // Check log output contains all object ids.
+    let obj_id = object_refs.get(1).unwrap().object_id;
+
+    let resp = SuiClientCommands::Transfer {
+        gas: None,
+        to: recipient.into(),
+        object_id: obj_id,
+        gas_budget: Some(50000),
+        gas_price: None,
+        yes: true,
+        authority: None,
+    }
+    .execute(&mut context)
+    .await?;
+
+    // Print it out to CLI/logs
+    resp.print(OutputFormat::Human);
+
+    // Get the mutated objects
+    let (_mut_obj1, _mut_obj2) =
+        if let SuiClientCommandResult::Transfer(_, _, SuiTransactionEffects { mutated, .. }) = resp
+        {
+            (
+                mutated.get(0).unwrap().reference.object_id,
+                mutated.get(1).unwrap().reference.object_id,
+            )
         } else {
             assert!(false);
             panic!()
         };
 
-    // Sync both to fetch objects
+    Ok(())
+}
+
+#[test]
+fn test_confirm_transaction() {
+    // --yes skips the prompt, with or without a terminal.
+    assert!(confirm_transaction(true, false, "transfer").is_ok());
+    assert!(confirm_transaction(true, true, "transfer").is_ok());
+
+    // Without a terminal, the prompt cannot be answered so --yes is required.
+    let err = confirm_transaction(false, false, "transfer").unwrap_err();
+    assert!(err.to_string().contains("--yes"));
+}
+
+#[test]
+// Test for issue https://github.com/MystenLabs/sui/issues/1078
+fn test_bug_1078() {
+    let read = SuiClientCommandResult::Object(GetObjectDataResponse::NotExists(ObjectID::random()));
+    let mut writer = String::new();
+    // fmt ObjectRead should not fail.
+    write!(writer, "{}", read).unwrap();
+    write!(writer, "{:?}", read).unwrap();
+}
+
+#[allow(clippy::assertions_on_constants)]
+#[tokio::test]
+async fn test_switch_command() -> Result<(), anyhow::Error> {
+    let network = start_test_network(None).await?;
+
+    // Create Wallet context.
+    let wallet_conf = network.dir().join(SUI_CLIENT_CONFIG);
+
+    let mut context = WalletContext::new(&wallet_conf).await?;
+
+    // Get the active address
+    let addr1 = context.active_address()?;
+
+    // Sync client to retrieve objects from the network.
+    SuiClientCommands::SyncClientState {
+        address: Some(addr1.into()),
+        timeout: None,
+        from: None,
+    }
+    .execute(&mut context)
+    .await?;
+
+    // Run a command with address omitted
+    let os = SuiClientCommands::Objects {
+        address: None,
+        limit: None,
+        cursor: None,
+        type_: None,
+    }
+    .execute(&mut context)
+    .await?;
+
+    let mut cmd_objs = if let SuiClientCommandResult::Objects(v, _) = os {
+        v
+    } else {
+        panic!("Command failed")
+    };
+
+    // Check that we indeed fetched for addr1
+    let mut actual_objs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(addr1)
+        .await
+        .unwrap();
+    cmd_objs.sort();
+    actual_objs.sort();
+    assert_eq!(cmd_objs, actual_objs);
+
+    // Switch the address
+    let addr2 = context.keystore.addresses().get(1).cloned().unwrap();
+    let resp = SuiClientCommands::Switch {
+        address: Some(addr2.into()),
+        gateway: None,
+        fullnode: None,
+    }
+    .execute(&mut context)
+    .await?;
+    assert_eq!(addr2, context.active_address()?);
+    assert_ne!(addr1, context.active_address()?);
+    assert_eq!(
+        format!("{resp}"),
+        format!(
+            "{}",
+            SuiClientCommandResult::Switch(SwitchResponse {
+                address: Some(addr2),
+                gateway: None,
+                fullnode: None,
+            })
+        )
+    );
+
+    // Wipe all the address info
+    context.config.active_address = None;
+
+    // Create a new address
+    let os = SuiClientCommands::NewAddress {
+        key_scheme: SignatureScheme::ED25519,
+    }
+    .execute(&mut context)
+    .await?;
+    let new_addr = if let SuiClientCommandResult::NewAddress((a, _, _)) = os {
+        a
+    } else {
+        panic!("Command failed")
+    };
+
+    // Check that we can switch to this address
+    // Switch the address
+    let resp = SuiClientCommands::Switch {
+        address: Some(new_addr.into()),
+        gateway: None,
+        fullnode: None,
+    }
+    .execute(&mut context)
+    .await?;
+    assert_eq!(new_addr, context.active_address()?);
+    assert_eq!(
+        format!("{resp}"),
+        format!(
+            "{}",
+            SuiClientCommandResult::Switch(SwitchResponse {
+                address: Some(new_addr),
+                gateway: None,
+                fullnode: None,
+            })
+        )
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_new_address_command_by_flag() -> Result<(), anyhow::Error> {
+    // Create Wallet context.
+    let network = start_test_network(None).await?;
+    let wallet_conf = network.dir().join(SUI_CLIENT_CONFIG);
+    let mut context = WalletContext::new(&wallet_conf).await?;
+
+    // keypairs loaded from config are Ed25519
+    assert_eq!(
+        context
+            .keystore
+            .keys()
+            .iter()
+            .filter(|k| k.flag() == Ed25519SuiSignature::SCHEME.flag())
+            .count(),
+        5
+    );
+
+    SuiClientCommands::NewAddress {
+        key_scheme: SignatureScheme::Secp256k1,
+    }
+    .execute(&mut context)
+    .await?;
+
+    // new keypair generated is Secp256k1
+    assert_eq!(
+        context
+            .keystore
+            .keys()
+            .iter()
+            .filter(|k| k.flag() == Secp256k1SuiSignature::SCHEME.flag())
+            .count(),
+        1
+    );
+
+    Ok(())
+}
+
+#[allow(clippy::assertions_on_constants)]
+#[tokio::test]
+async fn test_active_address_command() -> Result<(), anyhow::Error> {
+    let network = start_test_network(None).await?;
+
+    // Create Wallet context.
+    let wallet_conf = network.dir().join(SUI_CLIENT_CONFIG);
+
+    let mut context = WalletContext::new(&wallet_conf).await?;
+
+    // Get the active address
+    let addr1 = context.active_address()?;
+
+    // Sync client to retrieve objects from the network.
+    SuiClientCommands::SyncClientState {
+        address: Some(addr1.into()),
+        timeout: None,
+        from: None,
+    }
+    .execute(&mut context)
+    .await?;
+
+    // Run a command with address omitted
+    let os = SuiClientCommands::ActiveAddress {}
+        .execute(&mut context)
+        .await?;
+
+    let a = if let SuiClientCommandResult::ActiveAddress(Some(v)) = os {
+        v
+    } else {
+        panic!("Command failed")
+    };
+    assert_eq!(a, addr1);
+
+    let addr2 = context.keystore.addresses().get(1).cloned().unwrap();
+    let resp = SuiClientCommands::Switch {
+        address: Some(addr2.into()),
+        gateway: None,
+        fullnode: None,
+    }
+    .execute(&mut context)
+    .await?;
+    assert_eq!(
+        format!("{resp}"),
+        format!(
+            "{}",
+            SuiClientCommandResult::Switch(SwitchResponse {
+                address: Some(addr2),
+                gateway: None,
+                fullnode: None
+            })
+        )
+    );
+    Ok(())
+}
+
+fn get_gas_value(o: &SuiParsedObject) -> u64 {
+    GasCoin::try_from(o).unwrap().value()
+}
+
+async fn get_object(id: ObjectID, context: &mut WalletContext) -> Option<SuiParsedObject> {
+    let response = context
+        .gateway
+        .read_api()
+        .get_parsed_object(id)
+        .await
+        .unwrap();
+    if let GetObjectDataResponse::Exists(o) = response {
+        Some(o)
+    } else {
+        None
+    }
+}
+
+async fn get_object_value(id: ObjectID, context: &mut WalletContext) -> u64 {
+    let object = get_object(id, context).await.unwrap();
+    let fields = object.data.try_as_move().unwrap().fields.clone();
+    fields.to_json_value().unwrap()["value"].as_u64().unwrap()
+}
+
+#[allow(clippy::assertions_on_constants)]
+#[tokio::test]
+async fn test_merge_coin() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+
+    // Check log output contains all object ids.
+    let gas = object_refs.first().unwrap().object_id;
+    let primary_coin = object_refs.get(1).unwrap().object_id;
+    let coin_to_merge = object_refs.get(2).unwrap().object_id;
+
+    let total_value = get_gas_value(&get_object(primary_coin, &mut context).await.unwrap())
+        + get_gas_value(&get_object(coin_to_merge, &mut context).await.unwrap());
+
+    // Test with gas specified
+    let resp = SuiClientCommands::MergeCoin {
+        primary_coin,
+        coin_to_merge,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+        yes: true,
+    }
+    .execute(&mut context)
+    .await?;
+
+    let g = if let SuiClientCommandResult::MergeCoin(r) = resp {
+        r.parsed_data.unwrap().to_merge_coin_response().unwrap()
+    } else {
+        panic!("Command failed")
+    };
+
+    // Check total value is expected
+    assert_eq!(get_gas_value(&g.updated_coin), total_value);
+
+    // Check that old coin is deleted
+    assert_eq!(get_object(coin_to_merge, &mut context).await, None);
+
+    // Sync client to retrieve objects from the network.
+    SuiClientCommands::SyncClientState {
+        address: Some(address.into()),
+        timeout: None,
+        from: None,
+    }
+    .execute(&mut context)
+    .await?;
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+
+    let primary_coin = object_refs.get(1).unwrap().object_id;
+    let coin_to_merge = object_refs.get(2).unwrap().object_id;
+
+    let total_value = get_gas_value(&get_object(primary_coin, &mut context).await.unwrap())
+        + get_gas_value(&get_object(coin_to_merge, &mut context).await.unwrap());
+
+    // Test with no gas specified
+    let resp = SuiClientCommands::MergeCoin {
+        primary_coin,
+        coin_to_merge,
+        gas: None,
+        gas_budget: Some(1000),
+        gas_price: None,
+        yes: true,
+    }
+    .execute(&mut context)
+    .await?;
+
+    let g = if let SuiClientCommandResult::MergeCoin(r) = resp {
+        r.parsed_data.unwrap().to_merge_coin_response().unwrap()
+    } else {
+        panic!("Command failed")
+    };
+
+    // Check total value is expected
+    assert_eq!(get_gas_value(&g.updated_coin), total_value);
+
+    // Check that old coin is deleted
+    assert_eq!(get_object(coin_to_merge, &mut context).await, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_merge_all_coins() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let coins = context.gas_objects(address).await?;
+    assert!(coins.len() > 2);
+    let (gas_value, gas, _) = coins.iter().min_by_key(|(value, _, _)| *value).unwrap();
+    let gas = gas.id();
+    let total_value: u64 = coins.iter().map(|(value, _, _)| value).sum::<u64>() - gas_value;
+
+    // The gas object pays for every merge, so it must hold the budget of all of them.
+    let merges = coins.len() as u64 - 2;
+    context.config.max_gas_budget = *gas_value;
+    let result = SuiClientCommands::MergeAllCoins {
+        gas: Some(gas),
+        gas_budget: gas_value / merges + 1,
+        gas_price: None,
+        yes: true,
+    }
+    .execute(&mut context)
+    .await;
+    assert!(result.is_err());
+    context.config.max_gas_budget = DEFAULT_MAX_GAS_BUDGET;
+
+    let resp = SuiClientCommands::MergeAllCoins {
+        gas: Some(gas),
+        gas_budget: 1000,
+        gas_price: None,
+        yes: true,
+    }
+    .execute(&mut context)
+    .await?;
+    // Each merge is a transaction of its own.
+    assert_eq!(resp.transaction_digests().len(), coins.len() - 2);
+    let result = if let SuiClientCommandResult::MergeAllCoins(result) = resp {
+        result
+    } else {
+        panic!("Command failed")
+    };
+    assert_eq!(result.merged_coins.len(), coins.len() - 2);
+    assert_eq!(result.total_value, total_value);
+    assert!(!result.merged_coins.contains(&gas));
+    assert!(!result.merged_coins.contains(&result.primary_coin));
+
+    // Only the merged coin and the gas object are left.
+    let remaining = context.gas_objects(address).await?;
+    assert_eq!(remaining.len(), 2);
+    let primary_coin = get_object(result.primary_coin, &mut context).await.unwrap();
+    assert_eq!(get_gas_value(&primary_coin), total_value);
+
+    // Nothing is left to merge besides the gas object.
+    let result = SuiClientCommands::MergeAllCoins {
+        gas: Some(gas),
+        gas_budget: 1000,
+        gas_price: None,
+        yes: true,
+    }
+    .execute(&mut context)
+    .await?;
+    if let SuiClientCommandResult::MergeAllCoins(result) = result {
+        assert!(result.merged_coins.is_empty());
+    } else {
+        panic!("Command failed")
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transfer_sui_with_input_coins() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+
+    let primary_coin = object_refs.get(1).unwrap().object_id;
+    let coin_to_merge = object_refs.get(2).unwrap().object_id;
+    let primary_value = get_gas_value(&get_object(primary_coin, &mut context).await.unwrap());
+    let total_value =
+        primary_value + get_gas_value(&get_object(coin_to_merge, &mut context).await.unwrap());
+    let recipient = SuiAddress::random_for_testing_only();
+
+    // The input coins cannot cover the amount.
+    let result = SuiClientCommands::TransferSui {
+        to: recipient.into(),
+        sui_coin_object_id: None,
+        input_coins: Some(vec![primary_coin, coin_to_merge]),
+        gas_budget: 1000,
+        gas_price: None,
+        amount: Some(total_value + 1),
+        yes: true,
+    }
+    .execute(&mut context)
+    .await;
+    assert!(result.is_err());
+
+    // The amount exceeds the first coin, so it is sent whole and the second one is split, in a
+    // single transaction paid for by another coin.
+    let amount = primary_value + 10;
+    let resp = SuiClientCommands::TransferSui {
+        to: recipient.into(),
+        sui_coin_object_id: None,
+        input_coins: Some(vec![primary_coin, coin_to_merge]),
+        gas_budget: 1000,
+        gas_price: None,
+        amount: Some(amount),
+        yes: true,
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(resp.to_string().contains("Transfer confirmed after"));
+
+    let effects = if let SuiClientCommandResult::TransferSui(_, _, effects) = resp {
+        effects
+    } else {
+        panic!("Command failed")
+    };
+    let gas_object = effects.gas_object.reference.object_id;
+    assert!(gas_object != primary_coin && gas_object != coin_to_merge);
+    let split = effects.created.first().unwrap().reference.object_id;
+    assert_eq!(
+        get_gas_value(&get_object(split, &mut context).await.unwrap()),
+        10
+    );
+
+    let mut received: Vec<_> = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(recipient)
+        .await?
+        .into_iter()
+        .map(|info| info.object_id)
+        .collect();
+    received.sort();
+    let mut expected = vec![primary_coin, split];
+    expected.sort();
+    assert_eq!(received, expected);
+    assert_eq!(
+        get_gas_value(&get_object(coin_to_merge, &mut context).await.unwrap()),
+        total_value - amount
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_address_book() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let coin = object_refs.get(1).unwrap().object_id;
+    let recipient = SuiAddress::random_for_testing_only();
+
+    // Names which are addresses are rejected, as they would shadow the address.
+    assert!(SuiClientCommands::AddressBook {
+        cmd: AddressBookCommand::Add {
+            name: recipient.to_string(),
+            address: recipient,
+        },
+    }
+    .execute(&mut context)
+    .await
+    .is_err());
+
+    SuiClientCommands::AddressBook {
+        cmd: AddressBookCommand::Add {
+            name: "bob".to_string(),
+            address: recipient,
+        },
+    }
+    .execute(&mut context)
+    .await?;
+
+    // Export and re-import the book through a file.
+    let path = tempfile::tempdir()?.into_path().join("address_book.json");
+    SuiClientCommands::AddressBook {
+        cmd: AddressBookCommand::Export { path: path.clone() },
+    }
+    .execute(&mut context)
+    .await?;
+    SuiClientCommands::AddressBook {
+        cmd: AddressBookCommand::Remove {
+            name: "bob".to_string(),
+        },
+    }
+    .execute(&mut context)
+    .await?;
+    let resp = SuiClientCommands::AddressBook {
+        cmd: AddressBookCommand::Import { path },
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(matches!(
+        resp,
+        SuiClientCommandResult::AddressBook(book) if book.get("bob") == Some(&recipient)
+    ));
+
+    // Imported names which are addresses are rejected too, and nothing is imported then.
+    let path = tempfile::tempdir()?.into_path().join("address_book.json");
+    std::fs::write(
+        &path,
+        json!({ "carol": recipient, recipient.to_string(): address }).to_string(),
+    )?;
+    assert!(SuiClientCommands::AddressBook {
+        cmd: AddressBookCommand::Import { path },
+    }
+    .execute(&mut context)
+    .await
+    .is_err());
+    assert!(!context.config.address_book.contains_key("carol"));
+
+    // The name resolves to the book entry wherever a recipient is expected.
+    let resp = SuiClientCommands::TransferSui {
+        to: "bob".parse()?,
+        sui_coin_object_id: Some(coin),
+        input_coins: None,
+        gas_budget: 1000,
+        gas_price: None,
+        amount: Some(10),
+        yes: true,
+    }
+    .execute(&mut context)
+    .await?;
+    let effects = if let SuiClientCommandResult::TransferSui(_, _, effects) = resp {
+        effects
+    } else {
+        panic!("Command failed")
+    };
+    assert_eq!(
+        effects.created.first().unwrap().owner,
+        Owner::AddressOwner(recipient)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_address_book_names_own_addresses() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    SuiClientCommands::AddressBook {
+        cmd: AddressBookCommand::Add {
+            name: "main".to_string(),
+            address,
+        },
+    }
+    .execute(&mut context)
+    .await?;
+
+    // `addresses` shows the names of the managed addresses.
+    let resp = SuiClientCommands::Addresses.execute(&mut context).await?;
+    // The JSON output keeps listing the addresses alone.
+    let json: serde_json::Value = serde_json::from_str(&format!("{resp:?}"))?;
+    assert_eq!(json, json!(context.keystore.addresses()));
+    let addresses = if let SuiClientCommandResult::Addresses(addresses) = resp {
+        addresses
+    } else {
+        panic!("Command failed")
+    };
+    let managed = addresses.iter().find(|a| a.address == address).unwrap();
+    assert_eq!(managed.names, vec!["main".to_string()]);
+
+    // Names are accepted by `--address`.
+    let by_name = SuiClientCommands::Gas {
+        address: Some("main".parse()?),
+        min_balance: None,
+        refund_estimate: false,
+    }
+    .execute(&mut context)
+    .await?;
+    let by_address = SuiClientCommands::Gas {
+        address: Some(address.to_string().parse()?),
+        min_balance: None,
+        refund_estimate: false,
+    }
+    .execute(&mut context)
+    .await?;
+    assert_eq!(format!("{by_name}"), format!("{by_address}"));
+
+    let other = switch_to_new_address(&mut context).await?;
+    SuiClientCommands::Switch {
+        address: Some("main".parse()?),
+        gateway: None,
+        fullnode: None,
+    }
+    .execute(&mut context)
+    .await?;
+    assert_eq!(context.active_address()?, address);
+    assert_ne!(context.active_address()?, other);
+
+    assert!(SuiClientCommands::Objects {
+        address: Some("nobody".parse()?),
+        limit: None,
+        cursor: None,
+        type_: None,
+    }
+    .execute(&mut context)
+    .await
+    .is_err());
+
+    Ok(())
+}
+
+/// Add a new key to the keystore of the active profile and make its address the active one.
+async fn switch_to_new_address(context: &mut WalletContext) -> Result<SuiAddress, anyhow::Error> {
+    let (address, keypair) = get_key_pair();
+    context
+        .keystore
+        .add_key(SuiKeyPair::Ed25519SuiKeyPair(keypair))?;
+    SuiClientCommands::Switch {
+        address: Some(address.into()),
+        gateway: None,
+        fullnode: None,
+    }
+    .execute(context)
+    .await?;
+    Ok(address)
+}
+
+#[tokio::test]
+async fn test_profiles() -> Result<(), anyhow::Error> {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let working_dir = temp_dir.path();
+    let wallet_config = SuiClientConfig {
+        keystore: KeystoreType::File(working_dir.join(SUI_KEYSTORE_FILENAME)),
+        gateway: ClientType::RPC("http://127.0.0.1:5001".to_string(), None),
+        active_address: None,
+        fullnode: None,
+        max_gas_budget: DEFAULT_MAX_GAS_BUDGET,
+        gas_budget_margin_percent: DEFAULT_GAS_BUDGET_MARGIN_PERCENT,
+        address_book: Default::default(),
+        active_profile: DEFAULT_PROFILE_NAME.to_string(),
+        profiles: Default::default(),
+    };
+    let wallet_conf_path = working_dir.join(SUI_CLIENT_CONFIG);
+    wallet_config.persisted(&wallet_conf_path).save()?;
+    let mut context = WalletContext::new(&wallet_conf_path).await?;
+    let local_address = switch_to_new_address(&mut context).await?;
+
+    // Creating a profile does not switch to it.
+    SuiClientCommands::Profile {
+        cmd: ProfileCommand::Create {
+            name: "devnet".to_string(),
+            gateway: "http://127.0.0.1:5002".to_string(),
+            fullnode: None,
+            keystore: Some(working_dir.join("devnet.keystore")),
+        },
+    }
+    .execute(&mut context)
+    .await?;
+    assert_eq!(context.config.active_profile, DEFAULT_PROFILE_NAME);
+    let result = SuiClientCommands::Profile {
+        cmd: ProfileCommand::Create {
+            name: DEFAULT_PROFILE_NAME.to_string(),
+            gateway: "http://127.0.0.1:5003".to_string(),
+            fullnode: None,
+            keystore: None,
+        },
+    }
+    .execute(&mut context)
+    .await;
+    assert!(result.is_err());
+
+    // The new profile starts with its own empty keystore.
+    let use_profile = |name: &str| SuiClientCommands::Profile {
+        cmd: ProfileCommand::Use {
+            name: name.to_string(),
+        },
+    };
+    use_profile("devnet").execute(&mut context).await?;
+    assert_eq!(context.config.active_profile, "devnet");
+    assert!(context.keystore.addresses().is_empty());
+    assert_eq!(context.config.active_address, None);
+    let devnet_address = switch_to_new_address(&mut context).await?;
+
+    // Each profile keeps its active address when switched away from.
+    use_profile(DEFAULT_PROFILE_NAME)
+        .execute(&mut context)
+        .await?;
+    assert_eq!(context.config.active_address, Some(local_address));
+    assert_eq!(context.keystore.addresses(), vec![local_address]);
+    use_profile("devnet").execute(&mut context).await?;
+    assert_eq!(context.config.active_address, Some(devnet_address));
+    assert!(use_profile("testnet").execute(&mut context).await.is_err());
+
+    // A profile that cannot be connected to is not switched to.
+    SuiClientCommands::Profile {
+        cmd: ProfileCommand::Create {
+            name: "broken".to_string(),
+            gateway: "not a url".to_string(),
+            fullnode: None,
+            keystore: Some(working_dir.join("broken.keystore")),
+        },
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(use_profile("broken").execute(&mut context).await.is_err());
+    assert_eq!(context.config.active_profile, "devnet");
+    assert_eq!(context.config.active_address, Some(devnet_address));
+    assert!(context.config.profiles.contains_key("broken"));
+
+    // The active profile is loaded by the next context.
+    drop(context);
+    let mut context = WalletContext::new(&wallet_conf_path).await?;
+    assert_eq!(context.config.active_profile, "devnet");
+    assert_eq!(context.active_address()?, devnet_address);
+
+    let result = SuiClientCommands::Profile {
+        cmd: ProfileCommand::List,
+    }
+    .execute(&mut context)
+    .await?;
+    let profiles = if let SuiClientCommandResult::Profiles(profiles) = result {
+        profiles
+    } else {
+        panic!("Command failed")
+    };
+    let listed: Vec<_> = profiles
+        .iter()
+        .map(|p| (p.name.as_str(), p.active, p.active_address))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            ("broken", false, None),
+            (DEFAULT_PROFILE_NAME, false, Some(local_address)),
+            ("devnet", true, Some(devnet_address)),
+        ]
+    );
+
+    Ok(())
+}
+
+#[allow(clippy::assertions_on_constants)]
+#[tokio::test]
+async fn test_split_coin() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+
+    // Check log output contains all object ids.
+    let gas = object_refs.first().unwrap().object_id;
+    let mut coin = object_refs.get(1).unwrap().object_id;
+
+    let orig_value = get_gas_value(&get_object(coin, &mut context).await.unwrap());
+
+    // Test with gas specified
+    let resp = SuiClientCommands::SplitCoin {
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+        coin_id: coin,
+        amounts: Some(vec![1000, 10]),
+        count: None,
+    }
+    .execute(&mut context)
+    .await?;
+
+    let g = if let SuiClientCommandResult::SplitCoin(r) = resp {
+        r.parsed_data.unwrap().to_split_coin_response().unwrap()
+    } else {
+        panic!("Command failed")
+    };
+
+    // Check values expected
+    assert_eq!(get_gas_value(&g.updated_coin) + 1000 + 10, orig_value);
+    assert!((get_gas_value(&g.new_coins[0]) == 1000) || (get_gas_value(&g.new_coins[0]) == 10));
+    assert!((get_gas_value(&g.new_coins[1]) == 1000) || (get_gas_value(&g.new_coins[1]) == 10));
+
     SuiClientCommands::SyncClientState {
-        address: Some(address),
+        address: Some(address.into()),
+        timeout: None,
+        from: None,
     }
     .execute(&mut context)
     .await?
-    .print(true);
+    .print(OutputFormat::Human);
+
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+
+    // Get another coin
+    for c in object_refs {
+        if get_gas_value(&get_object(c.object_id, &mut context).await.unwrap()) > 2000 {
+            coin = c.object_id;
+        }
+    }
+    let orig_value = get_gas_value(&get_object(coin, &mut context).await.unwrap());
+
+    // Test split coin into equal parts
+    let resp = SuiClientCommands::SplitCoin {
+        gas: None,
+        gas_budget: Some(1000),
+        gas_price: None,
+        coin_id: coin,
+        amounts: None,
+        count: Some(3),
+    }
+    .execute(&mut context)
+    .await?;
+
+    let g = if let SuiClientCommandResult::SplitCoin(r) = resp {
+        r.parsed_data.unwrap().to_split_coin_response().unwrap()
+    } else {
+        panic!("Command failed")
+    };
+
+    // Check values expected
+    assert_eq!(
+        get_gas_value(&g.updated_coin),
+        orig_value / 3 + orig_value % 3
+    );
+    assert_eq!(get_gas_value(&g.new_coins[0]), orig_value / 3);
+    assert_eq!(get_gas_value(&g.new_coins[1]), orig_value / 3);
+
     SuiClientCommands::SyncClientState {
-        address: Some(recipient),
+        address: Some(address.into()),
+        timeout: None,
+        from: None,
     }
     .execute(&mut context)
     .await?
-    .print(true);
+    .print(OutputFormat::Human);
 
-    // Check the objects
-    let resp = SuiClientCommands::Object { id: mut_obj1 }
-        .execute(&mut context)
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
         .await?;
-    let mut_obj1 =
-        if let SuiClientCommandResult::Object(GetObjectDataResponse::Exists(object)) = resp {
-            object
-        } else {
-            // Fail this way because Panic! causes test issues
-            assert!(false);
-            panic!()
-        };
 
-    let resp = SuiClientCommands::Object { id: mut_obj2 }
-        .execute(&mut context)
+    // Get another coin
+    for c in object_refs {
+        if get_gas_value(&get_object(c.object_id, &mut context).await.unwrap()) > 2000 {
+            coin = c.object_id;
+        }
+    }
+    let orig_value = get_gas_value(&get_object(coin, &mut context).await.unwrap());
+
+    // Test with no gas specified
+    let resp = SuiClientCommands::SplitCoin {
+        gas: None,
+        gas_budget: Some(1000),
+        gas_price: None,
+        coin_id: coin,
+        amounts: Some(vec![1000, 10]),
+        count: None,
+    }
+    .execute(&mut context)
+    .await?;
+
+    let g = if let SuiClientCommandResult::SplitCoin(r) = resp {
+        r.parsed_data.unwrap().to_split_coin_response().unwrap()
+    } else {
+        panic!("Command failed")
+    };
+
+    // Check values expected
+    assert_eq!(get_gas_value(&g.updated_coin) + 1000 + 10, orig_value);
+    assert!((get_gas_value(&g.new_coins[0]) == 1000) || (get_gas_value(&g.new_coins[0]) == 10));
+    assert!((get_gas_value(&g.new_coins[1]) == 1000) || (get_gas_value(&g.new_coins[1]) == 10));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_split_coin_count_validation() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
         .await?;
-    let mut_obj2 =
-        if let SuiClientCommandResult::Object(GetObjectDataResponse::Exists(object)) = resp {
-            object
-        } else {
-            // Fail this way because Panic! causes test issues
-            assert!(false);
-            panic!()
-        };
+    let gas = object_refs.first().unwrap().object_id;
+    let coin = object_refs.get(1).unwrap().object_id;
+    let value = get_gas_value(&get_object(coin, &mut context).await.unwrap());
+
+    let split = |amounts: Option<Vec<u64>>, count: Option<u64>, gas: ObjectID| {
+        SuiClientCommands::SplitCoin {
+            coin_id: coin,
+            amounts,
+            count,
+            gas: Some(gas),
+            gas_budget: Some(1000),
+            gas_price: None,
+        }
+    };
 
-    let (gas, obj) = if mut_obj1.owner.get_owner_address().unwrap() == address {
-        (mut_obj1, mut_obj2)
+    let error = split(Some(vec![10]), Some(2), gas)
+        .execute(&mut context)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("cannot be used together"));
+
+    let error = split(None, Some(0), gas)
+        .execute(&mut context)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("must be greater than 0"));
+
+    // Every coin needs at least the minimum coin value of 1.
+    let error = split(None, Some(value + 1), gas)
+        .execute(&mut context)
+        .await
+        .unwrap_err();
+    assert!(error
+        .to_string()
+        .contains(&format!("supports at most {value}")));
+
+    // Paying gas out of the split coin leaves less to split.
+    let error = split(Some(vec![value - 10]), None, coin)
+        .execute(&mut context)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("cannot cover the split"));
+
+    // The remainder is left in the split coin.
+    let resp = split(None, Some(4), gas).execute(&mut context).await?;
+    let g = if let SuiClientCommandResult::SplitCoin(r) = resp {
+        r.parsed_data.unwrap().to_split_coin_response().unwrap()
     } else {
-        (mut_obj2, mut_obj1)
+        panic!("Command failed")
     };
+    assert_eq!(g.new_coins.len(), 3);
+    for new_coin in &g.new_coins {
+        assert_eq!(get_gas_value(new_coin), value / 4);
+    }
+    assert_eq!(get_gas_value(&g.updated_coin), value / 4 + value % 4);
 
-    assert_eq!(gas.owner.get_owner_address().unwrap(), address);
-    assert_eq!(obj.owner.get_owner_address().unwrap(), recipient);
+    Ok(())
+}
 
-    // Sync client to retrieve objects from the network.
-    SuiClientCommands::SyncClientState {
-        address: Some(address),
+#[tokio::test]
+async fn test_dry_run_split_coin() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let gas = object_refs.first().unwrap().object_id;
+    let coin = object_refs.get(1).unwrap().object_id;
+    let orig_gas = get_object(gas, &mut context).await.unwrap();
+    let orig_coin = get_object(coin, &mut context).await.unwrap();
+
+    context.dry_run = true;
+    let resp = SuiClientCommands::SplitCoin {
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+        coin_id: coin,
+        amounts: Some(vec![1000, 10]),
+        count: None,
     }
     .execute(&mut context)
-    .await?
-    .print(true);
+    .await?;
+
+    let effects = if let SuiClientCommandResult::DryRun(effects) = resp {
+        effects
+    } else {
+        panic!("Command failed")
+    };
+    assert_eq!(effects.status, SuiExecutionStatus::Success);
+    assert_eq!(effects.created.len(), 2);
+
+    // Nothing was submitted, so the coins are unchanged and no new coin is owned.
+    let gas_object = get_object(gas, &mut context).await.unwrap();
+    let coin_object = get_object(coin, &mut context).await.unwrap();
+    assert_eq!(gas_object.reference.version, orig_gas.reference.version);
+    assert_eq!(coin_object.reference.version, orig_coin.reference.version);
+    assert_eq!(get_gas_value(&coin_object), get_gas_value(&orig_coin));
+
+    // Transactions signed elsewhere are dry run too.
+    let recipient = SuiAddress::random_for_testing_only();
+    let data = context
+        .gateway
+        .transaction_builder()
+        .transfer_object(address, coin, Some(gas), 1000, recipient)
+        .await?;
+    let resp = SuiClientCommands::ExecuteSigned {
+        tx_bytes: Base64::encode(data.to_bytes()),
+        signature: context.keystore.sign(&address, &data.to_bytes())?,
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(matches!(resp, SuiClientCommandResult::DryRun(_)));
+    let file = tempfile::tempdir()?.into_path().join("tx_bytes");
+    std::fs::write(&file, Base64::encode(data.to_bytes()))?;
+    let resp = SuiClientCommands::SignAndSubmit { file }
+        .execute(&mut context)
+        .await?;
+    assert!(matches!(resp, SuiClientCommandResult::DryRun(_)));
+
+    // Commands submitting several transactions, or none, reject the flag.
+    for command in [
+        SuiClientCommands::MergeAllCoins {
+            gas: None,
+            gas_budget: 1000,
+            gas_price: None,
+            yes: true,
+        },
+        SuiClientCommands::BatchSubmit {
+            estimate_only: false,
+        },
+        SuiClientCommands::Gas {
+            address: None,
+            min_balance: None,
+            refund_estimate: false,
+        },
+    ] {
+        assert!(command.execute(&mut context).await.is_err());
+    }
 
+    let coin_object = get_object(coin, &mut context).await.unwrap();
+    assert_eq!(coin_object.owner, Owner::AddressOwner(address));
+    let owned = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    assert_eq!(owned.len(), object_refs.len());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_serialize_transaction_and_execute_signed() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
     let object_refs = context
         .gateway
         .read_api()
         .get_objects_owned_by_address(address)
         .await?;
+    let gas = object_refs.first().unwrap().object_id;
+    let object_to_send = object_refs.get(1).unwrap().object_id;
+    let recipient = SuiAddress::random_for_testing_only();
 
-    // Check log output contains all object ids.
-    let obj_id = object_refs.get(1).unwrap().object_id;
-
+    context.serialize_transaction = true;
     let resp = SuiClientCommands::Transfer {
-        gas: None,
-        to: recipient,
-        object_id: obj_id,
-        gas_budget: 50000,
+        to: recipient.into(),
+        object_id: object_to_send,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+        yes: true,
+        authority: None,
     }
     .execute(&mut context)
     .await?;
+    let tx_bytes = if let SuiClientCommandResult::SerializedTransaction(tx_bytes) = resp {
+        tx_bytes
+    } else {
+        panic!("Command failed")
+    };
+    context.serialize_transaction = false;
+
+    // Nothing was submitted.
+    let object = get_object(object_to_send, &mut context).await.unwrap();
+    assert_eq!(object.owner, Owner::AddressOwner(address));
+
+    // A signature by another key is rejected before submitting.
+    let (other, other_keypair) = get_key_pair();
+    context
+        .keystore
+        .add_key(SuiKeyPair::Ed25519SuiKeyPair(other_keypair))?;
+    let result = SuiClientCommands::ExecuteSigned {
+        tx_bytes: tx_bytes.clone(),
+        signature: context.keystore.sign(&other, &Base64::decode(&tx_bytes)?)?,
+    }
+    .execute(&mut context)
+    .await;
+    assert!(result.is_err());
 
-    // Print it out to CLI/logs
-    resp.print(true);
-
-    // Get the mutated objects
-    let (_mut_obj1, _mut_obj2) =
-        if let SuiClientCommandResult::Transfer(_, _, SuiTransactionEffects { mutated, .. }) = resp
-        {
-            (
-                mutated.get(0).unwrap().reference.object_id,
-                mutated.get(1).unwrap().reference.object_id,
-            )
-        } else {
-            assert!(false);
-            panic!()
-        };
-
-    Ok(())
-}
-
-#[test]
-// Test for issue https://github.com/MystenLabs/sui/issues/1078
-fn test_bug_1078() {
-    let read = SuiClientCommandResult::Object(GetObjectDataResponse::NotExists(ObjectID::random()));
-    let mut writer = String::new();
-    // fmt ObjectRead should not fail.
-    write!(writer, "{}", read).unwrap();
-    write!(writer, "{:?}", read).unwrap();
-}
-
-#[allow(clippy::assertions_on_constants)]
-#[tokio::test]
-async fn test_switch_command() -> Result<(), anyhow::Error> {
-    let network = start_test_network(None).await?;
-
-    // Create Wallet context.
-    let wallet_conf = network.dir().join(SUI_CLIENT_CONFIG);
-
-    let mut context = WalletContext::new(&wallet_conf).await?;
-
-    // Get the active address
-    let addr1 = context.active_address()?;
-
-    // Sync client to retrieve objects from the network.
-    SuiClientCommands::SyncClientState {
-        address: Some(addr1),
+    let resp = SuiClientCommands::ExecuteSigned {
+        signature: context
+            .keystore
+            .sign(&address, &Base64::decode(&tx_bytes)?)?,
+        tx_bytes,
     }
     .execute(&mut context)
     .await?;
-
-    // Run a command with address omitted
-    let os = SuiClientCommands::Objects { address: None }
-        .execute(&mut context)
-        .await?;
-
-    let mut cmd_objs = if let SuiClientCommandResult::Objects(v) = os {
-        v
+    let response = if let SuiClientCommandResult::ExecuteSigned(response) = resp {
+        response
     } else {
         panic!("Command failed")
     };
+    assert_eq!(response.effects.status, SuiExecutionStatus::Success);
+    let object = get_object(object_to_send, &mut context).await.unwrap();
+    assert_eq!(object.owner, Owner::AddressOwner(recipient));
 
-    // Check that we indeed fetched for addr1
-    let mut actual_objs = context
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_expiration_epoch() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let object_refs = context
         .gateway
         .read_api()
-        .get_objects_owned_by_address(addr1)
+        .get_objects_owned_by_address(address)
+        .await?;
+    let recipient = SuiAddress::random_for_testing_only();
+
+    // Commands building no transaction reject the flag.
+    context.expiration_epoch = Some(0);
+    assert!(SuiClientCommands::Addresses
+        .execute(&mut context)
         .await
-        .unwrap();
-    cmd_objs.sort();
-    actual_objs.sort();
-    assert_eq!(cmd_objs, actual_objs);
+        .is_err());
 
-    // Switch the address
-    let addr2 = context.keystore.addresses().get(1).cloned().unwrap();
-    let resp = SuiClientCommands::Switch {
-        address: Some(addr2),
-        gateway: None,
-        fullnode: None,
+    context.serialize_transaction = true;
+    let resp = SuiClientCommands::TransferSui {
+        to: recipient.into(),
+        sui_coin_object_id: Some(object_refs[0].object_id),
+        input_coins: None,
+        gas_budget: 1000,
+        gas_price: None,
+        amount: Some(10),
+        yes: true,
     }
     .execute(&mut context)
     .await?;
-    assert_eq!(addr2, context.active_address()?);
-    assert_ne!(addr1, context.active_address()?);
-    assert_eq!(
-        format!("{resp}"),
-        format!(
-            "{}",
-            SuiClientCommandResult::Switch(SwitchResponse {
-                address: Some(addr2),
-                gateway: None,
-                fullnode: None,
-            })
-        )
-    );
-
-    // Wipe all the address info
-    context.config.active_address = None;
-
-    // Create a new address
-    let os = SuiClientCommands::NewAddress {
-        key_scheme: SignatureScheme::ED25519,
+    let tx_bytes = if let SuiClientCommandResult::SerializedTransaction(tx_bytes) = resp {
+        tx_bytes
+    } else {
+        panic!("Command failed")
+    };
+    let data = TransactionData::from_signable_bytes(&Base64::decode(&tx_bytes)?)?;
+    assert_eq!(data.expiration_epoch, Some(0));
+    context.serialize_transaction = false;
+    context.expiration_epoch = None;
+
+    // The network is still in the expiration epoch, which accepts the transaction.
+    let resp = SuiClientCommands::ExecuteSigned {
+        signature: context
+            .keystore
+            .sign(&address, &Base64::decode(&tx_bytes)?)?,
+        tx_bytes,
     }
     .execute(&mut context)
     .await?;
-    let new_addr = if let SuiClientCommandResult::NewAddress((a, _, _)) = os {
-        a
+    let response = if let SuiClientCommandResult::ExecuteSigned(response) = resp {
+        response
     } else {
         panic!("Command failed")
     };
+    assert_eq!(response.effects.status, SuiExecutionStatus::Success);
 
-    // Check that we can switch to this address
-    // Switch the address
-    let resp = SuiClientCommands::Switch {
-        address: Some(new_addr),
-        gateway: None,
-        fullnode: None,
-    }
-    .execute(&mut context)
-    .await?;
-    assert_eq!(new_addr, context.active_address()?);
-    assert_eq!(
-        format!("{resp}"),
-        format!(
-            "{}",
-            SuiClientCommandResult::Switch(SwitchResponse {
-                address: Some(new_addr),
-                gateway: None,
-                fullnode: None,
-            })
-        )
-    );
     Ok(())
 }
 
 #[tokio::test]
-async fn test_new_address_command_by_flag() -> Result<(), anyhow::Error> {
-    // Create Wallet context.
-    let network = start_test_network(None).await?;
-    let wallet_conf = network.dir().join(SUI_CLIENT_CONFIG);
-    let mut context = WalletContext::new(&wallet_conf).await?;
-
-    // keypairs loaded from config are Ed25519
-    assert_eq!(
-        context
-            .keystore
-            .keys()
-            .iter()
-            .filter(|k| k.flag() == Ed25519SuiSignature::SCHEME.flag())
-            .count(),
-        5
-    );
-
-    SuiClientCommands::NewAddress {
-        key_scheme: SignatureScheme::Secp256k1,
+async fn test_serialize_transaction_coverage() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let recipient = SuiAddress::random_for_testing_only();
+    context.serialize_transaction = true;
+
+    // Every command building a single transaction returns it unsigned.
+    for command in [
+        SuiClientCommands::TransferObjects {
+            to: recipient.into(),
+            object_ids: vec![object_refs[1].object_id],
+            gas: Some(object_refs[0].object_id),
+            gas_budget: 1000,
+            gas_price: None,
+            yes: true,
+        },
+        SuiClientCommands::TransferSui {
+            to: recipient.into(),
+            sui_coin_object_id: Some(object_refs[2].object_id),
+            input_coins: None,
+            gas_budget: 1000,
+            gas_price: None,
+            amount: Some(10),
+            yes: true,
+        },
+        SuiClientCommands::TransferSui {
+            to: recipient.into(),
+            sui_coin_object_id: None,
+            input_coins: Some(vec![object_refs[2].object_id, object_refs[3].object_id]),
+            gas_budget: 1000,
+            gas_price: None,
+            amount: Some(10),
+            yes: true,
+        },
+        SuiClientCommands::CreateExampleNFT {
+            name: None,
+            description: None,
+            url: None,
+            gas: None,
+            gas_budget: None,
+            gas_price: None,
+        },
+    ] {
+        let resp = command.execute(&mut context).await?;
+        assert!(matches!(
+            resp,
+            SuiClientCommandResult::SerializedTransaction(_)
+        ));
     }
-    .execute(&mut context)
-    .await?;
 
-    // new keypair generated is Secp256k1
-    assert_eq!(
-        context
-            .keystore
-            .keys()
-            .iter()
-            .filter(|k| k.flag() == Secp256k1SuiSignature::SCHEME.flag())
-            .count(),
-        1
-    );
+    // Commands submitting several transactions, or none, reject the flag.
+    for command in [
+        SuiClientCommands::MergeAllCoins {
+            gas: None,
+            gas_budget: 1000,
+            gas_price: None,
+            yes: true,
+        },
+        SuiClientCommands::Addresses,
+    ] {
+        assert!(command.execute(&mut context).await.is_err());
+    }
 
+    // Nothing was submitted.
+    let owned = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    assert_eq!(owned.len(), object_refs.len());
     Ok(())
 }
 
-#[allow(clippy::assertions_on_constants)]
 #[tokio::test]
-async fn test_active_address_command() -> Result<(), anyhow::Error> {
-    let network = start_test_network(None).await?;
+async fn test_signature_flag() -> Result<(), anyhow::Error> {
+    let res = SignatureScheme::from_flag("0");
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().flag(), SignatureScheme::ED25519.flag());
 
-    // Create Wallet context.
-    let wallet_conf = network.dir().join(SUI_CLIENT_CONFIG);
+    let res = SignatureScheme::from_flag("1");
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().flag(), SignatureScheme::Secp256k1.flag());
 
-    let mut context = WalletContext::new(&wallet_conf).await?;
+    let res = SignatureScheme::from_flag("2");
+    assert!(res.is_err());
 
-    // Get the active address
-    let addr1 = context.active_address()?;
+    let res = SignatureScheme::from_flag("something");
+    assert!(res.is_err());
+    Ok(())
+}
 
-    // Sync client to retrieve objects from the network.
-    SuiClientCommands::SyncClientState {
-        address: Some(addr1),
-    }
+#[tokio::test]
+async fn test_multisig() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let public_keys: Vec<_> = context.keystore.keys().into_iter().take(3).collect();
+    let signers: Vec<SuiAddress> = public_keys.iter().map(|pk| pk.into()).collect();
+    let recipient = SuiAddress::random_for_testing_only();
+
+    let multisig = match (SuiClientCommands::MultisigAddress {
+        public_keys: public_keys.clone(),
+        threshold: 2,
+    })
     .execute(&mut context)
-    .await?;
+    .await?
+    {
+        SuiClientCommandResult::MultisigAddress(multisig) => multisig,
+        _ => panic!("Command failed"),
+    };
 
-    // Run a command with address omitted
-    let os = SuiClientCommands::ActiveAddress {}
+    // Fund the multisig address with a coin to send and a coin to pay gas with.
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    for object in &object_refs[1..3] {
+        SuiClientCommands::Transfer {
+            to: multisig.into(),
+            object_id: object.object_id,
+            gas: Some(object_refs[0].object_id),
+            gas_budget: Some(1000),
+            gas_price: None,
+            yes: true,
+            authority: None,
+        }
         .execute(&mut context)
         .await?;
+    }
 
-    let a = if let SuiClientCommandResult::ActiveAddress(Some(v)) = os {
-        v
-    } else {
-        panic!("Command failed")
-    };
-    assert_eq!(a, addr1);
+    let data = context
+        .gateway
+        .transaction_builder()
+        .transfer_object(
+            multisig,
+            object_refs[1].object_id,
+            Some(object_refs[2].object_id),
+            1000,
+            recipient,
+        )
+        .await?;
+    let tx_bytes = Base64::encode(data.to_bytes());
+    let signatures = signers[..2]
+        .iter()
+        .map(|signer| context.keystore.sign(signer, &data.to_bytes()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // A single signature does not meet the threshold.
+    let result = SuiClientCommands::MultisigExecute {
+        tx_bytes: tx_bytes.clone(),
+        public_keys: public_keys.clone(),
+        threshold: 2,
+        signatures: signatures[..1].to_vec(),
+    }
+    .execute(&mut context)
+    .await;
+    assert!(result.is_err());
 
-    let addr2 = context.keystore.addresses().get(1).cloned().unwrap();
-    let resp = SuiClientCommands::Switch {
-        address: Some(addr2),
-        gateway: None,
-        fullnode: None,
+    SuiClientCommands::MultisigExecute {
+        tx_bytes,
+        public_keys,
+        threshold: 2,
+        signatures,
     }
     .execute(&mut context)
     .await?;
-    assert_eq!(
-        format!("{resp}"),
-        format!(
-            "{}",
-            SuiClientCommandResult::Switch(SwitchResponse {
-                address: Some(addr2),
-                gateway: None,
-                fullnode: None
-            })
-        )
-    );
-    Ok(())
-}
-
-fn get_gas_value(o: &SuiParsedObject) -> u64 {
-    GasCoin::try_from(o).unwrap().value()
-}
 
-async fn get_object(id: ObjectID, context: &mut WalletContext) -> Option<SuiParsedObject> {
-    let response = context
+    let received = context
         .gateway
         .read_api()
-        .get_parsed_object(id)
-        .await
-        .unwrap();
-    if let GetObjectDataResponse::Exists(o) = response {
-        Some(o)
-    } else {
-        None
-    }
+        .get_objects_owned_by_address(recipient)
+        .await?;
+    assert!(received
+        .iter()
+        .any(|object| object.object_id == object_refs[1].object_id));
+
+    Ok(())
 }
 
-#[allow(clippy::assertions_on_constants)]
 #[tokio::test]
-async fn test_merge_coin() -> Result<(), anyhow::Error> {
+async fn test_batch_submit() -> Result<(), anyhow::Error> {
     let (_network, mut context, address) = setup_network_and_wallet().await?;
-
+    let recipient = SuiAddress::random_for_testing_only();
     let object_refs = context
         .gateway
         .read_api()
         .get_objects_owned_by_address(address)
         .await?;
 
-    // Check log output contains all object ids.
-    let gas = object_refs.first().unwrap().object_id;
-    let primary_coin = object_refs.get(1).unwrap().object_id;
-    let coin_to_merge = object_refs.get(2).unwrap().object_id;
-
-    let total_value = get_gas_value(&get_object(primary_coin, &mut context).await.unwrap())
-        + get_gas_value(&get_object(coin_to_merge, &mut context).await.unwrap());
-
-    // Test with gas specified
-    let resp = SuiClientCommands::MergeCoin {
-        primary_coin,
-        coin_to_merge,
-        gas: Some(gas),
-        gas_budget: 1000,
+    // Transfer two objects, then the first object again with its now stale version.
+    let mut lines = vec![];
+    for (object, gas) in [(1, 0), (2, 3), (1, 4)] {
+        let data = context
+            .gateway
+            .transaction_builder()
+            .transfer_object(
+                address,
+                object_refs[object].object_id,
+                Some(object_refs[gas].object_id),
+                1000,
+                recipient,
+            )
+            .await?;
+        let signature = context.keystore.sign(&address, &data.to_bytes())?;
+        lines.push(format!(
+            "{} {}",
+            Base64::encode(data.to_bytes()),
+            Base64::encode(signature)
+        ));
     }
-    .execute(&mut context)
-    .await?;
+    lines.insert(2, String::new());
+    lines.push("not a transaction".to_string());
 
-    let g = if let SuiClientCommandResult::MergeCoin(r) = resp {
-        r.parsed_data.unwrap().to_merge_coin_response().unwrap()
-    } else {
-        panic!("Command failed")
-    };
+    let results = context.batch_submit(lines).await;
+    assert_eq!(
+        results.iter().map(|r| r.line).collect::<Vec<_>>(),
+        vec![1, 2, 4, 5]
+    );
+    assert!(results[..2]
+        .iter()
+        .all(|r| r.status == Some(SuiExecutionStatus::Success)));
+    assert!(results[2].digest.is_some() && results[2].error.is_some());
+    assert!(results[3].digest.is_none() && results[3].error.is_some());
 
-    // Check total value is expected
-    assert_eq!(get_gas_value(&g.updated_coin), total_value);
+    // Only the executed transactions have effects.
+    assert_eq!(
+        SuiClientCommandResult::BatchSubmit(results.clone()).transaction_digests(),
+        results[..2]
+            .iter()
+            .map(|r| r.digest.unwrap())
+            .collect::<Vec<_>>()
+    );
 
-    // Check that old coin is deleted
-    assert_eq!(get_object(coin_to_merge, &mut context).await, None);
+    let received = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(recipient)
+        .await?;
+    assert_eq!(received.len(), 2);
+    Ok(())
+}
 
-    // Sync client to retrieve objects from the network.
-    SuiClientCommands::SyncClientState {
-        address: Some(address),
-    }
-    .execute(&mut context)
-    .await?;
+#[tokio::test]
+async fn test_batch_estimate() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = SuiAddress::random_for_testing_only();
     let object_refs = context
         .gateway
         .read_api()
         .get_objects_owned_by_address(address)
         .await?;
 
-    let primary_coin = object_refs.get(1).unwrap().object_id;
-    let coin_to_merge = object_refs.get(2).unwrap().object_id;
-
-    let total_value = get_gas_value(&get_object(primary_coin, &mut context).await.unwrap())
-        + get_gas_value(&get_object(coin_to_merge, &mut context).await.unwrap());
-
-    // Test with no gas specified
-    let resp = SuiClientCommands::MergeCoin {
-        primary_coin,
-        coin_to_merge,
-        gas: None,
-        gas_budget: 1000,
+    // Plan two transfers, the second one unsigned.
+    let mut lines = vec![];
+    let mut individual_estimates = 0;
+    for (object, gas) in [(1, 0), (2, 3)] {
+        let data = context
+            .gateway
+            .transaction_builder()
+            .transfer_object(
+                address,
+                object_refs[object].object_id,
+                Some(object_refs[gas].object_id),
+                1000,
+                recipient,
+            )
+            .await?;
+        let effects = context
+            .gateway
+            .quorum_driver()
+            .dry_run_transaction(Base64::from_bytes(&data.to_bytes()))
+            .await?;
+        individual_estimates += effects.gas_used.gas_used();
+        let signature = context.keystore.sign(&address, &data.to_bytes())?;
+        lines.push(if lines.is_empty() {
+            format!(
+                "{} {}",
+                Base64::encode(data.to_bytes()),
+                Base64::encode(signature)
+            )
+        } else {
+            Base64::encode(data.to_bytes())
+        });
     }
-    .execute(&mut context)
-    .await?;
-
-    let g = if let SuiClientCommandResult::MergeCoin(r) = resp {
-        r.parsed_data.unwrap().to_merge_coin_response().unwrap()
-    } else {
-        panic!("Command failed")
-    };
-
-    // Check total value is expected
-    assert_eq!(get_gas_value(&g.updated_coin), total_value);
-
-    // Check that old coin is deleted
-    assert_eq!(get_object(coin_to_merge, &mut context).await, None);
+    lines.push("not a transaction".to_string());
+
+    let estimate = context.batch_estimate(lines).await;
+    assert_eq!(estimate.transactions.len(), 3);
+    assert!(estimate.transactions[..2]
+        .iter()
+        .all(|r| r.status == Some(SuiExecutionStatus::Success) && r.gas_used.is_some()));
+    assert!(estimate.transactions[2].error.is_some());
+    assert_eq!(
+        estimate.total_gas_used,
+        estimate
+            .transactions
+            .iter()
+            .filter_map(|r| r.gas_used)
+            .sum::<u64>()
+    );
+    assert_eq!(estimate.total_gas_used, individual_estimates);
 
+    // Nothing was submitted.
+    let received = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(recipient)
+        .await?;
+    assert!(received.is_empty());
     Ok(())
 }
 
-#[allow(clippy::assertions_on_constants)]
 #[tokio::test]
-async fn test_split_coin() -> Result<(), anyhow::Error> {
+async fn test_sign_and_submit() -> Result<(), anyhow::Error> {
     let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = SuiAddress::random_for_testing_only();
     let object_refs = context
         .gateway
         .read_api()
         .get_objects_owned_by_address(address)
         .await?;
+    let data = context
+        .gateway
+        .transaction_builder()
+        .transfer_object(
+            address,
+            object_refs[1].object_id,
+            Some(object_refs[0].object_id),
+            1000,
+            recipient,
+        )
+        .await?;
+    let temp_dir = tempfile::tempdir()?;
+    let file = temp_dir.path().join("tx_bytes");
+    std::fs::write(&file, Base64::encode(data.to_bytes()))?;
 
-    // Check log output contains all object ids.
-    let gas = object_refs.first().unwrap().object_id;
-    let mut coin = object_refs.get(1).unwrap().object_id;
-
-    let orig_value = get_gas_value(&get_object(coin, &mut context).await.unwrap());
-
-    // Test with gas specified
-    let resp = SuiClientCommands::SplitCoin {
-        gas: Some(gas),
-        gas_budget: 1000,
-        coin_id: coin,
-        amounts: Some(vec![1000, 10]),
-        count: 0,
+    // Only the sender of the transaction can sign it.
+    let other = context.keystore.addresses().get(1).cloned().unwrap();
+    SuiClientCommands::Switch {
+        address: Some(other.into()),
+        gateway: None,
+        fullnode: None,
     }
     .execute(&mut context)
     .await?;
+    assert!(SuiClientCommands::SignAndSubmit { file: file.clone() }
+        .execute(&mut context)
+        .await
+        .is_err());
 
-    let g = if let SuiClientCommandResult::SplitCoin(r) = resp {
-        r.parsed_data.unwrap().to_split_coin_response().unwrap()
-    } else {
-        panic!("Command failed")
-    };
-
-    // Check values expected
-    assert_eq!(get_gas_value(&g.updated_coin) + 1000 + 10, orig_value);
-    assert!((get_gas_value(&g.new_coins[0]) == 1000) || (get_gas_value(&g.new_coins[0]) == 10));
-    assert!((get_gas_value(&g.new_coins[1]) == 1000) || (get_gas_value(&g.new_coins[1]) == 10));
-
-    SuiClientCommands::SyncClientState {
-        address: Some(address),
+    SuiClientCommands::Switch {
+        address: Some(address.into()),
+        gateway: None,
+        fullnode: None,
     }
     .execute(&mut context)
-    .await?
-    .print(true);
+    .await?;
+    let result = SuiClientCommands::SignAndSubmit { file }
+        .execute(&mut context)
+        .await?;
+    assert!(matches!(result, SuiClientCommandResult::SignAndSubmit(_)));
+
+    let received = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(recipient)
+        .await?;
+    assert_eq!(received[0].object_id, object_refs[1].object_id);
+    Ok(())
+}
 
+#[tokio::test]
+async fn test_prove_and_verify_object_ownership() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
     let object_refs = context
         .gateway
         .read_api()
         .get_objects_owned_by_address(address)
         .await?;
+    let object_id = object_refs[1].object_id;
 
-    // Get another coin
-    for c in object_refs {
-        if get_gas_value(&get_object(c.object_id, &mut context).await.unwrap()) > 2000 {
-            coin = c.object_id;
+    let challenge = "nonce-1".to_string();
+    let proof = match (SuiClientCommands::ProveObjectOwnership {
+        object_id,
+        challenge: challenge.clone(),
+    })
+    .execute(&mut context)
+    .await?
+    {
+        SuiClientCommandResult::ProveObjectOwnership(proof) => proof,
+        result => panic!("Unexpected result {result:?}"),
+    };
+
+    let verify = |proof: &str| SuiClientCommands::VerifyObjectOwnership {
+        proof: proof.to_string(),
+        challenge: challenge.clone(),
+        check_current: true,
+    };
+    match verify(&proof).execute(&mut context).await? {
+        SuiClientCommandResult::VerifyObjectOwnership(statement, still_owned) => {
+            assert_eq!(statement.owner, address);
+            assert_eq!(statement.object_ref, object_refs[1].to_object_ref());
+            assert_eq!(still_owned, Some(true));
         }
+        result => panic!("Unexpected result {result:?}"),
     }
-    let orig_value = get_gas_value(&get_object(coin, &mut context).await.unwrap());
 
-    // Test split coin into equal parts
-    let resp = SuiClientCommands::SplitCoin {
-        gas: None,
-        gas_budget: 1000,
-        coin_id: coin,
-        amounts: None,
-        count: 3,
-    }
+    // The proof cannot be replayed to a verifier with another challenge.
+    assert!((SuiClientCommands::VerifyObjectOwnership {
+        proof: proof.clone(),
+        challenge: "nonce-2".to_string(),
+        check_current: false,
+    })
     .execute(&mut context)
-    .await?;
-
-    let g = if let SuiClientCommandResult::SplitCoin(r) = resp {
-        r.parsed_data.unwrap().to_split_coin_response().unwrap()
-    } else {
-        panic!("Command failed")
-    };
+    .await
+    .is_err());
+
+    // Nor can its challenge be changed.
+    let mut replayed: ObjectOwnershipProof = bcs::from_bytes(&Base64::decode(&proof)?)?;
+    replayed.statement.challenge = "nonce-2".to_string();
+    assert!((SuiClientCommands::VerifyObjectOwnership {
+        proof: Base64::encode(bcs::to_bytes(&replayed)?),
+        challenge: "nonce-2".to_string(),
+        check_current: false,
+    })
+    .execute(&mut context)
+    .await
+    .is_err());
 
-    // Check values expected
-    assert_eq!(
-        get_gas_value(&g.updated_coin),
-        orig_value / 3 + orig_value % 3
-    );
-    assert_eq!(get_gas_value(&g.new_coins[0]), orig_value / 3);
-    assert_eq!(get_gas_value(&g.new_coins[1]), orig_value / 3);
+    // A proof claiming ownership for another address does not verify.
+    let mut forged: ObjectOwnershipProof = bcs::from_bytes(&Base64::decode(&proof)?)?;
+    forged.statement.owner = SuiAddress::random_for_testing_only();
+    let forged = Base64::encode(bcs::to_bytes(&forged)?);
+    assert!(verify(&forged).execute(&mut context).await.is_err());
 
-    SuiClientCommands::SyncClientState {
-        address: Some(address),
+    // Once transferred, the proof still verifies but the object is no longer owned.
+    SuiClientCommands::Transfer {
+        to: SuiAddress::random_for_testing_only().into(),
+        object_id,
+        gas: Some(object_refs[0].object_id),
+        gas_budget: Some(1000),
+        gas_price: None,
+        yes: true,
+        authority: None,
     }
     .execute(&mut context)
-    .await?
-    .print(true);
+    .await?;
+    match verify(&proof).execute(&mut context).await? {
+        SuiClientCommandResult::VerifyObjectOwnership(_, still_owned) => {
+            assert_eq!(still_owned, Some(false));
+        }
+        result => panic!("Unexpected result {result:?}"),
+    }
+
+    // Only owned objects can be proven.
+    assert!((SuiClientCommands::ProveObjectOwnership {
+        object_id,
+        challenge
+    })
+    .execute(&mut context)
+    .await
+    .is_err());
+    Ok(())
+}
 
+#[tokio::test]
+async fn test_coin_flow() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = SuiAddress::random_for_testing_only();
     let object_refs = context
         .gateway
         .read_api()
         .get_objects_owned_by_address(address)
         .await?;
+    let gas = object_refs[0].object_id;
+    let coin = object_refs[1].object_id;
 
-    // Get another coin
-    for c in object_refs {
-        if get_gas_value(&get_object(c.object_id, &mut context).await.unwrap()) > 2000 {
-            coin = c.object_id;
-        }
-    }
-    let orig_value = get_gas_value(&get_object(coin, &mut context).await.unwrap());
-
-    // Test with no gas specified
     let resp = SuiClientCommands::SplitCoin {
-        gas: None,
-        gas_budget: 1000,
         coin_id: coin,
-        amounts: Some(vec![1000, 10]),
-        count: 0,
+        amounts: Some(vec![1000, 2000]),
+        count: None,
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
     }
     .execute(&mut context)
     .await?;
-
-    let g = if let SuiClientCommandResult::SplitCoin(r) = resp {
-        r.parsed_data.unwrap().to_split_coin_response().unwrap()
+    let new_coins: Vec<_> = if let SuiClientCommandResult::SplitCoin(r) = resp {
+        r.parsed_data
+            .unwrap()
+            .to_split_coin_response()
+            .unwrap()
+            .new_coins
+            .iter()
+            .map(|c| c.id())
+            .collect()
     } else {
         panic!("Command failed")
     };
+    SuiClientCommands::Transfer {
+        to: recipient.into(),
+        object_id: new_coins[0],
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+        yes: true,
+        authority: None,
+    }
+    .execute(&mut context)
+    .await?;
+    SuiClientCommands::MergeCoin {
+        primary_coin: coin,
+        coin_to_merge: new_coins[1],
+        gas: Some(gas),
+        gas_budget: Some(1000),
+        gas_price: None,
+        yes: true,
+    }
+    .execute(&mut context)
+    .await?;
+
+    let flow = context.coin_flow(coin).await?;
+    assert_eq!(flow.coin, coin);
+    assert_eq!(flow.events.len(), 1);
+    let split_coins = match &flow.events[0] {
+        CoinFlowEvent::Split { coins, .. } => coins,
+        event => panic!("Unexpected event {event:?}"),
+    };
+    assert_eq!(split_coins.len(), 2);
+    for split in split_coins {
+        assert_eq!(split.created_by.as_ref().unwrap().split_from, Some(coin));
+        match &split.events[..] {
+            [CoinFlowEvent::Transferred { recipient: to, .. }] => {
+                assert_eq!(split.coin, new_coins[0]);
+                assert_eq!(*to, Owner::AddressOwner(recipient));
+            }
+            [CoinFlowEvent::Merged { into, .. }] => {
+                assert_eq!(split.coin, new_coins[1]);
+                assert_eq!(*into, coin);
+            }
+            events => panic!("Unexpected events {events:?}"),
+        }
+    }
 
-    // Check values expected
-    assert_eq!(get_gas_value(&g.updated_coin) + 1000 + 10, orig_value);
-    assert!((get_gas_value(&g.new_coins[0]) == 1000) || (get_gas_value(&g.new_coins[0]) == 10));
-    assert!((get_gas_value(&g.new_coins[1]) == 1000) || (get_gas_value(&g.new_coins[1]) == 10));
+    // The lineage of a split coin starts at the coin it was split from.
+    let flow = context.coin_flow(new_coins[0]).await?;
+    assert_eq!(flow.created_by.unwrap().split_from, Some(coin));
     Ok(())
 }
 
-#[tokio::test]
-async fn test_signature_flag() -> Result<(), anyhow::Error> {
-    let res = SignatureScheme::from_flag("0");
-    assert!(res.is_ok());
-    assert_eq!(res.unwrap().flag(), SignatureScheme::ED25519.flag());
+struct MockNameResolver(SuiAddress);
 
-    let res = SignatureScheme::from_flag("1");
-    assert!(res.is_ok());
-    assert_eq!(res.unwrap().flag(), SignatureScheme::Secp256k1.flag());
+#[async_trait]
+impl NameResolver for MockNameResolver {
+    async fn resolve_name(&self, name: &str) -> Result<SuiAddress, anyhow::Error> {
+        match name {
+            "alice.sui" => Ok(self.0),
+            // A name service answering for hex addresses must not redirect them.
+            _ if SuiAddress::from_str(name).is_ok() => Ok(self.0),
+            _ => Err(anyhow!("Name {name} is not registered")),
+        }
+    }
+}
 
-    let res = SignatureScheme::from_flag("2");
-    assert!(res.is_err());
+#[tokio::test]
+async fn test_transfer_to_name() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let alice = SuiAddress::random_for_testing_only();
+    let bob = SuiAddress::random_for_testing_only();
+    context
+        .gateway
+        .embedded_gateway()
+        .unwrap()
+        .set_name_resolver(Arc::new(MockNameResolver(alice)));
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
 
-    let res = SignatureScheme::from_flag("something");
-    assert!(res.is_err());
+    // Hex addresses are used as they are, and anything else is resolved as a name.
+    assert_eq!(
+        bob.to_string().parse::<AddressOrName>()?,
+        AddressOrName::Address(bob)
+    );
+    for (object, to, owner) in [
+        (1, "alice.sui".to_string(), alice),
+        (2, bob.to_string(), bob),
+    ] {
+        SuiClientCommands::Transfer {
+            to: to.parse()?,
+            object_id: object_refs[object].object_id,
+            gas: Some(object_refs[0].object_id),
+            gas_budget: Some(1000),
+            gas_price: None,
+            yes: true,
+            authority: None,
+        }
+        .execute(&mut context)
+        .await?;
+        let owned = context
+            .gateway
+            .read_api()
+            .get_objects_owned_by_address(owner)
+            .await?;
+        assert_eq!(owned[0].object_id, object_refs[object].object_id);
+    }
+
+    let result = SuiClientCommands::Transfer {
+        to: "carol.sui".parse()?,
+        object_id: object_refs[3].object_id,
+        gas: Some(object_refs[0].object_id),
+        gas_budget: Some(1000),
+        gas_price: None,
+        yes: true,
+        authority: None,
+    }
+    .execute(&mut context)
+    .await;
+    assert!(result.is_err());
     Ok(())
 }
@@ -4,9 +4,10 @@
 /// Test CTURD object basics (create, transfer, update, read, delete)
 module examples::object_basics {
     use sui::event;
-    use sui::object::{Self, UID};
+    use sui::object::{Self, ID, UID};
     use sui::tx_context::{Self, TxContext};
     use sui::transfer;
+    use std::vector;
 
     struct Object has key, store {
         id: UID,
@@ -41,6 +42,15 @@ module examples::object_basics {
         o.value = value;
     }
 
+    // test that vector args work, by setting the value to their length
+    public entry fun set_value_from_bytes(o: &mut Object, bytes: vector<u8>) {
+        o.value = vector::length(&bytes);
+    }
+
+    public entry fun set_value_from_ids(o: &mut Object, ids: vector<ID>) {
+        o.value = vector::length(&ids);
+    }
+
     // test that reading o2 and updating o1 works
     public entry fun update(o1: &mut Object, o2: &Object) {
         o1.value = o2.value;
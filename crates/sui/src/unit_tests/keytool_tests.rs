@@ -4,8 +4,10 @@
 use crate::keytool::read_authority_keypair_from_file;
 use crate::keytool::read_keypair_from_file;
 
+use super::sign_data;
 use super::write_keypair_to_file;
 use super::KeyToolCommand;
+use super::{export_keypair, parse_public_key, KeyOutput};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use sui_sdk::crypto::KeystoreType;
@@ -20,6 +22,7 @@ use sui_types::crypto::Secp256k1SuiSignature;
 use sui_types::crypto::Signature;
 use sui_types::crypto::SuiKeyPair;
 use sui_types::crypto::SuiSignatureInner;
+use sui_types::sui_serde::{Base64, Encoding};
 use tempfile::TempDir;
 
 #[test]
@@ -33,7 +36,48 @@ fn test_addresses_command() -> Result<(), anyhow::Error> {
     }
 
     // List all addresses with flag
-    KeyToolCommand::List.execute(&mut keystore).unwrap();
+    KeyToolCommand::List { json: false }
+        .execute(&mut keystore)
+        .unwrap();
+    KeyToolCommand::List { json: true }
+        .execute(&mut keystore)
+        .unwrap();
+    Ok(())
+}
+
+#[test]
+fn test_address_and_export_commands() -> Result<(), anyhow::Error> {
+    let mut keystore = KeystoreType::InMem(1).init().unwrap();
+    keystore.add_key(SuiKeyPair::Secp256k1SuiKeyPair(get_key_pair().1))?;
+
+    for public_key in keystore.keys() {
+        let address: SuiAddress = (&public_key).into();
+
+        // The listed public key derives the address it is listed with.
+        let listed = KeyOutput::from(&public_key);
+        assert_eq!(listed.address, address);
+        let parsed = parse_public_key(&listed.public_key_base64)?;
+        assert_eq!(KeyOutput::from(&parsed).address, address);
+
+        // The exported keypair decodes to the key of the address.
+        let exported = export_keypair(&keystore, address)?;
+        let keypair = SuiKeyPair::decode_base64(&exported.keypair_base64).unwrap();
+        assert_eq!(SuiAddress::from(&keypair.public()), address);
+
+        KeyToolCommand::Address {
+            public_key,
+            json: true,
+        }
+        .execute(&mut keystore)?;
+        KeyToolCommand::Export {
+            address,
+            json: true,
+        }
+        .execute(&mut keystore)?;
+    }
+
+    // Keys not in the keystore cannot be exported.
+    assert!(export_keypair(&keystore, SuiAddress::random_for_testing_only()).is_err());
     Ok(())
 }
 
@@ -64,11 +108,45 @@ fn test_flag_in_signature_and_keypair() -> Result<(), anyhow::Error> {
                 );
                 assert!(pk1.flag() == Secp256k1SuiSignature::SCHEME.flag())
             }
+            Signature::MultiSigSuiSignature(_) => {
+                unreachable!("The keystore signs with single keys")
+            }
         }
     }
     Ok(())
 }
 
+#[test]
+fn test_sign_json_output() -> Result<(), anyhow::Error> {
+    let keystore = KeystoreType::InMem(1).init().unwrap();
+    let pub_key = keystore.keys().pop().unwrap();
+    let address: SuiAddress = (&pub_key).into();
+
+    let output = sign_data(&keystore, address, &Base64::encode(b"hello"))?;
+    let json: serde_json::Value = serde_json::to_value(&output)?;
+    assert_eq!(json["address"], serde_json::to_value(address)?);
+
+    let field = |name: &str| Base64::decode(json[name].as_str().unwrap()).unwrap();
+    assert_eq!(field("pubKeyBase64"), pub_key.as_ref());
+    assert_eq!(field("flagBase64"), vec![pub_key.flag()]);
+    assert_eq!(field("signatureBase64").len(), 64);
+
+    // The serialized signature is the concatenation of the other fields and verifies.
+    let serialized = field("serializedSignatureBase64");
+    assert_eq!(
+        serialized,
+        [
+            field("flagBase64"),
+            field("signatureBase64"),
+            field("pubKeyBase64")
+        ]
+        .concat()
+    );
+    let signature = <Signature as signature::Signature>::from_bytes(&serialized)?;
+    assert_eq!(signature, keystore.sign(&address, b"hello")?);
+    Ok(())
+}
+
 #[test]
 fn test_read_write_keystore_with_flag() {
     let dir = tempfile::TempDir::new().unwrap();
@@ -143,3 +221,26 @@ fn test_load_keystore_err() {
     // cannot load keypair due to missing flag
     assert!(KeystoreType::File(path2).init().is_err());
 }
+
+#[test]
+fn test_load_keystore_without_flag() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("sui.keystore");
+
+    // Keystores written before keys carried their scheme flag hold bare ed25519 keypairs.
+    let kp: AuthorityKeyPair = get_key_pair_from_rng(&mut StdRng::from_seed([0; 32])).1;
+    let contents = serde_json::to_string(&[kp.encode_base64()]).unwrap();
+    std::fs::write(&path, contents).unwrap();
+
+    let keystore = KeystoreType::File(path).init().unwrap();
+    let keys = keystore.keys();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].flag(), Ed25519SuiSignature::SCHEME.flag());
+    assert_eq!(keys[0].as_ref(), kp.public().as_ref());
+    assert_eq!(
+        keystore.addresses(),
+        vec![SuiAddress::from(
+            &SuiKeyPair::Ed25519SuiKeyPair(kp).public()
+        )]
+    );
+}
@@ -5,7 +5,7 @@ use std::net::SocketAddr;
 use std::str::FromStr;
 use std::{collections::BTreeMap, sync::Arc};
 
-use futures::future;
+use futures::{future, StreamExt};
 use jsonrpsee::core::client::{Client, ClientT, Subscription, SubscriptionClientT};
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::rpc_params;
@@ -31,13 +31,14 @@ use sui_json_rpc_types::{
     SuiMoveValue, SuiObjectRead, SuiTransactionFilter, SuiTransactionResponse,
 };
 use sui_node::SuiNode;
+use sui_sdk::SuiClient;
 use sui_swarm::memory::Swarm;
 use sui_types::messages::{
     ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
 };
 use sui_types::{
     base_types::{ObjectID, SuiAddress, TransactionDigest},
-    messages::TransactionInfoRequest,
+    messages::{Transaction, TransactionInfoRequest},
 };
 use test_utils::messages::get_account_and_gas_coins;
 use test_utils::messages::make_transactions_with_wallet_context;
@@ -63,10 +64,13 @@ async fn transfer_coin(
         object_to_send, sender, receiver
     );
     let res = SuiClientCommands::Transfer {
-        to: receiver,
+        to: receiver.into(),
         object_id: object_to_send,
         gas: None,
-        gas_budget: 50000,
+        gas_budget: Some(50000),
+        gas_price: None,
+        yes: true,
+        authority: None,
     }
     .execute(context)
     .await?;
@@ -92,11 +96,13 @@ async fn emit_move_events(
         url: Some("https://sui.io/_nuxt/img/sui-logo.8d3c44e.svg".into()),
         gas: Some(*gas_object),
         gas_budget: Some(50000),
+        gas_price: None,
     }
     .execute(context)
     .await?;
 
     let (object_id, digest) = if let SuiClientCommandResult::CreateExampleNFT(
+        _,
         SuiObjectRead::Exists(obj),
     ) = res
     {
@@ -252,6 +258,21 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     let txes = node.state().get_transactions_from_addr(receiver).await?;
     assert_eq!(txes.len(), 0);
 
+    // The transfer touches both the sender and the receiver, and paging past it finds nothing.
+    for address in [sender, receiver] {
+        let txes = node
+            .state()
+            .get_transactions_touching_addr(address, 0, 10)
+            .await?;
+        assert_eq!(txes.len(), 1);
+        assert_eq!(txes[0].1, digest);
+        let txes = node
+            .state()
+            .get_transactions_touching_addr(address, txes[0].0 + 1, 10)
+            .await?;
+        assert!(txes.is_empty());
+    }
+
     // timestamp is recorded
     let ts = node.state().get_timestamp_ms(&digest).await?;
     assert!(ts.is_some());
@@ -398,7 +419,9 @@ async fn test_full_node_sync_flood() -> Result<(), anyhow::Error> {
                 let context = &mut context.lock().await;
                 let address = context.keystore.addresses()[i];
                 SuiClientCommands::SyncClientState {
-                    address: Some(address),
+                    address: Some(address.into()),
+                    timeout: None,
+                    from: None,
                 }
                 .execute(context)
                 .await
@@ -419,10 +442,11 @@ async fn test_full_node_sync_flood() -> Result<(), anyhow::Error> {
                     let context = &mut context.lock().await;
                     SuiClientCommands::SplitCoin {
                         amounts: Some(vec![1]),
-                        count: 0,
+                        count: None,
                         coin_id: object_to_split.0,
                         gas: gas_object,
-                        gas_budget: 50000,
+                        gas_budget: Some(50000),
+                        gas_price: None,
                     }
                     .execute(context)
                     .await
@@ -467,6 +491,41 @@ async fn test_full_node_sync_flood() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_history_command() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+    let jsonrpc_addr: SocketAddr = format!("127.0.0.1:{}", get_available_port()).parse()?;
+    let mut config = swarm.config().generate_fullnode_config();
+    config.json_rpc_address = jsonrpc_addr;
+    let node = SuiNode::start(&config).await?;
+
+    let (_, sender, receiver, digest) = transfer_coin(&mut context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+
+    // The history is read from the index of the full node.
+    context.fullnode =
+        Some(SuiClient::new_rpc_client(&format!("http://{}", jsonrpc_addr), None).await?);
+    for address in [sender, receiver] {
+        let result = SuiClientCommands::History {
+            address: Some(address),
+            start: 0,
+            count: 10,
+        }
+        .execute(&mut context)
+        .await?;
+        match result {
+            SuiClientCommandResult::History(history_address, txes) => {
+                assert_eq!(history_address, address);
+                assert_eq!(txes.len(), 1);
+                assert_eq!(txes[0].1, digest);
+            }
+            _ => panic!("Command result should be History"),
+        }
+    }
+
+    Ok(())
+}
+
 /// Call this function to set up a network and a fullnode with subscription enabled.
 async fn set_up_subscription(swarm: &Swarm) -> Result<(SuiNode, Client), anyhow::Error> {
     let port = get_available_port();
@@ -554,6 +613,118 @@ async fn test_full_node_transaction_streaming_basic() -> Result<(), anyhow::Erro
     Ok(())
 }
 
+#[tokio::test]
+async fn test_full_node_transaction_streaming_mutated_object() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+    let jsonrpc_server_url = format!("127.0.0.1:{}", get_available_port());
+    let ws_server_url = format!("127.0.0.1:{}", get_available_port());
+    let mut config = swarm.config().generate_fullnode_config();
+    config.json_rpc_address = jsonrpc_server_url.parse().unwrap();
+    config.websocket_address = Some(ws_server_url.parse().unwrap());
+    let node = SuiNode::start(&config).await?;
+    let client = SuiClient::new_rpc_client(
+        &format!("http://{}", jsonrpc_server_url),
+        Some(&format!("ws://{}", ws_server_url)),
+    )
+    .await?;
+
+    // transfer_coin sends the second object of the sender
+    let sender = context.keystore.addresses().get(0).cloned().unwrap();
+    let object_refs = context
+        .gateway
+        .read_api()
+        .get_objects_owned_by_address(sender)
+        .await?;
+    let followed = object_refs.get(1).unwrap().object_id;
+
+    let sub = client
+        .event_api()
+        .subscribe_transaction(SuiTransactionFilter::MutatedObject(followed))
+        .await?;
+    futures::pin_mut!(sub);
+
+    // The first transfer moves the followed coin, the second one does not touch it
+    let (object_id, _, receiver, digest) = transfer_coin(&mut context).await?;
+    assert_eq!(object_id, followed);
+    let (other_object_id, _, _, other_digest) = transfer_coin(&mut context).await?;
+    assert_ne!(other_object_id, followed);
+    wait_for_all_txes(vec![digest, other_digest], node.state().clone()).await;
+
+    match timeout(Duration::from_secs(3), sub.next()).await {
+        Ok(Some(Ok(resp))) => {
+            assert_eq!(resp.certificate.transaction_digest, digest);
+        }
+        other => panic!(
+            "Failed to get Ok item from transaction streaming, but {:?}",
+            other
+        ),
+    };
+    let object = client.read_api().get_parsed_object(followed).await?;
+    assert_eq!(object.object()?.owner, Owner::AddressOwner(receiver));
+
+    // No more
+    match timeout(Duration::from_secs(3), sub.next()).await {
+        Err(_) => (),
+        other => panic!(
+            "Expect to time out because the followed object is not changed again. Got {:?}",
+            other
+        ),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_watch_balance() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, sender) = setup_network_and_wallet().await?;
+    let jsonrpc_server_url = format!("127.0.0.1:{}", get_available_port());
+    let ws_server_url = format!("127.0.0.1:{}", get_available_port());
+    let mut config = swarm.config().generate_fullnode_config();
+    config.json_rpc_address = jsonrpc_server_url.parse().unwrap();
+    config.websocket_address = Some(ws_server_url.parse().unwrap());
+    let node = SuiNode::start(&config).await?;
+    context.fullnode = Some(
+        SuiClient::new_rpc_client(
+            &format!("http://{}", jsonrpc_server_url),
+            Some(&format!("ws://{}", ws_server_url)),
+        )
+        .await?,
+    );
+
+    let receiver = context.keystore.addresses().get(1).cloned().unwrap();
+    let updates = context.watch_balance(receiver).await?;
+    futures::pin_mut!(updates);
+    let before = context.balance(receiver).await?;
+
+    // Transfer a coin to the watched address
+    let (value, coin, _) = context.gas_objects(sender).await?.swap_remove(0);
+    let data = context
+        .gateway
+        .transaction_builder()
+        .transfer_object(sender, coin.id(), None, 50000, receiver)
+        .await?;
+    let signature = context.keystore.sign(&sender, &data.to_bytes())?;
+    let response = context
+        .gateway
+        .quorum_driver()
+        .execute_transaction(Transaction::new(data, signature))
+        .await?;
+    wait_for_tx(
+        response.certificate.transaction_digest,
+        node.state().clone(),
+    )
+    .await;
+
+    let after = timeout(Duration::from_secs(10), updates.next())
+        .await?
+        .unwrap()?;
+    assert_eq!(after.address, receiver);
+    assert_eq!(after.balance, before.balance + value);
+    assert_eq!(after.coin_count, before.coin_count + 1);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Error> {
     let (swarm, mut context, _) = setup_network_and_wallet().await?;
@@ -49,7 +49,9 @@ pub async fn get_account_and_gas_coins(
     let accounts = context.keystore.addresses();
     for address in accounts {
         let result = SuiClientCommands::Gas {
-            address: Some(address),
+            address: Some(address.into()),
+            min_balance: None,
+            refund_estimate: false,
         }
         .execute(context)
         .await?;
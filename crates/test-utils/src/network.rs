@@ -7,7 +7,10 @@ use std::num::NonZeroUsize;
 use std::path::Path;
 use sui::{
     client_commands::{SuiClientCommands, WalletContext},
-    config::SuiClientConfig,
+    config::{
+        SuiClientConfig, DEFAULT_GAS_BUDGET_MARGIN_PERCENT, DEFAULT_MAX_GAS_BUDGET,
+        DEFAULT_PROFILE_NAME,
+    },
 };
 use sui_config::gateway::GatewayConfig;
 use sui_config::genesis_config::GenesisConfig;
@@ -97,6 +100,11 @@ pub async fn start_test_network_with_fullnodes(
         }),
         active_address,
         fullnode: None,
+        max_gas_budget: DEFAULT_MAX_GAS_BUDGET,
+        gas_budget_margin_percent: DEFAULT_GAS_BUDGET_MARGIN_PERCENT,
+        address_book: Default::default(),
+        active_profile: DEFAULT_PROFILE_NAME.to_string(),
+        profiles: Default::default(),
     }
     .save(&wallet_path)?;
 
@@ -116,7 +124,9 @@ pub async fn setup_network_and_wallet() -> Result<(Swarm, WalletContext, SuiAddr
 
     // Sync client to retrieve objects from the network.
     SuiClientCommands::SyncClientState {
-        address: Some(address),
+        address: Some(address.into()),
+        timeout: None,
+        from: None,
     }
     .execute(&mut context)
     .await?;
@@ -238,10 +238,9 @@ pub async fn submit_shared_object_transaction(
             .into_iter()
             // Remove all `FailedToHearBackFromConsensus` replies. Note that the original Sui error type
             // `SuiError::FailedToHearBackFromConsensus(..)` is lost when the message is sent through the
-            // network (it is replaced by `RpcError`). As a result, the following filter doesn't work:
-            // `.filter(|result| !matches!(result, Err(SuiError::FailedToHearBackFromConsensus(..))))`.
+            // network (it is replaced by `RpcError`), so the replies are told apart by error code.
             .filter(|result| match result {
-                Err(e) => !e.to_string().contains("deadline has elapsed"),
+                Err(e) => e.code() != "FailedToHearBackFromConsensus",
                 _ => true,
             })
             .collect();